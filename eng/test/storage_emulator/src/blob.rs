@@ -0,0 +1,1313 @@
+//! The Blob service: containers, block blobs and append blobs.
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/blob-service-rest-api>
+
+use crate::{
+    conditions::{self, ResourceVersion},
+    lease::{Lease, LeaseOutcome, LeasedResource},
+    not_implemented,
+    request::EmulatorRequest,
+    response::{
+        optional_xml_element, xml_element, xml_escape, EmulatorResponse, EmulatorResult,
+        StorageError,
+    },
+    shared_key::Authorization,
+    Env,
+};
+use azure_core::{
+    base64, date,
+    headers::{
+        HeaderName, BLOB_CACHE_CONTROL, BLOB_COMMITTED_BLOCK_COUNT, BLOB_PUBLIC_ACCESS, BLOB_TYPE,
+        CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_LANGUAGE, CONTENT_LENGTH,
+        CONTENT_MD5, CONTENT_RANGE, CONTENT_TYPE, CREATION_TIME, DELETE_TYPE_PERMANENT,
+        ENCRYPTION_KEY, ENCRYPTION_KEY_SHA256, ETAG, HAS_IMMUTABILITY_POLICY, HAS_LEGAL_HOLD,
+        LAST_MODIFIED, LEASE_ID, LEASE_TIME, MS_RANGE, RANGE, RANGE_GET_CONTENT_MD5,
+        REQUEST_SERVER_ENCRYPTED, SERVER_ENCRYPTED,
+    },
+    Method, StatusCode,
+};
+use azure_storage::headers::{
+    DEFAULT_ENCRYPTION_SCOPE, DENY_ENCRYPTION_SCOPE_OVERRIDE, ENCRYPTION_SCOPE,
+};
+use bytes::Bytes;
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
+
+const BLOB_CONTENT_TYPE: HeaderName = HeaderName::from_static("x-ms-blob-content-type");
+const BLOB_CONTENT_ENCODING: HeaderName = HeaderName::from_static("x-ms-blob-content-encoding");
+const BLOB_CONTENT_LANGUAGE: HeaderName = HeaderName::from_static("x-ms-blob-content-language");
+const BLOB_CONTENT_DISPOSITION: HeaderName =
+    HeaderName::from_static("x-ms-blob-content-disposition");
+const BLOB_CONTENT_MD5: HeaderName = HeaderName::from_static("x-ms-blob-content-md5");
+const BLOB_CONTENT_LENGTH: HeaderName = HeaderName::from_static("x-ms-blob-content-length");
+const BLOB_APPEND_OFFSET: HeaderName = HeaderName::from_static("x-ms-blob-append-offset");
+const BLOB_CONDITION_APPENDPOS: HeaderName =
+    HeaderName::from_static("x-ms-blob-condition-appendpos");
+const BLOB_CONDITION_MAXSIZE: HeaderName = HeaderName::from_static("x-ms-blob-condition-maxsize");
+const ACCEPT_RANGES: HeaderName = HeaderName::from_static("accept-ranges");
+
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+const MAX_RESULTS: usize = 5000;
+const MAX_BLOCK_ID_LEN: usize = 64;
+const MAX_APPEND_BLOCKS: u32 = 50_000;
+
+#[derive(Debug, Default)]
+pub(crate) struct BlobService {
+    containers: BTreeMap<String, Container>,
+}
+
+#[derive(Debug)]
+struct Container {
+    etag: String,
+    last_modified: OffsetDateTime,
+    metadata: BTreeMap<String, String>,
+    lease: Lease,
+    public_access: Option<String>,
+    default_encryption_scope: Option<String>,
+    deny_encryption_scope_override: bool,
+    signed_identifiers: String,
+    blobs: BTreeMap<String, Blob>,
+    /// Uncommitted blocks, by blob name. A blob does not need to exist to
+    /// have blocks staged for it.
+    staged_blocks: BTreeMap<String, Vec<Block>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlobKind {
+    Block,
+    Append,
+}
+
+impl BlobKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            BlobKind::Block => "BlockBlob",
+            BlobKind::Append => "AppendBlob",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Block {
+    id: Vec<u8>,
+    data: Bytes,
+}
+
+#[derive(Debug, Default, Clone)]
+struct ContentProperties {
+    content_type: Option<String>,
+    content_encoding: Option<String>,
+    content_language: Option<String>,
+    content_disposition: Option<String>,
+    cache_control: Option<String>,
+    content_md5: Option<String>,
+}
+
+impl ContentProperties {
+    /// The `x-ms-blob-*` headers of a put or set properties request.
+    fn from_request(request: &EmulatorRequest) -> Self {
+        let header = |name: &HeaderName| request.header(name).map(str::to_owned);
+        Self {
+            content_type: header(&BLOB_CONTENT_TYPE),
+            content_encoding: header(&BLOB_CONTENT_ENCODING),
+            content_language: header(&BLOB_CONTENT_LANGUAGE),
+            content_disposition: header(&BLOB_CONTENT_DISPOSITION),
+            cache_control: header(&BLOB_CACHE_CONTROL),
+            content_md5: header(&BLOB_CONTENT_MD5),
+        }
+    }
+}
+
+/// How the content of a blob is encrypted: with a customer-provided key,
+/// identified by its hash, or with an encryption scope.
+#[derive(Debug, Default, Clone)]
+struct Encryption {
+    key_sha256: Option<String>,
+    scope: Option<String>,
+}
+
+impl Encryption {
+    /// The encryption of a write to a blob in `container`.
+    fn from_request(
+        container: &Container,
+        request: &EmulatorRequest,
+    ) -> Result<Self, StorageError> {
+        let key_sha256 = request.header(&ENCRYPTION_KEY_SHA256).map(str::to_owned);
+        if key_sha256.is_some() != request.header(&ENCRYPTION_KEY).is_some() {
+            return Err(StorageError::new(
+                400,
+                "MissingRequiredHeader",
+                "a customer-provided key requires both x-ms-encryption-key and x-ms-encryption-key-sha256",
+            ));
+        }
+        let scope = request.header(&ENCRYPTION_SCOPE).map(str::to_owned);
+        if key_sha256.is_some() && scope.is_some() {
+            return Err(StorageError::new(
+                400,
+                "InvalidHeaderValue",
+                "a customer-provided key and an encryption scope cannot both be specified",
+            ));
+        }
+        if container.deny_encryption_scope_override
+            && (key_sha256.is_some()
+                || scope.is_some() && scope != container.default_encryption_scope)
+        {
+            return Err(StorageError::new(
+                409,
+                "RequestForbiddenByContainerEncryptionPolicy",
+                "the container's encryption policy does not allow overriding its encryption scope",
+            ));
+        }
+        let scope = match key_sha256 {
+            Some(_) => None,
+            None => scope.or_else(|| container.default_encryption_scope.clone()),
+        };
+        Ok(Self { key_sha256, scope })
+    }
+
+    /// Data encrypted with a customer-provided key can only be read or
+    /// changed with the same key.
+    fn check_key(&self, request: &EmulatorRequest) -> Result<(), StorageError> {
+        match (
+            self.key_sha256.as_deref(),
+            request.header(&ENCRYPTION_KEY_SHA256),
+        ) {
+            (None, None) => Ok(()),
+            (Some(expected), Some(actual)) if expected == actual => Ok(()),
+            (Some(_), None) => Err(StorageError::new(
+                409,
+                "BlobUsesCustomerSpecifiedEncryption",
+                "the blob is encrypted with a customer-provided key, which was not specified",
+            )),
+            (Some(_), Some(_)) => Err(StorageError::new(
+                409,
+                "BlobCustomerSpecifiedEncryptionMismatch",
+                "the customer-provided key does not match the key the blob is encrypted with",
+            )),
+            (None, Some(_)) => Err(StorageError::new(
+                409,
+                "BlobDoesNotUseCustomerSpecifiedEncryption",
+                "the blob is not encrypted with a customer-provided key",
+            )),
+        }
+    }
+
+    fn headers(&self, response: EmulatorResponse) -> EmulatorResponse {
+        response
+            .optional_header(ENCRYPTION_KEY_SHA256, self.key_sha256.clone())
+            .optional_header(ENCRYPTION_SCOPE, self.scope.clone())
+    }
+}
+
+#[derive(Debug)]
+struct Blob {
+    kind: BlobKind,
+    created: OffsetDateTime,
+    etag: String,
+    last_modified: OffsetDateTime,
+    metadata: BTreeMap<String, String>,
+    properties: ContentProperties,
+    encryption: Encryption,
+    lease: Lease,
+    /// The committed blocks of a block blob, or the appended blocks of an
+    /// append blob. A blob uploaded in a single request has a single block
+    /// without an ID.
+    blocks: Vec<Block>,
+}
+
+impl Blob {
+    fn len(&self) -> usize {
+        self.blocks.iter().map(|block| block.data.len()).sum()
+    }
+
+    fn data(&self) -> Vec<u8> {
+        self.blocks
+            .iter()
+            .flat_map(|block| block.data.iter())
+            .copied()
+            .collect()
+    }
+
+    fn version(&self) -> ResourceVersion {
+        ResourceVersion {
+            etag: &self.etag,
+            last_modified: self.last_modified,
+        }
+    }
+}
+
+impl Container {
+    fn version(&self) -> ResourceVersion {
+        ResourceVersion {
+            etag: &self.etag,
+            last_modified: self.last_modified,
+        }
+    }
+
+    /// Whether anonymous requests may read blobs, or may also list them.
+    fn allows_anonymous(&self, list: bool) -> bool {
+        match self.public_access.as_deref() {
+            Some("container") => true,
+            Some("blob") => !list,
+            _ => false,
+        }
+    }
+}
+
+impl BlobService {
+    pub(crate) fn handle(
+        &mut self,
+        env: &mut Env,
+        request: &EmulatorRequest,
+        authorization: Authorization,
+    ) -> EmulatorResult {
+        let comp = request.query("comp");
+        match request.segments.as_slice() {
+            [] => {
+                require_authorized(authorization)?;
+                match (&request.method, comp) {
+                    (Method::Get, Some("list")) => self.list_containers(request, env.now()),
+                    _ => Err(not_implemented()),
+                }
+            }
+            [container] if request.query("restype") == Some("container") => {
+                self.handle_container(env, request, authorization, container)
+            }
+            [_] => Err(not_implemented()),
+            [container, name @ ..] => {
+                let container = self
+                    .containers
+                    .get_mut(container)
+                    .ok_or_else(container_not_found)?;
+                let name = name.join("/");
+                if authorization == Authorization::Anonymous {
+                    let is_read = matches!(request.method, Method::Get | Method::Head);
+                    if !is_read || comp.is_some() || !container.allows_anonymous(false) {
+                        return Err(resource_not_found());
+                    }
+                }
+                handle_blob(container, env, request, &name)
+            }
+        }
+    }
+
+    fn handle_container(
+        &mut self,
+        env: &mut Env,
+        request: &EmulatorRequest,
+        authorization: Authorization,
+        name: &str,
+    ) -> EmulatorResult {
+        let now = env.now();
+        let comp = request.query("comp");
+
+        if request.method == Method::Put && comp.is_none() {
+            require_authorized(authorization)?;
+            return self.create_container(env, request, name);
+        }
+
+        let container = self
+            .containers
+            .get_mut(name)
+            .ok_or_else(container_not_found)?;
+
+        if authorization == Authorization::Anonymous {
+            let allowed = matches!(request.method, Method::Get | Method::Head)
+                && matches!(comp, None | Some("list"))
+                && container.allows_anonymous(true);
+            if !allowed {
+                return Err(resource_not_found());
+            }
+        }
+
+        match (&request.method, comp) {
+            (Method::Delete, None) => {
+                container
+                    .lease
+                    .check_write(request, LeasedResource::Container, now)?;
+                conditions::check(request, Some(container.version()))?;
+                self.containers.remove(name);
+                Ok(EmulatorResponse::new(StatusCode::Accepted))
+            }
+            (Method::Get | Method::Head, None | Some("metadata")) => {
+                container
+                    .lease
+                    .check_read(request, LeasedResource::Container, now)?;
+                let mut response =
+                    container_version_headers(EmulatorResponse::new(StatusCode::Ok), container)
+                        .optional_header(BLOB_PUBLIC_ACCESS, container.public_access.clone());
+                response = metadata_headers(response, &container.metadata);
+                if comp.is_none() {
+                    for (name, value) in container.lease.response_headers(now) {
+                        response = response.header(name, value);
+                    }
+                    response = response
+                        .header(HAS_IMMUTABILITY_POLICY, "false")
+                        .header(HAS_LEGAL_HOLD, "false");
+                    if let Some(scope) = &container.default_encryption_scope {
+                        response = response
+                            .header(DEFAULT_ENCRYPTION_SCOPE, scope.clone())
+                            .header(
+                                DENY_ENCRYPTION_SCOPE_OVERRIDE,
+                                container.deny_encryption_scope_override.to_string(),
+                            );
+                    }
+                }
+                Ok(response)
+            }
+            (Method::Put, Some("metadata")) => {
+                container
+                    .lease
+                    .check_read(request, LeasedResource::Container, now)?;
+                conditions::check(request, Some(container.version()))?;
+                container.metadata = request.metadata();
+                touch_container(container, env);
+                Ok(container_version_headers(
+                    EmulatorResponse::new(StatusCode::Ok),
+                    container,
+                ))
+            }
+            (Method::Get | Method::Head, Some("acl")) => {
+                container
+                    .lease
+                    .check_read(request, LeasedResource::Container, now)?;
+                Ok(
+                    container_version_headers(EmulatorResponse::new(StatusCode::Ok), container)
+                        .optional_header(BLOB_PUBLIC_ACCESS, container.public_access.clone())
+                        .xml(container.signed_identifiers.clone()),
+                )
+            }
+            (Method::Put, Some("acl")) => {
+                container
+                    .lease
+                    .check_read(request, LeasedResource::Container, now)?;
+                conditions::check(request, Some(container.version()))?;
+                container.public_access = public_access(request)?;
+                container.signed_identifiers = request.signed_identifiers()?;
+                touch_container(container, env);
+                Ok(container_version_headers(
+                    EmulatorResponse::new(StatusCode::Ok),
+                    container,
+                ))
+            }
+            (Method::Put, Some("lease")) => {
+                conditions::check(request, Some(container.version()))?;
+                let outcome = container
+                    .lease
+                    .apply(request, LeasedResource::Container, now)?;
+                Ok(lease_response(
+                    container_version_headers(EmulatorResponse::new(StatusCode::Ok), container),
+                    outcome,
+                ))
+            }
+            (Method::Get, Some("list")) => list_blobs(container, request, now),
+            _ => Err(not_implemented()),
+        }
+    }
+
+    fn create_container(
+        &mut self,
+        env: &mut Env,
+        request: &EmulatorRequest,
+        name: &str,
+    ) -> EmulatorResult {
+        if !is_valid_container_name(name) {
+            return Err(StorageError::new(
+                400,
+                "InvalidResourceName",
+                "the specified resource name contains invalid characters",
+            ));
+        }
+        if self.containers.contains_key(name) {
+            return Err(StorageError::new(
+                409,
+                "ContainerAlreadyExists",
+                "the specified container already exists",
+            ));
+        }
+        let container = Container {
+            etag: env.etag(),
+            last_modified: env.now(),
+            metadata: request.metadata(),
+            lease: Lease::default(),
+            public_access: public_access(request)?,
+            default_encryption_scope: request.header(&DEFAULT_ENCRYPTION_SCOPE).map(str::to_owned),
+            deny_encryption_scope_override: request
+                .parse_header(&DENY_ENCRYPTION_SCOPE_OVERRIDE)?
+                .unwrap_or(false),
+            signed_identifiers: "<SignedIdentifiers />".to_owned(),
+            blobs: BTreeMap::new(),
+            staged_blocks: BTreeMap::new(),
+        };
+        let response =
+            container_version_headers(EmulatorResponse::new(StatusCode::Created), &container);
+        self.containers.insert(name.to_owned(), container);
+        Ok(response)
+    }
+
+    fn list_containers(&self, request: &EmulatorRequest, now: OffsetDateTime) -> EmulatorResult {
+        let prefix = request.query("prefix").unwrap_or_default();
+        let include_metadata = includes(request, "metadata");
+        let entries = self
+            .containers
+            .iter()
+            .filter(|(name, _)| name.starts_with(prefix))
+            .map(|(name, container)| (name.clone(), container))
+            .collect::<Vec<_>>();
+        let (page, next_marker) = page(entries, request)?;
+
+        let mut xml = format!(
+            "<EnumerationResults ServiceEndpoint=\"{}\">",
+            xml_escape(&request.service_endpoint())
+        );
+        xml.push_str(&optional_xml_element("Prefix", request.query("prefix")));
+        xml.push_str(&optional_xml_element("Marker", request.query("marker")));
+        xml.push_str(&optional_xml_element(
+            "MaxResults",
+            request.query("maxresults"),
+        ));
+        xml.push_str("<Containers>");
+        for (name, container) in page {
+            xml.push_str(&format!(
+                "<Container><Name>{}</Name><Properties>\
+                 <Last-Modified>{}</Last-Modified><Etag>{}</Etag>{}",
+                xml_escape(&name),
+                date::to_rfc1123(&container.last_modified),
+                container.etag.trim_matches('"'),
+                lease_xml(&container.lease, now),
+            ));
+            if let Some(public_access) = &container.public_access {
+                xml.push_str(&format!("<PublicAccess>{public_access}</PublicAccess>"));
+            }
+            if let Some(scope) = &container.default_encryption_scope {
+                xml.push_str(&format!(
+                    "<DefaultEncryptionScope>{}</DefaultEncryptionScope>\
+                     <DenyEncryptionScopeOverride>{}</DenyEncryptionScopeOverride>",
+                    xml_escape(scope),
+                    container.deny_encryption_scope_override
+                ));
+            }
+            xml.push_str(
+                "<HasImmutabilityPolicy>false</HasImmutabilityPolicy>\
+                 <HasLegalHold>false</HasLegalHold></Properties>",
+            );
+            if include_metadata {
+                xml.push_str(&metadata_xml(&container.metadata));
+            }
+            xml.push_str("</Container>");
+        }
+        xml.push_str("</Containers>");
+        xml.push_str(&xml_element("NextMarker", next_marker.as_deref()));
+        xml.push_str("</EnumerationResults>");
+        Ok(EmulatorResponse::new(StatusCode::Ok).xml(xml))
+    }
+}
+
+fn handle_blob(
+    container: &mut Container,
+    env: &mut Env,
+    request: &EmulatorRequest,
+    name: &str,
+) -> EmulatorResult {
+    let now = env.now();
+    match (&request.method, request.query("comp")) {
+        (Method::Put, None) => put_blob(container, env, request, name),
+        (Method::Put, Some("block")) => put_block(container, request, name, now),
+        (Method::Put, Some("blocklist")) => put_block_list(container, env, request, name),
+        (Method::Get, Some("blocklist")) => get_block_list(container, request, name, now),
+        (Method::Put, Some("appendblock")) => append_block(container, env, request, name),
+        (Method::Get, None) => get_blob(container, request, name, now),
+        (Method::Head, None) => {
+            let blob = container.blobs.get(name).ok_or_else(blob_not_found)?;
+            blob.lease.check_read(request, LeasedResource::Blob, now)?;
+            conditions::check(request, Some(blob.version()))?;
+            blob.encryption.check_key(request)?;
+            Ok(
+                blob_headers(EmulatorResponse::new(StatusCode::Ok), blob, now)
+                    .header(CONTENT_LENGTH, blob.len().to_string())
+                    .optional_header(CONTENT_MD5, blob.properties.content_md5.clone()),
+            )
+        }
+        (Method::Delete, None) => {
+            let blob = container.blobs.get(name).ok_or_else(blob_not_found)?;
+            blob.lease.check_write(request, LeasedResource::Blob, now)?;
+            conditions::check(request, Some(blob.version()))?;
+            container.blobs.remove(name);
+            container.staged_blocks.remove(name);
+            Ok(EmulatorResponse::new(StatusCode::Accepted).header(DELETE_TYPE_PERMANENT, "true"))
+        }
+        (Method::Get | Method::Head, Some("metadata")) => {
+            let blob = container.blobs.get(name).ok_or_else(blob_not_found)?;
+            blob.lease.check_read(request, LeasedResource::Blob, now)?;
+            conditions::check(request, Some(blob.version()))?;
+            blob.encryption.check_key(request)?;
+            Ok(metadata_headers(
+                blob_version_headers(EmulatorResponse::new(StatusCode::Ok), blob),
+                &blob.metadata,
+            ))
+        }
+        (Method::Put, Some("metadata" | "properties")) => {
+            let blob = container.blobs.get_mut(name).ok_or_else(blob_not_found)?;
+            blob.lease.check_write(request, LeasedResource::Blob, now)?;
+            conditions::check(request, Some(blob.version()))?;
+            if request.query("comp") == Some("metadata") {
+                blob.encryption.check_key(request)?;
+                blob.metadata = request.metadata();
+            } else {
+                // properties that are not sent are cleared, except the MD5 of the content
+                let properties = ContentProperties::from_request(request);
+                blob.properties = ContentProperties {
+                    content_md5: properties
+                        .content_md5
+                        .or_else(|| blob.properties.content_md5.take()),
+                    ..properties
+                };
+            }
+            blob.etag = env.etag();
+            blob.last_modified = now;
+            Ok(blob.encryption.headers(
+                blob_version_headers(EmulatorResponse::new(StatusCode::Ok), blob)
+                    .header(REQUEST_SERVER_ENCRYPTED, "true"),
+            ))
+        }
+        (Method::Put, Some("lease")) => {
+            let blob = container.blobs.get_mut(name).ok_or_else(blob_not_found)?;
+            conditions::check(request, Some(blob.version()))?;
+            let outcome = blob.lease.apply(request, LeasedResource::Blob, now)?;
+            Ok(lease_response(
+                blob_version_headers(EmulatorResponse::new(StatusCode::Ok), blob),
+                outcome,
+            ))
+        }
+        _ => Err(not_implemented()),
+    }
+}
+
+fn put_blob(
+    container: &mut Container,
+    env: &mut Env,
+    request: &EmulatorRequest,
+    name: &str,
+) -> EmulatorResult {
+    let now = env.now();
+    let kind = match request.header(&BLOB_TYPE) {
+        Some("BlockBlob") => BlobKind::Block,
+        Some("AppendBlob") => BlobKind::Append,
+        Some("PageBlob") => return Err(not_implemented()),
+        _ => {
+            return Err(StorageError::new(
+                400,
+                "InvalidHeaderValue",
+                "the x-ms-blob-type header is missing or not valid",
+            ))
+        }
+    };
+    if kind == BlobKind::Append && !request.body.is_empty() {
+        return Err(StorageError::new(
+            400,
+            "InvalidHeaderValue",
+            "an append blob must be created empty",
+        ));
+    }
+    let content_md5 = check_content_md5(request)?;
+    let encryption = Encryption::from_request(container, request)?;
+
+    let existing = container.blobs.get(name);
+    if let Some(existing) = existing {
+        existing
+            .lease
+            .check_write(request, LeasedResource::Blob, now)?;
+    } else if request.header(&LEASE_ID).is_some() {
+        return Err(blob_not_found());
+    }
+    conditions::check(request, existing.map(Blob::version))?;
+
+    let mut properties = ContentProperties::from_request(request);
+    if kind == BlobKind::Block && properties.content_md5.is_none() {
+        properties.content_md5 = Some(content_md5.clone());
+    }
+    let blocks = if request.body.is_empty() {
+        Vec::new()
+    } else {
+        vec![Block {
+            id: Vec::new(),
+            data: request.body.clone(),
+        }]
+    };
+    let blob = Blob {
+        kind,
+        created: now,
+        etag: env.etag(),
+        last_modified: now,
+        metadata: request.metadata(),
+        properties,
+        encryption,
+        // the lease survives the blob being overwritten
+        lease: existing.map(|blob| blob.lease.clone()).unwrap_or_default(),
+        blocks,
+    };
+    let mut response = blob.encryption.headers(
+        blob_version_headers(EmulatorResponse::new(StatusCode::Created), &blob)
+            .header(REQUEST_SERVER_ENCRYPTED, "true"),
+    );
+    if kind == BlobKind::Block {
+        response = response.header(CONTENT_MD5, content_md5);
+    }
+    container.blobs.insert(name.to_owned(), blob);
+    container.staged_blocks.remove(name);
+    Ok(response)
+}
+
+fn put_block(
+    container: &mut Container,
+    request: &EmulatorRequest,
+    name: &str,
+    now: OffsetDateTime,
+) -> EmulatorResult {
+    let id = block_id(request.query("blockid").unwrap_or_default())?;
+    let content_md5 = check_content_md5(request)?;
+    let encryption = Encryption::from_request(container, request)?;
+    if let Some(blob) = container.blobs.get(name) {
+        if blob.kind != BlobKind::Block {
+            return Err(invalid_blob_type());
+        }
+        blob.lease.check_read(request, LeasedResource::Blob, now)?;
+    }
+    let staged = container.staged_blocks.entry(name.to_owned()).or_default();
+    staged.retain(|block| block.id != id);
+    staged.push(Block {
+        id,
+        data: request.body.clone(),
+    });
+    Ok(encryption.headers(
+        EmulatorResponse::new(StatusCode::Created)
+            .header(CONTENT_MD5, content_md5)
+            .header(REQUEST_SERVER_ENCRYPTED, "true"),
+    ))
+}
+
+fn put_block_list(
+    container: &mut Container,
+    env: &mut Env,
+    request: &EmulatorRequest,
+    name: &str,
+) -> EmulatorResult {
+    let now = env.now();
+    let encryption = Encryption::from_request(container, request)?;
+    let existing = container.blobs.get(name);
+    if let Some(existing) = existing {
+        if existing.kind != BlobKind::Block {
+            return Err(invalid_blob_type());
+        }
+        existing
+            .lease
+            .check_write(request, LeasedResource::Blob, now)?;
+    }
+    conditions::check(request, existing.map(Blob::version))?;
+
+    let committed = existing
+        .map(|blob| blob.blocks.as_slice())
+        .unwrap_or_default();
+    let staged = container
+        .staged_blocks
+        .get(name)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let find = |blocks: &[Block], id: &[u8]| blocks.iter().find(|block| block.id == id).cloned();
+
+    let mut blocks = Vec::new();
+    for (element, value) in xml_children(request.body_str()?) {
+        let id = block_id(&value)?;
+        let block = match element.as_str() {
+            "Committed" => find(committed, &id),
+            "Uncommitted" => find(staged, &id),
+            "Latest" => find(staged, &id).or_else(|| find(committed, &id)),
+            _ => None,
+        };
+        blocks.push(block.ok_or_else(|| {
+            StorageError::new(
+                400,
+                "InvalidBlockList",
+                "the specified block list is invalid",
+            )
+        })?);
+    }
+
+    let blob = Blob {
+        kind: BlobKind::Block,
+        created: existing.map(|blob| blob.created).unwrap_or(now),
+        etag: env.etag(),
+        last_modified: now,
+        metadata: request.metadata(),
+        properties: ContentProperties::from_request(request),
+        encryption,
+        lease: existing.map(|blob| blob.lease.clone()).unwrap_or_default(),
+        blocks,
+    };
+    let response = blob.encryption.headers(
+        blob_version_headers(EmulatorResponse::new(StatusCode::Created), &blob)
+            .header(REQUEST_SERVER_ENCRYPTED, "true"),
+    );
+    container.blobs.insert(name.to_owned(), blob);
+    container.staged_blocks.remove(name);
+    Ok(response)
+}
+
+fn get_block_list(
+    container: &Container,
+    request: &EmulatorRequest,
+    name: &str,
+    now: OffsetDateTime,
+) -> EmulatorResult {
+    let blob = container.blobs.get(name);
+    let staged = container.staged_blocks.get(name);
+    if blob.is_none() && staged.is_none() {
+        return Err(blob_not_found());
+    }
+    if let Some(blob) = blob {
+        blob.lease.check_read(request, LeasedResource::Blob, now)?;
+    }
+
+    let block_list_type = request.query("blocklisttype").unwrap_or("committed");
+    let blocks_xml = |element: &str, blocks: &[Block]| {
+        let mut xml = format!("<{element}>");
+        for block in blocks {
+            xml.push_str(&format!(
+                "<Block><Name>{}</Name><Size>{}</Size></Block>",
+                base64::encode(&block.id),
+                block.data.len()
+            ));
+        }
+        xml.push_str(&format!("</{element}>"));
+        xml
+    };
+
+    let mut xml = "<BlockList>".to_owned();
+    if matches!(block_list_type, "committed" | "all") {
+        let committed = blob.map(|blob| blob.blocks.as_slice()).unwrap_or_default();
+        xml.push_str(&blocks_xml("CommittedBlocks", committed));
+    }
+    if matches!(block_list_type, "uncommitted" | "all") {
+        let staged = staged.map(Vec::as_slice).unwrap_or_default();
+        xml.push_str(&blocks_xml("UncommittedBlocks", staged));
+    }
+    xml.push_str("</BlockList>");
+
+    let mut response = EmulatorResponse::new(StatusCode::Ok);
+    if let Some(blob) = blob {
+        response = blob_version_headers(response, blob)
+            .header(BLOB_CONTENT_LENGTH, blob.len().to_string());
+    }
+    Ok(response.xml(xml))
+}
+
+fn append_block(
+    container: &mut Container,
+    env: &mut Env,
+    request: &EmulatorRequest,
+    name: &str,
+) -> EmulatorResult {
+    let now = env.now();
+    let content_md5 = check_content_md5(request)?;
+    let blob = container.blobs.get_mut(name).ok_or_else(blob_not_found)?;
+    if blob.kind != BlobKind::Append {
+        return Err(invalid_blob_type());
+    }
+    blob.lease.check_write(request, LeasedResource::Blob, now)?;
+    conditions::check(request, Some(blob.version()))?;
+    blob.encryption.check_key(request)?;
+
+    let offset = blob.len();
+    if let Some(position) = request.parse_header::<usize>(&BLOB_CONDITION_APPENDPOS)? {
+        if position != offset {
+            return Err(StorageError::new(
+                412,
+                "AppendPositionConditionNotMet",
+                "the append position condition specified was not met",
+            ));
+        }
+    }
+    if let Some(max_size) = request.parse_header::<usize>(&BLOB_CONDITION_MAXSIZE)? {
+        if offset + request.body.len() > max_size {
+            return Err(StorageError::new(
+                412,
+                "MaxBlobSizeConditionNotMet",
+                "the max blob size condition specified was not met",
+            ));
+        }
+    }
+    if blob.blocks.len() as u32 >= MAX_APPEND_BLOCKS {
+        return Err(StorageError::new(
+            409,
+            "BlockCountExceedsLimit",
+            "the committed block count cannot exceed the maximum limit of 50,000 blocks",
+        ));
+    }
+
+    blob.blocks.push(Block {
+        id: Vec::new(),
+        data: request.body.clone(),
+    });
+    blob.etag = env.etag();
+    blob.last_modified = now;
+    Ok(blob.encryption.headers(
+        blob_version_headers(EmulatorResponse::new(StatusCode::Created), blob)
+            .header(CONTENT_MD5, content_md5)
+            .header(BLOB_APPEND_OFFSET, offset.to_string())
+            .header(BLOB_COMMITTED_BLOCK_COUNT, blob.blocks.len().to_string())
+            .header(REQUEST_SERVER_ENCRYPTED, "true"),
+    ))
+}
+
+fn get_blob(
+    container: &Container,
+    request: &EmulatorRequest,
+    name: &str,
+    now: OffsetDateTime,
+) -> EmulatorResult {
+    let blob = container.blobs.get(name).ok_or_else(blob_not_found)?;
+    blob.lease.check_read(request, LeasedResource::Blob, now)?;
+    conditions::check(request, Some(blob.version()))?;
+    blob.encryption.check_key(request)?;
+
+    let data = blob.data();
+    let len = data.len();
+    let range = request
+        .header(&MS_RANGE)
+        .or_else(|| request.header(&RANGE))
+        .filter(|_| len > 0)
+        .map(parse_range)
+        .transpose()?;
+
+    let response = blob_headers(EmulatorResponse::new(StatusCode::Ok), blob, now);
+    let Some((start, end)) = range else {
+        return Ok(response
+            .optional_header(CONTENT_MD5, blob.properties.content_md5.clone())
+            .body(
+                blob.properties
+                    .content_type
+                    .as_deref()
+                    .unwrap_or(DEFAULT_CONTENT_TYPE),
+                data,
+            ));
+    };
+
+    if start >= len {
+        return Err(StorageError::new(
+            416,
+            "InvalidRange",
+            "the range specified is invalid for the current size of the resource",
+        ));
+    }
+    let end = end.map_or(len - 1, |end| end.min(len - 1));
+    let slice = Bytes::copy_from_slice(&data[start..=end]);
+    let mut response = response
+        .status(StatusCode::PartialContent)
+        .header(CONTENT_RANGE, format!("bytes {start}-{end}/{len}"))
+        .optional_header(BLOB_CONTENT_MD5, blob.properties.content_md5.clone());
+    if request.header(&RANGE_GET_CONTENT_MD5) == Some("true") {
+        response = response.header(CONTENT_MD5, base64::encode(md5::compute(&slice).0));
+    }
+    Ok(response.body(
+        blob.properties
+            .content_type
+            .as_deref()
+            .unwrap_or(DEFAULT_CONTENT_TYPE),
+        slice,
+    ))
+}
+
+fn list_blobs(
+    container: &Container,
+    request: &EmulatorRequest,
+    now: OffsetDateTime,
+) -> EmulatorResult {
+    let prefix = request.query("prefix").unwrap_or_default();
+    let delimiter = request.query("delimiter").filter(|d| !d.is_empty());
+    let include_metadata = includes(request, "metadata");
+    let include_uncommitted = includes(request, "uncommittedblobs");
+
+    enum Entry<'a> {
+        Blob(&'a Blob),
+        Uncommitted,
+        Prefix,
+    }
+
+    let mut names = container
+        .blobs
+        .iter()
+        .map(|(name, blob)| (name.as_str(), Entry::Blob(blob)))
+        .collect::<Vec<_>>();
+    if include_uncommitted {
+        names.extend(
+            container
+                .staged_blocks
+                .keys()
+                .filter(|name| !container.blobs.contains_key(*name))
+                .map(|name| (name.as_str(), Entry::Uncommitted)),
+        );
+        names.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+
+    let mut entries: Vec<(String, Entry)> = Vec::new();
+    for (name, entry) in names {
+        let Some(rest) = name.strip_prefix(prefix) else {
+            continue;
+        };
+        match delimiter.and_then(|delimiter| rest.find(delimiter).map(|i| i + delimiter.len())) {
+            Some(end) => {
+                let blob_prefix = format!("{prefix}{}", &rest[..end]);
+                if entries.last().map(|(name, _)| name) != Some(&blob_prefix) {
+                    entries.push((blob_prefix, Entry::Prefix));
+                }
+            }
+            None => entries.push((name.to_owned(), entry)),
+        }
+    }
+    let (page, next_marker) = page(entries, request)?;
+
+    let mut xml = format!(
+        "<EnumerationResults ServiceEndpoint=\"{}\" ContainerName=\"{}\">",
+        xml_escape(&request.service_endpoint()),
+        xml_escape(&request.segments[0]),
+    );
+    xml.push_str(&optional_xml_element("Prefix", request.query("prefix")));
+    xml.push_str(&optional_xml_element("Marker", request.query("marker")));
+    xml.push_str(&optional_xml_element(
+        "MaxResults",
+        request.query("maxresults"),
+    ));
+    xml.push_str(&optional_xml_element("Delimiter", delimiter));
+    xml.push_str("<Blobs>");
+    for (name, entry) in page {
+        match entry {
+            Entry::Prefix => {
+                xml.push_str(&format!(
+                    "<BlobPrefix><Name>{}</Name></BlobPrefix>",
+                    xml_escape(&name)
+                ));
+            }
+            Entry::Uncommitted => {
+                xml.push_str(&format!(
+                    "<Blob><Name>{}</Name><Properties>\
+                     <Creation-Time>{created}</Creation-Time><Last-Modified>{created}</Last-Modified>\
+                     <Etag>0x0</Etag><Content-Length>0</Content-Length>\
+                     <Content-Type>{DEFAULT_CONTENT_TYPE}</Content-Type>\
+                     <BlobType>BlockBlob</BlobType>\
+                     <LeaseStatus>unlocked</LeaseStatus><LeaseState>available</LeaseState>\
+                     <ServerEncrypted>true</ServerEncrypted></Properties></Blob>",
+                    xml_escape(&name),
+                    created = date::to_rfc1123(&container.last_modified),
+                ));
+            }
+            Entry::Blob(blob) => {
+                xml.push_str(&format!(
+                    "<Blob><Name>{}</Name><Properties>\
+                     <Creation-Time>{}</Creation-Time><Last-Modified>{}</Last-Modified>\
+                     <Etag>{}</Etag><Content-Length>{}</Content-Length>\
+                     <Content-Type>{}</Content-Type>",
+                    xml_escape(&name),
+                    date::to_rfc1123(&blob.created),
+                    date::to_rfc1123(&blob.last_modified),
+                    blob.etag.trim_matches('"'),
+                    blob.len(),
+                    xml_escape(
+                        blob.properties
+                            .content_type
+                            .as_deref()
+                            .unwrap_or(DEFAULT_CONTENT_TYPE)
+                    ),
+                ));
+                let properties = [
+                    ("Content-Encoding", &blob.properties.content_encoding),
+                    ("Content-Language", &blob.properties.content_language),
+                    ("Content-MD5", &blob.properties.content_md5),
+                    ("Cache-Control", &blob.properties.cache_control),
+                    ("Content-Disposition", &blob.properties.content_disposition),
+                ];
+                for (element, value) in properties {
+                    if let Some(value) = value {
+                        xml.push_str(&xml_element(element, Some(value)));
+                    }
+                }
+                xml.push_str(&format!(
+                    "<BlobType>{}</BlobType>{}<ServerEncrypted>true</ServerEncrypted></Properties>",
+                    blob.kind.as_str(),
+                    lease_xml(&blob.lease, now),
+                ));
+                if include_metadata {
+                    xml.push_str(&metadata_xml(&blob.metadata));
+                }
+                xml.push_str("</Blob>");
+            }
+        }
+    }
+    xml.push_str("</Blobs>");
+    xml.push_str(&xml_element("NextMarker", next_marker.as_deref()));
+    xml.push_str("</EnumerationResults>");
+    Ok(EmulatorResponse::new(StatusCode::Ok).xml(xml))
+}
+
+/// The entries of a listing page and the marker of the next page.
+type Page<T> = (Vec<(String, T)>, Option<String>);
+
+/// Apply the `marker` and `maxresults` parameters to entries sorted by name,
+/// returning the page and the marker of the next one.
+fn page<T>(entries: Vec<(String, T)>, request: &EmulatorRequest) -> Result<Page<T>, StorageError> {
+    let max_results = request
+        .parse_query::<usize>("maxresults")?
+        .unwrap_or(MAX_RESULTS)
+        .clamp(1, MAX_RESULTS);
+    let marker = request.query("marker").unwrap_or_default();
+    let mut entries = entries
+        .into_iter()
+        .filter(|(name, _)| name.as_str() >= marker)
+        .collect::<Vec<_>>();
+    let next_marker = if entries.len() > max_results {
+        let rest = entries.split_off(max_results);
+        rest.into_iter().next().map(|(name, _)| name)
+    } else {
+        None
+    };
+    Ok((entries, next_marker))
+}
+
+fn blob_version_headers(response: EmulatorResponse, blob: &Blob) -> EmulatorResponse {
+    response
+        .header(ETAG, blob.etag.clone())
+        .header(LAST_MODIFIED, date::to_rfc1123(&blob.last_modified))
+}
+
+fn container_version_headers(
+    response: EmulatorResponse,
+    container: &Container,
+) -> EmulatorResponse {
+    response
+        .header(ETAG, container.etag.clone())
+        .header(LAST_MODIFIED, date::to_rfc1123(&container.last_modified))
+}
+
+/// The headers of a get blob or get blob properties response, other than the
+/// MD5, which depends on the range requested.
+fn blob_headers(response: EmulatorResponse, blob: &Blob, now: OffsetDateTime) -> EmulatorResponse {
+    let properties = &blob.properties;
+    let mut response = blob
+        .encryption
+        .headers(blob_version_headers(response, blob))
+        .header(CREATION_TIME, date::to_rfc1123(&blob.created))
+        .header(BLOB_TYPE, blob.kind.as_str())
+        .header(SERVER_ENCRYPTED, "true")
+        .header(ACCEPT_RANGES, "bytes")
+        .header(
+            CONTENT_TYPE,
+            properties
+                .content_type
+                .as_deref()
+                .unwrap_or(DEFAULT_CONTENT_TYPE),
+        )
+        .optional_header(CONTENT_ENCODING, properties.content_encoding.clone())
+        .optional_header(CONTENT_LANGUAGE, properties.content_language.clone())
+        .optional_header(CONTENT_DISPOSITION, properties.content_disposition.clone())
+        .optional_header(CACHE_CONTROL, properties.cache_control.clone());
+    if blob.kind == BlobKind::Append {
+        response = response.header(BLOB_COMMITTED_BLOCK_COUNT, blob.blocks.len().to_string());
+    }
+    for (name, value) in blob.lease.response_headers(now) {
+        response = response.header(name, value);
+    }
+    metadata_headers(response, &blob.metadata)
+}
+
+fn metadata_headers(
+    mut response: EmulatorResponse,
+    metadata: &BTreeMap<String, String>,
+) -> EmulatorResponse {
+    for (name, value) in metadata {
+        response = response.header(HeaderName::from(format!("x-ms-meta-{name}")), value.clone());
+    }
+    response
+}
+
+fn metadata_xml(metadata: &BTreeMap<String, String>) -> String {
+    let mut xml = "<Metadata>".to_owned();
+    for (name, value) in metadata {
+        xml.push_str(&format!("<{name}>{}</{name}>", xml_escape(value)));
+    }
+    xml.push_str("</Metadata>");
+    xml
+}
+
+fn lease_response(response: EmulatorResponse, outcome: LeaseOutcome) -> EmulatorResponse {
+    match outcome {
+        LeaseOutcome::Acquired(id) => response.status(StatusCode::Created).header(LEASE_ID, id),
+        LeaseOutcome::Renewed(id) | LeaseOutcome::Changed(id) => response.header(LEASE_ID, id),
+        LeaseOutcome::Released => response,
+        LeaseOutcome::Broken(seconds) => response
+            .status(StatusCode::Accepted)
+            .header(LEASE_TIME, seconds.to_string()),
+    }
+}
+
+/// The `LeaseStatus` and `LeaseState` elements of a listed container or blob.
+fn lease_xml(lease: &Lease, now: OffsetDateTime) -> String {
+    let headers = lease.response_headers(now);
+    format!(
+        "<LeaseStatus>{}</LeaseStatus><LeaseState>{}</LeaseState>",
+        headers[0].1, headers[1].1
+    )
+}
+
+fn touch_container(container: &mut Container, env: &mut Env) {
+    container.etag = env.etag();
+    container.last_modified = env.now();
+}
+
+fn public_access(request: &EmulatorRequest) -> Result<Option<String>, StorageError> {
+    match request.header(&BLOB_PUBLIC_ACCESS) {
+        None => Ok(None),
+        Some(access @ ("container" | "blob")) => Ok(Some(access.to_owned())),
+        Some(_) => Err(StorageError::new(
+            400,
+            "InvalidHeaderValue",
+            "the value for the x-ms-blob-public-access header is not valid",
+        )),
+    }
+}
+
+/// The name and text of the child elements of the root element of a flat
+/// XML document, such as a block list.
+fn xml_children(xml: &str) -> Vec<(String, String)> {
+    let mut children = Vec::new();
+    let mut rest = xml;
+    let mut depth = 0;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('?') || tag.starts_with('!') || tag.ends_with('/') {
+            continue;
+        }
+        if tag.starts_with('/') {
+            depth -= 1;
+            continue;
+        }
+        depth += 1;
+        if depth == 2 {
+            let name = tag.split_whitespace().next().unwrap_or_default();
+            let text = rest.split('<').next().unwrap_or_default();
+            children.push((name.to_owned(), text.trim().to_owned()));
+        }
+    }
+    children
+}
+
+/// Parse a `bytes=<start>-[<end>]` range.
+fn parse_range(range: &str) -> Result<(usize, Option<usize>), StorageError> {
+    let invalid = || {
+        StorageError::new(
+            416,
+            "InvalidRange",
+            format!("the range specified is invalid: {range}"),
+        )
+    };
+    let (start, end) = range
+        .strip_prefix("bytes=")
+        .and_then(|range| range.split_once('-'))
+        .ok_or_else(invalid)?;
+    let start = start.parse::<usize>().map_err(|_| invalid())?;
+    let end = if end.is_empty() {
+        None
+    } else {
+        Some(end.parse::<usize>().map_err(|_| invalid())?)
+    };
+    if end.is_some_and(|end| end < start) {
+        return Err(invalid());
+    }
+    Ok((start, end))
+}
+
+fn block_id(id: &str) -> Result<Vec<u8>, StorageError> {
+    let invalid = || {
+        StorageError::new(
+            400,
+            "InvalidQueryParameterValue",
+            "the block ID is not a valid base64 string of at most 64 bytes",
+        )
+    };
+    let id = base64::decode(id).map_err(|_| invalid())?;
+    if id.is_empty() || id.len() > MAX_BLOCK_ID_LEN {
+        return Err(invalid());
+    }
+    Ok(id)
+}
+
+/// Validate the transactional `Content-MD5` of a request, returning the MD5
+/// of the body.
+fn check_content_md5(request: &EmulatorRequest) -> Result<String, StorageError> {
+    let content_md5 = base64::encode(md5::compute(&request.body).0);
+    if let Some(expected) = request.header(&CONTENT_MD5) {
+        if expected != content_md5 {
+            return Err(StorageError::new(
+                400,
+                "Md5Mismatch",
+                "the MD5 value specified in the request did not match the MD5 value calculated by the server",
+            ));
+        }
+    }
+    Ok(content_md5)
+}
+
+fn includes(request: &EmulatorRequest, dataset: &str) -> bool {
+    request
+        .query("include")
+        .is_some_and(|include| include.split(',').any(|item| item == dataset))
+}
+
+fn is_valid_container_name(name: &str) -> bool {
+    name == "$root"
+        || ((3..=63).contains(&name.len())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            && !name.starts_with('-')
+            && !name.ends_with('-')
+            && !name.contains("--"))
+}
+
+fn invalid_blob_type() -> StorageError {
+    StorageError::new(
+        409,
+        "InvalidBlobType",
+        "the blob type is invalid for this operation",
+    )
+}
+
+fn require_authorized(authorization: Authorization) -> Result<(), StorageError> {
+    match authorization {
+        Authorization::Authorized => Ok(()),
+        Authorization::Anonymous => Err(resource_not_found()),
+    }
+}
+
+fn container_not_found() -> StorageError {
+    StorageError::new(
+        404,
+        "ContainerNotFound",
+        "the specified container does not exist",
+    )
+}
+
+fn blob_not_found() -> StorageError {
+    StorageError::new(404, "BlobNotFound", "the specified blob does not exist")
+}
+
+fn resource_not_found() -> StorageError {
+    StorageError::new(
+        404,
+        "ResourceNotFound",
+        "the specified resource does not exist",
+    )
+}
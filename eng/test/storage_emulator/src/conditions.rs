@@ -0,0 +1,119 @@
+//! Conditional request headers.
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/specifying-conditional-headers-for-blob-service-operations>
+
+use crate::{request::EmulatorRequest, response::StorageError};
+use azure_core::{
+    date,
+    headers::{HeaderName, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_UNMODIFIED_SINCE},
+    Method,
+};
+use time::OffsetDateTime;
+
+/// The version of an existing resource that conditions are checked against.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ResourceVersion<'a> {
+    pub etag: &'a str,
+    pub last_modified: OffsetDateTime,
+}
+
+/// Check the `If-*` headers of a request against a resource, which is `None`
+/// when it does not exist yet.
+///
+/// Failed conditions are reported with `304 Not Modified` for reads and
+/// `412 Precondition Failed` for writes.
+pub(crate) fn check(
+    request: &EmulatorRequest,
+    resource: Option<ResourceVersion>,
+) -> Result<(), StorageError> {
+    let is_read = matches!(request.method, Method::Get | Method::Head);
+
+    if let Some(if_match) = request.header(&IF_MATCH) {
+        let matched = resource.is_some_and(|resource| etag_matches(if_match, resource.etag));
+        if !matched {
+            return Err(condition_not_met());
+        }
+    }
+
+    if let Some(if_none_match) = request.header(&IF_NONE_MATCH) {
+        if let Some(resource) = resource {
+            if if_none_match.trim() == "*" && !is_read {
+                return Err(StorageError::new(
+                    409,
+                    "BlobAlreadyExists",
+                    "the specified blob already exists",
+                ));
+            }
+            if etag_matches(if_none_match, resource.etag) {
+                return Err(not_modified_or_failed(is_read));
+            }
+        }
+    }
+
+    if let Some(resource) = resource {
+        let last_modified = truncate_to_seconds(resource.last_modified);
+        if let Some(since) = date_header(request, &IF_MODIFIED_SINCE)? {
+            if last_modified <= since {
+                return Err(not_modified_or_failed(is_read));
+            }
+        }
+        if let Some(since) = date_header(request, &IF_UNMODIFIED_SINCE)? {
+            if last_modified > since {
+                return Err(condition_not_met());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether an `If-Match`/`If-None-Match` value matches an ETag, ignoring quotes.
+pub(crate) fn etag_matches(condition: &str, etag: &str) -> bool {
+    let condition = condition.trim();
+    condition == "*"
+        || condition
+            .split(',')
+            .any(|candidate| candidate.trim().trim_matches('"') == etag.trim_matches('"'))
+}
+
+fn date_header(
+    request: &EmulatorRequest,
+    name: &HeaderName,
+) -> Result<Option<OffsetDateTime>, StorageError> {
+    request
+        .header(name)
+        .map(|value| {
+            date::parse_rfc1123(value).map_err(|_| {
+                StorageError::new(
+                    400,
+                    "InvalidHeaderValue",
+                    format!("invalid date in header {}: {value}", name.as_str()),
+                )
+            })
+        })
+        .transpose()
+}
+
+pub(crate) fn truncate_to_seconds(time: OffsetDateTime) -> OffsetDateTime {
+    time.replace_nanosecond(0).unwrap_or(time)
+}
+
+fn condition_not_met() -> StorageError {
+    StorageError::new(
+        412,
+        "ConditionNotMet",
+        "the condition specified using HTTP conditional header(s) is not met",
+    )
+}
+
+fn not_modified_or_failed(is_read: bool) -> StorageError {
+    if is_read {
+        StorageError::new(
+            304,
+            "ConditionNotMet",
+            "the condition specified using HTTP conditional header(s) is not met",
+        )
+    } else {
+        condition_not_met()
+    }
+}
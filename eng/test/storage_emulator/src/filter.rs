@@ -0,0 +1,407 @@
+//! A parser and evaluator for the OData `$filter` expressions of table queries.
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/querying-tables-and-entities>
+
+use azure_core::{base64, date};
+use serde_json::{Map, Value as Json};
+use std::cmp::Ordering;
+use time::OffsetDateTime;
+
+/// A typed property value, or literal.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Int(i64),
+    Double(f64),
+    Bool(bool),
+    DateTime(OffsetDateTime),
+    Guid(String),
+    Binary(Vec<u8>),
+}
+
+impl Value {
+    /// The typed value of a property of an entity, using its `@odata.type`
+    /// annotation when it has one.
+    pub fn from_property(properties: &Map<String, Json>, name: &str) -> Option<Self> {
+        let value = properties.get(name)?;
+        let edm_type = properties
+            .get(&format!("{name}@odata.type"))
+            .and_then(Json::as_str);
+        Some(match (edm_type, value) {
+            (Some("Edm.Int64"), Json::String(value)) => Value::Int(value.parse().ok()?),
+            (Some("Edm.Int32") | Some("Edm.Int64") | None, Json::Number(number))
+                if number.is_i64() =>
+            {
+                Value::Int(number.as_i64()?)
+            }
+            (Some("Edm.Double") | None, Json::Number(number)) => Value::Double(number.as_f64()?),
+            (Some("Edm.Double"), Json::String(value)) => Value::Double(value.parse().ok()?),
+            (Some("Edm.DateTime"), Json::String(value)) => {
+                Value::DateTime(date::parse_rfc3339(value).ok()?)
+            }
+            (Some("Edm.Guid"), Json::String(value)) => Value::Guid(value.to_lowercase()),
+            (Some("Edm.Binary"), Json::String(value)) => Value::Binary(base64::decode(value).ok()?),
+            (Some("Edm.Boolean") | None, Json::Bool(value)) => Value::Bool(*value),
+            (Some("Edm.String") | None, Json::String(value)) => Value::String(value.clone()),
+            _ => return None,
+        })
+    }
+
+    fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+            (Value::Double(a), Value::Double(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Double(b)) => (*a as f64).partial_cmp(b),
+            (Value::Double(a), Value::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+            (Value::DateTime(a), Value::DateTime(b)) => Some(a.cmp(b)),
+            (Value::Guid(a), Value::Guid(b)) => Some(a.cmp(b)),
+            (Value::Binary(a), Value::Binary(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Operand {
+    Property(String),
+    Literal(Value),
+}
+
+/// A parsed `$filter` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Filter {
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Compare(Operand, Operator, Operand),
+}
+
+impl Filter {
+    pub fn parse(filter: &str) -> Result<Self, String> {
+        let tokens = tokenize(filter)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let filter = parser.or()?;
+        match parser.peek() {
+            None => Ok(filter),
+            Some(token) => Err(format!("unexpected token {token:?}")),
+        }
+    }
+
+    /// Whether the properties satisfy the filter. Comparisons with missing
+    /// properties, or between values of different types, are false.
+    pub fn matches(&self, properties: &Map<String, Json>) -> bool {
+        match self {
+            Filter::And(a, b) => a.matches(properties) && b.matches(properties),
+            Filter::Or(a, b) => a.matches(properties) || b.matches(properties),
+            Filter::Not(filter) => !filter.matches(properties),
+            Filter::Compare(left, operator, right) => {
+                let value = |operand: &Operand| match operand {
+                    Operand::Property(name) => Value::from_property(properties, name),
+                    Operand::Literal(value) => Some(value.clone()),
+                };
+                let (Some(left), Some(right)) = (value(left), value(right)) else {
+                    return false;
+                };
+                let Some(ordering) = left.compare(&right) else {
+                    return false;
+                };
+                match operator {
+                    Operator::Eq => ordering == Ordering::Equal,
+                    Operator::Ne => ordering != Ordering::Equal,
+                    Operator::Gt => ordering == Ordering::Greater,
+                    Operator::Ge => ordering != Ordering::Less,
+                    Operator::Lt => ordering == Ordering::Less,
+                    Operator::Le => ordering != Ordering::Greater,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    OpenParen,
+    CloseParen,
+    Identifier(String),
+    Literal(Value),
+}
+
+fn tokenize(filter: &str) -> Result<Vec<Token>, String> {
+    let chars = filter.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    let quoted = |i: &mut usize| -> Result<String, String> {
+        // `i` is at the opening quote; a quote is escaped by doubling it
+        let mut value = String::new();
+        *i += 1;
+        loop {
+            match chars.get(*i) {
+                None => return Err("unterminated string literal".to_owned()),
+                Some('\'') if chars.get(*i + 1) == Some(&'\'') => {
+                    value.push('\'');
+                    *i += 2;
+                }
+                Some('\'') => {
+                    *i += 1;
+                    return Ok(value);
+                }
+                Some(c) => {
+                    value.push(*c);
+                    *i += 1;
+                }
+            }
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::OpenParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::CloseParen);
+            i += 1;
+        } else if c == '\'' {
+            tokens.push(Token::Literal(Value::String(quoted(&mut i)?)));
+        } else if c.is_ascii_digit() || c == '-' {
+            let start = i;
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric()
+                    || chars[i] == '.'
+                    || (matches!(chars[i], '+' | '-') && matches!(chars[i - 1], 'e' | 'E')))
+            {
+                i += 1;
+            }
+            let number = chars[start..i].iter().collect::<String>();
+            tokens.push(Token::Literal(parse_number(&number)?));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let identifier = chars[start..i].iter().collect::<String>();
+            if chars.get(i) == Some(&'\'') {
+                let value = quoted(&mut i)?;
+                tokens.push(Token::Literal(typed_literal(&identifier, &value)?));
+            } else {
+                tokens.push(Token::Identifier(identifier));
+            }
+        } else {
+            return Err(format!("unexpected character '{c}'"));
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_number(number: &str) -> Result<Value, String> {
+    let invalid = || format!("invalid number {number}");
+    if let Some(number) = number
+        .strip_suffix('L')
+        .or_else(|| number.strip_suffix('l'))
+    {
+        return number.parse().map(Value::Int).map_err(|_| invalid());
+    }
+    if number.contains(['.', 'e', 'E']) {
+        return number.parse().map(Value::Double).map_err(|_| invalid());
+    }
+    number.parse().map(Value::Int).map_err(|_| invalid())
+}
+
+fn typed_literal(prefix: &str, value: &str) -> Result<Value, String> {
+    match prefix {
+        "datetime" => date::parse_rfc3339(value)
+            .map(Value::DateTime)
+            .map_err(|_| format!("invalid datetime literal {value}")),
+        "guid" => Ok(Value::Guid(value.to_lowercase())),
+        "X" | "binary" => {
+            if value.len() % 2 != 0 {
+                return Err(format!("invalid binary literal {value}"));
+            }
+            (0..value.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Binary)
+                .map_err(|_| format!("invalid binary literal {value}"))
+        }
+        _ => Err(format!("unknown literal type {prefix}")),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Identifier(identifier)) if identifier == keyword => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn or(&mut self) -> Result<Filter, String> {
+        let mut filter = self.and()?;
+        while self.keyword("or") {
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        Ok(filter)
+    }
+
+    fn and(&mut self) -> Result<Filter, String> {
+        let mut filter = self.not()?;
+        while self.keyword("and") {
+            filter = Filter::And(Box::new(filter), Box::new(self.not()?));
+        }
+        Ok(filter)
+    }
+
+    fn not(&mut self) -> Result<Filter, String> {
+        if self.keyword("not") {
+            return Ok(Filter::Not(Box::new(self.not()?)));
+        }
+        if self.peek() == Some(&Token::OpenParen) {
+            self.position += 1;
+            let filter = self.or()?;
+            return match self.next() {
+                Some(Token::CloseParen) => Ok(filter),
+                _ => Err("expected ')'".to_owned()),
+            };
+        }
+        let left = self.operand()?;
+        let operator = match self.peek() {
+            Some(Token::Identifier(operator)) => match operator.as_str() {
+                "eq" => Operator::Eq,
+                "ne" => Operator::Ne,
+                "gt" => Operator::Gt,
+                "ge" => Operator::Ge,
+                "lt" => Operator::Lt,
+                "le" => Operator::Le,
+                // a boolean property on its own is true when it is set to true
+                _ => return Ok(Filter::Compare(left, Operator::Eq, bool_literal(true))),
+            },
+            _ => return Ok(Filter::Compare(left, Operator::Eq, bool_literal(true))),
+        };
+        self.position += 1;
+        let right = self.operand()?;
+        Ok(Filter::Compare(left, operator, right))
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Literal(value)) => Ok(Operand::Literal(value.clone())),
+            Some(Token::Identifier(identifier)) => Ok(match identifier.as_str() {
+                "true" => bool_literal(true),
+                "false" => bool_literal(false),
+                _ => Operand::Property(identifier.clone()),
+            }),
+            token => Err(format!("expected a property or a literal, found {token:?}")),
+        }
+    }
+}
+
+fn bool_literal(value: bool) -> Operand {
+    Operand::Literal(Value::Bool(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn properties() -> Map<String, Json> {
+        let Json::Object(properties) = json!({
+            "PartitionKey": "p1",
+            "RowKey": "r'1",
+            "Age": 42,
+            "Big": "9007199254740993",
+            "Big@odata.type": "Edm.Int64",
+            "Ratio": 0.5,
+            "Active": true,
+            "When": "2022-06-01T00:00:00Z",
+            "When@odata.type": "Edm.DateTime",
+            "Id": "C9DA6455-213D-42C9-9A79-3E9149A57833",
+            "Id@odata.type": "Edm.Guid",
+            "Data": "AQI=",
+            "Data@odata.type": "Edm.Binary",
+        }) else {
+            unreachable!()
+        };
+        properties
+    }
+
+    fn matches(filter: &str) -> bool {
+        Filter::parse(filter).unwrap().matches(&properties())
+    }
+
+    #[test]
+    fn compare_typed_values() {
+        assert!(matches("PartitionKey eq 'p1'"));
+        assert!(matches("RowKey eq 'r''1'"));
+        assert!(matches("Age gt 41 and Age le 42"));
+        assert!(matches("Big eq 9007199254740993L"));
+        assert!(matches("Ratio lt 1.0 and Age gt 41.5"));
+        assert!(matches("Active eq true"));
+        assert!(matches("Active"));
+        assert!(matches("When ge datetime'2022-01-01T00:00:00Z'"));
+        assert!(matches("Id eq guid'c9da6455-213d-42c9-9a79-3e9149a57833'"));
+        assert!(matches("Data eq X'0102'"));
+    }
+
+    #[test]
+    fn combine_conditions() {
+        assert!(matches(
+            "(Age eq 1 or Age eq 42) and not (PartitionKey ne 'p1')"
+        ));
+        assert!(!matches("Age eq 1 or Age eq 2"));
+        assert!(matches("not Age eq 1"));
+    }
+
+    #[test]
+    fn missing_properties_and_type_mismatches_are_false() {
+        assert!(!matches("Missing eq 'x'"));
+        assert!(!matches("Missing ne 'x'"));
+        assert!(!matches("Age eq '42'"));
+    }
+
+    #[test]
+    fn reject_malformed_filters() {
+        assert!(Filter::parse("Age eq").is_err());
+        assert!(Filter::parse("(Age eq 1").is_err());
+        assert!(Filter::parse("Name eq 'unterminated").is_err());
+        assert!(Filter::parse("When eq datetime'yesterday'").is_err());
+    }
+}
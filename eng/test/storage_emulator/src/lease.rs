@@ -0,0 +1,328 @@
+//! The lease state machine shared by containers and blobs.
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/lease-blob>
+
+use crate::{request::EmulatorRequest, response::StorageError};
+use azure_core::headers::{
+    HeaderName, LEASE_ACTION, LEASE_BREAK_PERIOD, LEASE_DURATION, LEASE_ID, LEASE_STATE,
+    LEASE_STATUS, PROPOSED_LEASE_ID,
+};
+use time::{Duration, OffsetDateTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Available,
+    Leased,
+    Breaking,
+    Broken,
+}
+
+/// The lease on a container or a blob.
+#[derive(Debug, Clone)]
+pub(crate) struct Lease {
+    state: State,
+    id: Option<String>,
+    /// `None` for an infinite lease.
+    duration: Option<Duration>,
+    /// When a fixed-duration lease expires, or when a lease being broken breaks.
+    until: Option<OffsetDateTime>,
+}
+
+impl Default for Lease {
+    fn default() -> Self {
+        Self {
+            state: State::Available,
+            id: None,
+            duration: None,
+            until: None,
+        }
+    }
+}
+
+/// What a lease request did, to be reported in the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LeaseOutcome {
+    Acquired(String),
+    Renewed(String),
+    Changed(String),
+    Released,
+    /// The seconds remaining until the lease breaks.
+    Broken(i64),
+}
+
+/// Whether the leased resource is a container or a blob, which changes the
+/// error codes reported.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum LeasedResource {
+    Container,
+    Blob,
+}
+
+impl LeasedResource {
+    fn name(self) -> &'static str {
+        match self {
+            LeasedResource::Container => "Container",
+            LeasedResource::Blob => "Blob",
+        }
+    }
+}
+
+impl Lease {
+    /// The lease state as observed at `now`, which accounts for expired
+    /// leases and finished break periods.
+    fn effective_state(&self, now: OffsetDateTime) -> (State, bool) {
+        match (self.state, self.until) {
+            (State::Leased, Some(until)) if until <= now => (State::Available, true),
+            (State::Breaking, Some(until)) if until <= now => (State::Broken, false),
+            (state, _) => (state, false),
+        }
+    }
+
+    fn is_active(&self, now: OffsetDateTime) -> bool {
+        matches!(self.effective_state(now).0, State::Leased | State::Breaking)
+    }
+
+    pub(crate) fn response_headers(&self, now: OffsetDateTime) -> Vec<(HeaderName, &'static str)> {
+        let (state, expired) = self.effective_state(now);
+        let (status, state_name) = match state {
+            State::Available if expired => ("unlocked", "expired"),
+            State::Available => ("unlocked", "available"),
+            State::Leased => ("locked", "leased"),
+            State::Breaking => ("locked", "breaking"),
+            State::Broken => ("unlocked", "broken"),
+        };
+        let mut headers = vec![(LEASE_STATUS, status), (LEASE_STATE, state_name)];
+        if state == State::Leased {
+            let duration = if self.duration.is_some() {
+                "fixed"
+            } else {
+                "infinite"
+            };
+            headers.push((LEASE_DURATION, duration));
+        }
+        headers
+    }
+
+    /// Check the `x-ms-lease-id` header of a request that writes to, or
+    /// deletes, the leased resource.
+    pub(crate) fn check_write(
+        &self,
+        request: &EmulatorRequest,
+        resource: LeasedResource,
+        now: OffsetDateTime,
+    ) -> Result<(), StorageError> {
+        let lease_id = request.header(&LEASE_ID);
+        let name = resource.name();
+        match (self.is_active(now), lease_id) {
+            (true, None) => Err(StorageError::new(
+                412,
+                "LeaseIdMissing",
+                format!("there is currently a lease on the {name} and no lease ID was specified in the request"),
+            )),
+            (true, Some(lease_id)) if Some(lease_id) != self.id.as_deref() => {
+                Err(StorageError::new(
+                    412,
+                    format!("LeaseIdMismatchWith{name}Operation"),
+                    "the lease ID specified did not match the lease ID for the resource",
+                ))
+            }
+            (false, Some(_)) => Err(StorageError::new(
+                412,
+                format!("LeaseNotPresentWith{name}Operation"),
+                format!("there is currently no lease on the {name}"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the `x-ms-lease-id` header of a request that reads the leased
+    /// resource. Reads do not need the lease, but a lease ID must match.
+    pub(crate) fn check_read(
+        &self,
+        request: &EmulatorRequest,
+        resource: LeasedResource,
+        now: OffsetDateTime,
+    ) -> Result<(), StorageError> {
+        if request.header(&LEASE_ID).is_some() {
+            self.check_write(request, resource, now)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Apply the `x-ms-lease-action` of a lease request.
+    pub(crate) fn apply(
+        &mut self,
+        request: &EmulatorRequest,
+        resource: LeasedResource,
+        now: OffsetDateTime,
+    ) -> Result<LeaseOutcome, StorageError> {
+        let (state, _) = self.effective_state(now);
+        let lease_id = request.header(&LEASE_ID);
+        let proposed_lease_id = request.header(&PROPOSED_LEASE_ID);
+        let matches_lease = lease_id.is_some() && lease_id == self.id.as_deref();
+        let name = resource.name();
+
+        let not_present = || {
+            StorageError::new(
+                409,
+                "LeaseNotPresentWithLeaseOperation",
+                "there is currently no lease on the resource",
+            )
+        };
+        let mismatch = || {
+            StorageError::new(
+                409,
+                "LeaseIdMismatchWithLeaseOperation",
+                "the lease ID specified did not match the lease ID for the resource",
+            )
+        };
+        let required = |value: Option<&str>, header: &HeaderName| {
+            value.map(str::to_owned).ok_or_else(|| {
+                StorageError::new(
+                    400,
+                    "MissingRequiredHeader",
+                    format!("the header {} is required", header.as_str()),
+                )
+            })
+        };
+
+        match request.header(&LEASE_ACTION) {
+            Some("acquire") => {
+                let duration = match request.parse_header::<i64>(&LEASE_DURATION)? {
+                    Some(-1) => None,
+                    Some(seconds @ 15..=60) => Some(Duration::seconds(seconds)),
+                    _ => {
+                        return Err(StorageError::new(
+                            400,
+                            "InvalidHeaderValue",
+                            "the lease duration must be -1 or between 15 and 60 seconds",
+                        ))
+                    }
+                };
+                match state {
+                    State::Breaking => {
+                        return Err(StorageError::new(
+                            409,
+                            "LeaseIsBreakingAndCannotBeAcquired",
+                            "there is already a lease present and it is being broken",
+                        ))
+                    }
+                    State::Leased
+                        if proposed_lease_id.is_none()
+                            || proposed_lease_id != self.id.as_deref() =>
+                    {
+                        return Err(StorageError::new(
+                            409,
+                            "LeaseAlreadyPresent",
+                            "there is already a lease present",
+                        ))
+                    }
+                    _ => {}
+                }
+                let id = proposed_lease_id
+                    .map(str::to_owned)
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+                *self = Lease {
+                    state: State::Leased,
+                    id: Some(id.clone()),
+                    duration,
+                    until: duration.map(|duration| now + duration),
+                };
+                Ok(LeaseOutcome::Acquired(id))
+            }
+            Some("renew") => {
+                let id = required(lease_id, &LEASE_ID)?;
+                match state {
+                    State::Available if self.id.is_none() => Err(not_present()),
+                    State::Breaking | State::Broken if matches_lease => Err(StorageError::new(
+                        409,
+                        "LeaseIsBrokenAndCannotBeRenewed",
+                        "the lease ID matched, but the lease has been broken explicitly and cannot be renewed",
+                    )),
+                    _ if !matches_lease => Err(mismatch()),
+                    _ => {
+                        self.state = State::Leased;
+                        self.until = self.duration.map(|duration| now + duration);
+                        Ok(LeaseOutcome::Renewed(id))
+                    }
+                }
+            }
+            Some("change") => {
+                let _ = required(lease_id, &LEASE_ID)?;
+                let proposed = required(proposed_lease_id, &PROPOSED_LEASE_ID)?;
+                match state {
+                    State::Available | State::Broken => Err(not_present()),
+                    State::Breaking => Err(StorageError::new(
+                        409,
+                        "LeaseIsBreakingAndCannotBeChanged",
+                        "the lease ID matched, but the lease is currently in breaking state and cannot be changed",
+                    )),
+                    State::Leased
+                        if !matches_lease && Some(proposed.as_str()) != self.id.as_deref() =>
+                    {
+                        Err(mismatch())
+                    }
+                    State::Leased => {
+                        self.id = Some(proposed.clone());
+                        Ok(LeaseOutcome::Changed(proposed))
+                    }
+                }
+            }
+            Some("release") => {
+                let _ = required(lease_id, &LEASE_ID)?;
+                match state {
+                    State::Available if self.id.is_none() => Err(not_present()),
+                    _ if !matches_lease => Err(mismatch()),
+                    _ => {
+                        *self = Lease::default();
+                        Ok(LeaseOutcome::Released)
+                    }
+                }
+            }
+            Some("break") => {
+                let period = request.parse_header::<i64>(&LEASE_BREAK_PERIOD)?;
+                if period.is_some_and(|period| !(0..=60).contains(&period)) {
+                    return Err(StorageError::new(
+                        400,
+                        "InvalidHeaderValue",
+                        "the lease break period must be between 0 and 60 seconds",
+                    ));
+                }
+                match state {
+                    State::Available => Err(not_present()),
+                    State::Broken => Ok(LeaseOutcome::Broken(0)),
+                    State::Leased | State::Breaking => {
+                        let remaining = self.until.map(|until| (until - now).whole_seconds());
+                        let seconds = match (state, period, remaining) {
+                            // a lease being broken can only break sooner
+                            (State::Breaking, Some(period), Some(remaining)) => {
+                                period.min(remaining)
+                            }
+                            (State::Breaking, None, Some(remaining)) => remaining,
+                            (_, Some(period), Some(remaining)) => period.min(remaining),
+                            (_, Some(period), None) => period,
+                            (_, None, Some(remaining)) => remaining,
+                            // infinite leases break immediately by default
+                            (_, None, None) => 0,
+                        }
+                        .max(0);
+                        if seconds == 0 {
+                            self.state = State::Broken;
+                            self.until = None;
+                        } else {
+                            self.state = State::Breaking;
+                            self.until = Some(now + Duration::seconds(seconds));
+                        }
+                        Ok(LeaseOutcome::Broken(seconds))
+                    }
+                }
+            }
+            _ => Err(StorageError::new(
+                400,
+                "InvalidHeaderValue",
+                format!("the {name} lease request has no valid x-ms-lease-action header"),
+            )),
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! The Queue service: queues and their messages.
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/queue-service-rest-api>
+
+use crate::{
+    not_implemented,
+    request::EmulatorRequest,
+    response::{
+        optional_xml_element, xml_element, xml_escape, xml_unescape, EmulatorResponse,
+        EmulatorResult, StorageError,
+    },
+    shared_key::Authorization,
+    Env,
+};
+use azure_core::{date, headers::HeaderName, Method, StatusCode};
+use std::collections::{BTreeMap, VecDeque};
+use time::{Duration, OffsetDateTime};
+
+const APPROXIMATE_MESSAGES_COUNT: HeaderName =
+    HeaderName::from_static("x-ms-approximate-messages-count");
+const POP_RECEIPT: HeaderName = HeaderName::from_static("x-ms-popreceipt");
+const TIME_NEXT_VISIBLE: HeaderName = HeaderName::from_static("x-ms-time-next-visible");
+
+const MAX_RESULTS: usize = 5000;
+const MAX_MESSAGE_SIZE: usize = 64 * 1024;
+const MAX_MESSAGES: usize = 32;
+const DEFAULT_TTL: Duration = Duration::days(7);
+const DEFAULT_VISIBILITY_TIMEOUT: Duration = Duration::seconds(30);
+const MAX_VISIBILITY_TIMEOUT: Duration = Duration::days(7);
+
+#[derive(Debug, Default)]
+pub(crate) struct QueueService {
+    queues: BTreeMap<String, Queue>,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    metadata: BTreeMap<String, String>,
+    signed_identifiers: String,
+    messages: VecDeque<Message>,
+}
+
+#[derive(Debug, Clone)]
+struct Message {
+    id: String,
+    text: String,
+    inserted: OffsetDateTime,
+    /// `None` for messages that never expire.
+    expires: Option<OffsetDateTime>,
+    next_visible: OffsetDateTime,
+    pop_receipt: String,
+    dequeue_count: u64,
+}
+
+impl Queue {
+    fn purge_expired(&mut self, now: OffsetDateTime) {
+        self.messages
+            .retain(|message| message.expires.map_or(true, |expires| expires > now));
+    }
+}
+
+impl QueueService {
+    pub(crate) fn handle(
+        &mut self,
+        env: &mut Env,
+        request: &EmulatorRequest,
+        authorization: Authorization,
+    ) -> EmulatorResult {
+        if authorization == Authorization::Anonymous {
+            return Err(StorageError::new(
+                403,
+                "AuthenticationFailed",
+                "the request is not authorized",
+            ));
+        }
+
+        let now = env.now();
+        let comp = request.query("comp");
+        match request.segments.as_slice() {
+            [] => match (&request.method, comp) {
+                (Method::Get, Some("list")) => self.list_queues(request),
+                _ => Err(not_implemented()),
+            },
+            [name] => match (&request.method, comp) {
+                (Method::Put, None) => self.create_queue(request, name),
+                (Method::Delete, None) => {
+                    self.queues.remove(name).ok_or_else(queue_not_found)?;
+                    Ok(EmulatorResponse::new(StatusCode::NoContent))
+                }
+                (Method::Get | Method::Head, Some("metadata")) => {
+                    let queue = self.queue(name, now)?;
+                    let mut response = EmulatorResponse::new(StatusCode::Ok)
+                        .header(APPROXIMATE_MESSAGES_COUNT, queue.messages.len().to_string());
+                    for (name, value) in &queue.metadata {
+                        response = response
+                            .header(HeaderName::from(format!("x-ms-meta-{name}")), value.clone());
+                    }
+                    Ok(response)
+                }
+                (Method::Put, Some("metadata")) => {
+                    self.queue(name, now)?.metadata = request.metadata();
+                    Ok(EmulatorResponse::new(StatusCode::NoContent))
+                }
+                (Method::Get | Method::Head, Some("acl")) => {
+                    let queue = self.queue(name, now)?;
+                    Ok(EmulatorResponse::new(StatusCode::Ok).xml(queue.signed_identifiers.clone()))
+                }
+                (Method::Put, Some("acl")) => {
+                    let signed_identifiers = request.signed_identifiers()?;
+                    self.queue(name, now)?.signed_identifiers = signed_identifiers;
+                    Ok(EmulatorResponse::new(StatusCode::NoContent))
+                }
+                _ => Err(not_implemented()),
+            },
+            [name, messages] if messages == "messages" => {
+                let queue = self.queue(name, now)?;
+                match &request.method {
+                    Method::Post => put_message(queue, request, now),
+                    Method::Get if request.query("peekonly") == Some("true") => {
+                        peek_messages(queue, request, now)
+                    }
+                    Method::Get => get_messages(queue, request, now),
+                    Method::Delete => {
+                        queue.messages.clear();
+                        Ok(EmulatorResponse::new(StatusCode::NoContent))
+                    }
+                    _ => Err(not_implemented()),
+                }
+            }
+            [name, messages, id] if messages == "messages" => {
+                let queue = self.queue(name, now)?;
+                let pop_receipt = request.query("popreceipt").ok_or_else(|| {
+                    StorageError::new(
+                        400,
+                        "MissingRequiredQueryParameter",
+                        "the popreceipt query parameter is required",
+                    )
+                })?;
+                let index = queue
+                    .messages
+                    .iter()
+                    .position(|message| message.id == *id)
+                    .ok_or_else(|| {
+                        StorageError::new(
+                            404,
+                            "MessageNotFound",
+                            "the specified message does not exist",
+                        )
+                    })?;
+                if queue.messages[index].pop_receipt != pop_receipt {
+                    return Err(StorageError::new(
+                        400,
+                        "PopReceiptMismatch",
+                        "the specified pop receipt did not match the pop receipt for a dequeued message",
+                    ));
+                }
+                match &request.method {
+                    Method::Delete => {
+                        queue.messages.remove(index);
+                        Ok(EmulatorResponse::new(StatusCode::NoContent))
+                    }
+                    Method::Put => {
+                        let visibility_timeout = visibility_timeout(request)?.ok_or_else(|| {
+                            StorageError::new(
+                                400,
+                                "MissingRequiredQueryParameter",
+                                "the visibilitytimeout query parameter is required",
+                            )
+                        })?;
+                        let text = if request.body.is_empty() {
+                            None
+                        } else {
+                            Some(message_text(request)?)
+                        };
+                        let message = &mut queue.messages[index];
+                        if let Some(text) = text {
+                            message.text = text;
+                        }
+                        message.next_visible = now + visibility_timeout;
+                        message.pop_receipt = new_pop_receipt();
+                        Ok(EmulatorResponse::new(StatusCode::NoContent)
+                            .header(POP_RECEIPT, message.pop_receipt.clone())
+                            .header(TIME_NEXT_VISIBLE, date::to_rfc1123(&message.next_visible)))
+                    }
+                    _ => Err(not_implemented()),
+                }
+            }
+            _ => Err(StorageError::new(
+                400,
+                "InvalidUri",
+                "the requested URI does not represent any resource on the server",
+            )),
+        }
+    }
+
+    /// An existing queue, with its expired messages removed.
+    fn queue(&mut self, name: &str, now: OffsetDateTime) -> Result<&mut Queue, StorageError> {
+        let queue = self.queues.get_mut(name).ok_or_else(queue_not_found)?;
+        queue.purge_expired(now);
+        Ok(queue)
+    }
+
+    fn create_queue(&mut self, request: &EmulatorRequest, name: &str) -> EmulatorResult {
+        if !is_valid_queue_name(name) {
+            return Err(StorageError::new(
+                400,
+                "InvalidResourceName",
+                "the specified resource name contains invalid characters",
+            ));
+        }
+        let metadata = request.metadata();
+        match self.queues.get(name) {
+            // creating an existing queue succeeds as long as its metadata is the same
+            Some(queue) if queue.metadata == metadata => {
+                Ok(EmulatorResponse::new(StatusCode::NoContent))
+            }
+            Some(_) => Err(StorageError::new(
+                409,
+                "QueueAlreadyExists",
+                "the specified queue already exists",
+            )),
+            None => {
+                self.queues.insert(
+                    name.to_owned(),
+                    Queue {
+                        metadata,
+                        signed_identifiers: "<SignedIdentifiers />".to_owned(),
+                        messages: VecDeque::new(),
+                    },
+                );
+                Ok(EmulatorResponse::new(StatusCode::Created))
+            }
+        }
+    }
+
+    fn list_queues(&self, request: &EmulatorRequest) -> EmulatorResult {
+        let prefix = request.query("prefix").unwrap_or_default();
+        let marker = request.query("marker").unwrap_or_default();
+        let max_results = request
+            .parse_query::<usize>("maxresults")?
+            .unwrap_or(MAX_RESULTS)
+            .clamp(1, MAX_RESULTS);
+        let include_metadata = request.query("include") == Some("metadata");
+
+        let mut queues = self
+            .queues
+            .iter()
+            .filter(|(name, _)| name.starts_with(prefix) && name.as_str() >= marker);
+        let page = queues.by_ref().take(max_results).collect::<Vec<_>>();
+        let next_marker = queues.next().map(|(name, _)| name.as_str());
+
+        let mut xml = format!(
+            "<EnumerationResults ServiceEndpoint=\"{}\">",
+            xml_escape(&request.service_endpoint())
+        );
+        xml.push_str(&optional_xml_element("Prefix", request.query("prefix")));
+        xml.push_str(&optional_xml_element("Marker", request.query("marker")));
+        xml.push_str(&optional_xml_element(
+            "MaxResults",
+            request.query("maxresults"),
+        ));
+        xml.push_str("<Queues>");
+        for (name, queue) in page {
+            xml.push_str(&format!("<Queue><Name>{}</Name>", xml_escape(name)));
+            if include_metadata {
+                xml.push_str("<Metadata>");
+                for (name, value) in &queue.metadata {
+                    xml.push_str(&format!("<{name}>{}</{name}>", xml_escape(value)));
+                }
+                xml.push_str("</Metadata>");
+            }
+            xml.push_str("</Queue>");
+        }
+        xml.push_str("</Queues>");
+        xml.push_str(&xml_element("NextMarker", next_marker));
+        xml.push_str("</EnumerationResults>");
+        Ok(EmulatorResponse::new(StatusCode::Ok).xml(xml))
+    }
+}
+
+fn put_message(
+    queue: &mut Queue,
+    request: &EmulatorRequest,
+    now: OffsetDateTime,
+) -> EmulatorResult {
+    let text = message_text(request)?;
+    let ttl = match request.parse_query::<i64>("messagettl")? {
+        None => Some(DEFAULT_TTL),
+        Some(-1) => None,
+        Some(seconds) if seconds > 0 => Some(Duration::seconds(seconds)),
+        Some(_) => return Err(invalid_query("messagettl")),
+    };
+    let visibility_timeout = visibility_timeout(request)?.unwrap_or(Duration::ZERO);
+    if ttl.is_some_and(|ttl| visibility_timeout >= ttl) {
+        return Err(invalid_query("visibilitytimeout"));
+    }
+
+    let message = Message {
+        id: uuid::Uuid::new_v4().to_string(),
+        text,
+        inserted: now,
+        expires: ttl.map(|ttl| now + ttl),
+        next_visible: now + visibility_timeout,
+        pop_receipt: new_pop_receipt(),
+        dequeue_count: 0,
+    };
+    let xml = format!(
+        "<QueueMessagesList><QueueMessage>\
+         <MessageId>{}</MessageId>{}\
+         <PopReceipt>{}</PopReceipt><TimeNextVisible>{}</TimeNextVisible>\
+         </QueueMessage></QueueMessagesList>",
+        message.id,
+        times_xml(&message),
+        message.pop_receipt,
+        date::to_rfc1123(&message.next_visible),
+    );
+    queue.messages.push_back(message);
+    Ok(EmulatorResponse::new(StatusCode::Created).xml(xml))
+}
+
+fn get_messages(
+    queue: &mut Queue,
+    request: &EmulatorRequest,
+    now: OffsetDateTime,
+) -> EmulatorResult {
+    let count = number_of_messages(request)?;
+    let visibility_timeout = visibility_timeout(request)?.unwrap_or(DEFAULT_VISIBILITY_TIMEOUT);
+    if visibility_timeout < Duration::SECOND {
+        return Err(invalid_query("visibilitytimeout"));
+    }
+
+    let mut xml = "<QueueMessagesList>".to_owned();
+    for message in queue
+        .messages
+        .iter_mut()
+        .filter(|message| message.next_visible <= now)
+        .take(count)
+    {
+        message.dequeue_count += 1;
+        message.next_visible = now + visibility_timeout;
+        message.pop_receipt = new_pop_receipt();
+        xml.push_str(&format!(
+            "<QueueMessage><MessageId>{}</MessageId>{}\
+             <PopReceipt>{}</PopReceipt><TimeNextVisible>{}</TimeNextVisible>\
+             <DequeueCount>{}</DequeueCount><MessageText>{}</MessageText></QueueMessage>",
+            message.id,
+            times_xml(message),
+            message.pop_receipt,
+            date::to_rfc1123(&message.next_visible),
+            message.dequeue_count,
+            xml_escape(&message.text),
+        ));
+    }
+    xml.push_str("</QueueMessagesList>");
+    Ok(EmulatorResponse::new(StatusCode::Ok).xml(xml))
+}
+
+fn peek_messages(queue: &Queue, request: &EmulatorRequest, now: OffsetDateTime) -> EmulatorResult {
+    let count = number_of_messages(request)?;
+    let mut xml = "<QueueMessagesList>".to_owned();
+    for message in queue
+        .messages
+        .iter()
+        .filter(|message| message.next_visible <= now)
+        .take(count)
+    {
+        xml.push_str(&format!(
+            "<QueueMessage><MessageId>{}</MessageId>{}\
+             <DequeueCount>{}</DequeueCount><MessageText>{}</MessageText></QueueMessage>",
+            message.id,
+            times_xml(message),
+            message.dequeue_count,
+            xml_escape(&message.text),
+        ));
+    }
+    xml.push_str("</QueueMessagesList>");
+    Ok(EmulatorResponse::new(StatusCode::Ok).xml(xml))
+}
+
+fn times_xml(message: &Message) -> String {
+    // messages that never expire report the maximum date, as the service does
+    let expires = message
+        .expires
+        .map(|expires| date::to_rfc1123(&expires))
+        .unwrap_or_else(|| "Fri, 31 Dec 9999 23:59:59 GMT".to_owned());
+    format!(
+        "<InsertionTime>{}</InsertionTime><ExpirationTime>{expires}</ExpirationTime>",
+        date::to_rfc1123(&message.inserted),
+    )
+}
+
+/// The text of the `QueueMessage` in the body of a put or update message request.
+fn message_text(request: &EmulatorRequest) -> Result<String, StorageError> {
+    let body = request.body_str()?;
+    let text = body
+        .split_once("<MessageText>")
+        .and_then(|(_, rest)| rest.split_once("</MessageText>"))
+        .map(|(text, _)| text)
+        .or_else(|| body.contains("<MessageText/>").then_some(""))
+        .or_else(|| body.contains("<MessageText />").then_some(""))
+        .ok_or_else(|| {
+            StorageError::new(
+                400,
+                "InvalidXmlDocument",
+                "the request body is not a valid QueueMessage document",
+            )
+        })?;
+    if text.len() > MAX_MESSAGE_SIZE {
+        return Err(StorageError::new(
+            413,
+            "RequestBodyTooLarge",
+            "the message exceeds the maximum allowed size of 64 KiB",
+        ));
+    }
+    Ok(xml_unescape(text))
+}
+
+fn visibility_timeout(request: &EmulatorRequest) -> Result<Option<Duration>, StorageError> {
+    match request.parse_query::<i64>("visibilitytimeout")? {
+        None => Ok(None),
+        Some(seconds) if (0..=MAX_VISIBILITY_TIMEOUT.whole_seconds()).contains(&seconds) => {
+            Ok(Some(Duration::seconds(seconds)))
+        }
+        Some(_) => Err(invalid_query("visibilitytimeout")),
+    }
+}
+
+fn number_of_messages(request: &EmulatorRequest) -> Result<usize, StorageError> {
+    match request.parse_query::<usize>("numofmessages")? {
+        None => Ok(1),
+        Some(count @ 1..=MAX_MESSAGES) => Ok(count),
+        Some(_) => Err(invalid_query("numofmessages")),
+    }
+}
+
+fn new_pop_receipt() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn is_valid_queue_name(name: &str) -> bool {
+    (3..=63).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+}
+
+fn invalid_query(name: &str) -> StorageError {
+    StorageError::new(
+        400,
+        "OutOfRangeQueryParameterValue",
+        format!("the value of the query parameter {name} is out of range"),
+    )
+}
+
+fn queue_not_found() -> StorageError {
+    StorageError::new(404, "QueueNotFound", "the specified queue does not exist")
+}
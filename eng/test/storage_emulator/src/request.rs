@@ -0,0 +1,207 @@
+use crate::response::StorageError;
+use azure_core::{
+    headers::{HeaderName, Headers, META_PREFIX},
+    Method, Url,
+};
+use bytes::Bytes;
+use percent_encoding::percent_decode_str;
+use std::{collections::BTreeMap, str::FromStr};
+
+/// A request as seen by the emulator: the account is stripped from the path,
+/// and the path segments and query pairs are decoded.
+#[derive(Debug)]
+pub(crate) struct EmulatorRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: Headers,
+    pub body: Bytes,
+    pub account: String,
+    /// The decoded path segments following the account name.
+    pub segments: Vec<String>,
+    query: Vec<(String, String)>,
+    /// The number of path segments addressing the account.
+    prefix_segments: usize,
+}
+
+impl EmulatorRequest {
+    pub fn new(
+        method: Method,
+        url: Url,
+        headers: Headers,
+        body: Bytes,
+        account: &str,
+    ) -> Result<Self, StorageError> {
+        // Requests either use the emulator's path-style addressing, where the
+        // account is the first path segment, or a `<account>.<service>` host.
+        let host_style = url
+            .host_str()
+            .and_then(|host| host.split('.').next())
+            .is_some_and(|first| first == account);
+        let prefix_segments = if host_style {
+            0
+        } else {
+            let first = url
+                .path_segments()
+                .into_iter()
+                .flatten()
+                .find(|segment| !segment.is_empty());
+            if first != Some(account) {
+                return Err(StorageError::new(
+                    400,
+                    "InvalidUri",
+                    format!("the request does not target the account {account}"),
+                ));
+            }
+            1
+        };
+        Ok(Self::with_prefix(
+            method,
+            url,
+            headers,
+            body,
+            account.to_owned(),
+            prefix_segments,
+        ))
+    }
+
+    /// A request embedded in this one, such as an operation of a table batch,
+    /// which addresses the same account.
+    pub fn nested(&self, method: Method, url: Url, headers: Headers, body: Bytes) -> Self {
+        Self::with_prefix(
+            method,
+            url,
+            headers,
+            body,
+            self.account.clone(),
+            self.prefix_segments,
+        )
+    }
+
+    fn with_prefix(
+        method: Method,
+        url: Url,
+        headers: Headers,
+        body: Bytes,
+        account: String,
+        prefix_segments: usize,
+    ) -> Self {
+        let segments = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty())
+            .skip(prefix_segments)
+            .map(|segment| percent_decode_str(segment).decode_utf8_lossy().into_owned())
+            .collect();
+        let query = url
+            .query_pairs()
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+        Self {
+            method,
+            url,
+            headers,
+            body,
+            account,
+            segments,
+            query,
+            prefix_segments,
+        }
+    }
+
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn parse_query<T: FromStr>(&self, name: &str) -> Result<Option<T>, StorageError> {
+        self.query(name)
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    StorageError::new(
+                        400,
+                        "InvalidQueryParameterValue",
+                        format!("invalid value for query parameter {name}: {value}"),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    pub fn header(&self, name: &HeaderName) -> Option<&str> {
+        self.headers.get_optional_str(name)
+    }
+
+    pub fn parse_header<T: FromStr>(&self, name: &HeaderName) -> Result<Option<T>, StorageError> {
+        self.header(name)
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    StorageError::new(
+                        400,
+                        "InvalidHeaderValue",
+                        format!("invalid value for header {}: {value}", name.as_str()),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// The user-defined metadata carried in `x-ms-meta-*` headers.
+    pub fn metadata(&self) -> BTreeMap<String, String> {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| {
+                name.as_str()
+                    .strip_prefix(META_PREFIX.as_str())
+                    .map(|name| (name.to_owned(), value.as_str().to_owned()))
+            })
+            .collect()
+    }
+
+    /// The URL of the account, as reported in the `ServiceEndpoint` of listings.
+    pub fn service_endpoint(&self) -> String {
+        let mut url = self.url.clone();
+        url.set_query(None);
+        let mut path = String::from("/");
+        for segment in url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty())
+            .take(self.prefix_segments)
+        {
+            path.push_str(segment);
+            path.push('/');
+        }
+        url.set_path(&path);
+        url.to_string()
+    }
+
+    /// The `SignedIdentifiers` document of a set ACL request, without its XML
+    /// declaration.
+    pub fn signed_identifiers(&self) -> Result<String, StorageError> {
+        let body = self.xml_document()?;
+        Ok(if body.is_empty() {
+            "<SignedIdentifiers />".to_owned()
+        } else {
+            body.to_owned()
+        })
+    }
+
+    /// The XML document of the request body, without its XML declaration.
+    pub fn xml_document(&self) -> Result<&str, StorageError> {
+        let body = self.body_str()?.trim();
+        Ok(match body.strip_prefix("<?xml") {
+            Some(rest) => rest.split_once("?>").map_or("", |(_, rest)| rest).trim(),
+            None => body,
+        })
+    }
+
+    pub fn body_str(&self) -> Result<&str, StorageError> {
+        std::str::from_utf8(&self.body).map_err(|_| {
+            StorageError::new(400, "InvalidInput", "the request body is not valid UTF-8")
+        })
+    }
+}
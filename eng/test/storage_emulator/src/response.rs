@@ -0,0 +1,174 @@
+use azure_core::{
+    date,
+    headers::{HeaderName, Headers, CONTENT_LENGTH, CONTENT_TYPE, ERROR_CODE},
+    BytesStream, Response, StatusCode,
+};
+use azure_storage::clients::ServiceType;
+use bytes::Bytes;
+use time::OffsetDateTime;
+
+pub(crate) type EmulatorResult = Result<EmulatorResponse, StorageError>;
+
+/// A response under construction.
+///
+/// The common headers (`x-ms-request-id`, `x-ms-version`, `date` and `server`)
+/// are added when it is converted into an `azure_core::Response`.
+#[derive(Debug)]
+pub(crate) struct EmulatorResponse {
+    pub status: StatusCode,
+    pub headers: Headers,
+    pub body: Bytes,
+}
+
+impl EmulatorResponse {
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: Headers::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    pub fn header(mut self, name: HeaderName, value: impl Into<String>) -> Self {
+        self.headers.insert(name, value.into());
+        self
+    }
+
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn optional_header(self, name: HeaderName, value: Option<impl Into<String>>) -> Self {
+        match value {
+            Some(value) => self.header(name, value),
+            None => self,
+        }
+    }
+
+    pub fn body(mut self, content_type: &str, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self.header(CONTENT_TYPE, content_type)
+    }
+
+    pub fn xml(self, body: String) -> Self {
+        self.body("application/xml", format!("{XML_DECLARATION}{body}"))
+    }
+
+    pub fn json(self, body: &serde_json::Value) -> Self {
+        self.body(
+            "application/json;odata=fullmetadata;streaming=true;charset=utf-8",
+            body.to_string(),
+        )
+    }
+
+    pub(crate) fn into_response(
+        mut self,
+        service_type: ServiceType,
+        version: &str,
+        now: OffsetDateTime,
+        head: bool,
+    ) -> Response {
+        let server = match service_type {
+            ServiceType::Queue => "Windows-Azure-Queue/1.0 Microsoft-HTTPAPI/2.0",
+            ServiceType::Table => "Windows-Azure-Table/1.0 Microsoft-HTTPAPI/2.0",
+            _ => "Windows-Azure-Blob/1.0 Microsoft-HTTPAPI/2.0",
+        };
+        self.headers.insert(
+            azure_core::headers::REQUEST_ID,
+            uuid::Uuid::new_v4().to_string(),
+        );
+        self.headers
+            .insert(azure_core::headers::VERSION, version.to_owned());
+        self.headers
+            .insert(azure_core::headers::DATE, date::to_rfc1123(&now));
+        self.headers.insert(azure_core::headers::SERVER, server);
+        if self.headers.get_optional_str(&CONTENT_LENGTH).is_none() {
+            self.headers
+                .insert(CONTENT_LENGTH, self.body.len().to_string());
+        }
+
+        let body = if head { Bytes::new() } else { self.body };
+        Response::new(self.status, self.headers, Box::pin(BytesStream::new(body)))
+    }
+}
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>";
+
+/// An error reported with the service's error code.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageError {
+    pub status: StatusCode,
+    pub code: String,
+    pub message: String,
+}
+
+impl StorageError {
+    pub fn new(status: u16, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::try_from(status).unwrap_or(StatusCode::InternalServerError),
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn into_response(self, service_type: ServiceType) -> EmulatorResponse {
+        let response = EmulatorResponse::new(self.status).header(ERROR_CODE, self.code.clone());
+        match service_type {
+            ServiceType::Table => response.body(
+                "application/json;odata=minimalmetadata;streaming=true;charset=utf-8",
+                serde_json::json!({
+                    "odata.error": {
+                        "code": self.code,
+                        "message": { "lang": "en-US", "value": self.message },
+                    }
+                })
+                .to_string(),
+            ),
+            _ => response.xml(format!(
+                "<Error><Code>{}</Code><Message>{}</Message></Error>",
+                xml_escape(&self.code),
+                xml_escape(&self.message)
+            )),
+        }
+    }
+}
+
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub(crate) fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Render an optional XML element, or an empty element when `value` is `None`.
+pub(crate) fn xml_element(name: &str, value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("<{name}>{}</{name}>", xml_escape(value)),
+        None => format!("<{name} />"),
+    }
+}
+
+/// Render an XML element only when `value` is set, as listings do for the
+/// parameters of the request they echo.
+pub(crate) fn optional_xml_element(name: &str, value: Option<&str>) -> String {
+    value
+        .map(|value| xml_element(name, Some(value)))
+        .unwrap_or_default()
+}
@@ -0,0 +1,308 @@
+//! Server-side checks of Shared Key and shared access signature authorization.
+//!
+//! The string to sign is rebuilt from the request as the service documents it,
+//! independently of the client's `AuthorizationPolicy`, so a signing bug in the
+//! client shows up as an `AuthenticationFailed` error.
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/authorize-with-shared-key>
+
+use crate::{request::EmulatorRequest, response::StorageError};
+use azure_core::{
+    auth::Secret,
+    headers::{self, HeaderName},
+    hmac::hmac_sha256,
+    Method,
+};
+use azure_storage::{
+    clients::ServiceType,
+    shared_access_signature::parsed_sas::{ParsedSharedAccessSignature, SasKind},
+};
+use time::OffsetDateTime;
+
+/// How a request was authorized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Authorization {
+    /// Shared Key, a valid shared access signature or a bearer token.
+    Authorized,
+    /// No credentials at all, which is only allowed for public blob data.
+    Anonymous,
+}
+
+pub(crate) fn authorize(
+    request: &EmulatorRequest,
+    service_type: ServiceType,
+    account: &str,
+    key: &Secret,
+    now: OffsetDateTime,
+) -> Result<Authorization, StorageError> {
+    if let Some(authorization) = request.header(&headers::AUTHORIZATION) {
+        if authorization.starts_with("Bearer ") {
+            // tokens are not validated, as there is no identity provider to check them against
+            return Ok(Authorization::Authorized);
+        }
+        let Some(signature) = authorization
+            .strip_prefix("SharedKey ")
+            .and_then(|credential| credential.strip_prefix(account))
+            .and_then(|credential| credential.strip_prefix(':'))
+        else {
+            return Err(authentication_failed(
+                "the authorization header is not a Shared Key header for this account",
+            ));
+        };
+
+        let string_to_sign = string_to_sign(request, service_type, account);
+        let expected = hmac_sha256(&string_to_sign, key)
+            .map_err(|_| authentication_failed("the account key is not valid base64"))?;
+        if signature != expected {
+            return Err(authentication_failed(&format!(
+                "the MAC signature found in the HTTP request is not the same as any computed \
+                 signature. Server used following string to sign: '{string_to_sign}'"
+            )));
+        }
+        return Ok(Authorization::Authorized);
+    }
+
+    if request.query("sig").is_some() {
+        authorize_sas(request, service_type, account, key, now)?;
+        return Ok(Authorization::Authorized);
+    }
+
+    Ok(Authorization::Anonymous)
+}
+
+fn authorize_sas(
+    request: &EmulatorRequest,
+    service_type: ServiceType,
+    account: &str,
+    key: &Secret,
+    now: OffsetDateTime,
+) -> Result<(), StorageError> {
+    let sas = ParsedSharedAccessSignature::parse(request.url.as_str())
+        .map_err(|error| authentication_failed(&error.to_string()))?;
+
+    match sas.kind() {
+        SasKind::Account => {
+            sas.verify_account(account, key)
+                .map_err(|error| authentication_failed(&error.to_string()))?;
+            let service = match service_type {
+                ServiceType::Queue => 'q',
+                ServiceType::Table => 't',
+                ServiceType::File => 'f',
+                _ => 'b',
+            };
+            if !sas
+                .services
+                .as_deref()
+                .unwrap_or_default()
+                .contains(service)
+            {
+                return Err(StorageError::new(
+                    403,
+                    "AuthorizationServiceMismatch",
+                    "this request is not authorized to perform this operation using this service",
+                ));
+            }
+        }
+        SasKind::Service => {
+            let resource = sas_canonicalized_resource(request, service_type, account, &sas);
+            sas.verify_service(&resource, key)
+                .map_err(|error| authentication_failed(&error.to_string()))?;
+        }
+        SasKind::UserDelegation => {
+            return Err(authentication_failed(
+                "user delegation signatures are not supported by the storage emulator",
+            ))
+        }
+    }
+
+    if !sas.is_valid_at(now) {
+        return Err(authentication_failed(
+            "the shared access signature is not valid at the current time",
+        ));
+    }
+
+    let permissions = sas.permissions.as_deref().unwrap_or_default();
+    let required = required_permissions(request, service_type);
+    if !required.chars().any(|p| permissions.contains(p)) {
+        return Err(StorageError::new(
+            403,
+            "AuthorizationPermissionMismatch",
+            "this request is not authorized to perform this operation using this permission",
+        ));
+    }
+
+    if matches!(service_type, ServiceType::Table) {
+        if let Some(key) = crate::table::request_entity_key(request) {
+            if !is_in_key_range(&sas, &key) {
+                return Err(StorageError::new(
+                    403,
+                    "AuthorizationFailure",
+                    "this request is not authorized to perform this operation",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether an entity is in the partition and row key range of a table SAS,
+/// both ends included.
+fn is_in_key_range(
+    sas: &ParsedSharedAccessSignature,
+    (partition_key, row_key): &(String, String),
+) -> bool {
+    let key = (partition_key.as_str(), row_key.as_str());
+    let after_start = match &sas.start_partition_key {
+        Some(start) => {
+            key >= (
+                start.as_str(),
+                sas.start_row_key.as_deref().unwrap_or_default(),
+            )
+        }
+        None => true,
+    };
+    let before_end = match (&sas.end_partition_key, &sas.end_row_key) {
+        (Some(end), Some(end_row_key)) => key <= (end.as_str(), end_row_key.as_str()),
+        (Some(end), None) => partition_key <= end,
+        (None, _) => true,
+    };
+    after_start && before_end
+}
+
+fn sas_canonicalized_resource(
+    request: &EmulatorRequest,
+    service_type: ServiceType,
+    account: &str,
+    sas: &ParsedSharedAccessSignature,
+) -> String {
+    match service_type {
+        ServiceType::Queue => format!(
+            "/queue/{account}/{}",
+            request
+                .segments
+                .first()
+                .map(String::as_str)
+                .unwrap_or_default()
+        ),
+        ServiceType::Table => format!(
+            "/table/{account}/{}",
+            sas.table_name.as_deref().unwrap_or_default().to_lowercase()
+        ),
+        _ => {
+            let container = request
+                .segments
+                .first()
+                .map(String::as_str)
+                .unwrap_or_default();
+            if sas.resource.as_deref() == Some("c") {
+                format!("/blob/{account}/{container}")
+            } else {
+                format!(
+                    "/blob/{account}/{container}/{}",
+                    request.segments.get(1..).unwrap_or_default().join("/")
+                )
+            }
+        }
+    }
+}
+
+/// The permissions, any of which allows the request.
+fn required_permissions(request: &EmulatorRequest, service_type: ServiceType) -> &'static str {
+    let is_list = request.query("comp") == Some("list");
+    match (service_type, &request.method) {
+        (ServiceType::Queue, Method::Get) if request.query("peekonly") == Some("true") => "r",
+        (ServiceType::Queue, Method::Get) if request.segments.get(1).is_some() => "p",
+        (ServiceType::Queue, Method::Delete) if request.segments.get(1).is_some() => "p",
+        (ServiceType::Queue, Method::Post) => "a",
+        (ServiceType::Queue, Method::Put) if request.segments.get(2).is_some() => "u",
+        (_, Method::Get | Method::Head) if is_list => "l",
+        (_, Method::Get | Method::Head) => "r",
+        (_, Method::Delete) => "d",
+        (ServiceType::Table, Method::Post) => "a",
+        (ServiceType::Table, _) => "u",
+        _ => "wca",
+    }
+}
+
+fn authentication_failed(detail: &str) -> StorageError {
+    StorageError::new(
+        403,
+        "AuthenticationFailed",
+        format!(
+            "Server failed to authenticate the request. Make sure the value of Authorization \
+             header is formed correctly including the signature. {detail}"
+        ),
+    )
+}
+
+fn header<'a>(request: &'a EmulatorRequest, name: &HeaderName) -> &'a str {
+    request.header(name).unwrap_or_default()
+}
+
+fn string_to_sign(request: &EmulatorRequest, service_type: ServiceType, account: &str) -> String {
+    if matches!(service_type, ServiceType::Table) {
+        let date = request
+            .header(&headers::MS_DATE)
+            .unwrap_or_else(|| header(request, &headers::DATE));
+        let mut resource = format!("/{account}{}", request.url.path());
+        if let Some(comp) = request.query("comp") {
+            resource.push_str("?comp=");
+            resource.push_str(comp);
+        }
+        return format!(
+            "{}\n{}\n{}\n{date}\n{resource}",
+            request.method.as_ref(),
+            header(request, &headers::CONTENT_MD5),
+            header(request, &headers::CONTENT_TYPE),
+        );
+    }
+
+    let content_length = match header(request, &headers::CONTENT_LENGTH) {
+        "0" => "",
+        length => length,
+    };
+
+    // sorted by name, so that a name sorts before the names it prefixes
+    let mut headers = request
+        .headers
+        .iter()
+        .filter(|(name, _)| name.as_str().starts_with("x-ms-"))
+        .collect::<Vec<_>>();
+    headers.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+    let canonicalized_headers = headers
+        .into_iter()
+        .map(|(name, value)| format!("{}:{}\n", name.as_str(), value.as_str().trim()))
+        .collect::<Vec<_>>();
+
+    let mut canonicalized_resource = format!("/{account}{}", request.url.path());
+    let mut parameters: Vec<(String, Vec<String>)> = Vec::new();
+    for (name, value) in request.url.query_pairs() {
+        let name = name.to_lowercase();
+        match parameters.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, values)) => values.push(value.into_owned()),
+            None => parameters.push((name, vec![value.into_owned()])),
+        }
+    }
+    parameters.sort();
+    for (name, mut values) in parameters {
+        values.sort();
+        canonicalized_resource.push_str(&format!("\n{name}:{}", values.join(",")));
+    }
+
+    format!(
+        "{}\n{}\n{}\n{content_length}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}{canonicalized_resource}",
+        request.method.as_ref(),
+        header(request, &headers::CONTENT_ENCODING),
+        header(request, &headers::CONTENT_LANGUAGE),
+        header(request, &headers::CONTENT_MD5),
+        header(request, &headers::CONTENT_TYPE),
+        header(request, &headers::DATE),
+        header(request, &headers::IF_MODIFIED_SINCE),
+        header(request, &headers::IF_MATCH),
+        header(request, &headers::IF_NONE_MATCH),
+        header(request, &headers::IF_UNMODIFIED_SINCE),
+        header(request, &headers::RANGE),
+        canonicalized_headers.concat(),
+    )
+}
@@ -0,0 +1,888 @@
+//! The Table service: tables, their entities and entity group transactions.
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/table-service-rest-api>
+
+use crate::{
+    filter::Filter,
+    not_implemented,
+    request::EmulatorRequest,
+    response::{EmulatorResponse, EmulatorResult, StorageError},
+    shared_key::Authorization,
+    Env,
+};
+use azure_core::{
+    date,
+    headers::{HeaderName, Headers, ETAG, IF_MATCH, LOCATION, PREFER},
+    Method, StatusCode, Url,
+};
+use azure_storage::clients::ServiceType;
+use bytes::Bytes;
+use serde_json::{json, Map, Value as Json};
+use std::collections::{BTreeMap, BTreeSet};
+
+const PREFERENCE_APPLIED: HeaderName = HeaderName::from_static("preference-applied");
+const DATA_SERVICE_ID: HeaderName = HeaderName::from_static("dataserviceid");
+const NEXT_TABLE_NAME: HeaderName = HeaderName::from_static("x-ms-continuation-nexttablename");
+const NEXT_PARTITION_KEY: HeaderName =
+    HeaderName::from_static("x-ms-continuation-nextpartitionkey");
+const NEXT_ROW_KEY: HeaderName = HeaderName::from_static("x-ms-continuation-nextrowkey");
+
+const MAX_RESULTS: usize = 1000;
+
+/// The sections of the properties of a new account: no logging, metrics or
+/// CORS rules.
+const DEFAULT_SERVICE_PROPERTIES: [(&str, &str); 4] = [
+    (
+        "Logging",
+        "<Logging><Version>1.0</Version><Delete>false</Delete><Read>false</Read>\
+         <Write>false</Write><RetentionPolicy><Enabled>false</Enabled></RetentionPolicy></Logging>",
+    ),
+    (
+        "HourMetrics",
+        "<HourMetrics><Version>1.0</Version><Enabled>false</Enabled>\
+         <RetentionPolicy><Enabled>false</Enabled></RetentionPolicy></HourMetrics>",
+    ),
+    (
+        "MinuteMetrics",
+        "<MinuteMetrics><Version>1.0</Version><Enabled>false</Enabled>\
+         <RetentionPolicy><Enabled>false</Enabled></RetentionPolicy></MinuteMetrics>",
+    ),
+    ("Cors", "<Cors />"),
+];
+const MAX_BATCH_OPERATIONS: usize = 100;
+
+type EntityKey = (String, String);
+
+/// The table a batch ran against, with its updated copy and the responses of
+/// the operations.
+type BatchOutcome = (String, Table, Vec<EmulatorResponse>);
+
+#[derive(Debug, Default)]
+pub(crate) struct TableService {
+    /// Table names are case-insensitive, so tables are keyed by their
+    /// lowercase name.
+    tables: BTreeMap<String, Table>,
+    /// The sections of the `StorageServiceProperties` document which were set,
+    /// by name.
+    properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+struct Table {
+    name: String,
+    entities: BTreeMap<EntityKey, Entity>,
+    signed_identifiers: String,
+}
+
+#[derive(Debug, Clone)]
+struct Entity {
+    /// The properties with their `@odata.type` annotations, including the
+    /// `PartitionKey`, `RowKey` and `Timestamp` system properties.
+    properties: Map<String, Json>,
+    etag: String,
+}
+
+impl Entity {
+    fn new(env: &mut Env, mut properties: Map<String, Json>) -> Self {
+        let timestamp = date::to_rfc3339(&env.timestamp());
+        let etag = format!("W/\"datetime'{}'\"", timestamp.replace(':', "%3A"));
+        properties.insert("Timestamp".to_owned(), Json::String(timestamp));
+        properties.insert(
+            "Timestamp@odata.type".to_owned(),
+            Json::String("Edm.DateTime".to_owned()),
+        );
+        Self { properties, etag }
+    }
+}
+
+impl TableService {
+    pub(crate) fn handle(
+        &mut self,
+        env: &mut Env,
+        request: &EmulatorRequest,
+        authorization: Authorization,
+    ) -> EmulatorResult {
+        if authorization == Authorization::Anonymous {
+            return Err(StorageError::new(
+                403,
+                "AuthenticationFailed",
+                "the request is not authorized",
+            ));
+        }
+
+        match request.segments.as_slice() {
+            [] if request.query("restype") == Some("service") => {
+                match (&request.method, request.query("comp")) {
+                    (Method::Get, Some("properties")) => {
+                        let mut xml = "<StorageServiceProperties>".to_owned();
+                        for (name, default) in DEFAULT_SERVICE_PROPERTIES {
+                            xml.push_str(self.properties.get(name).map_or(default, String::as_str));
+                        }
+                        xml.push_str("</StorageServiceProperties>");
+                        Ok(EmulatorResponse::new(StatusCode::Ok).xml(xml))
+                    }
+                    // the sections left out keep their value
+                    (Method::Put, Some("properties")) => {
+                        self.properties
+                            .extend(xml_sections(request.xml_document()?));
+                        Ok(EmulatorResponse::new(StatusCode::Accepted))
+                    }
+                    (Method::Get, Some("stats")) => {
+                        Ok(EmulatorResponse::new(StatusCode::Ok).xml(format!(
+                            "<StorageServiceStats><GeoReplication><Status>live</Status>\
+                             <LastSyncTime>{}</LastSyncTime></GeoReplication></StorageServiceStats>",
+                            date::to_rfc1123(&env.now())
+                        )))
+                    }
+                    _ => Err(not_implemented()),
+                }
+            }
+            [tables] if tables == "Tables" => match &request.method {
+                Method::Get => self.list_tables(request),
+                Method::Post => self.create_table(request),
+                _ => Err(not_implemented()),
+            },
+            [batch] if batch == "$batch" => match &request.method {
+                Method::Post => self.batch(env, request),
+                _ => Err(not_implemented()),
+            },
+            [resource] => {
+                if let Some(name) = resource
+                    .strip_prefix("Tables('")
+                    .and_then(|name| name.strip_suffix("')"))
+                {
+                    return match &request.method {
+                        Method::Delete => {
+                            self.tables
+                                .remove(&name.to_lowercase())
+                                .ok_or_else(resource_not_found)?;
+                            Ok(EmulatorResponse::new(StatusCode::NoContent))
+                        }
+                        _ => Err(not_implemented()),
+                    };
+                }
+                let (name, key) = parse_resource(resource)?;
+                self.tables
+                    .get_mut(&name.to_lowercase())
+                    .ok_or_else(table_not_found)?
+                    .handle(env, request, key)
+            }
+            _ => Err(invalid_uri()),
+        }
+    }
+
+    fn create_table(&mut self, request: &EmulatorRequest) -> EmulatorResult {
+        let body: Json = serde_json::from_slice(&request.body).map_err(|_| invalid_input())?;
+        let name = body
+            .get("TableName")
+            .and_then(Json::as_str)
+            .ok_or_else(invalid_input)?;
+        if !is_valid_table_name(name) {
+            return Err(StorageError::new(
+                400,
+                "InvalidResourceName",
+                "the specified resource name contains invalid characters",
+            ));
+        }
+        if self.tables.contains_key(&name.to_lowercase()) {
+            return Err(StorageError::new(
+                409,
+                "TableAlreadyExists",
+                "the table specified already exists",
+            ));
+        }
+        self.tables.insert(
+            name.to_lowercase(),
+            Table {
+                name: name.to_owned(),
+                entities: BTreeMap::new(),
+                signed_identifiers: "<SignedIdentifiers />".to_owned(),
+            },
+        );
+
+        let endpoint = request.service_endpoint();
+        let response = EmulatorResponse::new(StatusCode::Created)
+            .header(LOCATION, format!("{endpoint}Tables('{name}')"));
+        if request.header(&PREFER) == Some("return-no-content") {
+            return Ok(response
+                .status(StatusCode::NoContent)
+                .header(PREFERENCE_APPLIED, "return-no-content"));
+        }
+        let mut table = table_json(request, name);
+        table.insert(
+            "odata.metadata".to_owned(),
+            Json::String(format!("{endpoint}$metadata#Tables/@Element")),
+        );
+        Ok(response
+            .header(PREFERENCE_APPLIED, "return-content")
+            .json(&Json::Object(table)))
+    }
+
+    fn list_tables(&self, request: &EmulatorRequest) -> EmulatorResult {
+        let filter = filter(request)?;
+        let top = top(request)?;
+        let start = request
+            .query("NextTableName")
+            .unwrap_or_default()
+            .to_owned();
+
+        let mut tables = self.tables.range(start..).filter(|(_, table)| {
+            let mut properties = Map::new();
+            properties.insert("TableName".to_owned(), Json::String(table.name.clone()));
+            filter
+                .as_ref()
+                .map_or(true, |filter| filter.matches(&properties))
+        });
+        let value = tables
+            .by_ref()
+            .take(top)
+            .map(|(_, table)| Json::Object(table_json(request, &table.name)))
+            .collect::<Vec<_>>();
+        let next_table_name = tables.next().map(|(key, _)| key.clone());
+
+        Ok(EmulatorResponse::new(StatusCode::Ok)
+            .optional_header(NEXT_TABLE_NAME, next_table_name)
+            .json(&json!({
+                "odata.metadata": format!("{}$metadata#Tables", request.service_endpoint()),
+                "value": value,
+            })))
+    }
+
+    /// Run the operations of an entity group transaction against a copy of
+    /// their table, which replaces the table only if they all succeed.
+    fn batch(&mut self, env: &mut Env, request: &EmulatorRequest) -> EmulatorResult {
+        let operations = parse_batch(request)?;
+        if operations.is_empty() || operations.len() > MAX_BATCH_OPERATIONS {
+            return Err(StorageError::new(
+                400,
+                "InvalidInput",
+                format!("a batch must contain between 1 and {MAX_BATCH_OPERATIONS} operations"),
+            ));
+        }
+
+        match self.run_batch(env, &operations) {
+            Ok((name, table, responses)) => {
+                self.tables.insert(name, table);
+                Ok(batch_response(&responses))
+            }
+            Err((index, error)) => {
+                let error = StorageError {
+                    message: format!("{index}:{}", error.message),
+                    ..error
+                };
+                Ok(batch_response(&[error.into_response(ServiceType::Table)]))
+            }
+        }
+    }
+
+    fn run_batch(
+        &self,
+        env: &mut Env,
+        operations: &[EmulatorRequest],
+    ) -> Result<BatchOutcome, (usize, StorageError)> {
+        let mut target: Option<(String, Table)> = None;
+        let mut keys = BTreeSet::new();
+        let mut responses = Vec::with_capacity(operations.len());
+
+        for (index, operation) in operations.iter().enumerate() {
+            let fail = |error| (index, error);
+            let resource = match operation.segments.as_slice() {
+                [resource] => resource,
+                _ => return Err(fail(invalid_uri())),
+            };
+            let (name, key) = parse_resource(resource).map_err(fail)?;
+            let name = name.to_lowercase();
+
+            let (_, table) = match &mut target {
+                Some((target_name, _)) if *target_name != name => {
+                    return Err(fail(StorageError::new(
+                        400,
+                        "InvalidInput",
+                        "all operations in a batch must act on the same table",
+                    )))
+                }
+                Some(target) => target,
+                None => {
+                    let table = self.tables.get(&name).ok_or_else(table_not_found);
+                    target.insert((name, table.map_err(fail)?.clone()))
+                }
+            };
+
+            let entity_key = match &key {
+                Some(key) => key.clone(),
+                None => {
+                    let properties = entity_properties(operation).map_err(fail)?;
+                    entity_key(&properties).map_err(fail)?
+                }
+            };
+            if keys
+                .iter()
+                .next()
+                .is_some_and(|(partition_key, _): &EntityKey| *partition_key != entity_key.0)
+            {
+                return Err(fail(StorageError::new(
+                    400,
+                    "CommandsInBatchActOnDifferentPartitions",
+                    "all commands in a batch must operate on the same entity group",
+                )));
+            }
+            if !keys.insert(entity_key) {
+                return Err(fail(StorageError::new(
+                    400,
+                    "InvalidDuplicateRow",
+                    "the batch request contains multiple changes with the same row key",
+                )));
+            }
+
+            responses.push(table.handle(env, operation, key).map_err(fail)?);
+        }
+
+        let (name, table) = target.ok_or_else(|| (0, invalid_input()))?;
+        Ok((name, table, responses))
+    }
+}
+
+impl Table {
+    fn handle(
+        &mut self,
+        env: &mut Env,
+        request: &EmulatorRequest,
+        key: Option<EntityKey>,
+    ) -> EmulatorResult {
+        match (&request.method, key, request.query("comp")) {
+            (Method::Get | Method::Head, None, Some("acl")) => {
+                Ok(EmulatorResponse::new(StatusCode::Ok).xml(self.signed_identifiers.clone()))
+            }
+            (Method::Put, None, Some("acl")) => {
+                self.signed_identifiers = request.signed_identifiers()?;
+                Ok(EmulatorResponse::new(StatusCode::NoContent))
+            }
+            (Method::Post, None, None) => self.insert(env, request),
+            (Method::Get, None, None) => self.query(request),
+            (Method::Get, Some(key), None) => {
+                let entity = self.entities.get(&key).ok_or_else(resource_not_found)?;
+                let select = select(request);
+                let mut body = self.entity_json(request, entity, select.as_deref());
+                body.insert(
+                    "odata.metadata".to_owned(),
+                    Json::String(self.metadata_url(request, true)),
+                );
+                Ok(EmulatorResponse::new(StatusCode::Ok)
+                    .header(ETAG, entity.etag.clone())
+                    .json(&Json::Object(body)))
+            }
+            (Method::Put, Some(key), None) => self.update(env, request, key, false),
+            (Method::Merge | Method::Patch, Some(key), None) => {
+                self.update(env, request, key, true)
+            }
+            (Method::Delete, Some(key), None) => {
+                let if_match = request.header(&IF_MATCH).ok_or_else(|| {
+                    StorageError::new(
+                        400,
+                        "MissingRequiredHeader",
+                        "the If-Match header is required",
+                    )
+                })?;
+                let entity = self.entities.get(&key).ok_or_else(resource_not_found)?;
+                check_if_match(entity, if_match)?;
+                self.entities.remove(&key);
+                Ok(EmulatorResponse::new(StatusCode::NoContent))
+            }
+            _ => Err(not_implemented()),
+        }
+    }
+
+    fn insert(&mut self, env: &mut Env, request: &EmulatorRequest) -> EmulatorResult {
+        let properties = entity_properties(request)?;
+        let key = entity_key(&properties)?;
+        if self.entities.contains_key(&key) {
+            return Err(StorageError::new(
+                409,
+                "EntityAlreadyExists",
+                "the specified entity already exists",
+            ));
+        }
+        let entity = Entity::new(env, properties);
+        let id = self.entity_id(request, &key);
+        let response = EmulatorResponse::new(StatusCode::Created)
+            .header(ETAG, entity.etag.clone())
+            .header(LOCATION, id.clone())
+            .header(DATA_SERVICE_ID, id);
+
+        let response = if request.header(&PREFER) == Some("return-no-content") {
+            response
+                .status(StatusCode::NoContent)
+                .header(PREFERENCE_APPLIED, "return-no-content")
+        } else {
+            let mut body = self.entity_json(request, &entity, None);
+            body.insert(
+                "odata.metadata".to_owned(),
+                Json::String(self.metadata_url(request, true)),
+            );
+            response
+                .header(PREFERENCE_APPLIED, "return-content")
+                .json(&Json::Object(body))
+        };
+        self.entities.insert(key, entity);
+        Ok(response)
+    }
+
+    /// Update, merge, or upsert an entity. Without an `If-Match` header the
+    /// entity is inserted when it does not exist.
+    fn update(
+        &mut self,
+        env: &mut Env,
+        request: &EmulatorRequest,
+        key: EntityKey,
+        merge: bool,
+    ) -> EmulatorResult {
+        let mut properties = entity_properties(request)?;
+        properties.insert("PartitionKey".to_owned(), Json::String(key.0.clone()));
+        properties.insert("RowKey".to_owned(), Json::String(key.1.clone()));
+
+        let existing = self.entities.get(&key);
+        match (existing, request.header(&IF_MATCH)) {
+            (None, Some(_)) => return Err(resource_not_found()),
+            (Some(entity), Some(if_match)) => check_if_match(entity, if_match)?,
+            _ => {}
+        }
+
+        if let (true, Some(existing)) = (merge, existing) {
+            let mut merged = existing.properties.clone();
+            // a merged property replaces the type annotation of the old value
+            for name in properties.keys().filter(|name| !name.contains('@')) {
+                merged.remove(&format!("{name}@odata.type"));
+            }
+            merged.extend(properties);
+            properties = merged;
+        }
+
+        let entity = Entity::new(env, properties);
+        let etag = entity.etag.clone();
+        self.entities.insert(key, entity);
+        Ok(EmulatorResponse::new(StatusCode::NoContent).header(ETAG, etag))
+    }
+
+    fn query(&self, request: &EmulatorRequest) -> EmulatorResult {
+        let filter = filter(request)?;
+        let top = top(request)?;
+        let select = select(request);
+        let start = (
+            request
+                .query("NextPartitionKey")
+                .unwrap_or_default()
+                .to_owned(),
+            request.query("NextRowKey").unwrap_or_default().to_owned(),
+        );
+
+        let mut entities = self.entities.range(start..).filter(|(_, entity)| {
+            filter
+                .as_ref()
+                .map_or(true, |filter| filter.matches(&entity.properties))
+        });
+        let value = entities
+            .by_ref()
+            .take(top)
+            .map(|(_, entity)| Json::Object(self.entity_json(request, entity, select.as_deref())))
+            .collect::<Vec<_>>();
+        let next = entities.next().map(|(key, _)| key.clone());
+
+        Ok(EmulatorResponse::new(StatusCode::Ok)
+            .optional_header(NEXT_PARTITION_KEY, next.as_ref().map(|key| key.0.clone()))
+            .optional_header(NEXT_ROW_KEY, next.map(|key| key.1))
+            .json(&json!({
+                "odata.metadata": self.metadata_url(request, false),
+                "value": value,
+            })))
+    }
+
+    fn metadata_url(&self, request: &EmulatorRequest, element: bool) -> String {
+        let suffix = if element { "/@Element" } else { "" };
+        format!(
+            "{}$metadata#{}{suffix}",
+            request.service_endpoint(),
+            self.name
+        )
+    }
+
+    fn edit_link(&self, (partition_key, row_key): &EntityKey) -> String {
+        format!(
+            "{}(PartitionKey='{}',RowKey='{}')",
+            self.name,
+            partition_key.replace('\'', "''"),
+            row_key.replace('\'', "''")
+        )
+    }
+
+    fn entity_id(&self, request: &EmulatorRequest, key: &EntityKey) -> String {
+        format!("{}{}", request.service_endpoint(), self.edit_link(key))
+    }
+
+    /// The entity as returned in a response body, with its OData metadata and
+    /// either all of its properties or only the selected ones.
+    fn entity_json(
+        &self,
+        request: &EmulatorRequest,
+        entity: &Entity,
+        select: Option<&[String]>,
+    ) -> Map<String, Json> {
+        let key = (
+            string_property(&entity.properties, "PartitionKey"),
+            string_property(&entity.properties, "RowKey"),
+        );
+        let mut body = Map::new();
+        body.insert(
+            "odata.type".to_owned(),
+            Json::String(format!("{}.{}", request.account, self.name)),
+        );
+        body.insert(
+            "odata.id".to_owned(),
+            Json::String(self.entity_id(request, &key)),
+        );
+        body.insert("odata.etag".to_owned(), Json::String(entity.etag.clone()));
+        body.insert(
+            "odata.editLink".to_owned(),
+            Json::String(self.edit_link(&key)),
+        );
+        for (name, value) in &entity.properties {
+            let property = name.split('@').next().unwrap_or_default();
+            if select.map_or(true, |select| select.iter().any(|s| s == property)) {
+                body.insert(name.clone(), value.clone());
+            }
+        }
+        body
+    }
+}
+
+/// The key of the entity a request acts on, if it names one in its path or,
+/// for an insert, in its body.
+pub(crate) fn request_entity_key(request: &EmulatorRequest) -> Option<EntityKey> {
+    let [resource] = request.segments.as_slice() else {
+        return None;
+    };
+    match parse_resource(resource).ok()? {
+        (_, Some(key)) => Some(key),
+        (_, None) if request.method == Method::Post => {
+            entity_key(&entity_properties(request).ok()?).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Split a resource path segment such as `mytable`, `mytable()` or
+/// `mytable(PartitionKey='pk',RowKey='rk')` into a table name and entity key.
+fn parse_resource(resource: &str) -> Result<(String, Option<EntityKey>), StorageError> {
+    let Some((name, rest)) = resource.split_once('(') else {
+        return Ok((resource.to_owned(), None));
+    };
+    let keys = rest.strip_suffix(')').ok_or_else(invalid_uri)?;
+    if keys.is_empty() {
+        return Ok((name.to_owned(), None));
+    }
+    let (partition_key, rest) = keys
+        .strip_prefix("PartitionKey='")
+        .and_then(split_quoted)
+        .ok_or_else(invalid_uri)?;
+    let (row_key, rest) = rest
+        .strip_prefix(",RowKey='")
+        .and_then(split_quoted)
+        .ok_or_else(invalid_uri)?;
+    if !rest.is_empty() {
+        return Err(invalid_uri());
+    }
+    Ok((name.to_owned(), Some((partition_key, row_key))))
+}
+
+/// Read a quoted OData string literal, whose opening quote has been consumed,
+/// returning its value and the rest of the input.
+fn split_quoted(s: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != '\'' {
+            value.push(c);
+        } else if chars.next_if(|(_, c)| *c == '\'').is_some() {
+            value.push('\'');
+        } else {
+            return Some((value, &s[index + 1..]));
+        }
+    }
+    None
+}
+
+/// The properties of the entity in a request body, without the OData
+/// metadata and the `Timestamp` the service maintains.
+fn entity_properties(request: &EmulatorRequest) -> Result<Map<String, Json>, StorageError> {
+    let Json::Object(mut properties) =
+        serde_json::from_slice(&request.body).map_err(|_| invalid_input())?
+    else {
+        return Err(invalid_input());
+    };
+    properties.retain(|name, _| {
+        !name.starts_with("odata.") && name != "Timestamp" && name != "Timestamp@odata.type"
+    });
+    if properties
+        .values()
+        .any(|value| matches!(value, Json::Object(_) | Json::Array(_)))
+    {
+        return Err(StorageError::new(
+            400,
+            "PropertyValueInvalid",
+            "the property value is not a valid primitive value",
+        ));
+    }
+    Ok(properties)
+}
+
+fn entity_key(properties: &Map<String, Json>) -> Result<EntityKey, StorageError> {
+    let key = |name: &str| {
+        properties
+            .get(name)
+            .and_then(Json::as_str)
+            .filter(|key| !key.contains(['/', '\\', '#', '?']))
+            .map(str::to_owned)
+            .ok_or_else(|| {
+                StorageError::new(
+                    400,
+                    "InvalidInput",
+                    format!("the entity has no valid {name} property"),
+                )
+            })
+    };
+    Ok((key("PartitionKey")?, key("RowKey")?))
+}
+
+fn string_property(properties: &Map<String, Json>, name: &str) -> String {
+    properties
+        .get(name)
+        .and_then(Json::as_str)
+        .unwrap_or_default()
+        .to_owned()
+}
+
+fn check_if_match(entity: &Entity, if_match: &str) -> Result<(), StorageError> {
+    if if_match == "*" || if_match == entity.etag {
+        Ok(())
+    } else {
+        Err(StorageError::new(
+            412,
+            "UpdateConditionNotSatisfied",
+            "the update condition specified in the request was not satisfied",
+        ))
+    }
+}
+
+fn filter(request: &EmulatorRequest) -> Result<Option<Filter>, StorageError> {
+    request
+        .query("$filter")
+        .map(|filter| {
+            Filter::parse(filter).map_err(|message| {
+                StorageError::new(400, "InvalidInput", format!("invalid $filter: {message}"))
+            })
+        })
+        .transpose()
+}
+
+fn top(request: &EmulatorRequest) -> Result<usize, StorageError> {
+    match request.parse_query::<usize>("$top")? {
+        None => Ok(MAX_RESULTS),
+        Some(top @ 1..=MAX_RESULTS) => Ok(top),
+        Some(_) => Err(StorageError::new(
+            400,
+            "InvalidInput",
+            format!("$top must be between 1 and {MAX_RESULTS}"),
+        )),
+    }
+}
+
+fn select(request: &EmulatorRequest) -> Option<Vec<String>> {
+    request.query("$select").map(|select| {
+        select
+            .split(',')
+            .map(|property| property.trim().to_owned())
+            .collect()
+    })
+}
+
+fn table_json(request: &EmulatorRequest, name: &str) -> Map<String, Json> {
+    let mut table = Map::new();
+    table.insert(
+        "odata.type".to_owned(),
+        Json::String(format!("{}.Tables", request.account)),
+    );
+    table.insert(
+        "odata.id".to_owned(),
+        Json::String(format!("{}Tables('{name}')", request.service_endpoint())),
+    );
+    table.insert(
+        "odata.editLink".to_owned(),
+        Json::String(format!("Tables('{name}')")),
+    );
+    table.insert("TableName".to_owned(), Json::String(name.to_owned()));
+    table
+}
+
+/// The operations of a batch request, which carries one change set.
+fn parse_batch(request: &EmulatorRequest) -> Result<Vec<EmulatorRequest>, StorageError> {
+    let body = request.body_str()?.replace("\r\n", "\n");
+    let change_set = body
+        .lines()
+        .find_map(|line| line.split_once("boundary=changeset_"))
+        .map(|(_, boundary)| format!("--changeset_{}", boundary.trim()))
+        .ok_or_else(invalid_input)?;
+
+    let mut operations = Vec::new();
+    for part in body.split(&change_set).skip(1) {
+        if part.starts_with("--") {
+            break;
+        }
+        // each part has MIME headers, then the HTTP request
+        let (_, http) = part
+            .trim_start_matches('\n')
+            .split_once("\n\n")
+            .ok_or_else(invalid_input)?;
+        let (head, body) = http.split_once("\n\n").unwrap_or((http, ""));
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+        let method = request_line
+            .next()
+            .and_then(|method| method.parse::<Method>().ok())
+            .ok_or_else(invalid_input)?;
+        let url = request_line
+            .next()
+            .and_then(|url| Url::parse(url).ok())
+            .ok_or_else(invalid_input)?;
+        let mut headers = Headers::new();
+        for (name, value) in lines.filter_map(|line| line.split_once(':')) {
+            headers.insert(
+                HeaderName::from(name.trim().to_owned()),
+                value.trim().to_owned(),
+            );
+        }
+        operations.push(request.nested(method, url, headers, Bytes::from(body.trim().to_owned())));
+    }
+    Ok(operations)
+}
+
+/// Render the responses of the operations of a batch as a multipart response.
+fn batch_response(responses: &[EmulatorResponse]) -> EmulatorResponse {
+    let batch = format!("batchresponse_{}", uuid::Uuid::new_v4());
+    let change_set = format!("changesetresponse_{}", uuid::Uuid::new_v4());
+    let mut body =
+        format!("--{batch}\r\nContent-Type: multipart/mixed; boundary={change_set}\r\n\r\n");
+    for response in responses {
+        body.push_str(&format!(
+            "--{change_set}\r\nContent-Type: application/http\r\nContent-Transfer-Encoding: binary\r\n\r\n"
+        ));
+        body.push_str(&format!(
+            "HTTP/1.1 {} {}\r\n",
+            response.status as u16,
+            response.status.canonical_reason()
+        ));
+        for (name, value) in response.headers.iter() {
+            body.push_str(&format!(
+                "{}: {}\r\n",
+                display_header_name(name.as_str()),
+                value.as_str()
+            ));
+        }
+        body.push_str("\r\n");
+        body.push_str(&String::from_utf8_lossy(&response.body));
+        body.push_str("\r\n");
+    }
+    body.push_str(&format!("--{change_set}--\r\n--{batch}--\r\n"));
+    EmulatorResponse::new(StatusCode::Accepted)
+        .body(&format!("multipart/mixed; boundary={batch}"), body)
+}
+
+/// The casing the service uses for the headers of batch responses.
+/// The name and markup of the child elements of the root element of an XML
+/// document.
+fn xml_sections(xml: &str) -> Vec<(String, String)> {
+    let mut sections = Vec::new();
+    let mut depth = 0;
+    let mut section_start = 0;
+    let mut position = 0;
+    while let Some(start) = xml[position..].find('<').map(|start| position + start) {
+        let Some(end) = xml[start..].find('>').map(|end| start + end + 1) else {
+            break;
+        };
+        position = end;
+        let tag = &xml[start + 1..end - 1];
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            depth -= 1;
+            if depth == 1 {
+                sections.push((name.trim().to_owned(), xml[section_start..end].to_owned()));
+            }
+            continue;
+        }
+        if depth == 1 {
+            section_start = start;
+        }
+        if tag.ends_with('/') {
+            if depth == 1 {
+                let name = tag.trim_end_matches('/').split_whitespace().next();
+                let name = name.unwrap_or_default().to_owned();
+                sections.push((name, xml[start..end].to_owned()));
+            }
+            continue;
+        }
+        depth += 1;
+    }
+    sections
+}
+
+fn display_header_name(name: &str) -> String {
+    match name {
+        "etag" => "ETag".to_owned(),
+        "dataserviceid" => "DataServiceId".to_owned(),
+        _ => name
+            .split('-')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join("-"),
+    }
+}
+
+fn is_valid_table_name(name: &str) -> bool {
+    (3..=63).contains(&name.len())
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn invalid_uri() -> StorageError {
+    StorageError::new(
+        400,
+        "InvalidUri",
+        "the requested URI does not represent any resource on the server",
+    )
+}
+
+fn invalid_input() -> StorageError {
+    StorageError::new(
+        400,
+        "InvalidInput",
+        "one of the request inputs is not valid",
+    )
+}
+
+fn table_not_found() -> StorageError {
+    StorageError::new(404, "TableNotFound", "the table specified does not exist")
+}
+
+fn resource_not_found() -> StorageError {
+    StorageError::new(
+        404,
+        "ResourceNotFound",
+        "the specified resource does not exist",
+    )
+}
@@ -48,6 +48,16 @@ impl HttpError {
     pub fn error_message(&self) -> Option<&str> {
         self.details.message.as_deref()
     }
+
+    /// Get the headers of the response.
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    /// Get the body of the response.
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
 }
 
 impl std::fmt::Display for HttpError {
@@ -113,16 +123,32 @@ pub(crate) fn get_error_code_from_header(headers: &Headers) -> Option<String> {
 
 #[derive(Deserialize)]
 struct NestedError {
-    #[serde(alias = "Message")]
-    message: Option<String>,
+    #[serde(alias = "Message", default)]
+    message: Option<ErrorMessage>,
     #[serde(alias = "Code")]
     code: Option<String>,
 }
 
+/// OData services, such as the Table service, wrap the message in an object
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ErrorMessage {
+    Text(String),
+    OData { value: String },
+}
+
+impl From<ErrorMessage> for String {
+    fn from(message: ErrorMessage) -> Self {
+        match message {
+            ErrorMessage::Text(value) | ErrorMessage::OData { value } => value,
+        }
+    }
+}
+
 /// Error from a response body, aliases are set because XML responses follow different case-ing
 #[derive(Deserialize)]
 struct ErrorBody {
-    #[serde(alias = "Error")]
+    #[serde(alias = "Error", alias = "odata.error")]
     error: Option<NestedError>,
     #[serde(alias = "Message")]
     message: Option<String>,
@@ -137,7 +163,7 @@ impl ErrorBody {
     fn into_code_message(self) -> (Option<String>, Option<String>) {
         let (nested_code, nested_message) = self
             .error
-            .map(|nested_error| (nested_error.code, nested_error.message))
+            .map(|nested_error| (nested_error.code, nested_error.message.map(Into::into)))
             .unwrap_or((None, None));
         (nested_code.or(self.code), nested_message.or(self.message))
     }
@@ -174,3 +200,29 @@ pub(crate) fn get_error_code_message_from_body(
         .map(ErrorBody::into_code_message)
         .unwrap_or((None, None))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_code_message_from_odata_body() {
+        let body = br#"{"odata.error":{"code":"ResourceNotFound","message":{"lang":"en-us","value":"The specified resource does not exist."}}}"#;
+        assert_eq!(
+            get_error_code_message_from_body(body, Some("application/json;odata=minimalmetadata")),
+            (
+                Some("ResourceNotFound".to_owned()),
+                Some("The specified resource does not exist.".to_owned())
+            )
+        );
+
+        let body = br#"{"error":{"code":"BadRequest","message":"Invalid input"}}"#;
+        assert_eq!(
+            get_error_code_message_from_body(body, None),
+            (
+                Some("BadRequest".to_owned()),
+                Some("Invalid input".to_owned())
+            )
+        );
+    }
+}
@@ -188,6 +188,12 @@ impl ResponseBody {
     }
 }
 
+impl From<Bytes> for ResponseBody {
+    fn from(bytes: Bytes) -> Self {
+        Self::new(Box::pin(futures::stream::once(async move { Ok(bytes) })))
+    }
+}
+
 impl Stream for ResponseBody {
     type Item = crate::Result<Bytes>;
     fn poll_next(
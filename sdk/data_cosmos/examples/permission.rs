@@ -69,7 +69,6 @@ async fn main() -> azure_core::Result<()> {
         .unwrap();
 
     println!("create_permission2_response == {create_permission2_response:#?}");
-    let user = user;
 
     let list_permissions_response = user
         .list_permissions()
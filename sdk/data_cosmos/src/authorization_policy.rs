@@ -108,6 +108,7 @@ fn generate_resource_link(request: &Request) -> String {
         "/pkranges",
         "/udfs",
         "/triggers",
+        "/offers",
     ];
 
     // This strips the leading slash from the uri of the passed request.
@@ -115,6 +116,11 @@ fn generate_resource_link(request: &Request) -> String {
     let uri = uri_path.trim_start_matches('/');
     trace!("uri used by AuthorizationPolicy == {:#?}", uri);
 
+    // Offers are only addressed by resource id, which is signed in lowercase.
+    if let Some(offer_id) = uri.strip_prefix("offers/") {
+        return offer_id.trim_end_matches('/').to_lowercase();
+    }
+
     // We find the above resource names. If found, we strip it and eagerly return. Note that the
     // resource names have a leading slash so the suffix will match `test/users` but not
     // `test-users`.
@@ -227,6 +233,7 @@ fn string_to_sign(
             _ => "extension",
         },
         match rt {
+            ResourceType::DatabaseAccount => "",
             ResourceType::Databases => "dbs",
             ResourceType::Collections => "colls",
             ResourceType::Documents => "docs",
@@ -237,6 +244,7 @@ fn string_to_sign(
             ResourceType::PartitionKeyRanges => "pkranges",
             ResourceType::UserDefinedFunctions => "udfs",
             ResourceType::Triggers => "triggers",
+            ResourceType::Offers => "offers",
         },
         resource_link,
         date::to_rfc1123(&time_nonce).to_lowercase()
@@ -365,6 +373,20 @@ mon, 01 jan 1900 01:00:00 gmt
         assert_eq!(&generate_resource_link(&request), "dbs/test_db");
     }
 
+    #[test]
+    fn generate_resource_link_offers() {
+        let request = Request::new(
+            Url::parse("https://.documents.azure.com/offers").unwrap(),
+            azure_core::Method::Post,
+        );
+        assert_eq!(&generate_resource_link(&request), "");
+        let request = Request::new(
+            Url::parse("https://.documents.azure.com/offers/HVpX").unwrap(),
+            azure_core::Method::Get,
+        );
+        assert_eq!(&generate_resource_link(&request), "hvpx");
+    }
+
     #[test]
     fn scope_from_url_01() {
         let scope =
@@ -0,0 +1,347 @@
+//! Run many document operations with as few requests as possible.
+//!
+//! A [`BulkExecutor`] groups operations by partition key range, sends each
+//! group as non atomic batch requests of up to [`MAX_BATCH_OPERATIONS`]
+//! operations, and retries the operations which were throttled, halving the
+//! number of concurrent requests every time the collection runs out of request
+//! units. Unlike a [`TransactionalBatchBuilder`], operations are independent
+//! from each other: some can fail while others succeed, and they can be
+//! applied in any order.
+//!
+//! Operations are grouped by partition key range for collections with large
+//! partition keys (version 2 of the partition key definition), and by
+//! partition key otherwise, which includes collections created with the
+//! default [`PartitionKey`](crate::resources::collection::PartitionKey).
+
+pub(crate) mod partition_key_hash;
+
+use crate::{
+    cosmos_entity::{add_as_partition_key_header_serialized, serialize_partition_key},
+    operations::batch_request,
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    StatusCode,
+};
+use futures::future::join_all;
+use serde::Serialize;
+use std::{collections::BTreeMap, time::Duration};
+use time::OffsetDateTime;
+
+/// How many times an operation is sent before its failure is reported.
+const MAX_ATTEMPTS: u32 = 10;
+/// How long to wait after a throttled operation without a retry delay.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_millis(100);
+
+/// An operation of a bulk execution, and the partition key of its document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkOperation {
+    partition_key: String,
+    operation: BatchOperation,
+}
+
+impl BulkOperation {
+    /// Run `operation` on a document with this partition key.
+    pub fn new<PK: Serialize>(
+        partition_key: &PK,
+        operation: BatchOperation,
+    ) -> azure_core::Result<Self> {
+        Ok(Self {
+            partition_key: serialize_partition_key(partition_key)?,
+            operation,
+        })
+    }
+}
+
+/// The outcome of a bulk execution.
+#[derive(Debug, Clone)]
+pub struct BulkResponse {
+    /// The result of every operation, in the order they were given.
+    pub results: Vec<BatchOperationResult>,
+    /// The request charge of all the requests sent.
+    pub charge: f64,
+    /// How many times operations were throttled, and sent again.
+    pub throttled: usize,
+}
+
+impl BulkResponse {
+    /// Whether every operation succeeded.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(BatchOperationResult::is_success)
+    }
+}
+
+operation! {
+    #[stream]
+    /// A builder for a [`BulkExecutor`].
+    ///
+    /// Only collections using version 2 of the partition key hash have their
+    /// operations grouped by partition key range. In the other collections,
+    /// including the ones created with the default
+    /// [`PartitionKey`](crate::resources::collection::PartitionKey), a batch
+    /// request only holds the operations of a single partition key, so
+    /// operations take at least one request per partition key.
+    BulkExecutor,
+    client: CollectionClient,
+    ?max_concurrency: usize,
+    ?max_request_units_per_second: f64
+}
+
+impl BulkExecutorBuilder {
+    /// Create the executor. No request is sent until operations are executed.
+    pub fn build(self) -> BulkExecutor {
+        BulkExecutor {
+            collection: self.client,
+            max_concurrency: self.max_concurrency.unwrap_or(16).max(1),
+            max_request_units_per_second: self.max_request_units_per_second,
+            context: self.context,
+        }
+    }
+}
+
+/// Runs operations on the documents of a collection concurrently.
+///
+/// At most `max_concurrency` batch requests are in flight at once. When
+/// `max_request_units_per_second` is set, the executor also waits between
+/// rounds of requests so that their request charge stays below it.
+#[derive(Debug, Clone)]
+pub struct BulkExecutor {
+    collection: CollectionClient,
+    max_concurrency: usize,
+    max_request_units_per_second: Option<f64>,
+    context: Context,
+}
+
+/// Where the operations of a batch request are sent.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Target {
+    PartitionKeyRange(String),
+    PartitionKey(String),
+}
+
+/// How operations are grouped into batch requests.
+enum Routing {
+    /// By partition key range, from the effective partition key.
+    Ranges(Vec<(String, PartitionKeyRange)>),
+    /// By partition key, when the effective partition key is unknown.
+    PartitionKeys,
+}
+
+impl Routing {
+    fn target(&self, operation: &BulkOperation) -> azure_core::Result<Target> {
+        let Routing::Ranges(ranges) = self else {
+            return Ok(Target::PartitionKey(operation.partition_key.clone()));
+        };
+        let epk = partition_key_hash::effective_partition_key_v2(&operation.partition_key)?;
+        ranges
+            .iter()
+            .find(|(_, range)| range.min_exclusive <= epk && epk < range.max_exclusive)
+            .map(|(id, _)| Target::PartitionKeyRange(id.clone()))
+            .ok_or_else(|| {
+                Error::with_message(ErrorKind::Other, || {
+                    format!("no partition key range holds the partition key {epk}")
+                })
+            })
+    }
+}
+
+impl BulkExecutor {
+    /// Run `operations`, and report the result of each of them.
+    ///
+    /// An operation failing does not fail the execution: its result tells why
+    /// it failed. Errors are only returned when a whole request failed.
+    pub async fn execute(
+        &self,
+        operations: Vec<BulkOperation>,
+    ) -> azure_core::Result<BulkResponse> {
+        let hash_v2 = self
+            .collection
+            .get_collection()
+            .context(self.context.clone())
+            .await?
+            .collection
+            .parition_key
+            .version
+            == Some(2);
+        let mut routing = self.routing(hash_v2).await?;
+
+        let mut results: Vec<Option<BatchOperationResult>> = vec![None; operations.len()];
+        let mut attempts = vec![0; operations.len()];
+        let mut pending: Vec<usize> = (0..operations.len()).collect();
+        let mut concurrency = self.max_concurrency;
+        let mut response = BulkResponse {
+            results: Vec::new(),
+            charge: 0.0,
+            throttled: 0,
+        };
+
+        while !pending.is_empty() {
+            let mut groups = BTreeMap::<Target, Vec<usize>>::new();
+            for index in pending.drain(..) {
+                let target = routing.target(&operations[index])?;
+                groups.entry(target).or_default().push(index);
+            }
+            let mut batches: Vec<(Target, Vec<usize>)> = groups
+                .into_iter()
+                .flat_map(|(target, indices)| {
+                    indices
+                        .chunks(MAX_BATCH_OPERATIONS)
+                        .map(|chunk| (target.clone(), chunk.to_vec()))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            // the batches beyond the concurrency wait for the next round
+            for (_, indices) in batches.split_off(concurrency.min(batches.len())) {
+                pending.extend(indices);
+            }
+
+            let started = OffsetDateTime::now_utc();
+            let sent = join_all(
+                batches
+                    .iter()
+                    .map(|(target, indices)| self.send(target, indices, &operations)),
+            )
+            .await;
+
+            let mut round_charge = 0.0;
+            let mut retry_after = None;
+            let mut gone = false;
+            for ((_, indices), sent) in batches.into_iter().zip(sent) {
+                let batch = match sent {
+                    Ok(batch) => batch,
+                    Err(error) if status(&error) == Some(StatusCode::Gone) => {
+                        for index in indices {
+                            attempts[index] += 1;
+                            if attempts[index] >= MAX_ATTEMPTS {
+                                return Err(error);
+                            }
+                            pending.push(index);
+                        }
+                        gone = true;
+                        continue;
+                    }
+                    Err(error) => return Err(error),
+                };
+                round_charge += batch.charge;
+                for (index, result) in indices.into_iter().zip(batch.results) {
+                    attempts[index] += 1;
+                    let retry = match result.status_code {
+                        StatusCode::TooManyRequests => {
+                            response.throttled += 1;
+                            retry_after = retry_after
+                                .max(Some(result.retry_after.unwrap_or(DEFAULT_RETRY_AFTER)));
+                            true
+                        }
+                        StatusCode::Gone => {
+                            gone = true;
+                            true
+                        }
+                        _ => false,
+                    };
+                    if retry && attempts[index] < MAX_ATTEMPTS {
+                        pending.push(index);
+                    } else {
+                        results[index] = Some(result);
+                    }
+                }
+            }
+            response.charge += round_charge;
+
+            if gone {
+                routing = self.routing(hash_v2).await?;
+            }
+            if let Some(retry_after) = retry_after {
+                concurrency = (concurrency / 2).max(1);
+                azure_core::sleep(retry_after).await;
+            } else {
+                concurrency = (concurrency + 1).min(self.max_concurrency);
+            }
+            if let Some(budget) = self.max_request_units_per_second {
+                let minimum = Duration::from_secs_f64(round_charge / budget);
+                let elapsed = OffsetDateTime::now_utc() - started;
+                if let Ok(elapsed) = Duration::try_from(elapsed) {
+                    if elapsed < minimum {
+                        azure_core::sleep(minimum - elapsed).await;
+                    }
+                }
+            }
+        }
+
+        response.results = results.into_iter().flatten().collect();
+        Ok(response)
+    }
+
+    async fn routing(&self, hash_v2: bool) -> azure_core::Result<Routing> {
+        if !hash_v2 {
+            return Ok(Routing::PartitionKeys);
+        }
+        let ranges = self
+            .collection
+            .get_partition_key_ranges()
+            .context(self.context.clone())
+            .await?
+            .partition_key_ranges;
+        Ok(Routing::Ranges(
+            ranges
+                .into_iter()
+                .map(|range| (range.id.clone(), range))
+                .collect(),
+        ))
+    }
+
+    async fn send(
+        &self,
+        target: &Target,
+        indices: &[usize],
+        operations: &[BulkOperation],
+    ) -> azure_core::Result<TransactionalBatchResponse> {
+        let mut request = self.collection.docs_request(azure_core::Method::Post);
+        let by_range = match target {
+            Target::PartitionKeyRange(id) => {
+                request.insert_headers(&PartitionRangeId::new(id.clone()));
+                true
+            }
+            Target::PartitionKey(partition_key) => {
+                add_as_partition_key_header_serialized(partition_key, &mut request);
+                false
+            }
+        };
+        let batch: Vec<_> = indices
+            .iter()
+            .map(|index| {
+                let mut operation = operations[*index].operation.clone();
+                if by_range {
+                    operation.partition_key = Some(operations[*index].partition_key.clone());
+                }
+                operation
+            })
+            .collect();
+        batch_request(&mut request, &batch, false)?;
+
+        let result = self
+            .collection
+            .pipeline()
+            .send(
+                self.context.clone().insert(ResourceType::Documents),
+                &mut request,
+            )
+            .await;
+        let response = TransactionalBatchResponse::try_from(result).await?;
+        if response.results.len() != indices.len() {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "a batch request of {} operations returned {} results",
+                    indices.len(),
+                    response.results.len()
+                )
+            }));
+        }
+        Ok(response)
+    }
+}
+
+fn status(error: &Error) -> Option<StatusCode> {
+    error.as_http_error().map(|error| error.status())
+}
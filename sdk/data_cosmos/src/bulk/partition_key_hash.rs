@@ -0,0 +1,143 @@
+//! The effective partition key of a document, which tells the partition key
+//! range it belongs to.
+//!
+//! Only the hash of collections with large partition keys, version 2 of the
+//! partition key definition, is implemented.
+
+use azure_core::error::{Error, ErrorKind, ResultExt};
+use serde_json::Value;
+
+const NULL: u8 = 0x01;
+const FALSE: u8 = 0x02;
+const TRUE: u8 = 0x03;
+const NUMBER: u8 = 0x05;
+const STRING: u8 = 0x08;
+
+/// The effective partition key of a partition key serialized as the
+/// `x-ms-documentdb-partitionkey` header, as an hexadecimal string comparable
+/// with the bounds of the partition key ranges.
+pub(crate) fn effective_partition_key_v2(partition_key: &str) -> azure_core::Result<String> {
+    let components: Vec<Value> = serde_json::from_str(partition_key).context(
+        ErrorKind::DataConversion,
+        "the partition key is not a JSON array",
+    )?;
+    let mut bytes = Vec::new();
+    for component in components {
+        match component {
+            Value::Null => bytes.push(NULL),
+            Value::Bool(false) => bytes.push(FALSE),
+            Value::Bool(true) => bytes.push(TRUE),
+            Value::Number(number) => {
+                let number = number.as_f64().ok_or_else(|| {
+                    Error::message(ErrorKind::DataConversion, "invalid partition key number")
+                })?;
+                bytes.push(NUMBER);
+                bytes.extend_from_slice(&number.to_le_bytes());
+            }
+            Value::String(string) => {
+                bytes.push(STRING);
+                bytes.extend_from_slice(string.as_bytes());
+                bytes.push(0xFF);
+            }
+            _ => {
+                return Err(Error::message(
+                    ErrorKind::DataConversion,
+                    "a partition key can only hold strings, numbers, booleans and null",
+                ))
+            }
+        }
+    }
+
+    // the two most significant bits are cleared, so that the hash stays
+    // below the "FF" upper bound of the last partition key range
+    let hash = murmur3_x64_128(&bytes) & (u128::MAX >> 2);
+    Ok(format!("{hash:032X}"))
+}
+
+/// MurmurHash3 x64 128 bits with a zero seed, as `h2 << 64 | h1`.
+pub(crate) fn murmur3_x64_128(data: &[u8]) -> u128 {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    fn fmix(mut k: u64) -> u64 {
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        k ^ (k >> 33)
+    }
+
+    let (mut h1, mut h2) = (0u64, 0u64);
+    let mut blocks = data.chunks_exact(16);
+    for block in &mut blocks {
+        let k1 = u64::from_le_bytes(block[..8].try_into().unwrap_or_default());
+        let k2 = u64::from_le_bytes(block[8..].try_into().unwrap_or_default());
+
+        h1 ^= k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+
+        h2 ^= k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+    }
+
+    let tail = blocks.remainder();
+    let mut padded = [0u8; 16];
+    padded[..tail.len()].copy_from_slice(tail);
+    let k1 = u64::from_le_bytes(padded[..8].try_into().unwrap_or_default());
+    let k2 = u64::from_le_bytes(padded[8..].try_into().unwrap_or_default());
+    if tail.len() > 8 {
+        h2 ^= k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+    }
+    if !tail.is_empty() {
+        h1 ^= k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+    }
+
+    h1 ^= data.len() as u64;
+    h2 ^= data.len() as u64;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix(h1);
+    h2 = fmix(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+
+    (u128::from(h2) << 64) | u128::from(h1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn murmur3_matches_the_reference() {
+        assert_eq!(murmur3_x64_128(b""), 0);
+        assert_eq!(
+            murmur3_x64_128(b"hello"),
+            0x5B1E_906A_48AE_1D19_CBD8_A7B3_41BD_9B02
+        );
+        assert_eq!(
+            murmur3_x64_128(b"The quick brown fox jumps over the lazy dog"),
+            0x7A43_3CA9_C49A_9347_E34B_BC7B_BC07_1B6C
+        );
+    }
+
+    #[test]
+    fn effective_partition_key() {
+        assert_eq!(
+            effective_partition_key_v2(r#"["redmond"]"#).unwrap(),
+            "22E342F38A486A088463DFF7838A5963"
+        );
+        let epk = effective_partition_key_v2("[42]").unwrap();
+        assert_eq!(epk.len(), 32);
+        assert!(epk.as_str() < "40");
+        assert!(effective_partition_key_v2(r#"[{"a": 1}]"#).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! Read the change feed of a collection.
+//!
+//! The change feed of a collection is the list of the documents it created or
+//! updated, in the order of their modification, kept for each partition key
+//! range. A [`ChangeFeedIterator`] reads every partition key range in turn,
+//! following them through splits and merges, and its [`ChangeFeedState`] can be
+//! persisted to resume reading later. A [`ChangeFeedProcessor`] spreads the
+//! partition key ranges over several instances instead, keeping its state in
+//! leases stored in a Cosmos collection.
+
+mod processor;
+
+pub use processor::{ChangeFeedProcessor, ChangeFeedProcessorBuilder, Lease};
+
+use crate::{
+    headers::{HEADER_END_EPK, HEADER_START_EPK},
+    operations::ListDocumentsResponse,
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::etag_from_headers_optional,
+    prelude::*,
+    Context, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use time::OffsetDateTime;
+
+/// Where a change feed starts, for the partition key ranges it has no
+/// continuation for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangeFeedStartFrom {
+    /// The oldest change still in the change feed.
+    #[default]
+    Beginning,
+    /// The changes made after the first read.
+    Now,
+    /// The changes made after this time.
+    PointInTime(OffsetDateTime),
+}
+
+/// A range of effective partition keys, as hexadecimal strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedRange {
+    /// The lowest effective partition key in the range.
+    pub min_inclusive: String,
+    /// The lowest effective partition key after the range.
+    pub max_exclusive: String,
+}
+
+impl FeedRange {
+    fn overlaps(&self, other: &FeedRange) -> bool {
+        self.min_inclusive < other.max_exclusive && other.min_inclusive < self.max_exclusive
+    }
+
+    fn intersection(&self, other: &FeedRange) -> FeedRange {
+        FeedRange {
+            min_inclusive: self.min_inclusive.clone().max(other.min_inclusive.clone()),
+            max_exclusive: self.max_exclusive.clone().min(other.max_exclusive.clone()),
+        }
+    }
+}
+
+impl From<&PartitionKeyRange> for FeedRange {
+    fn from(range: &PartitionKeyRange) -> Self {
+        Self {
+            min_inclusive: range.min_exclusive.clone(),
+            max_exclusive: range.max_exclusive.clone(),
+        }
+    }
+}
+
+/// How far the change feed of a feed range was read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FeedRangeContinuation {
+    feed_range: FeedRange,
+    partition_key_range_id: String,
+    /// Set when the feed range only covers part of its partition key range,
+    /// after it was merged with another one.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    partial: bool,
+    /// The etag of the last page read, absent until a page is read.
+    continuation: Option<String>,
+}
+
+impl FeedRangeContinuation {
+    fn new(range: &PartitionKeyRange) -> Self {
+        Self {
+            feed_range: range.into(),
+            partition_key_range_id: range.id.clone(),
+            partial: false,
+            continuation: None,
+        }
+    }
+
+    /// Read the next page of changes, or find out the partition key range is gone.
+    async fn read<T: DeserializeOwned>(
+        &self,
+        collection: &CollectionClient,
+        context: &Context,
+        start_from: ChangeFeedStartFrom,
+        max_item_count: MaxItemCount,
+    ) -> azure_core::Result<Read<T>> {
+        let mut request = collection.docs_request(azure_core::Method::Get);
+        request.insert_headers(&ChangeFeed::Incremental);
+        request.insert_headers(&PartitionRangeId::new(self.partition_key_range_id.clone()));
+        request.insert_headers(&max_item_count);
+        match (&self.continuation, start_from) {
+            (Some(etag), _) => request.insert_headers(&IfMatchCondition::NotMatch(etag.clone())),
+            (None, ChangeFeedStartFrom::Now) => {
+                request.insert_headers(&IfMatchCondition::NotMatch("*".to_owned()));
+            }
+            (None, ChangeFeedStartFrom::PointInTime(time)) => {
+                request.insert_headers(&IfModifiedSince::new(time));
+            }
+            (None, ChangeFeedStartFrom::Beginning) => {}
+        }
+        if self.partial {
+            request.insert_header(HEADER_START_EPK, self.feed_range.min_inclusive.clone());
+            request.insert_header(HEADER_END_EPK, self.feed_range.max_exclusive.clone());
+        }
+
+        let result = collection
+            .pipeline()
+            .send(
+                context.clone().insert(ResourceType::Documents),
+                &mut request,
+            )
+            .await;
+        match result {
+            Ok(response) => {
+                let continuation = etag_from_headers_optional(response.headers())?;
+                let response = ListDocumentsResponse::try_from(response).await?;
+                Ok(Read::Changes {
+                    documents: response.documents,
+                    continuation,
+                    charge: response.charge,
+                })
+            }
+            Err(error) => match error.as_http_error() {
+                Some(http_error) if http_error.status() == StatusCode::NotModified => {
+                    Ok(Read::NotModified {
+                        continuation: etag_from_headers_optional(http_error.headers())?,
+                    })
+                }
+                Some(http_error) if http_error.status() == StatusCode::Gone => {
+                    Ok(Read::Gone(error))
+                }
+                _ => Err(error),
+            },
+        }
+    }
+
+    /// The continuations replacing this one once its partition key range is
+    /// gone, from the current partition key ranges of the collection.
+    ///
+    /// A split leaves several ranges overlapping the feed range, each starting
+    /// from the same continuation. A merge leaves a single range, wider than
+    /// the feed range, which is then read partially.
+    fn resolve(&self, ranges: &[PartitionKeyRange]) -> Vec<FeedRangeContinuation> {
+        ranges
+            .iter()
+            .filter_map(|range| {
+                let partition = FeedRange::from(range);
+                if !partition.overlaps(&self.feed_range) {
+                    return None;
+                }
+                let feed_range = partition.intersection(&self.feed_range);
+                Some(FeedRangeContinuation {
+                    partial: feed_range != partition,
+                    feed_range,
+                    partition_key_range_id: range.id.clone(),
+                    continuation: self.continuation.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Like [`Self::resolve`] with the current partition key ranges, failing
+    /// with `error` when the partition key range is still there.
+    async fn refresh(
+        &self,
+        collection: &CollectionClient,
+        context: &Context,
+        error: Error,
+    ) -> azure_core::Result<Vec<FeedRangeContinuation>> {
+        let ranges = partition_key_ranges(collection, context).await?;
+        let resolved = self.resolve(&ranges);
+        if resolved.is_empty() || resolved == [self.clone()] {
+            return Err(error);
+        }
+        Ok(resolved)
+    }
+}
+
+async fn partition_key_ranges(
+    collection: &CollectionClient,
+    context: &Context,
+) -> azure_core::Result<Vec<PartitionKeyRange>> {
+    Ok(collection
+        .get_partition_key_ranges()
+        .context(context.clone())
+        .await?
+        .partition_key_ranges)
+}
+
+/// The outcome of reading a page of a feed range.
+enum Read<T> {
+    Changes {
+        documents: Vec<Document<T>>,
+        continuation: Option<String>,
+        charge: f64,
+    },
+    NotModified {
+        continuation: Option<String>,
+    },
+    /// The partition key range was split or merged.
+    Gone(Error),
+}
+
+/// A page of changes of a feed range.
+#[derive(Debug, Clone)]
+pub struct ChangeFeedPage<T> {
+    /// The feed range the changes were made in.
+    pub feed_range: FeedRange,
+    /// The documents created or updated, at their latest version.
+    pub documents: Vec<Document<T>>,
+    /// Whether the feed range had no new changes.
+    pub not_modified: bool,
+    /// The request charge of the page.
+    pub charge: f64,
+}
+
+/// How far a change feed was read, to resume reading it later.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeFeedState {
+    ranges: Vec<FeedRangeContinuation>,
+}
+
+operation! {
+    #[stream]
+    /// A builder for a [`ChangeFeedIterator`].
+    ChangeFeedIterator,
+    client: CollectionClient,
+    ?start_from: ChangeFeedStartFrom,
+    ?max_item_count: MaxItemCount,
+    ?state: ChangeFeedState
+}
+
+impl ChangeFeedIteratorBuilder {
+    /// Create the iterator. No request is sent until the first page is read.
+    pub fn into_iterator(self) -> ChangeFeedIterator {
+        ChangeFeedIterator {
+            collection: self.client,
+            ranges: self.state.unwrap_or_default().ranges.into(),
+            start_from: self.start_from.unwrap_or_default(),
+            max_item_count: self.max_item_count.unwrap_or_default(),
+            context: self.context,
+        }
+    }
+}
+
+/// Reads the change feed of every partition key range of a collection, in turn.
+///
+/// The change feed never ends: a page with [`ChangeFeedPage::not_modified`] set
+/// tells a partition key range has no new changes, and reading it again later
+/// returns the changes made in the meantime.
+#[derive(Debug, Clone)]
+pub struct ChangeFeedIterator {
+    collection: CollectionClient,
+    ranges: VecDeque<FeedRangeContinuation>,
+    start_from: ChangeFeedStartFrom,
+    max_item_count: MaxItemCount,
+    context: Context,
+}
+
+impl ChangeFeedIterator {
+    /// Read the next page of changes, from the next feed range.
+    pub async fn next_page<T: DeserializeOwned>(
+        &mut self,
+    ) -> azure_core::Result<ChangeFeedPage<T>> {
+        if self.ranges.is_empty() {
+            self.ranges = partition_key_ranges(&self.collection, &self.context)
+                .await?
+                .iter()
+                .map(FeedRangeContinuation::new)
+                .collect();
+        }
+
+        loop {
+            let mut range = self.ranges.pop_front().ok_or_else(|| {
+                Error::message(
+                    ErrorKind::Other,
+                    "the collection has no partition key ranges",
+                )
+            })?;
+            let read = range
+                .read(
+                    &self.collection,
+                    &self.context,
+                    self.start_from,
+                    self.max_item_count,
+                )
+                .await;
+            let read = match read {
+                Ok(read) => read,
+                Err(error) => {
+                    self.ranges.push_front(range);
+                    return Err(error);
+                }
+            };
+
+            let (documents, continuation, charge) = match read {
+                Read::Changes {
+                    documents,
+                    continuation,
+                    charge,
+                } => (documents, continuation, charge),
+                Read::NotModified { continuation } => (Vec::new(), continuation, 0.0),
+                Read::Gone(error) => {
+                    match range.refresh(&self.collection, &self.context, error).await {
+                        Ok(resolved) => {
+                            for range in resolved.into_iter().rev() {
+                                self.ranges.push_front(range);
+                            }
+                        }
+                        Err(error) => {
+                            self.ranges.push_front(range);
+                            return Err(error);
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            let page = ChangeFeedPage {
+                feed_range: range.feed_range.clone(),
+                not_modified: documents.is_empty(),
+                documents,
+                charge,
+            };
+            range.continuation = continuation.or(range.continuation);
+            self.ranges.push_back(range);
+            return Ok(page);
+        }
+    }
+
+    /// How far the change feed was read, to resume it with
+    /// [`ChangeFeedIteratorBuilder::state`].
+    pub fn state(&self) -> ChangeFeedState {
+        ChangeFeedState {
+            ranges: self.ranges.iter().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(id: &str, min: &str, max: &str, parents: &[&str]) -> PartitionKeyRange {
+        serde_json::from_value(serde_json::json!({
+            "_rid": "rid",
+            "id": id,
+            "_etag": "etag",
+            "minInclusive": min,
+            "maxExclusive": max,
+            "ridPrefix": 0,
+            "_self": "self",
+            "throughputFraction": 1.0,
+            "status": "online",
+            "parents": parents,
+            "_ts": 0,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn resolve_split_and_merge() {
+        let mut parent = FeedRangeContinuation::new(&range("0", "", "FF", &[]));
+        parent.continuation = Some("\"42\"".to_owned());
+
+        // split
+        let children =
+            parent.resolve(&[range("1", "", "7F", &["0"]), range("2", "7F", "FF", &["0"])]);
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].partition_key_range_id, "1");
+        assert_eq!(
+            children[0].feed_range,
+            FeedRange::from(&range("1", "", "7F", &[]))
+        );
+        assert_eq!(children[1].feed_range.min_inclusive, "7F");
+        assert!(children
+            .iter()
+            .all(|child| !child.partial && child.continuation.as_deref() == Some("\"42\"")));
+
+        // merge of the children keeps reading each of them on its own
+        let merged = [range("3", "", "FF", &["1", "2"])];
+        let left = children[0].resolve(&merged);
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].partition_key_range_id, "3");
+        assert_eq!(left[0].feed_range.max_exclusive, "7F");
+        assert!(left[0].partial);
+        let right = children[1].resolve(&merged);
+        assert_eq!(right[0].feed_range.min_inclusive, "7F");
+        assert!(right[0].partial);
+    }
+
+    #[test]
+    fn state_round_trips() {
+        let state = ChangeFeedState {
+            ranges: vec![FeedRangeContinuation::new(&range("0", "", "FF", &[]))],
+        };
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ranges": [{
+                    "feedRange": { "minInclusive": "", "maxExclusive": "FF" },
+                    "partitionKeyRangeId": "0",
+                    "continuation": null,
+                }]
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<ChangeFeedState>(json).unwrap(),
+            state
+        );
+    }
+}
@@ -0,0 +1,473 @@
+use super::{
+    partition_key_ranges, ChangeFeedPage, ChangeFeedStartFrom, FeedRangeContinuation, Read,
+};
+use crate::{prelude::*, CosmosEntity};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    StatusCode,
+};
+use futures::{Future, StreamExt};
+use serde::de::DeserializeOwned;
+use std::{collections::BTreeMap, time::Duration};
+use time::OffsetDateTime;
+
+/// The document of a lease in the lease collection, partitioned by `/id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseDocument {
+    id: String,
+    processor: String,
+    owner: Option<String>,
+    #[serde(flatten)]
+    range: FeedRangeContinuation,
+}
+
+impl CosmosEntity for LeaseDocument {
+    type Entity = String;
+
+    fn partition_key(&self) -> Self::Entity {
+        self.id.clone()
+    }
+}
+
+/// The ownership of a feed range by a [`ChangeFeedProcessor`] instance, and the
+/// continuation of its change feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lease {
+    document: LeaseDocument,
+    etag: String,
+    timestamp: OffsetDateTime,
+}
+
+impl Lease {
+    fn new(document: Document<LeaseDocument>) -> azure_core::Result<Self> {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(document.document_attributes.ts() as i64)
+                .map_err(|error| Error::new(ErrorKind::DataConversion, error))?;
+        Ok(Self {
+            etag: document.document_attributes.etag().to_owned(),
+            document: document.document,
+            timestamp,
+        })
+    }
+
+    /// The id of the lease document.
+    pub fn id(&self) -> &str {
+        &self.document.id
+    }
+
+    /// The instance owning the lease, if any.
+    pub fn owner(&self) -> Option<&str> {
+        self.document.owner.as_deref()
+    }
+
+    /// The feed range the lease is about.
+    pub fn feed_range(&self) -> &super::FeedRange {
+        &self.document.range.feed_range
+    }
+
+    /// The continuation of the change feed of the feed range, absent until the
+    /// first checkpoint.
+    pub fn continuation(&self) -> Option<&str> {
+        self.document.range.continuation.as_deref()
+    }
+
+    /// When the lease was last acquired, renewed or checkpointed.
+    pub fn timestamp(&self) -> OffsetDateTime {
+        self.timestamp
+    }
+
+    fn is_expired(&self, now: OffsetDateTime, expiration: Duration) -> bool {
+        self.document.owner.is_none() || self.timestamp + expiration < now
+    }
+}
+
+operation! {
+    #[stream]
+    /// A builder for a [`ChangeFeedProcessor`].
+    ChangeFeedProcessor,
+    client: CollectionClient,
+    processor_name: String,
+    lease_collection: CollectionClient,
+    instance_name: String,
+    ?start_from: ChangeFeedStartFrom,
+    ?max_item_count: MaxItemCount,
+    ?lease_expiration: Duration,
+    ?poll_interval: Duration
+}
+
+impl ChangeFeedProcessorBuilder {
+    /// Create the processor. No request is sent until it runs.
+    pub fn build(self) -> ChangeFeedProcessor {
+        ChangeFeedProcessor {
+            collection: self.client,
+            processor_name: self.processor_name,
+            leases: self.lease_collection,
+            instance_name: self.instance_name,
+            start_from: self.start_from.unwrap_or_default(),
+            max_item_count: self.max_item_count.unwrap_or_default(),
+            lease_expiration: self.lease_expiration.unwrap_or(Duration::from_secs(60)),
+            poll_interval: self.poll_interval.unwrap_or(Duration::from_secs(5)),
+            context: self.context,
+        }
+    }
+}
+
+/// Processes the change feed of a collection with several instances.
+///
+/// Every feed range of the collection has a lease in the lease collection,
+/// which must be partitioned by `/id` and can be shared by several processors
+/// with different names. Each instance renews the leases it owns, acquires the
+/// leases which expired, and takes leases from the busiest instance until all
+/// of them own as many leases, give or take one. It then hands a page of the
+/// change feed of each of its leases to the handler, and checkpoints the lease
+/// once the handler succeeds, so that a page is handled again by whichever
+/// instance owns the lease after a failure.
+#[derive(Debug, Clone)]
+pub struct ChangeFeedProcessor {
+    collection: CollectionClient,
+    processor_name: String,
+    leases: CollectionClient,
+    instance_name: String,
+    start_from: ChangeFeedStartFrom,
+    max_item_count: MaxItemCount,
+    lease_expiration: Duration,
+    poll_interval: Duration,
+    context: Context,
+}
+
+impl ChangeFeedProcessor {
+    /// Process the change feed until an error, waiting for the poll interval
+    /// after every round.
+    pub async fn run<T, F, Fut>(&self, handler: F) -> azure_core::Result<()>
+    where
+        T: DeserializeOwned,
+        F: Fn(ChangeFeedPage<T>) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+    {
+        loop {
+            self.run_once(&handler).await?;
+            azure_core::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Balance the leases, then handle a page of changes of every lease owned
+    /// by this instance, returning the number of changes handled.
+    pub async fn run_once<T, F, Fut>(&self, handler: &F) -> azure_core::Result<usize>
+    where
+        T: DeserializeOwned,
+        F: Fn(ChangeFeedPage<T>) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+    {
+        let mut leases = self.leases().await?;
+        if leases.is_empty() {
+            self.initialize().await?;
+            leases = self.leases().await?;
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let taken = leases_to_take(&leases, &self.instance_name, now, self.lease_expiration);
+        let mut owned = Vec::new();
+        for lease in leases {
+            let is_owned = lease.owner() == Some(self.instance_name.as_str());
+            if is_owned || taken.contains(&lease.id().to_owned()) {
+                // renewing and acquiring are the same update
+                let mut document = lease.document.clone();
+                document.owner = Some(self.instance_name.clone());
+                if let Some(lease) = self.replace(&lease, document).await? {
+                    owned.push(lease);
+                }
+            }
+        }
+
+        let mut handled = 0;
+        for lease in owned {
+            handled += self.process(lease, handler).await?;
+        }
+        Ok(handled)
+    }
+
+    /// The leases of this processor.
+    pub async fn leases(&self) -> azure_core::Result<Vec<Lease>> {
+        let mut pages = self
+            .leases
+            .list_documents()
+            .context(self.context.clone())
+            .into_stream::<LeaseDocument>();
+        let mut leases = Vec::new();
+        while let Some(page) = pages.next().await {
+            for document in page?.documents {
+                if document.document.processor == self.processor_name {
+                    leases.push(Lease::new(document)?);
+                }
+            }
+        }
+        leases.sort_by(|a, b| a.id().cmp(b.id()));
+        Ok(leases)
+    }
+
+    /// Create a lease for every partition key range, unless another instance
+    /// already did.
+    async fn initialize(&self) -> azure_core::Result<()> {
+        for range in partition_key_ranges(&self.collection, &self.context).await? {
+            self.create(FeedRangeContinuation::new(&range), None)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn process<T, F, Fut>(&self, lease: Lease, handler: &F) -> azure_core::Result<usize>
+    where
+        T: DeserializeOwned,
+        F: Fn(ChangeFeedPage<T>) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+    {
+        let read = lease
+            .document
+            .range
+            .read::<T>(
+                &self.collection,
+                &self.context,
+                self.start_from,
+                self.max_item_count,
+            )
+            .await?;
+
+        let (continuation, handled) = match read {
+            Read::Changes {
+                documents,
+                continuation,
+                charge,
+            } => {
+                let handled = documents.len();
+                handler(ChangeFeedPage {
+                    feed_range: lease.feed_range().clone(),
+                    not_modified: documents.is_empty(),
+                    documents,
+                    charge,
+                })
+                .await?;
+                (continuation, handled)
+            }
+            Read::NotModified { continuation } => (continuation, 0),
+            Read::Gone(error) => {
+                // the children take over from the parent, with its owner
+                let ranges = lease
+                    .document
+                    .range
+                    .refresh(&self.collection, &self.context, error)
+                    .await?;
+                for range in ranges {
+                    self.create(range, Some(self.instance_name.clone())).await?;
+                }
+                self.delete(&lease).await?;
+                return Ok(0);
+            }
+        };
+
+        if continuation.is_some() && continuation != lease.document.range.continuation {
+            let mut document = lease.document.clone();
+            document.range.continuation = continuation;
+            self.replace(&lease, document).await?;
+        }
+        Ok(handled)
+    }
+
+    async fn create(
+        &self,
+        range: FeedRangeContinuation,
+        owner: Option<String>,
+    ) -> azure_core::Result<()> {
+        let document = LeaseDocument {
+            id: format!(
+                "{}..{}-{}",
+                self.processor_name, range.feed_range.min_inclusive, range.feed_range.max_exclusive
+            ),
+            processor: self.processor_name.clone(),
+            owner,
+            range,
+        };
+        let result = self
+            .leases
+            .create_document(document)
+            .context(self.context.clone())
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(error) if status(&error) == Some(StatusCode::Conflict) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Update a lease unless another instance did in the meantime.
+    async fn replace(
+        &self,
+        lease: &Lease,
+        document: LeaseDocument,
+    ) -> azure_core::Result<Option<Lease>> {
+        let result = self
+            .leases
+            .document_client(lease.id(), &lease.document.id)?
+            .replace_document(document.clone())
+            .if_match_condition(IfMatchCondition::Match(lease.etag.clone()))
+            .context(self.context.clone())
+            .await;
+        match result {
+            Ok(response) => Ok(Some(Lease::new(Document {
+                document_attributes: response.document_attributes,
+                document,
+            })?)),
+            Err(error)
+                if matches!(
+                    status(&error),
+                    Some(StatusCode::PreconditionFailed | StatusCode::NotFound)
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn delete(&self, lease: &Lease) -> azure_core::Result<()> {
+        let result = self
+            .leases
+            .document_client(lease.id(), &lease.document.id)?
+            .delete_document()
+            .if_match_condition(IfMatchCondition::Match(lease.etag.clone()))
+            .context(self.context.clone())
+            .await;
+        match result {
+            Err(error)
+                if !matches!(
+                    status(&error),
+                    Some(StatusCode::PreconditionFailed | StatusCode::NotFound)
+                ) =>
+            {
+                Err(error)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn status(error: &Error) -> Option<StatusCode> {
+    error.as_http_error().map(|error| error.status())
+}
+
+/// The ids of the leases an instance should take so that every live instance
+/// owns as many leases, give or take one.
+///
+/// Expired leases are taken first. When there are none, a single lease is
+/// taken from the instance owning the most leases, if it owns more than its
+/// share, so that the ownership converges over a few rounds.
+fn leases_to_take(
+    leases: &[Lease],
+    instance: &str,
+    now: OffsetDateTime,
+    expiration: Duration,
+) -> Vec<String> {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    counts.insert(instance, 0);
+    for lease in leases {
+        if let (false, Some(owner)) = (lease.is_expired(now, expiration), lease.owner()) {
+            *counts.entry(owner).or_default() += 1;
+        }
+    }
+    let target = (leases.len() + counts.len() - 1) / counts.len();
+    let owned = counts[instance];
+    if owned >= target {
+        return Vec::new();
+    }
+
+    let expired: Vec<_> = leases
+        .iter()
+        .filter(|lease| lease.is_expired(now, expiration))
+        .take(target - owned)
+        .map(|lease| lease.id().to_owned())
+        .collect();
+    if !expired.is_empty() {
+        return expired;
+    }
+
+    let busiest = counts
+        .iter()
+        .filter(|(owner, _)| **owner != instance)
+        .max_by_key(|(_, count)| **count);
+    match busiest {
+        Some((owner, count)) if *count > target => leases
+            .iter()
+            .find(|lease| lease.owner() == Some(owner))
+            .map(|lease| vec![lease.id().to_owned()])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::change_feed::FeedRange;
+
+    const EXPIRATION: Duration = Duration::from_secs(60);
+
+    fn lease(id: &str, owner: Option<&str>, age: u64) -> Lease {
+        Lease {
+            document: LeaseDocument {
+                id: id.to_owned(),
+                processor: "processor".to_owned(),
+                owner: owner.map(ToOwned::to_owned),
+                range: FeedRangeContinuation {
+                    feed_range: FeedRange {
+                        min_inclusive: String::new(),
+                        max_exclusive: "FF".to_owned(),
+                    },
+                    partition_key_range_id: id.to_owned(),
+                    partial: false,
+                    continuation: None,
+                },
+            },
+            etag: String::new(),
+            timestamp: OffsetDateTime::UNIX_EPOCH + Duration::from_secs(1000 - age),
+        }
+    }
+
+    fn take(leases: &[Lease], instance: &str) -> Vec<String> {
+        let now = OffsetDateTime::UNIX_EPOCH + Duration::from_secs(1000);
+        leases_to_take(leases, instance, now, EXPIRATION)
+    }
+
+    #[test]
+    fn takes_free_and_expired_leases_first() {
+        let leases = [
+            lease("0", None, 0),
+            lease("1", Some("a"), 0),
+            lease("2", Some("a"), 0),
+            lease("3", Some("b"), 120),
+        ];
+        // "b" stopped renewing, "c" is new and takes its share
+        assert_eq!(take(&leases, "c"), ["0", "3"]);
+        // until "c" owns leases, "a" does not know about it
+        assert_eq!(take(&leases, "a"), ["0", "3"]);
+    }
+
+    #[test]
+    fn steals_from_the_busiest_instance() {
+        let leases = [
+            lease("0", Some("a"), 0),
+            lease("1", Some("a"), 0),
+            lease("2", Some("a"), 0),
+            lease("3", Some("b"), 0),
+        ];
+        assert_eq!(take(&leases, "c"), ["0"]);
+
+        // balanced, give or take one
+        let leases = [
+            lease("0", Some("a"), 0),
+            lease("1", Some("a"), 0),
+            lease("2", Some("c"), 0),
+        ];
+        assert!(take(&leases, "c").is_empty());
+    }
+}
@@ -1,7 +1,11 @@
+use crate::bulk::BulkExecutorBuilder;
+use crate::change_feed::{ChangeFeedIteratorBuilder, ChangeFeedProcessorBuilder};
 use crate::clients::*;
 use crate::operations::*;
+use crate::query::CrossPartitionQueryBuilder;
 use crate::resources::collection::PartitionKey;
 use crate::resources::document::Query;
+use crate::resources::Throughput;
 use crate::CosmosEntity;
 use crate::ReadonlyString;
 use azure_core::{Pipeline, Request};
@@ -56,11 +60,59 @@ impl CollectionClient {
         CreateDocumentBuilder::new(self.clone(), document)
     }
 
+    /// Run operations on the documents of a logical partition atomically.
+    pub fn transactional_batch<PK: Serialize>(
+        &self,
+        partition_key: &PK,
+    ) -> azure_core::Result<TransactionalBatchBuilder> {
+        Ok(TransactionalBatchBuilder::new(
+            self.clone(),
+            crate::cosmos_entity::serialize_partition_key(partition_key)?,
+            Vec::new(),
+        ))
+    }
+
+    /// Run many operations on the documents of a collection concurrently.
+    pub fn bulk(&self) -> BulkExecutorBuilder {
+        BulkExecutorBuilder::new(self.clone())
+    }
+
+    /// The session token the client keeps for the collection, to read it with
+    /// [`ConsistencyLevel::Session`](crate::ConsistencyLevel::Session) from
+    /// another client. `None` when no document was read or written yet, or
+    /// automatic session tokens are disabled.
+    pub fn session_token(&self) -> Option<String> {
+        self.cosmos_client().session_token(&format!(
+            "dbs/{}/colls/{}",
+            self.database_client().database_name(),
+            self.collection_name()
+        ))
+    }
+
+    /// Read the throughput of the collection.
+    pub fn read_throughput(&self) -> ReadThroughputBuilder {
+        ReadThroughputBuilder::new(ThroughputClient::Collection(self.clone()))
+    }
+
+    /// Replace the throughput of the collection.
+    pub fn replace_throughput(&self, throughput: Throughput) -> ReplaceThroughputBuilder {
+        ReplaceThroughputBuilder::new(ThroughputClient::Collection(self.clone()), throughput)
+    }
+
     /// Query documents in a collection.
     pub fn query_documents<Q: Into<Query>>(&self, query: Q) -> QueryDocumentsBuilder {
         QueryDocumentsBuilder::new(self.clone(), query.into())
     }
 
+    /// Query documents across the partition key ranges of a collection,
+    /// merging their results on the client.
+    ///
+    /// Unlike [`Self::query_documents`], this supports `ORDER BY`, aggregates,
+    /// `DISTINCT`, `GROUP BY`, `TOP` and `OFFSET LIMIT` over the whole collection.
+    pub fn cross_partition_query<Q: Into<Query>>(&self, query: Q) -> CrossPartitionQueryBuilder {
+        CrossPartitionQueryBuilder::new(self.clone(), query.into())
+    }
+
     /// List stored procedures in a collection.
     pub fn list_stored_procedures(&self) -> ListStoredProceduresBuilder {
         ListStoredProceduresBuilder::new(self.clone())
@@ -111,6 +163,27 @@ impl CollectionClient {
         StoredProcedureClient::new(self.clone(), stored_procedure_name)
     }
 
+    /// Read the change feed of a collection.
+    pub fn change_feed(&self) -> ChangeFeedIteratorBuilder {
+        ChangeFeedIteratorBuilder::new(self.clone())
+    }
+
+    /// Process the change feed of a collection with several instances,
+    /// keeping their leases in `lease_collection`.
+    pub fn change_feed_processor<P: Into<String>, I: Into<String>>(
+        &self,
+        processor_name: P,
+        lease_collection: CollectionClient,
+        instance_name: I,
+    ) -> ChangeFeedProcessorBuilder {
+        ChangeFeedProcessorBuilder::new(
+            self.clone(),
+            processor_name.into(),
+            lease_collection,
+            instance_name.into(),
+        )
+    }
+
     /// Get a [`CosmosClient`].
     pub fn cosmos_client(&self) -> &CosmosClient {
         self.database.cosmos_client()
@@ -2,12 +2,15 @@ use crate::clients::DatabaseClient;
 use crate::operations::*;
 use crate::resources::permission::AuthorizationToken;
 use crate::resources::ResourceType;
+use crate::session_policy::SessionContainer;
 use crate::ReadonlyString;
+use crate::SessionState;
 
 use azure_core::{ClientOptions, Context, Pipeline, Request, Response};
 
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// The well-known account key used by Azure Cosmos DB Emulator.
 /// <https://docs.microsoft.com/azure/cosmos-db/local-emulator?tabs=ssl-netstd21#connect-with-emulator-apis>
@@ -19,6 +22,9 @@ pub const EMULATOR_ACCOUNT_KEY: &str =
 pub struct CosmosClientBuilder {
     cloud_location: CloudLocation,
     options: ClientOptions,
+    preferred_regions: Option<Vec<String>>,
+    endpoint_refresh_interval: Duration,
+    automatic_session_tokens: bool,
 }
 
 impl CosmosClientBuilder {
@@ -37,6 +43,9 @@ impl CosmosClientBuilder {
         Self {
             options: ClientOptions::default(),
             cloud_location,
+            preferred_regions: None,
+            endpoint_refresh_interval: crate::location_policy::DEFAULT_REFRESH_INTERVAL,
+            automatic_session_tokens: true,
         }
     }
 
@@ -44,12 +53,68 @@ impl CosmosClientBuilder {
     #[must_use]
     pub fn build(self) -> CosmosClient {
         let auth_token = self.cloud_location.auth_token();
+        let location_policy = self.preferred_regions.map(|preferred_regions| {
+            crate::LocationPolicy::new(
+                self.cloud_location.url().parse().unwrap(),
+                preferred_regions,
+                self.endpoint_refresh_interval,
+            )
+        });
+        let session = self
+            .automatic_session_tokens
+            .then(|| Arc::new(SessionContainer::default()));
         CosmosClient {
-            pipeline: new_pipeline_from_options(self.options, auth_token),
+            pipeline: new_pipeline_from_options(
+                self.options,
+                auth_token,
+                location_policy,
+                session.clone(),
+            ),
             cloud_location: self.cloud_location,
+            session,
         }
     }
 
+    /// Whether the client keeps the session tokens of the documents it reads
+    /// and writes, and sends them with its reads, which is the default.
+    ///
+    /// This gives the reads of a client session consistency, like reading its
+    /// own writes, without passing [`ConsistencyLevel::Session`](crate::ConsistencyLevel::Session)
+    /// to every request. Reads which set a consistency level themselves are
+    /// sent as they are.
+    #[must_use]
+    pub fn automatic_session_tokens(mut self, enabled: bool) -> Self {
+        self.automatic_session_tokens = enabled;
+        self
+    }
+
+    /// Send requests to the regions of a globally distributed account.
+    ///
+    /// The client reads the regions of the account, sends reads to the first
+    /// available region in `preferred_regions` (then the other regions, in the
+    /// order of the account), and writes to the region which accepts them. A
+    /// region which is unavailable, or no longer accepts writes, is skipped and
+    /// the request is retried in the next one.
+    ///
+    /// Without it, every request is sent to the account endpoint.
+    #[must_use]
+    pub fn preferred_regions<I, S>(mut self, preferred_regions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.preferred_regions = Some(preferred_regions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set how often the regions of the account are read again, five minutes
+    /// by default. Only used with [`Self::preferred_regions`].
+    #[must_use]
+    pub fn endpoint_refresh_interval(mut self, interval: Duration) -> Self {
+        self.endpoint_refresh_interval = interval;
+        self
+    }
+
     /// Set the cloud location.
     #[must_use]
     pub fn cloud_location(mut self, cloud_location: CloudLocation) -> Self {
@@ -86,6 +151,7 @@ impl CosmosClientBuilder {
 pub struct CosmosClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    session: Option<Arc<SessionContainer>>,
 }
 
 impl CosmosClient {
@@ -127,6 +193,33 @@ impl CosmosClient {
         ListDatabasesBuilder::new(self.clone())
     }
 
+    /// Get the account, and the regions it is replicated to
+    pub fn get_database_account(&self) -> GetDatabaseAccountBuilder {
+        GetDatabaseAccountBuilder::new(self.clone())
+    }
+
+    /// The session tokens the client keeps, to resume its session with another
+    /// client. Empty when automatic session tokens are disabled.
+    pub fn export_session(&self) -> SessionState {
+        self.session
+            .as_ref()
+            .map(|session| session.export())
+            .unwrap_or_default()
+    }
+
+    /// Resume a session exported by another client, keeping the most recent
+    /// session token of every partition key range.
+    pub fn import_session(&self, state: &SessionState) {
+        if let Some(session) = &self.session {
+            session.import(state);
+        }
+    }
+
+    /// The session token the client keeps for a collection, like `dbs/db/colls/coll`.
+    pub(crate) fn session_token(&self, collection_link: &str) -> Option<String> {
+        self.session.as_ref()?.session_token(collection_link, None)
+    }
+
     /// Create a [`DatabaseClient`].
     pub fn database_client<S: Into<ReadonlyString>>(&self, database_name: S) -> DatabaseClient {
         DatabaseClient::new(self.clone(), database_name)
@@ -164,6 +257,8 @@ impl CosmosClient {
 fn new_pipeline_from_options(
     options: ClientOptions,
     authorization_token: AuthorizationToken,
+    location_policy: Option<crate::LocationPolicy>,
+    session: Option<Arc<SessionContainer>>,
 ) -> Pipeline {
     let auth_policy: Arc<dyn azure_core::Policy> =
         Arc::new(crate::AuthorizationPolicy::new(authorization_token));
@@ -171,13 +266,22 @@ fn new_pipeline_from_options(
     // The `AuthorizationPolicy` must be the **last** retry policy.
     // Policies can change the url and/or the headers, and the `AuthorizationPolicy`
     // must be able to inspect them or the resulting token will be invalid.
-    let per_retry_policies = vec![auth_policy];
+    let mut per_retry_policies = vec![auth_policy];
+    if let Some(location_policy) = location_policy {
+        per_retry_policies.insert(0, Arc::new(location_policy));
+    }
+
+    // The `SessionPolicy` sees the response of the last retry only.
+    let mut per_call_policies: Vec<Arc<dyn azure_core::Policy>> = Vec::new();
+    if let Some(session) = session {
+        per_call_policies.push(Arc::new(crate::SessionPolicy::new(session)));
+    }
 
     Pipeline::new(
         option_env!("CARGO_PKG_NAME"),
         option_env!("CARGO_PKG_VERSION"),
         options,
-        Vec::new(),
+        per_call_policies,
         per_retry_policies,
     )
 }
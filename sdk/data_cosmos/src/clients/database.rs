@@ -1,6 +1,7 @@
 use crate::clients::*;
 use crate::operations::*;
 use crate::resources::collection::PartitionKey;
+use crate::resources::Throughput;
 use crate::ReadonlyString;
 use azure_core::Method;
 use azure_core::Request;
@@ -44,6 +45,16 @@ impl DatabaseClient {
         CreateCollectionBuilder::new(self.clone(), collection_name.into(), partition_key.into())
     }
 
+    /// Read the throughput shared by the collections of the database.
+    pub fn read_throughput(&self) -> ReadThroughputBuilder {
+        ReadThroughputBuilder::new(ThroughputClient::Database(self.clone()))
+    }
+
+    /// Replace the throughput shared by the collections of the database.
+    pub fn replace_throughput(&self, throughput: Throughput) -> ReplaceThroughputBuilder {
+        ReplaceThroughputBuilder::new(ThroughputClient::Database(self.clone()), throughput)
+    }
+
     /// List users.
     pub fn list_users(&self) -> ListUsersBuilder {
         ListUsersBuilder::new(self.clone())
@@ -22,6 +22,16 @@ pub(crate) const HEADER_REQUEST_CHARGE: HeaderName = HeaderName::from_static("x-
 pub(crate) const HEADER_OFFER_THROUGHPUT: HeaderName =
     HeaderName::from_static("x-ms-offer-throughput");
 pub(crate) const HEADER_OFFER_TYPE: HeaderName = HeaderName::from_static("x-ms-offer-type");
+pub(crate) const HEADER_OFFER_AUTOPILOT_SETTINGS: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-offer-autopilot-settings");
+pub(crate) const HEADER_OFFER_REPLACE_PENDING: HeaderName =
+    HeaderName::from_static("x-ms-offer-replace-pending");
+pub(crate) const HEADER_MIN_THROUGHPUT: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-min-throughput");
+pub(crate) const HEADER_MIGRATE_OFFER_TO_AUTOPILOT: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-migrate-offer-to-autopilot");
+pub(crate) const HEADER_MIGRATE_OFFER_TO_MANUAL_THROUGHPUT: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-migrate-offer-to-manual-throughput");
 #[allow(dead_code)]
 pub(crate) const HEADER_DOCUMENTDB_ISQUERY: HeaderName =
     HeaderName::from_static("x-ms-documentdb-isquery");
@@ -69,3 +79,12 @@ pub(crate) const HEADER_MAX_MEDIA_STORAGE_USAGE_MB: HeaderName =
     HeaderName::from_static("x-ms-max-media-storage-usage-mb");
 pub(crate) const HEADER_MEDIA_STORAGE_USAGE_MB: HeaderName =
     HeaderName::from_static("x-ms-media-storage-usage-mb");
+pub(crate) const HEADER_SUB_STATUS: HeaderName = HeaderName::from_static("x-ms-substatus");
+pub(crate) const HEADER_IS_BATCH_REQUEST: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-is-batch-request");
+pub(crate) const HEADER_BATCH_ATOMIC: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-batch-atomic");
+pub(crate) const HEADER_BATCH_CONTINUE_ON_ERROR: HeaderName =
+    HeaderName::from_static("x-ms-cosmos-batch-continue-on-error");
+pub(crate) const HEADER_START_EPK: HeaderName = HeaderName::from_static("x-ms-start-epk");
+pub(crate) const HEADER_END_EPK: HeaderName = HeaderName::from_static("x-ms-end-epk");
@@ -91,21 +91,29 @@ extern crate serde;
 #[macro_use]
 extern crate azure_core;
 
+pub mod bulk;
+pub mod change_feed;
 pub mod clients;
 mod operations;
 pub mod prelude;
+pub mod query;
 pub mod resources;
 
 mod authorization_policy;
 mod consistency_level;
 mod cosmos_entity;
 mod headers;
+mod location_policy;
 mod resource_quota;
+mod session_policy;
 
 pub(crate) use authorization_policy::AuthorizationPolicy;
+pub(crate) use location_policy::LocationPolicy;
+pub(crate) use session_policy::SessionPolicy;
 
 pub use consistency_level::ConsistencyLevel;
 pub use cosmos_entity::CosmosEntity;
 pub use resource_quota::ResourceQuota;
+pub use session_policy::SessionState;
 
 type ReadonlyString = std::borrow::Cow<'static, str>;
@@ -0,0 +1,450 @@
+use crate::{
+    headers::HEADER_SUB_STATUS,
+    resources::{DatabaseAccount, ResourceType},
+};
+use azure_core::{
+    error::ErrorKind, Context, Method, Policy, PolicyResult, Request, StatusCode, Url,
+};
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use time::OffsetDateTime;
+use tracing::{debug, warn};
+
+/// The sub status of a `403 Forbidden` sent by a region which no longer
+/// accepts writes.
+const WRITE_FORBIDDEN: &str = "1008";
+/// How long an endpoint which failed is only tried after the other ones.
+const UNAVAILABLE_FOR: Duration = Duration::from_secs(5 * 60);
+/// How often the regions of the account are read again by default.
+pub(crate) const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// The `LocationPolicy` sends requests to the regions of a globally distributed
+/// account.
+///
+/// It reads the regions of the account from the account endpoint, and sends
+/// reads to the first available region of the preferred regions, and writes to
+/// the region which accepts them. When a region answers `503 Service
+/// Unavailable`, or `403 Forbidden` because it no longer accepts writes, the
+/// region is marked unavailable and the request is sent to the next region.
+///
+/// The policy must be added before the [`AuthorizationPolicy`](crate::AuthorizationPolicy),
+/// which it uses to read the regions of the account.
+#[derive(Debug, Clone)]
+pub struct LocationPolicy {
+    cache: Arc<RwLock<LocationCache>>,
+    refresh_interval: Duration,
+}
+
+impl LocationPolicy {
+    pub(crate) fn new(
+        default_endpoint: Url,
+        preferred_regions: Vec<String>,
+        refresh_interval: Duration,
+    ) -> Self {
+        Self {
+            cache: Arc::new(RwLock::new(LocationCache::new(
+                default_endpoint,
+                preferred_regions,
+            ))),
+            refresh_interval,
+        }
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&LocationCache) -> T) -> T {
+        f(&self.cache.read().unwrap_or_else(|error| error.into_inner()))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut LocationCache) -> T) -> T {
+        f(&mut self
+            .cache
+            .write()
+            .unwrap_or_else(|error| error.into_inner()))
+    }
+
+    /// The first endpoint to send a request to which was not tried yet.
+    fn next_endpoint(&self, is_write: bool, tried: &[Url]) -> Option<Url> {
+        let now = OffsetDateTime::now_utc();
+        self.read(|cache| {
+            cache
+                .endpoints(is_write, now)
+                .into_iter()
+                .find(|endpoint| !tried.contains(endpoint))
+        })
+    }
+
+    /// Read the regions of the account, from the first endpoint which answers.
+    ///
+    /// The regions already known are kept when no endpoint answers.
+    async fn refresh(&self, ctx: &Context, next: &[Arc<dyn Policy>]) {
+        let now = OffsetDateTime::now_utc();
+        let endpoints = self.write(|cache| {
+            cache.refreshed = Some(now);
+            cache.account_endpoints(now)
+        });
+        let mut ctx = ctx.clone();
+        ctx.insert(ResourceType::DatabaseAccount);
+        for endpoint in endpoints {
+            let mut request = Request::new(endpoint.clone(), Method::Get);
+            let account = match next[0].send(&ctx, &mut request, &next[1..]).await {
+                Ok(response) if response.status().is_success() => {
+                    response.json::<DatabaseAccount>().await
+                }
+                Ok(response) => {
+                    debug!(
+                        "{endpoint} answered {} to an account read",
+                        response.status()
+                    );
+                    continue;
+                }
+                Err(error) => Err(error),
+            };
+            match account {
+                Ok(account) => {
+                    self.write(|cache| cache.update(&account));
+                    return;
+                }
+                Err(error) => debug!("could not read the account from {endpoint}: {error}"),
+            }
+        }
+        warn!("could not read the regions of the account, keeping the known ones");
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for LocationPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        assert!(
+            !next.is_empty(),
+            "Location policies cannot be the last policy of a pipeline"
+        );
+
+        let now = OffsetDateTime::now_utc();
+        if self.read(|cache| cache.needs_refresh(now, self.refresh_interval)) {
+            self.refresh(ctx, next).await;
+        }
+
+        let is_write = is_write(request);
+        let mut tried: Vec<Url> = Vec::new();
+        // the account endpoint is always one of the endpoints
+        let Some(mut endpoint) = self.next_endpoint(is_write, &tried) else {
+            return next[0].send(ctx, request, &next[1..]).await;
+        };
+        loop {
+            route(request.url_mut(), &endpoint);
+            tried.push(endpoint.clone());
+
+            let result = next[0].send(ctx, request, &next[1..]).await;
+            let now = OffsetDateTime::now_utc();
+            match &result {
+                Ok(response) if response.status() == StatusCode::ServiceUnavailable => {
+                    debug!("{endpoint} is unavailable");
+                    self.write(|cache| cache.mark_unavailable(&endpoint, is_write, now));
+                }
+                Ok(response)
+                    if response.status() == StatusCode::Forbidden
+                        && response.headers().get_optional_str(&HEADER_SUB_STATUS)
+                            == Some(WRITE_FORBIDDEN) =>
+                {
+                    debug!("{endpoint} no longer accepts writes");
+                    self.write(|cache| cache.mark_unavailable(&endpoint, true, now));
+                    self.refresh(ctx, next).await;
+                }
+                Err(error) if error.kind() == &ErrorKind::Io => {
+                    debug!("{endpoint} could not be reached: {error}");
+                    self.write(|cache| cache.mark_unavailable(&endpoint, is_write, now));
+                }
+                _ => return result,
+            }
+            match self.next_endpoint(is_write, &tried) {
+                Some(next_endpoint) => endpoint = next_endpoint,
+                None => return result,
+            }
+        }
+    }
+}
+
+/// Whether a request writes, and must be sent to a region which accepts writes.
+pub(crate) fn is_write(request: &Request) -> bool {
+    match *request.method() {
+        Method::Get | Method::Head => false,
+        // queries are sent with POST, but are reads
+        Method::Post => {
+            request
+                .headers()
+                .get_optional_str(&crate::headers::HEADER_DOCUMENTDB_ISQUERY)
+                != Some("true")
+        }
+        _ => true,
+    }
+}
+
+/// Send a request to another endpoint of the account, keeping its path.
+fn route(url: &mut Url, endpoint: &Url) {
+    // these only fail for urls which cannot be a base, unlike http urls
+    let _ = url.set_scheme(endpoint.scheme());
+    let _ = url.set_host(endpoint.host_str());
+    let _ = url.set_port(endpoint.port());
+}
+
+/// The regions of an account, and which of them failed recently.
+#[derive(Debug, Clone)]
+struct LocationCache {
+    default_endpoint: Url,
+    preferred_regions: Vec<String>,
+    read_endpoints: Vec<(String, Url)>,
+    write_endpoints: Vec<(String, Url)>,
+    multiple_write_locations: bool,
+    unavailable: Vec<Unavailable>,
+    refreshed: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone)]
+struct Unavailable {
+    endpoint: Url,
+    for_writes: bool,
+    since: OffsetDateTime,
+}
+
+impl LocationCache {
+    fn new(default_endpoint: Url, preferred_regions: Vec<String>) -> Self {
+        Self {
+            default_endpoint,
+            preferred_regions,
+            read_endpoints: Vec::new(),
+            write_endpoints: Vec::new(),
+            multiple_write_locations: false,
+            unavailable: Vec::new(),
+            refreshed: None,
+        }
+    }
+
+    fn needs_refresh(&self, now: OffsetDateTime, interval: Duration) -> bool {
+        self.refreshed
+            .map_or(true, |refreshed| now - refreshed >= interval)
+    }
+
+    fn update(&mut self, account: &DatabaseAccount) {
+        let endpoints = |regions: &[crate::resources::AccountRegion]| {
+            regions
+                .iter()
+                .filter_map(|region| {
+                    let endpoint = Url::parse(&region.database_account_endpoint).ok()?;
+                    Some((region.name.clone(), endpoint))
+                })
+                .collect()
+        };
+        self.read_endpoints = endpoints(&account.readable_locations);
+        self.write_endpoints = endpoints(&account.writable_locations);
+        self.multiple_write_locations = account.enable_multiple_write_locations;
+    }
+
+    fn is_unavailable(&self, endpoint: &Url, for_writes: bool, now: OffsetDateTime) -> bool {
+        self.unavailable.iter().any(|unavailable| {
+            unavailable.endpoint == *endpoint
+                && unavailable.for_writes == for_writes
+                && now - unavailable.since < UNAVAILABLE_FOR
+        })
+    }
+
+    fn mark_unavailable(&mut self, endpoint: &Url, for_writes: bool, now: OffsetDateTime) {
+        self.unavailable
+            .retain(|unavailable| now - unavailable.since < UNAVAILABLE_FOR);
+        self.unavailable
+            .retain(|u| !(u.endpoint == *endpoint && u.for_writes == for_writes));
+        self.unavailable.push(Unavailable {
+            endpoint: endpoint.clone(),
+            for_writes,
+            since: now,
+        });
+    }
+
+    /// The endpoints to send a request to, in order.
+    ///
+    /// Regions follow the preferred regions, then the order of the account.
+    /// Writes only go to the first writable region, unless every region
+    /// accepts writes. The endpoints which failed recently come last, and the
+    /// account endpoint is always a last resort.
+    fn endpoints(&self, is_write: bool, now: OffsetDateTime) -> Vec<Url> {
+        let regions = if is_write {
+            if self.multiple_write_locations {
+                &self.write_endpoints[..]
+            } else {
+                &self.write_endpoints[..self.write_endpoints.len().min(1)]
+            }
+        } else {
+            &self.read_endpoints[..]
+        };
+        let rank = |name: &String| {
+            self.preferred_regions
+                .iter()
+                .position(|preferred| preferred.eq_ignore_ascii_case(name))
+                .unwrap_or(self.preferred_regions.len())
+        };
+        let mut endpoints: Vec<_> = regions.iter().collect();
+        // stable, so that regions which are not preferred keep the account order
+        endpoints.sort_by_key(|(name, _)| rank(name));
+        let (available, unavailable): (Vec<Url>, Vec<Url>) = endpoints
+            .into_iter()
+            .map(|(_, endpoint)| endpoint.clone())
+            .partition(|endpoint| !self.is_unavailable(endpoint, is_write, now));
+
+        let mut endpoints = available;
+        endpoints.extend(unavailable);
+        if !endpoints.contains(&self.default_endpoint) {
+            endpoints.push(self.default_endpoint.clone());
+        }
+        endpoints
+    }
+
+    /// The endpoints the account can be read from.
+    fn account_endpoints(&self, now: OffsetDateTime) -> Vec<Url> {
+        let mut endpoints = vec![self.default_endpoint.clone()];
+        for endpoint in self.endpoints(false, now) {
+            if !endpoints.contains(&endpoint) {
+                endpoints.push(endpoint);
+            }
+        }
+        endpoints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::AccountRegion;
+
+    fn region(name: &str, endpoint: &str) -> AccountRegion {
+        AccountRegion {
+            name: name.to_owned(),
+            database_account_endpoint: endpoint.to_owned(),
+        }
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    fn cache(preferred_regions: &[&str], multiple_write_locations: bool) -> LocationCache {
+        let mut cache = LocationCache::new(
+            url("https://acct.documents.azure.com"),
+            preferred_regions.iter().map(|r| r.to_string()).collect(),
+        );
+        cache.update(&DatabaseAccount {
+            id: "acct".to_owned(),
+            writable_locations: vec![
+                region("West US", "https://acct-westus.documents.azure.com:443/"),
+                region("East US", "https://acct-eastus.documents.azure.com:443/"),
+            ],
+            readable_locations: vec![
+                region("West US", "https://acct-westus.documents.azure.com:443/"),
+                region("East US", "https://acct-eastus.documents.azure.com:443/"),
+                region(
+                    "North Europe",
+                    "https://acct-northeurope.documents.azure.com:443/",
+                ),
+            ],
+            enable_multiple_write_locations: multiple_write_locations,
+        });
+        cache
+    }
+
+    fn hosts(endpoints: Vec<Url>) -> Vec<String> {
+        endpoints
+            .iter()
+            .map(|endpoint| endpoint.host_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn reads_follow_the_preferred_regions() {
+        let now = OffsetDateTime::now_utc();
+        let cache = cache(&["North Europe", "east us"], false);
+        assert_eq!(
+            hosts(cache.endpoints(false, now)),
+            [
+                "acct-northeurope.documents.azure.com",
+                "acct-eastus.documents.azure.com",
+                "acct-westus.documents.azure.com",
+                "acct.documents.azure.com",
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_go_to_the_write_region() {
+        let now = OffsetDateTime::now_utc();
+        assert_eq!(
+            hosts(cache(&["East US"], false).endpoints(true, now)),
+            [
+                "acct-westus.documents.azure.com",
+                "acct.documents.azure.com"
+            ]
+        );
+        assert_eq!(
+            hosts(cache(&["East US"], true).endpoints(true, now)),
+            [
+                "acct-eastus.documents.azure.com",
+                "acct-westus.documents.azure.com",
+                "acct.documents.azure.com",
+            ]
+        );
+    }
+
+    #[test]
+    fn unavailable_endpoints_come_last_until_they_expire() {
+        let now = OffsetDateTime::now_utc();
+        let mut cache = cache(&["East US", "West US"], false);
+        let east = url("https://acct-eastus.documents.azure.com");
+        cache.mark_unavailable(&east, false, now);
+        assert_eq!(
+            hosts(cache.endpoints(false, now)),
+            [
+                "acct-westus.documents.azure.com",
+                "acct-northeurope.documents.azure.com",
+                "acct-eastus.documents.azure.com",
+                "acct.documents.azure.com",
+            ]
+        );
+        // only for reads
+        assert!(!cache.is_unavailable(&east, true, now));
+        let later = now + UNAVAILABLE_FOR;
+        assert_eq!(
+            hosts(cache.endpoints(false, later))[0],
+            "acct-eastus.documents.azure.com"
+        );
+    }
+
+    #[test]
+    fn refreshes_after_the_interval() {
+        let now = OffsetDateTime::now_utc();
+        let mut cache = cache(&[], false);
+        assert!(cache.needs_refresh(now, DEFAULT_REFRESH_INTERVAL));
+        cache.refreshed = Some(now);
+        assert!(!cache.needs_refresh(now, DEFAULT_REFRESH_INTERVAL));
+        assert!(cache.needs_refresh(now + DEFAULT_REFRESH_INTERVAL, DEFAULT_REFRESH_INTERVAL));
+    }
+
+    #[test]
+    fn requests_are_routed_keeping_their_path() {
+        let mut request_url = url("https://acct.documents.azure.com/dbs/db/colls/c/docs?x=1");
+        route(
+            &mut request_url,
+            &url("https://acct-eastus.documents.azure.com:443/"),
+        );
+        assert_eq!(
+            request_url.as_str(),
+            "https://acct-eastus.documents.azure.com/dbs/db/colls/c/docs?x=1"
+        );
+        let mut emulator = url("https://localhost:8081/dbs");
+        route(&mut emulator, &url("https://127.0.0.1:8082/"));
+        assert_eq!(emulator.as_str(), "https://127.0.0.1:8082/dbs");
+    }
+}
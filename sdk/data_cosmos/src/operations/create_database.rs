@@ -9,13 +9,15 @@ operation! {
     CreateDatabase,
     client: CosmosClient,
     database_name: String,
-    ?consistency_level: ConsistencyLevel
+    ?consistency_level: ConsistencyLevel,
+    ?offer: Offer
 }
 
 impl CreateDatabaseBuilder {
     pub fn into_future(self) -> CreateDatabase {
         Box::pin(async move {
             let mut request = self.client.request("dbs", azure_core::Method::Post);
+            request.insert_headers(&self.offer);
 
             #[derive(Serialize)]
             struct CreateDatabaseBody<'a> {
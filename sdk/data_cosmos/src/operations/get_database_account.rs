@@ -0,0 +1,38 @@
+use crate::{headers::from_headers::*, prelude::*};
+use azure_core::Response as HttpResponse;
+
+operation! {
+    GetDatabaseAccount,
+    client: CosmosClient,
+}
+
+impl GetDatabaseAccountBuilder {
+    pub fn into_future(self) -> GetDatabaseAccount {
+        Box::pin(async move {
+            let request = self.client.request("", azure_core::Method::Get);
+            let response = self
+                .client
+                .send(request, self.context.clone(), ResourceType::DatabaseAccount)
+                .await?;
+            GetDatabaseAccountResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetDatabaseAccountResponse {
+    pub account: DatabaseAccount,
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+}
+
+impl GetDatabaseAccountResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        Ok(Self {
+            account: body.json().await?,
+            charge: request_charge_from_headers(&headers).unwrap_or_default(),
+            activity_id: activity_id_from_headers(&headers)?,
+        })
+    }
+}
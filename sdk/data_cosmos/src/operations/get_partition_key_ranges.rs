@@ -128,7 +128,9 @@ pub struct PartitionKeyRange {
     #[serde(rename = "throughputFraction")]
     pub throughput_fraction: f32,
     pub status: String,
-    // TODO: parents
+    /// The ranges this range was split from, or merged from.
+    #[serde(default)]
+    pub parents: Vec<String>,
     #[serde(rename = "_ts")]
     pub ts: u64,
 }
@@ -25,6 +25,7 @@ mod execute_stored_procedure;
 mod get_attachment;
 mod get_collection;
 mod get_database;
+mod get_database_account;
 mod get_document;
 mod get_partition_key_ranges;
 mod get_permission;
@@ -45,6 +46,8 @@ mod replace_document;
 mod replace_permission;
 mod replace_stored_procedure;
 mod replace_user;
+mod throughput;
+mod transactional_batch;
 
 pub use create_collection::*;
 pub use create_database::*;
@@ -69,6 +72,7 @@ pub use execute_stored_procedure::*;
 pub use get_attachment::*;
 pub use get_collection::*;
 pub use get_database::*;
+pub use get_database_account::*;
 pub use get_document::*;
 pub use get_partition_key_ranges::*;
 pub use get_permission::*;
@@ -89,3 +93,5 @@ pub use replace_document::*;
 pub use replace_permission::*;
 pub use replace_stored_procedure::*;
 pub use replace_user::*;
+pub use throughput::*;
+pub use transactional_batch::*;
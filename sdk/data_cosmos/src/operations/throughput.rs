@@ -0,0 +1,195 @@
+use crate::{
+    headers::{
+        from_headers::*, HEADER_DOCUMENTDB_ISQUERY, HEADER_MIGRATE_OFFER_TO_AUTOPILOT,
+        HEADER_MIGRATE_OFFER_TO_MANUAL_THROUGHPUT, HEADER_MIN_THROUGHPUT,
+        HEADER_OFFER_REPLACE_PENDING,
+    },
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{HeaderValue, CONTENT_TYPE},
+    Context, Method, Request, Response as HttpResponse,
+};
+
+/// The database or collection whose throughput is read or replaced.
+#[derive(Debug, Clone)]
+pub(crate) enum ThroughputClient {
+    Database(DatabaseClient),
+    Collection(CollectionClient),
+}
+
+impl ThroughputClient {
+    fn cosmos_client(&self) -> &CosmosClient {
+        match self {
+            ThroughputClient::Database(client) => client.cosmos_client(),
+            ThroughputClient::Collection(client) => client.cosmos_client(),
+        }
+    }
+
+    async fn send(&self, request: Request, context: &Context) -> azure_core::Result<HttpResponse> {
+        self.cosmos_client()
+            .send(request, context.clone(), ResourceType::Offers)
+            .await
+    }
+
+    /// Find the offer of the database or collection.
+    async fn offer(&self, context: &Context) -> azure_core::Result<ThroughputProperties> {
+        let resource_id = match self {
+            ThroughputClient::Database(client) => {
+                client
+                    .get_database()
+                    .context(context.clone())
+                    .await?
+                    .database
+                    .rid
+            }
+            ThroughputClient::Collection(client) => {
+                client
+                    .get_collection()
+                    .context(context.clone())
+                    .await?
+                    .collection
+                    .rid
+            }
+        };
+
+        let mut request = self.cosmos_client().request("offers", Method::Post);
+        request.insert_header(HEADER_DOCUMENTDB_ISQUERY, "true");
+        request.insert_header(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/query+json"),
+        );
+        request.set_json(&Query::with_params(
+            "SELECT * FROM root r WHERE r.offerResourceId = @offerResourceId".to_owned(),
+            vec![Param::new(
+                "@offerResourceId".to_owned(),
+                resource_id.clone(),
+            )],
+        ))?;
+
+        #[derive(Deserialize)]
+        struct Offers {
+            #[serde(rename = "Offers")]
+            offers: Vec<ThroughputProperties>,
+        }
+        let offers: Offers = self.send(request, context).await?.json().await?;
+        offers.offers.into_iter().next().ok_or_else(|| {
+            Error::with_message(ErrorKind::Other, || {
+                format!("no throughput is provisioned for the resource {resource_id}")
+            })
+        })
+    }
+
+    /// Read an offer, with its minimum throughput and whether it is scaling.
+    async fn read(
+        &self,
+        offer: &ThroughputProperties,
+        context: &Context,
+    ) -> azure_core::Result<ReadThroughputResponse> {
+        let request = self
+            .cosmos_client()
+            .request(&format!("offers/{}", offer.rid), Method::Get);
+        ReadThroughputResponse::try_from(self.send(request, context).await?).await
+    }
+
+    /// Replace an offer, migrating it to the kind of throughput of `migrate`
+    /// instead when set.
+    async fn replace(
+        &self,
+        offer: &ThroughputProperties,
+        migrate: Option<Throughput>,
+        context: &Context,
+    ) -> azure_core::Result<ReadThroughputResponse> {
+        let mut request = self
+            .cosmos_client()
+            .request(&format!("offers/{}", offer.rid), Method::Put);
+        match migrate {
+            Some(Throughput::Autoscale(_)) => {
+                request.insert_header(HEADER_MIGRATE_OFFER_TO_AUTOPILOT, "true");
+            }
+            Some(Throughput::Manual(_)) => {
+                request.insert_header(HEADER_MIGRATE_OFFER_TO_MANUAL_THROUGHPUT, "true");
+            }
+            None => {}
+        }
+        request.set_json(offer)?;
+        ReadThroughputResponse::try_from(self.send(request, context).await?).await
+    }
+}
+
+operation! {
+    ReadThroughput,
+    client: ThroughputClient,
+}
+
+impl ReadThroughputBuilder {
+    pub fn into_future(self) -> ReadThroughput {
+        Box::pin(async move {
+            let offer = self.client.offer(&self.context).await?;
+            self.client.read(&offer, &self.context).await
+        })
+    }
+}
+
+operation! {
+    /// Replace the throughput of a database or collection.
+    ///
+    /// Switching between manual and autoscale throughput first migrates the
+    /// offer, which picks a throughput of the new kind from the current one,
+    /// then sets the requested throughput if it differs.
+    ReplaceThroughput,
+    client: ThroughputClient,
+    throughput: Throughput,
+}
+
+impl ReplaceThroughputBuilder {
+    pub fn into_future(self) -> ReplaceThroughput {
+        Box::pin(async move {
+            let mut offer = self.client.offer(&self.context).await?;
+            let is_autoscale = |throughput| matches!(throughput, Throughput::Autoscale(_));
+            if is_autoscale(offer.throughput()) != is_autoscale(self.throughput) {
+                let migrated = self
+                    .client
+                    .replace(&offer, Some(self.throughput), &self.context)
+                    .await?;
+                if migrated.throughput.throughput() == self.throughput {
+                    return Ok(migrated);
+                }
+                offer = migrated.throughput;
+            }
+            offer.set_throughput(self.throughput);
+            self.client.replace(&offer, None, &self.context).await
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadThroughputResponse {
+    pub throughput: ThroughputProperties,
+    /// The lowest throughput which can be provisioned, from the storage and
+    /// the highest throughput ever provisioned.
+    pub min_throughput: Option<u64>,
+    /// Whether the throughput is still being scaled to the one provisioned.
+    pub replace_pending: bool,
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+}
+
+/// Replacing the throughput returns the offer replaced.
+pub type ReplaceThroughputResponse = ReadThroughputResponse;
+
+impl ReadThroughputResponse {
+    pub async fn try_from(response: HttpResponse) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        Ok(Self {
+            throughput: body.json().await?,
+            min_throughput: headers.get_optional_as(&HEADER_MIN_THROUGHPUT)?,
+            replace_pending: headers
+                .get_optional_as(&HEADER_OFFER_REPLACE_PENDING)?
+                .unwrap_or_default(),
+            charge: request_charge_from_headers(&headers)?,
+            activity_id: activity_id_from_headers(&headers)?,
+        })
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{
+    headers::{
+        from_headers::*, HEADER_BATCH_ATOMIC, HEADER_BATCH_CONTINUE_ON_ERROR,
+        HEADER_IS_BATCH_REQUEST,
+    },
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind, ResultExt},
+    headers::{session_token_from_headers, Headers},
+    Request, Response as HttpResponse, SessionToken, StatusCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// The most operations a batch request can hold.
+pub const MAX_BATCH_OPERATIONS: usize = 100;
+
+operation! {
+    /// Run operations on the documents of a logical partition atomically:
+    /// either all of them succeed, or none of them is applied.
+    TransactionalBatch,
+    client: CollectionClient,
+    partition_key: String,
+    operations: Vec<BatchOperation>,
+    ?consistency_level: ConsistencyLevel
+}
+
+impl TransactionalBatchBuilder {
+    /// Add an operation to the batch.
+    pub fn operation(mut self, operation: BatchOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    pub fn into_future(self) -> TransactionalBatch {
+        Box::pin(async move {
+            let mut request = self.client.docs_request(azure_core::Method::Post);
+            crate::cosmos_entity::add_as_partition_key_header_serialized(
+                &self.partition_key,
+                &mut request,
+            );
+            if let Some(cl) = &self.consistency_level {
+                request.insert_headers(cl);
+            }
+            batch_request(&mut request, &self.operations, true)?;
+
+            let result = self
+                .client
+                .pipeline()
+                .send(
+                    self.context.clone().insert(ResourceType::Documents),
+                    &mut request,
+                )
+                .await;
+            TransactionalBatchResponse::try_from(result).await
+        })
+    }
+}
+
+/// Turn a request into a batch request of `operations`.
+pub(crate) fn batch_request(
+    request: &mut Request,
+    operations: &[BatchOperation],
+    atomic: bool,
+) -> azure_core::Result<()> {
+    if operations.is_empty() || operations.len() > MAX_BATCH_OPERATIONS {
+        return Err(Error::with_message(ErrorKind::Other, || {
+            format!(
+                "a batch must hold between 1 and {MAX_BATCH_OPERATIONS} operations, not {}",
+                operations.len()
+            )
+        }));
+    }
+    request.insert_header(HEADER_IS_BATCH_REQUEST, "true");
+    request.insert_header(HEADER_BATCH_ATOMIC, atomic.to_string());
+    request.insert_header(HEADER_BATCH_CONTINUE_ON_ERROR, (!atomic).to_string());
+    request.set_json(&operations)?;
+    Ok(())
+}
+
+/// An operation of a batch request.
+///
+/// The document of an operation must belong to the partition key of the batch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOperation {
+    operation_type: BatchOperationType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    if_match: Option<String>,
+    /// Only set for bulk operations, which are sent by partition key range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) partition_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+enum BatchOperationType {
+    Create,
+    Upsert,
+    Replace,
+    Patch,
+    Delete,
+    Read,
+}
+
+impl BatchOperation {
+    fn new(
+        operation_type: BatchOperationType,
+        id: Option<String>,
+        resource_body: Option<Value>,
+    ) -> Self {
+        Self {
+            operation_type,
+            id,
+            resource_body,
+            if_match: None,
+            partition_key: None,
+        }
+    }
+
+    fn body<D: Serialize>(document: &D) -> azure_core::Result<Value> {
+        serde_json::to_value(document).context(
+            ErrorKind::DataConversion,
+            "could not serialize the document of a batch operation",
+        )
+    }
+
+    /// Create a document.
+    pub fn create<D: Serialize>(document: &D) -> azure_core::Result<Self> {
+        Ok(Self::new(
+            BatchOperationType::Create,
+            None,
+            Some(Self::body(document)?),
+        ))
+    }
+
+    /// Create a document, or replace it if it exists.
+    pub fn upsert<D: Serialize>(document: &D) -> azure_core::Result<Self> {
+        Ok(Self::new(
+            BatchOperationType::Upsert,
+            None,
+            Some(Self::body(document)?),
+        ))
+    }
+
+    /// Replace a document.
+    pub fn replace<S: Into<String>, D: Serialize>(id: S, document: &D) -> azure_core::Result<Self> {
+        Ok(Self::new(
+            BatchOperationType::Replace,
+            Some(id.into()),
+            Some(Self::body(document)?),
+        ))
+    }
+
+    /// Patch a document.
+    pub fn patch<S: Into<String>>(id: S, operations: Vec<Operation>) -> Self {
+        Self::new(
+            BatchOperationType::Patch,
+            Some(id.into()),
+            Some(serde_json::json!({ "operations": operations })),
+        )
+    }
+
+    /// Delete a document.
+    pub fn delete<S: Into<String>>(id: S) -> Self {
+        Self::new(BatchOperationType::Delete, Some(id.into()), None)
+    }
+
+    /// Read a document.
+    pub fn read<S: Into<String>>(id: S) -> Self {
+        Self::new(BatchOperationType::Read, Some(id.into()), None)
+    }
+
+    /// Only apply the operation if the document still has this etag.
+    pub fn if_match<S: Into<String>>(mut self, etag: S) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+}
+
+/// The outcome of an operation of a batch request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOperationResult {
+    pub status_code: StatusCode,
+    pub sub_status_code: Option<u32>,
+    pub charge: f64,
+    pub etag: Option<String>,
+    /// The document read or written, if the service returned it.
+    pub resource_body: Option<Value>,
+    /// How long to wait before retrying a throttled operation.
+    pub retry_after: Option<Duration>,
+}
+
+impl BatchOperationResult {
+    /// Whether the operation succeeded.
+    pub fn is_success(&self) -> bool {
+        self.status_code.is_success()
+    }
+
+    /// The document read or written, if the service returned it.
+    pub fn document<T: DeserializeOwned>(&self) -> azure_core::Result<Option<T>> {
+        self.resource_body
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context(
+                ErrorKind::DataConversion,
+                "could not deserialize the document of a batch operation",
+            )
+    }
+
+    /// Parse the results of a batch request, from a successful response, or
+    /// from the error the pipeline made of an unsuccessful one.
+    ///
+    /// A transactional batch which failed is answered with the status of the
+    /// failed operation, and the results of every operation in the body.
+    pub(crate) async fn from_result(
+        result: azure_core::Result<HttpResponse>,
+    ) -> azure_core::Result<(StatusCode, Headers, Vec<Self>)> {
+        match result {
+            Ok(response) => {
+                let (status_code, headers, body) = response.deconstruct();
+                let body = body.collect().await?;
+                Ok((status_code, headers, Self::from_body(&body)?))
+            }
+            Err(error) => {
+                let Some(http_error) = error.as_http_error() else {
+                    return Err(error);
+                };
+                match Self::from_body(http_error.body()) {
+                    Ok(results) => Ok((http_error.status(), http_error.headers().clone(), results)),
+                    Err(_) => Err(error),
+                }
+            }
+        }
+    }
+
+    fn from_body(body: &[u8]) -> azure_core::Result<Vec<Self>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Result {
+            status_code: u16,
+            sub_status_code: Option<u32>,
+            #[serde(default)]
+            request_charge: f64,
+            e_tag: Option<String>,
+            resource_body: Option<Value>,
+            retry_after_milliseconds: Option<u64>,
+        }
+
+        let results: Vec<Result> = serde_json::from_slice(body)?;
+        results
+            .into_iter()
+            .map(|result| {
+                Ok(Self {
+                    status_code: StatusCode::try_from(result.status_code).map_err(|_| {
+                        Error::with_message(ErrorKind::DataConversion, || {
+                            format!("invalid status code {}", result.status_code)
+                        })
+                    })?,
+                    sub_status_code: result.sub_status_code,
+                    charge: result.request_charge,
+                    etag: result.e_tag,
+                    resource_body: result.resource_body,
+                    retry_after: result.retry_after_milliseconds.map(Duration::from_millis),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionalBatchResponse {
+    /// `200 OK` when every operation succeeded, or the status of the
+    /// operation which failed.
+    pub status_code: StatusCode,
+    /// The result of every operation, in order. When an operation fails, the
+    /// other ones fail with `424 Failed Dependency`.
+    pub results: Vec<BatchOperationResult>,
+    pub charge: f64,
+    pub session_token: Option<SessionToken>,
+    pub activity_id: Option<uuid::Uuid>,
+}
+
+impl TransactionalBatchResponse {
+    pub async fn try_from(result: azure_core::Result<HttpResponse>) -> azure_core::Result<Self> {
+        let (status_code, headers, results) = BatchOperationResult::from_result(result).await?;
+        Ok(Self {
+            status_code,
+            results,
+            charge: request_charge_from_headers(&headers)?,
+            session_token: session_token_from_headers(&headers).ok(),
+            activity_id: activity_id_from_headers(&headers).ok(),
+        })
+    }
+
+    /// Whether every operation was applied.
+    pub fn is_success(&self) -> bool {
+        self.status_code.is_success() && self.results.iter().all(BatchOperationResult::is_success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_operations_serialize_like_the_service_expects() {
+        let operations = [
+            BatchOperation::create(&serde_json::json!({"id": "a", "pk": "p"})).unwrap(),
+            BatchOperation::patch("b", vec![Operation::incr("/count", 1).unwrap()])
+                .if_match("\"1\""),
+            BatchOperation::delete("c"),
+        ];
+        assert_eq!(
+            serde_json::to_value(operations).unwrap(),
+            serde_json::json!([
+                {"operationType": "Create", "resourceBody": {"id": "a", "pk": "p"}},
+                {
+                    "operationType": "Patch",
+                    "id": "b",
+                    "resourceBody": {"operations": [{"op": "incr", "path": "/count", "value": 1}]},
+                    "ifMatch": "\"1\"",
+                },
+                {"operationType": "Delete", "id": "c"},
+            ])
+        );
+    }
+
+    #[test]
+    fn batch_results_parse() {
+        let body = br#"[
+            {"statusCode": 409, "requestCharge": 1.24},
+            {"statusCode": 424, "subStatusCode": 5400, "requestCharge": 0},
+            {"statusCode": 429, "retryAfterMilliseconds": 25}
+        ]"#;
+        let results = BatchOperationResult::from_body(body).unwrap();
+        assert_eq!(results[0].status_code, StatusCode::Conflict);
+        assert_eq!(results[0].charge, 1.24);
+        assert_eq!(results[1].sub_status_code, Some(5400));
+        assert_eq!(results[2].retry_after, Some(Duration::from_millis(25)));
+        assert!(!results[0].is_success());
+    }
+}
@@ -0,0 +1,754 @@
+//! Run queries across partition key ranges.
+//!
+//! The gateway only runs a query on a single partition key range when it needs
+//! results from several of them to be merged, like `ORDER BY`, aggregates,
+//! `DISTINCT`, `TOP` or `OFFSET LIMIT`. A [`CrossPartitionQueryBuilder`] asks the
+//! gateway for the query plan, runs the rewritten query on every partition key
+//! range the query targets, and merges their results on the client. The
+//! continuation token of a page holds the continuation of every partition key
+//! range, so that a query can be resumed from it.
+
+mod pipeline;
+
+use crate::{bulk::partition_key_hash::murmur3_x64_128, headers, prelude::*};
+use azure_core::{
+    error::{Error, ErrorKind, ResultExt},
+    headers::{continuation_token_from_headers_optional, Header, HeaderValue, CONTENT_TYPE},
+    prelude::*,
+    Method, Pageable, Request, StatusCode,
+};
+use pipeline::{compare_order_by, Aggregator, DistinctType, QueryInfo};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+const HEADER_IS_QUERY_PLAN_REQUEST: azure_core::headers::HeaderName =
+    azure_core::headers::HeaderName::from_static("x-ms-cosmos-is-query-plan-request");
+const HEADER_SUPPORTED_QUERY_FEATURES: azure_core::headers::HeaderName =
+    azure_core::headers::HeaderName::from_static("x-ms-cosmos-supported-query-features");
+const HEADER_QUERY_VERSION: azure_core::headers::HeaderName =
+    azure_core::headers::HeaderName::from_static("x-ms-cosmos-query-version");
+const SUPPORTED_QUERY_FEATURES: &str =
+    "Aggregate, CompositeAggregate, Distinct, GroupBy, MultipleOrderBy, OffsetAndLimit, OrderBy, Top";
+/// How many results a page holds when the max item count is not set.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+operation! {
+    #[stream]
+    /// A builder for a [`CrossPartitionQuery`].
+    CrossPartitionQuery,
+    client: CollectionClient,
+    query: Query,
+    ?max_item_count: MaxItemCount,
+    ?consistency_level: ConsistencyLevel,
+    ?continuation: Continuation
+}
+
+impl CrossPartitionQueryBuilder {
+    /// Run the query, one page of merged results at a time.
+    pub fn into_stream<T>(self) -> CrossPartitionQuery<T>
+    where
+        T: DeserializeOwned + Send + Sync,
+    {
+        // the execution of the last page, to go on without reading again the
+        // pages buffered from each partition key range
+        let last: Arc<Mutex<Option<(String, Execution)>>> = Arc::default();
+
+        let make_request = move |continuation: Option<Continuation>| {
+            let this = self.clone();
+            let last = last.clone();
+            async move {
+                let token = continuation
+                    .or_else(|| this.continuation.clone())
+                    .map(|continuation| continuation.value().as_str().to_owned());
+                let cached = last.lock().ok().and_then(|mut last| match last.take() {
+                    Some((key, execution)) if Some(&key) == token.as_ref() => Some(execution),
+                    _ => None,
+                });
+                let mut execution = match cached {
+                    Some(execution) => execution,
+                    None => Execution::start(&this, token.as_deref()).await?,
+                };
+
+                let (results, charge) = execution.next_page(&this).await?;
+                let results = results
+                    .into_iter()
+                    .map(serde_json::from_value)
+                    .collect::<Result<Vec<T>, _>>()?;
+                let continuation_token = if execution.is_finished() {
+                    None
+                } else {
+                    let token = serde_json::to_string(&execution.state)?;
+                    if let Ok(mut last) = last.lock() {
+                        *last = Some((token.clone(), execution));
+                    }
+                    Some(Continuation::new(token))
+                };
+                Ok(CrossPartitionQueryResponse {
+                    results,
+                    charge,
+                    continuation_token,
+                })
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+
+    fn page_size(&self) -> usize {
+        let max_item_count = self.max_item_count.unwrap_or_default();
+        match max_item_count.value().as_str().parse::<usize>() {
+            Ok(size) if size > 0 => size,
+            _ => DEFAULT_PAGE_SIZE,
+        }
+    }
+
+    fn request(&self) -> Request {
+        let mut request = self.client.docs_request(Method::Post);
+        request.insert_header(headers::HEADER_DOCUMENTDB_ISQUERY, "true");
+        request.insert_header(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/query+json"),
+        );
+        request.insert_headers(&QueryCrossPartition::Yes);
+        if let Some(cl) = &self.consistency_level {
+            request.insert_headers(cl);
+        }
+        request
+    }
+
+    async fn send(&self, request: &mut Request) -> azure_core::Result<azure_core::Response> {
+        self.client
+            .pipeline()
+            .send(
+                self.context.clone().insert(ResourceType::Documents),
+                request,
+            )
+            .await
+    }
+
+    async fn query_plan(&self) -> azure_core::Result<QueryPlan> {
+        let mut request = self.request();
+        request.insert_header(HEADER_IS_QUERY_PLAN_REQUEST, "true");
+        request.insert_header(HEADER_SUPPORTED_QUERY_FEATURES, SUPPORTED_QUERY_FEATURES);
+        request.insert_header(HEADER_QUERY_VERSION, "1.4");
+        request.set_json(&self.query)?;
+        self.send(&mut request).await?.json().await
+    }
+}
+
+/// The pages of the merged results of a query.
+pub type CrossPartitionQuery<T> =
+    Pageable<CrossPartitionQueryResponse<T>, azure_core::error::Error>;
+
+/// A page of the merged results of a query.
+#[derive(Debug, Clone)]
+pub struct CrossPartitionQueryResponse<T> {
+    /// The results, in the order of the query.
+    pub results: Vec<T>,
+    /// The request charge of the requests sent for this page.
+    pub charge: f64,
+    /// Where to resume the query, absent after the last page.
+    pub continuation_token: Option<Continuation>,
+}
+
+impl<T> Continuable for CrossPartitionQueryResponse<T> {
+    type Continuation = Continuation;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.continuation_token.clone()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryPlan {
+    query_info: QueryInfo,
+    query_ranges: Vec<QueryRange>,
+}
+
+/// A range of effective partition keys a query targets.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryRange {
+    min: String,
+    max: String,
+    #[serde(default)]
+    is_max_inclusive: bool,
+}
+
+impl QueryRange {
+    fn overlaps(&self, range: &PartitionKeyRange) -> bool {
+        if self.min == self.max && self.is_max_inclusive {
+            range.min_exclusive <= self.min && self.min < range.max_exclusive
+        } else {
+            range.min_exclusive < self.max && self.min < range.max_exclusive
+        }
+    }
+}
+
+/// How far a query was read, the continuation token of its pages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryState {
+    ranges: Vec<RangeState>,
+    /// The results skipped for `OFFSET`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    skipped: usize,
+    /// The results returned, for `LIMIT` and `TOP`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    returned: usize,
+    /// The last result of an ordered `DISTINCT`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last: Option<Value>,
+    /// The hashes of the results of an unordered `DISTINCT`.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    seen: BTreeSet<u64>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// How far a partition key range was read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RangeState {
+    id: String,
+    min: String,
+    max: String,
+    /// The continuation of the page being read, absent for the first page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    /// The results of the page being read which were already merged.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    skip: usize,
+    /// The hashes of the results of the page being read which were already
+    /// merged, to leave them out of the page when it is read again by the
+    /// ranges this range is split into.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    merged: Vec<u64>,
+    /// Set when the range only covers part of its partition key range, after
+    /// it was merged with another one.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    partial: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    done: bool,
+}
+
+impl RangeState {
+    fn new(range: &PartitionKeyRange, token: Option<String>) -> Self {
+        Self {
+            id: range.id.clone(),
+            min: range.min_exclusive.clone(),
+            max: range.max_exclusive.clone(),
+            token,
+            skip: 0,
+            merged: Vec::new(),
+            partial: false,
+            done: false,
+        }
+    }
+
+    /// The states replacing this one once its partition key range is gone,
+    /// from the current partition key ranges of the collection, which go on
+    /// from the page being read.
+    ///
+    /// A split leaves several ranges overlapping this range. A merge leaves a
+    /// single range, wider than this range, which is then read partially so
+    /// that the results of its other parts are not read twice.
+    fn resolve(&self, ranges: &[PartitionKeyRange]) -> Vec<Self> {
+        ranges
+            .iter()
+            .filter(|range| range.min_exclusive < self.max && self.min < range.max_exclusive)
+            .map(|range| {
+                let min = self.min.clone().max(range.min_exclusive.clone());
+                let max = self.max.clone().min(range.max_exclusive.clone());
+                Self {
+                    partial: min != range.min_exclusive || max != range.max_exclusive,
+                    min,
+                    max,
+                    merged: self.merged.clone(),
+                    ..Self::new(range, self.token.clone())
+                }
+            })
+            .collect()
+    }
+
+    /// Go on to the page at `token`.
+    fn next_page(&mut self, token: String) {
+        self.token = Some(token);
+        self.skip = 0;
+        self.merged.clear();
+    }
+
+    fn merge(&mut self, result: &Value) {
+        self.skip += 1;
+        self.merged.push(result_hash(result));
+    }
+
+    /// Leave out the results of a page which were already merged.
+    fn skip_merged(&mut self, results: &mut VecDeque<Value>) {
+        if self.merged.len() > self.skip {
+            // the range was split from the range which merged them: they are
+            // the leading results of the page which the parent range merged
+            let merged = std::mem::take(&mut self.merged);
+            self.merged = results
+                .iter()
+                .map(result_hash)
+                .take_while(|hash| merged.contains(hash))
+                .collect();
+            self.skip = self.merged.len();
+        }
+        results.drain(..self.skip.min(results.len()));
+    }
+}
+
+/// Identifies a result of a page by its resource ID, or by its value for a
+/// projection without one.
+fn result_hash(result: &Value) -> u64 {
+    let key = match result.get("_rid") {
+        Some(Value::String(rid)) => rid.clone(),
+        _ => result.to_string(),
+    };
+    murmur3_x64_128(key.as_bytes()) as u64
+}
+
+/// A page read from a partition key range, not merged yet.
+#[derive(Debug, Clone, Default)]
+struct Buffer {
+    results: VecDeque<Value>,
+    /// The continuation of the next page, `None` after the last page.
+    next: Option<String>,
+    /// Whether the page of the range state was read.
+    read: bool,
+}
+
+/// The execution of a query, from a continuation token.
+#[derive(Debug, Clone)]
+struct Execution {
+    info: QueryInfo,
+    query: Query,
+    state: QueryState,
+    buffers: Vec<Buffer>,
+    finished: bool,
+}
+
+impl Execution {
+    async fn start(
+        builder: &CrossPartitionQueryBuilder,
+        token: Option<&str>,
+    ) -> azure_core::Result<Self> {
+        let plan = builder.query_plan().await?;
+        let state = match token {
+            Some(token) => serde_json::from_str(token).context(
+                ErrorKind::DataConversion,
+                "invalid continuation token of a cross partition query",
+            )?,
+            None => {
+                let mut ranges = builder
+                    .client
+                    .get_partition_key_ranges()
+                    .context(builder.context.clone())
+                    .await?
+                    .partition_key_ranges;
+                ranges.retain(|range| plan.query_ranges.iter().any(|q| q.overlaps(range)));
+                ranges.sort_by(|a, b| a.min_exclusive.cmp(&b.min_exclusive));
+                QueryState {
+                    ranges: ranges
+                        .iter()
+                        .map(|range| RangeState::new(range, None))
+                        .collect(),
+                    ..QueryState::default()
+                }
+            }
+        };
+        let query = Query::with_params(
+            plan.query_info.query(builder.query.query()),
+            builder.query.params().to_vec(),
+        );
+        Ok(Self {
+            buffers: vec![Buffer::default(); state.ranges.len()],
+            info: plan.query_info,
+            query,
+            state,
+            finished: false,
+        })
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished || self.state.ranges.iter().all(|range| range.done)
+    }
+
+    async fn next_page(
+        &mut self,
+        builder: &CrossPartitionQueryBuilder,
+    ) -> azure_core::Result<(Vec<Value>, f64)> {
+        let mut charge = 0.0;
+        if self.info.is_aggregate() {
+            let mut aggregator = Aggregator::new(self.info.clone());
+            for index in 0..self.state.ranges.len() {
+                while let Some(result) = self.pop(builder, index, &mut charge).await? {
+                    aggregator.add(result)?;
+                }
+            }
+            let mut results = Vec::new();
+            for result in aggregator.results() {
+                self.collect(result, &mut results);
+            }
+            self.finished = true;
+            return Ok((results, charge));
+        }
+
+        let page_size = builder.page_size();
+        let mut results = Vec::new();
+        while results.len() < page_size && !self.is_finished() {
+            let result = if self.info.order_by.is_empty() {
+                self.next_in_order(builder, &mut charge).await?
+            } else {
+                self.next_ordered(builder, &mut charge).await?
+            };
+            match result {
+                Some(result) => self.collect(result, &mut results),
+                None => self.finished = true,
+            }
+        }
+        Ok((results, charge))
+    }
+
+    /// Apply `DISTINCT`, `OFFSET`, `LIMIT` and `TOP` to a merged result.
+    fn collect(&mut self, result: Value, results: &mut Vec<Value>) {
+        let state = &mut self.state;
+        match self.info.distinct_type {
+            DistinctType::None => {}
+            DistinctType::Ordered => {
+                if state.last.as_ref() == Some(&result) {
+                    return;
+                }
+                state.last = Some(result.clone());
+            }
+            DistinctType::Unordered => {
+                let hash = murmur3_x64_128(result.to_string().as_bytes()) as u64;
+                if !state.seen.insert(hash) {
+                    return;
+                }
+            }
+        }
+        if state.skipped < self.info.offset.unwrap_or_default() {
+            state.skipped += 1;
+            return;
+        }
+        let limit = match (self.info.top, self.info.limit) {
+            (Some(top), Some(limit)) => Some(top.min(limit)),
+            (top, limit) => top.or(limit),
+        };
+        if limit.map_or(false, |limit| state.returned >= limit) {
+            self.finished = true;
+            return;
+        }
+        results.push(result);
+        state.returned += 1;
+        if limit == Some(state.returned) {
+            self.finished = true;
+        }
+    }
+
+    /// The next result of the first partition key range which has some left.
+    async fn next_in_order(
+        &mut self,
+        builder: &CrossPartitionQueryBuilder,
+        charge: &mut f64,
+    ) -> azure_core::Result<Option<Value>> {
+        for index in 0..self.state.ranges.len() {
+            if let Some(result) = self.pop(builder, index, charge).await? {
+                return Ok(Some(result));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The next result of an `ORDER BY`, from the partition key range with the
+    /// lowest one.
+    async fn next_ordered(
+        &mut self,
+        builder: &CrossPartitionQueryBuilder,
+        charge: &mut f64,
+    ) -> azure_core::Result<Option<Value>> {
+        let mut lowest: Option<usize> = None;
+        for index in 0..self.state.ranges.len() {
+            if !self.fill(builder, index, charge).await? {
+                continue;
+            }
+            let items = |index: usize| {
+                self.buffers[index]
+                    .results
+                    .front()
+                    .and_then(|result| result.get("orderByItems"))
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+            };
+            // ties go to the partition key range with the lowest keys
+            lowest = match lowest {
+                Some(current)
+                    if compare_order_by(items(index), items(current), &self.info.order_by)
+                        != Ordering::Less =>
+                {
+                    Some(current)
+                }
+                _ => Some(index),
+            };
+        }
+        let Some(index) = lowest else {
+            return Ok(None);
+        };
+        let result = self.pop(builder, index, charge).await?;
+        Ok(result.map(|mut result| {
+            result
+                .get_mut("payload")
+                .map(Value::take)
+                .unwrap_or_default()
+        }))
+    }
+
+    /// Take the next result of a partition key range.
+    async fn pop(
+        &mut self,
+        builder: &CrossPartitionQueryBuilder,
+        index: usize,
+        charge: &mut f64,
+    ) -> azure_core::Result<Option<Value>> {
+        if !self.fill(builder, index, charge).await? {
+            return Ok(None);
+        }
+        let buffer = &mut self.buffers[index];
+        let range = &mut self.state.ranges[index];
+        let result = buffer.results.pop_front();
+        if let Some(result) = &result {
+            range.merge(result);
+        }
+        if buffer.results.is_empty() {
+            match buffer.next.take() {
+                Some(next) => {
+                    range.next_page(next);
+                    buffer.read = false;
+                }
+                None => range.done = true,
+            }
+        }
+        Ok(result)
+    }
+
+    /// Read pages of a partition key range until it has a result buffered,
+    /// returning whether it has one.
+    async fn fill(
+        &mut self,
+        builder: &CrossPartitionQueryBuilder,
+        index: usize,
+        charge: &mut f64,
+    ) -> azure_core::Result<bool> {
+        loop {
+            let range = &self.state.ranges[index];
+            if range.done {
+                return Ok(false);
+            }
+            if !self.buffers[index].results.is_empty() {
+                return Ok(true);
+            }
+            if self.buffers[index].read {
+                // an empty page in the middle of the results
+                let range = &mut self.state.ranges[index];
+                match self.buffers[index].next.take() {
+                    Some(next) => {
+                        range.next_page(next);
+                        self.buffers[index].read = false;
+                    }
+                    None => {
+                        range.done = true;
+                        return Ok(false);
+                    }
+                }
+                continue;
+            }
+
+            let mut request = builder.request();
+            request.insert_headers(&PartitionRangeId::new(range.id.clone()));
+            request.insert_headers(&builder.max_item_count.unwrap_or_default());
+            if let Some(token) = &range.token {
+                request.insert_headers(&Continuation::new(token.clone()));
+            }
+            if range.partial {
+                request.insert_header(headers::HEADER_START_EPK, range.min.clone());
+                request.insert_header(headers::HEADER_END_EPK, range.max.clone());
+            }
+            request.set_json(&self.query)?;
+            let response = match builder.send(&mut request).await {
+                Ok(response) => response,
+                Err(error) => {
+                    self.refresh(builder, index, error).await?;
+                    continue;
+                }
+            };
+
+            *charge +=
+                crate::headers::from_headers::request_charge_from_headers(response.headers())
+                    .unwrap_or_default();
+            let next = continuation_token_from_headers_optional(response.headers())?
+                .map(|next| next.value().as_str().to_owned());
+            #[derive(Deserialize)]
+            struct Page {
+                #[serde(rename = "Documents")]
+                documents: VecDeque<Value>,
+            }
+            let mut page: Page = response.json().await?;
+            self.state.ranges[index].skip_merged(&mut page.documents);
+            self.buffers[index] = Buffer {
+                results: page.documents,
+                next,
+                read: true,
+            };
+        }
+    }
+
+    /// Replace a partition key range which was split or merged by the ranges
+    /// now covering it, which go on from its continuation, or fail with
+    /// `error` if it is still there.
+    ///
+    /// The new ranges leave out the results of the page being read which were
+    /// already merged, and the other ranges keep their buffered page.
+    async fn refresh(
+        &mut self,
+        builder: &CrossPartitionQueryBuilder,
+        index: usize,
+        error: Error,
+    ) -> azure_core::Result<()> {
+        let is_gone = error
+            .as_http_error()
+            .map_or(false, |error| error.status() == StatusCode::Gone);
+        if !is_gone {
+            return Err(error);
+        }
+        let range = &self.state.ranges[index];
+        let ranges = builder
+            .client
+            .get_partition_key_ranges()
+            .context(builder.context.clone())
+            .await?
+            .partition_key_ranges;
+        let resolved = range.resolve(&ranges);
+        if resolved.is_empty() || resolved.iter().any(|resolved| resolved.id == range.id) {
+            return Err(error);
+        }
+        let count = resolved.len();
+        self.state.ranges.splice(index..=index, resolved);
+        self.buffers
+            .splice(index..=index, vec![Buffer::default(); count]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn partition_key_range(id: &str, min: &str, max: &str) -> PartitionKeyRange {
+        serde_json::from_value(json!({
+            "_rid": "",
+            "id": id,
+            "_etag": "",
+            "minInclusive": min,
+            "maxExclusive": max,
+            "ridPrefix": 0,
+            "_self": "",
+            "throughputFraction": 0.5,
+            "status": "online",
+            "parents": ["0"],
+            "_ts": 0,
+        }))
+        .unwrap()
+    }
+
+    fn page(rids: &[&str]) -> VecDeque<Value> {
+        rids.iter().map(|rid| json!({ "_rid": rid })).collect()
+    }
+
+    #[test]
+    fn split_children_leave_out_merged_results() {
+        // the parent merged a and b from its page [a, b, c, d] before it was split
+        let mut parent = RangeState::new(&partition_key_range("0", "", "FF"), None);
+        parent.next_page("page 2".to_owned());
+        let mut results = page(&["a", "b", "c", "d"]);
+        parent.skip_merged(&mut results);
+        for result in results.drain(..2) {
+            parent.merge(&result);
+        }
+
+        // its children read their part of the same page again
+        let children = parent.resolve(&[
+            partition_key_range("1", "", "7F"),
+            partition_key_range("2", "7F", "FF"),
+        ]);
+        let [mut left, mut right] = <[RangeState; 2]>::try_from(children).unwrap();
+        assert!(!left.partial && !right.partial);
+        assert_eq!(left.token.as_deref(), Some("page 2"));
+        assert_eq!(right.token.as_deref(), Some("page 2"));
+
+        let mut left_results = page(&["a", "c"]);
+        left.skip_merged(&mut left_results);
+        assert_eq!(left_results, page(&["c"]));
+        let mut right_results = page(&["b", "d"]);
+        right.skip_merged(&mut right_results);
+        assert_eq!(right_results, page(&["d"]));
+
+        // and resume from their own continuation afterwards
+        let mut left: RangeState =
+            serde_json::from_str(&serde_json::to_string(&left).unwrap()).unwrap();
+        assert_eq!((left.skip, left.merged.len()), (1, 1));
+        let mut left_results = page(&["a", "c"]);
+        left.skip_merged(&mut left_results);
+        assert_eq!(left_results, page(&["c"]));
+
+        // a range which was not split skips the results it merged
+        let mut results = page(&["a", "b", "c", "d"]);
+        parent.skip_merged(&mut results);
+        assert_eq!(results, page(&["c", "d"]));
+    }
+
+    #[test]
+    fn merged_ranges_only_read_their_own_part() {
+        // two neighbouring ranges are merged into a single one
+        let merged = [partition_key_range("3", "", "FF")];
+        let mut left = RangeState::new(&partition_key_range("1", "", "7F"), None);
+        left.next_page("left page".to_owned());
+        left.merge(&json!({ "_rid": "a" }));
+        let right = RangeState::new(&partition_key_range("2", "7F", "FF"), None);
+
+        let [left] = <[RangeState; 1]>::try_from(left.resolve(&merged)).unwrap();
+        assert_eq!(
+            (left.id.as_str(), left.min.as_str(), left.max.as_str()),
+            ("3", "", "7F")
+        );
+        assert!(left.partial);
+        assert_eq!(left.token.as_deref(), Some("left page"));
+        assert_eq!((left.skip, left.merged.len()), (0, 1));
+
+        let [right] = <[RangeState; 1]>::try_from(right.resolve(&merged)).unwrap();
+        assert_eq!(
+            (right.id.as_str(), right.min.as_str(), right.max.as_str()),
+            ("3", "7F", "FF")
+        );
+        assert!(right.partial);
+        assert_eq!(right.token, None);
+
+        // a range which is still there resolves to itself
+        let [same] = <[RangeState; 1]>::try_from(left.resolve(&merged)).unwrap();
+        assert_eq!(
+            (same.id.as_str(), same.min.as_str(), same.max.as_str()),
+            ("3", "", "7F")
+        );
+    }
+}
@@ -0,0 +1,472 @@
+//! The parts of a query which are run by the client, from the query plan
+//! returned by the gateway.
+
+use azure_core::error::{Error, ErrorKind};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{cmp::Ordering, collections::BTreeMap};
+
+/// How a query must be run across partition key ranges.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct QueryInfo {
+    pub distinct_type: DistinctType,
+    pub top: Option<usize>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    #[serde(deserialize_with = "nullable")]
+    pub order_by: Vec<SortOrder>,
+    #[serde(deserialize_with = "nullable")]
+    pub group_by_expressions: Vec<String>,
+    #[serde(deserialize_with = "nullable")]
+    pub aggregates: Vec<AggregateKind>,
+    #[serde(deserialize_with = "nullable")]
+    pub group_by_alias_to_aggregate_type: BTreeMap<String, Option<AggregateKind>>,
+    pub rewritten_query: Option<String>,
+    pub has_select_value: bool,
+}
+
+fn nullable<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + serde::Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+impl QueryInfo {
+    /// Whether every result must be read before the first one is known.
+    pub fn is_aggregate(&self) -> bool {
+        !self.aggregates.is_empty()
+            || !self.group_by_expressions.is_empty()
+            || !self.group_by_alias_to_aggregate_type.is_empty()
+    }
+
+    /// The query sent to every partition key range.
+    pub fn query(&self, original: &str) -> String {
+        match &self.rewritten_query {
+            Some(query) if !query.is_empty() => {
+                query.replace("{documentdb-formattableorderbyquery-filter}", "true")
+            }
+            _ => original.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub(crate) enum DistinctType {
+    #[default]
+    None,
+    Ordered,
+    Unordered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum AggregateKind {
+    Average,
+    Count,
+    Max,
+    Min,
+    Sum,
+}
+
+/// The order of values of different types in an `ORDER BY`, with `None`
+/// standing for undefined.
+pub(crate) fn compare(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    fn rank(value: Option<&Value>) -> u8 {
+        match value {
+            None => 0,
+            Some(Value::Null) => 1,
+            Some(Value::Bool(_)) => 2,
+            Some(Value::Number(_)) => 3,
+            Some(Value::String(_)) => 4,
+            Some(Value::Array(_)) => 5,
+            Some(Value::Object(_)) => 6,
+        }
+    }
+
+    match (a, b) {
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (Some(Value::Number(a)), Some(Value::Number(b))) => {
+            let (a, b) = (
+                a.as_f64().unwrap_or(f64::NAN),
+                b.as_f64().unwrap_or(f64::NAN),
+            );
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        }
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Compare the `orderByItems` of two results of an `ORDER BY` query.
+pub(crate) fn compare_order_by(a: &[Value], b: &[Value], order: &[SortOrder]) -> Ordering {
+    for (index, order) in order.iter().enumerate() {
+        let item = |items: &[Value]| items.get(index).and_then(|item| item.get("item")).cloned();
+        let ordering = compare(item(a).as_ref(), item(b).as_ref());
+        let ordering = match order {
+            SortOrder::Ascending => ordering,
+            SortOrder::Descending => ordering.reverse(),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// The running value of an aggregate, from the partial values returned by
+/// each partition key range.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Aggregate {
+    Count(f64),
+    /// `None` once a partial sum is undefined.
+    Sum(Option<f64>),
+    Average {
+        sum: f64,
+        count: f64,
+    },
+    Min(Option<Value>),
+    Max(Option<Value>),
+}
+
+impl Aggregate {
+    pub fn new(kind: AggregateKind) -> Self {
+        match kind {
+            AggregateKind::Count => Self::Count(0.0),
+            AggregateKind::Sum => Self::Sum(Some(0.0)),
+            AggregateKind::Average => Self::Average {
+                sum: 0.0,
+                count: 0.0,
+            },
+            AggregateKind::Min => Self::Min(None),
+            AggregateKind::Max => Self::Max(None),
+        }
+    }
+
+    /// Merge a partial value, `None` when it is undefined.
+    pub fn add(&mut self, partial: Option<&Value>) {
+        let as_number = |value: Option<&Value>| value.and_then(Value::as_f64);
+        let is_min = matches!(self, Self::Min(_));
+        match self {
+            Self::Count(count) => *count += as_number(partial).unwrap_or_default(),
+            Self::Sum(sum) => {
+                *sum = sum
+                    .zip(as_number(partial))
+                    .map(|(sum, partial)| sum + partial);
+            }
+            Self::Average { sum, count } => {
+                let partial_sum = as_number(partial.and_then(|p| p.get("sum")));
+                let partial_count = as_number(partial.and_then(|p| p.get("count")));
+                if let (Some(partial_sum), Some(partial_count)) = (partial_sum, partial_count) {
+                    *sum += partial_sum;
+                    *count += partial_count;
+                }
+            }
+            Self::Min(current) | Self::Max(current) => {
+                // partial values are either `{"min": value, "count": count}`,
+                // or the value itself
+                let value = match partial {
+                    Some(Value::Object(object)) if object.contains_key("count") => {
+                        if as_number(object.get("count")) == Some(0.0) {
+                            return;
+                        }
+                        object.get(if is_min { "min" } else { "max" })
+                    }
+                    partial => partial,
+                };
+                let Some(value) = value else {
+                    return;
+                };
+                let replace = current.as_ref().map_or(true, |current| {
+                    let ordering = compare(Some(value), Some(current));
+                    if is_min {
+                        ordering == Ordering::Less
+                    } else {
+                        ordering == Ordering::Greater
+                    }
+                });
+                if replace {
+                    *current = Some(value.clone());
+                }
+            }
+        }
+    }
+
+    /// The value of the aggregate, `None` when it is undefined.
+    pub fn result(&self) -> Option<Value> {
+        match self {
+            Self::Count(count) => Some(number(*count)),
+            Self::Sum(sum) => sum.map(number),
+            Self::Average { sum, count } => (*count > 0.0).then(|| number(sum / count)),
+            Self::Min(value) | Self::Max(value) => value.clone(),
+        }
+    }
+}
+
+/// A number as an integer when it is one, like the service returns it.
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < 2f64.powi(53) {
+        Value::from(value as i64)
+    } else {
+        Value::from(value)
+    }
+}
+
+/// Merges the results of an aggregate query, with or without `GROUP BY`.
+#[derive(Debug, Clone)]
+pub(crate) struct Aggregator {
+    info: QueryInfo,
+    /// The aggregates of a `SELECT VALUE` query without `GROUP BY`.
+    values: Vec<Aggregate>,
+    /// The groups, by their serialized `groupByItems`, in order of appearance.
+    groups: Vec<(String, Group)>,
+}
+
+#[derive(Debug, Clone)]
+struct Group {
+    aggregates: BTreeMap<String, Aggregate>,
+    values: Map<String, Value>,
+    /// The value of a `SELECT VALUE` query without aggregate.
+    value: Option<Value>,
+}
+
+impl Aggregator {
+    pub fn new(info: QueryInfo) -> Self {
+        Self {
+            values: info
+                .aggregates
+                .iter()
+                .copied()
+                .map(Aggregate::new)
+                .collect(),
+            groups: Vec::new(),
+            info,
+        }
+    }
+
+    fn is_grouped(&self) -> bool {
+        !self.info.group_by_expressions.is_empty()
+            || !self.info.group_by_alias_to_aggregate_type.is_empty()
+    }
+
+    /// Merge a result returned by a partition key range.
+    pub fn add(&mut self, result: Value) -> azure_core::Result<()> {
+        if !self.is_grouped() {
+            // `SELECT VALUE COUNT(1)` returns `[{"item": partial}]`
+            let items = result.as_array().ok_or_else(|| unexpected(&result))?;
+            for (aggregate, item) in self.values.iter_mut().zip(items) {
+                aggregate.add(item.get("item"));
+            }
+            return Ok(());
+        }
+
+        let key = result
+            .get("groupByItems")
+            .map(Value::to_string)
+            .unwrap_or_default();
+        let payload = result.get("payload").cloned().unwrap_or(Value::Null);
+        let index = match self.groups.iter().position(|(k, _)| *k == key) {
+            Some(index) => index,
+            None => {
+                let aggregates = self
+                    .info
+                    .group_by_alias_to_aggregate_type
+                    .iter()
+                    .filter_map(|(alias, kind)| Some((alias.clone(), Aggregate::new((*kind)?))))
+                    .collect();
+                self.groups.push((
+                    key,
+                    Group {
+                        aggregates,
+                        values: Map::new(),
+                        value: None,
+                    },
+                ));
+                self.groups.len() - 1
+            }
+        };
+        let group = &mut self.groups[index].1;
+
+        if self.info.has_select_value {
+            match group.aggregates.values_mut().next() {
+                Some(aggregate) => aggregate.add(payload.get("item")),
+                None => {
+                    group.value.get_or_insert(payload);
+                }
+            }
+            return Ok(());
+        }
+        let Value::Object(payload) = payload else {
+            return Err(unexpected(&payload));
+        };
+        for (alias, value) in payload {
+            match group.aggregates.get_mut(&alias) {
+                Some(aggregate) => aggregate.add(value.get("item")),
+                None => {
+                    group.values.entry(alias).or_insert(value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The results of the query, once every partition key range was read.
+    pub fn results(self) -> Vec<Value> {
+        if !self.is_grouped() {
+            return self.values.iter().filter_map(Aggregate::result).collect();
+        }
+        let has_select_value = self.info.has_select_value;
+        self.groups
+            .into_iter()
+            .filter_map(|(_, group)| {
+                if has_select_value {
+                    return match group.aggregates.values().next() {
+                        Some(aggregate) => aggregate.result(),
+                        None => group.value,
+                    };
+                }
+                let mut values = group.values;
+                for (alias, aggregate) in group.aggregates {
+                    if let Some(result) = aggregate.result() {
+                        values.insert(alias, result);
+                    }
+                }
+                Some(Value::Object(values))
+            })
+            .collect()
+    }
+}
+
+fn unexpected(value: &Value) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!("unexpected result of an aggregate query: {value}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn info(value: Value) -> QueryInfo {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn query_info_from_plan() {
+        let info = info(json!({
+            "distinctType": "None",
+            "top": null,
+            "offset": 1,
+            "limit": 2,
+            "orderBy": ["Descending"],
+            "orderByExpressions": ["c.total"],
+            "groupByExpressions": [],
+            "groupByAliases": [],
+            "aggregates": [],
+            "groupByAliasToAggregateType": {},
+            "rewrittenQuery": "SELECT c._rid, [{\"item\": c.total}] AS orderByItems, c AS payload FROM c WHERE ({documentdb-formattableorderbyquery-filter}) ORDER BY c.total DESC",
+            "hasSelectValue": false,
+            "dCountInfo": null
+        }));
+        assert_eq!(info.order_by, [SortOrder::Descending]);
+        assert_eq!((info.offset, info.limit), (Some(1), Some(2)));
+        assert!(!info.is_aggregate());
+        assert!(info.query("").contains("WHERE (true)"));
+    }
+
+    #[test]
+    fn values_of_different_types_are_ordered() {
+        let values = [
+            None,
+            Some(json!(null)),
+            Some(json!(false)),
+            Some(json!(true)),
+            Some(json!(-1.5)),
+            Some(json!(2)),
+            Some(json!("a")),
+            Some(json!("b")),
+        ];
+        for pair in values.windows(2) {
+            assert_eq!(compare(pair[0].as_ref(), pair[1].as_ref()), Ordering::Less);
+        }
+        assert_eq!(
+            compare_order_by(
+                &[json!({"item": 1}), json!({"item": "x"})],
+                &[json!({"item": 1}), json!({"item": "y"})],
+                &[SortOrder::Ascending, SortOrder::Descending]
+            ),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn aggregates_merge_partial_values() {
+        let mut count = Aggregate::new(AggregateKind::Count);
+        count.add(Some(&json!(2)));
+        count.add(Some(&json!(3)));
+        assert_eq!(count.result(), Some(json!(5)));
+
+        let mut average = Aggregate::new(AggregateKind::Average);
+        average.add(Some(&json!({"sum": 6, "count": 2})));
+        average.add(Some(&json!({"sum": 3, "count": 2})));
+        assert_eq!(average.result(), Some(json!(2.25)));
+        assert_eq!(Aggregate::new(AggregateKind::Average).result(), None);
+
+        let mut min = Aggregate::new(AggregateKind::Min);
+        min.add(Some(&json!({"min": 4, "count": 1})));
+        min.add(Some(&json!({"count": 0})));
+        min.add(Some(&json!(3)));
+        assert_eq!(min.result(), Some(json!(3)));
+
+        let mut max = Aggregate::new(AggregateKind::Max);
+        max.add(Some(&json!({"max": "a", "count": 1})));
+        max.add(Some(&json!({"max": "b", "count": 2})));
+        assert_eq!(max.result(), Some(json!("b")));
+
+        let mut sum = Aggregate::new(AggregateKind::Sum);
+        sum.add(Some(&json!(1.5)));
+        sum.add(None);
+        assert_eq!(sum.result(), None);
+    }
+
+    #[test]
+    fn group_by_merges_groups() {
+        let mut aggregator = Aggregator::new(info(json!({
+            "groupByExpressions": ["c.customer"],
+            "groupByAliases": ["customer", "orders"],
+            "groupByAliasToAggregateType": {"customer": null, "orders": "Count"},
+            "hasSelectValue": false
+        })));
+        for (customer, orders) in [("a", 1), ("b", 2), ("a", 3)] {
+            aggregator
+                .add(json!({
+                    "groupByItems": [{"item": customer}],
+                    "payload": {"customer": customer, "orders": {"item": orders}}
+                }))
+                .unwrap();
+        }
+        assert_eq!(
+            aggregator.results(),
+            [
+                json!({"customer": "a", "orders": 4}),
+                json!({"customer": "b", "orders": 2})
+            ]
+        );
+
+        let mut aggregator = Aggregator::new(info(json!({
+            "aggregates": ["Sum"],
+            "hasSelectValue": true
+        })));
+        aggregator.add(json!([{"item": 2}])).unwrap();
+        aggregator.add(json!([{"item": 5}])).unwrap();
+        assert_eq!(aggregator.results(), [json!(7)]);
+    }
+}
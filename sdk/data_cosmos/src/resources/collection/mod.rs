@@ -136,6 +136,10 @@ pub struct PartitionKey {
     pub paths: Vec<String>,
     /// The algorithm used for partitioning
     pub kind: KeyKind,
+    /// The version of the hash of the partition key, 2 for collections
+    /// supporting large partition keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u8>,
 }
 
 impl std::default::Default for PartitionKey {
@@ -143,6 +147,7 @@ impl std::default::Default for PartitionKey {
         Self {
             paths: vec![],
             kind: KeyKind::Hash,
+            version: None,
         }
     }
 }
@@ -155,6 +160,7 @@ where
         Self {
             paths: vec![t.as_ref().to_owned()],
             kind: KeyKind::Hash,
+            version: None,
         }
     }
 }
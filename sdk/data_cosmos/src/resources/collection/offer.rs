@@ -3,11 +3,13 @@ use azure_core::Header;
 
 /// The collection performance level.
 ///
-/// It can either be custom or fixed. You can find more details [here](https://docs.microsoft.com/rest/api/cosmos-db/create-a-collection).
+/// It can either be custom, autoscale or fixed. You can find more details [here](https://docs.microsoft.com/rest/api/cosmos-db/create-a-collection).
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Offer {
     /// A Custom level of throughput
     Throughput(u64),
+    /// Autoscale throughput, scaling between a tenth of this maximum and the maximum
+    Autoscale(u64),
     /// Legacy throughput level 1
     S1,
     /// Legacy throughput level 2
@@ -20,6 +22,7 @@ impl Header for Offer {
     fn name(&self) -> azure_core::headers::HeaderName {
         match self {
             Offer::Throughput(_) => headers::HEADER_OFFER_THROUGHPUT,
+            Offer::Autoscale(_) => headers::HEADER_OFFER_AUTOPILOT_SETTINGS,
             _ => headers::HEADER_OFFER_TYPE,
         }
     }
@@ -27,6 +30,7 @@ impl Header for Offer {
     fn value(&self) -> azure_core::headers::HeaderValue {
         match self {
             Offer::Throughput(throughput) => throughput.to_string(),
+            Offer::Autoscale(max_throughput) => format!(r#"{{"maxThroughput":{max_throughput}}}"#),
             Offer::S1 => "S1".to_owned(),
             Offer::S2 => "S2".to_owned(),
             Offer::S3 => "S3".to_owned(),
@@ -34,3 +38,18 @@ impl Header for Offer {
         .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn autoscale_offers_set_the_autopilot_settings() {
+        let offer = Offer::Autoscale(4000);
+        assert_eq!(
+            offer.name().as_str(),
+            "x-ms-cosmos-offer-autopilot-settings"
+        );
+        assert_eq!(offer.value().as_str(), r#"{"maxThroughput":4000}"#);
+    }
+}
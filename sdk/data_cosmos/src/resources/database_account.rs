@@ -0,0 +1,31 @@
+//! Utilities for interacting with the [`DatabaseAccount`].
+
+/// The account a client connects to, and the regions it is replicated to.
+///
+/// You can learn more about global distribution [here](https://learn.microsoft.com/azure/cosmos-db/distribute-data-globally).
+#[derive(Serialize, Clone, PartialEq, Eq, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseAccount {
+    /// The account name
+    pub id: String,
+    /// The regions which accept writes. Unless multiple write locations are
+    /// enabled, only the first one does.
+    #[serde(default)]
+    pub writable_locations: Vec<AccountRegion>,
+    /// The regions which accept reads
+    #[serde(default)]
+    pub readable_locations: Vec<AccountRegion>,
+    /// Whether every region accepts writes
+    #[serde(default)]
+    pub enable_multiple_write_locations: bool,
+}
+
+/// A region of a [`DatabaseAccount`].
+#[derive(Serialize, Clone, PartialEq, Eq, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRegion {
+    /// The region name, like `West US`
+    pub name: String,
+    /// The endpoint of the account in this region
+    pub database_account_endpoint: String,
+}
@@ -11,6 +11,8 @@ pub mod user;
 
 mod attachment;
 mod database;
+mod database_account;
+mod throughput;
 mod user_defined_function;
 
 #[doc(inline)]
@@ -20,12 +22,16 @@ pub use collection::Collection;
 #[doc(inline)]
 pub use database::Database;
 #[doc(inline)]
+pub use database_account::{AccountRegion, DatabaseAccount};
+#[doc(inline)]
 pub use document::Document;
 #[doc(inline)]
 pub use permission::Permission;
 #[doc(inline)]
 pub use stored_procedure::StoredProcedure;
 #[doc(inline)]
+pub use throughput::{AutoscaleSettings, OfferContent, Throughput, ThroughputProperties};
+#[doc(inline)]
 pub use trigger::Trigger;
 #[doc(inline)]
 pub use user::User;
@@ -52,6 +58,7 @@ pub trait Resource {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ResourceType {
+    DatabaseAccount,
     Databases,
     Collections,
     Documents,
@@ -62,4 +69,5 @@ pub(crate) enum ResourceType {
     PartitionKeyRanges,
     UserDefinedFunctions,
     Triggers,
+    Offers,
 }
@@ -0,0 +1,131 @@
+//! Utilities for interacting with the throughput of databases and collections.
+
+use serde_json::{Map, Value};
+
+/// The throughput of a database or a collection, in request units per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Throughput {
+    /// A fixed throughput
+    Manual(u64),
+    /// A throughput scaling between a tenth of this maximum and the maximum
+    Autoscale(u64),
+}
+
+/// The throughput provisioned for a database or a collection, known as an offer.
+///
+/// You can learn more about offers [here](https://learn.microsoft.com/rest/api/cosmos-db/offers).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputProperties {
+    /// The offer id
+    pub id: String,
+    /// The resource id
+    #[serde(rename = "_rid")]
+    pub rid: String,
+    /// The offer's uri
+    #[serde(rename = "_self")]
+    pub _self: String,
+    /// The offer's etag used for concurrency control
+    #[serde(rename = "_etag")]
+    pub etag: String,
+    /// The last updated timestamp
+    #[serde(rename = "_ts")]
+    pub ts: u64,
+    /// The self link of the database or collection
+    pub resource: String,
+    /// The resource id of the database or collection
+    pub offer_resource_id: String,
+    /// The legacy performance level, `Invalid` for custom throughput
+    pub offer_type: String,
+    /// The offer version, `V2` for custom throughput
+    pub offer_version: String,
+    /// The throughput settings
+    pub content: OfferContent,
+}
+
+impl ThroughputProperties {
+    /// The throughput provisioned.
+    pub fn throughput(&self) -> Throughput {
+        match &self.content.autoscale {
+            Some(autoscale) => Throughput::Autoscale(autoscale.max_throughput),
+            None => Throughput::Manual(self.content.offer_throughput.unwrap_or_default()),
+        }
+    }
+
+    /// Provision another throughput, of the same kind as the current one.
+    pub(crate) fn set_throughput(&mut self, throughput: Throughput) {
+        match throughput {
+            Throughput::Manual(throughput) => self.content.offer_throughput = Some(throughput),
+            Throughput::Autoscale(max_throughput) => {
+                self.content.offer_throughput = None;
+                self.content
+                    .autoscale
+                    .get_or_insert_with(AutoscaleSettings::default)
+                    .max_throughput = max_throughput;
+            }
+        }
+    }
+}
+
+/// The throughput settings of an offer.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferContent {
+    /// The throughput, for manual throughput
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offer_throughput: Option<u64>,
+    /// The autoscale settings, for autoscale throughput
+    #[serde(
+        rename = "offerAutopilotSettings",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub autoscale: Option<AutoscaleSettings>,
+    /// The other settings, sent back as they are when the offer is replaced
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+/// The autoscale settings of an offer.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoscaleSettings {
+    /// The maximum throughput
+    pub max_throughput: u64,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offers_round_trip() {
+        let offer = serde_json::json!({
+            "resource": "dbs/HVpXAA==/colls/HVpXAPXQ1TE=/",
+            "offerType": "Invalid",
+            "offerResourceId": "HVpXAPXQ1TE=",
+            "offerVersion": "V2",
+            "content": {
+                "offerThroughput": 400,
+                "offerIsRUPerMinuteThroughputEnabled": false,
+                "offerMinimumThroughputParameters": {"maxThroughputEverProvisioned": 400}
+            },
+            "id": "kNbT",
+            "_rid": "kNbT",
+            "_self": "offers/kNbT/",
+            "_etag": "\"0000\"",
+            "_ts": 1792400000
+        });
+        let mut properties: ThroughputProperties = serde_json::from_value(offer.clone()).unwrap();
+        assert_eq!(properties.throughput(), Throughput::Manual(400));
+        assert_eq!(serde_json::to_value(&properties).unwrap(), offer);
+
+        properties.content.autoscale = Some(AutoscaleSettings::default());
+        properties.set_throughput(Throughput::Autoscale(4000));
+        assert_eq!(properties.throughput(), Throughput::Autoscale(4000));
+        let content = serde_json::to_value(&properties.content).unwrap();
+        assert_eq!(content["offerAutopilotSettings"]["maxThroughput"], 4000);
+        assert!(content.get("offerThroughput").is_none());
+    }
+}
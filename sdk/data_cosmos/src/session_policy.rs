@@ -0,0 +1,295 @@
+use crate::headers::{
+    HEADER_CONSISTENCY_LEVEL, HEADER_DOCUMENTDB_PARTITIONRANGEID, HEADER_SESSION_TOKEN,
+};
+use azure_core::{headers::Headers, Context, Method, Policy, PolicyResult, Request};
+use percent_encoding::percent_decode_str;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// The session tokens of the collections a client used, to resume a session
+/// with another client.
+///
+/// It serializes to JSON, so it can be kept between requests of a web
+/// application, for example in a cookie.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SessionState {
+    /// The session token of every partition key range, by collection link.
+    collections: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl SessionState {
+    /// Whether no session token is known.
+    pub fn is_empty(&self) -> bool {
+        self.collections.is_empty()
+    }
+}
+
+/// Keeps the latest session token of every partition key range of the
+/// collections a client used.
+#[derive(Debug, Default)]
+pub(crate) struct SessionContainer {
+    state: RwLock<SessionState>,
+}
+
+impl SessionContainer {
+    fn read<T>(&self, f: impl FnOnce(&SessionState) -> T) -> T {
+        f(&self.state.read().unwrap_or_else(|error| error.into_inner()))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut SessionState) -> T) -> T {
+        f(&mut self
+            .state
+            .write()
+            .unwrap_or_else(|error| error.into_inner()))
+    }
+
+    pub(crate) fn export(&self) -> SessionState {
+        self.read(Clone::clone)
+    }
+
+    pub(crate) fn import(&self, state: &SessionState) {
+        self.write(|current| {
+            for (collection, tokens) in &state.collections {
+                let current = current.collections.entry(collection.clone()).or_default();
+                for (range, token) in tokens {
+                    merge(current, range, token);
+                }
+            }
+        });
+    }
+
+    /// The session token of a collection, for all its partition key ranges or
+    /// only one of them.
+    pub(crate) fn session_token(&self, collection: &str, range: Option<&str>) -> Option<String> {
+        self.read(|state| {
+            let tokens = state.collections.get(collection)?;
+            let token = match range {
+                Some(range) => format!("{range}:{}", tokens.get(range)?),
+                None => tokens
+                    .iter()
+                    .map(|(range, token)| format!("{range}:{token}"))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            };
+            (!token.is_empty()).then_some(token)
+        })
+    }
+
+    /// Keep the session tokens a collection answered with, like `0:-1#12` or
+    /// `0:-1#12,1:-1#5`.
+    fn update(&self, collection: &str, session_token: &str) {
+        self.write(|state| {
+            let tokens = state.collections.entry(collection.to_owned()).or_default();
+            for token in session_token.split(',') {
+                if let Some((range, token)) = token.trim().split_once(':') {
+                    merge(tokens, range, token);
+                }
+            }
+        });
+    }
+
+    fn remove(&self, collection: &str) {
+        self.write(|state| state.collections.remove(collection));
+    }
+}
+
+/// Keep the most recent of two session tokens of a partition key range.
+fn merge(tokens: &mut BTreeMap<String, String>, range: &str, token: &str) {
+    match tokens.get(range) {
+        Some(current) if progress(current) >= progress(token) => {}
+        _ => {
+            tokens.insert(range.to_owned(), token.to_owned());
+        }
+    }
+}
+
+/// The version and the global LSN of a session token like `-1#12` or
+/// `2#12#1=10#2=8`, which grow as the partition key range is written to.
+fn progress(token: &str) -> Option<(i64, i64)> {
+    let mut parts = token.split('#');
+    let version = parts.next()?.parse().ok()?;
+    let lsn = parts.next()?.parse().ok()?;
+    Some((version, lsn))
+}
+
+/// The link of the collection of a request, like `dbs/db/colls/coll`, and the
+/// resources of the collection it is on, like `docs`, if any.
+///
+/// The names in the link are decoded, like the ones the clients are created with.
+fn collection_link(request: &Request) -> Option<(String, Option<&str>)> {
+    let mut segments = request.url().path_segments()?;
+    match (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) {
+        (Some("dbs"), Some(database), Some("colls"), Some(collection)) => Some((
+            format!(
+                "dbs/{}/colls/{}",
+                percent_decode_str(database).decode_utf8_lossy(),
+                percent_decode_str(collection).decode_utf8_lossy()
+            ),
+            segments.next(),
+        )),
+        _ => None,
+    }
+}
+
+/// The `SessionPolicy` gives session consistency to the requests of a client
+/// without the caller passing session tokens around.
+///
+/// It keeps the session tokens returned for the documents of every collection,
+/// and sends them with the reads of documents which do not set a session token
+/// or another consistency level themselves.
+#[derive(Debug, Clone)]
+pub struct SessionPolicy {
+    container: Arc<SessionContainer>,
+}
+
+impl SessionPolicy {
+    pub(crate) fn new(container: Arc<SessionContainer>) -> Self {
+        Self { container }
+    }
+
+    fn update(&self, collection: &str, headers: &Headers) {
+        if let Some(token) = headers.get_optional_str(&HEADER_SESSION_TOKEN) {
+            self.container.update(collection, token);
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for SessionPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let (collection, resources) = match collection_link(request) {
+            Some((collection, resources)) => (collection, resources.map(str::to_owned)),
+            None => return next[0].send(ctx, request, &next[1..]).await,
+        };
+        match resources.as_deref() {
+            Some("docs") => {}
+            // a collection which is deleted starts over if it is created again
+            None if *request.method() == Method::Delete => {
+                let result = next[0].send(ctx, request, &next[1..]).await;
+                if result.is_ok() {
+                    self.container.remove(&collection);
+                }
+                return result;
+            }
+            _ => return next[0].send(ctx, request, &next[1..]).await,
+        }
+
+        let headers = request.headers();
+        let is_session = headers
+            .get_optional_str(&HEADER_CONSISTENCY_LEVEL)
+            .map_or(true, |level| level == "Session");
+        if is_session
+            && !crate::location_policy::is_write(request)
+            && headers.get_optional_str(&HEADER_SESSION_TOKEN).is_none()
+        {
+            let range = headers.get_optional_string(&HEADER_DOCUMENTDB_PARTITIONRANGEID);
+            if let Some(token) = self.container.session_token(&collection, range.as_deref()) {
+                request.insert_header(HEADER_SESSION_TOKEN, token);
+            }
+        }
+
+        let result = next[0].send(ctx, request, &next[1..]).await;
+        match &result {
+            Ok(response) => self.update(&collection, response.headers()),
+            Err(error) => {
+                if let Some(error) = error.as_http_error() {
+                    self.update(&collection, error.headers());
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn the_latest_token_of_every_range_is_kept() {
+        let container = SessionContainer::default();
+        let collection = "dbs/db/colls/coll";
+        container.update(collection, "0:-1#12");
+        container.update(collection, "0:-1#9,1:-1#5");
+        container.update("dbs/db/colls/other", "0:-1#3");
+        assert_eq!(
+            container.session_token(collection, None).as_deref(),
+            Some("0:-1#12,1:-1#5")
+        );
+        assert_eq!(
+            container.session_token(collection, Some("1")).as_deref(),
+            Some("1:-1#5")
+        );
+        assert_eq!(container.session_token(collection, Some("2")), None);
+
+        // a newer version of the range wins, whatever its LSN
+        container.update(collection, "1:2#1#1=1");
+        assert_eq!(
+            container.session_token(collection, Some("1")).as_deref(),
+            Some("1:2#1#1=1")
+        );
+    }
+
+    #[test]
+    fn session_state_round_trips() {
+        let container = SessionContainer::default();
+        container.update("dbs/db/colls/coll", "0:-1#12");
+        let state = container.export();
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, r#"{"dbs/db/colls/coll":{"0":"-1#12"}}"#);
+
+        let other = SessionContainer::default();
+        other.update("dbs/db/colls/coll", "0:-1#20");
+        other.import(&serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            other.session_token("dbs/db/colls/coll", None).as_deref(),
+            Some("0:-1#20")
+        );
+    }
+
+    #[test]
+    fn requests_are_matched_to_their_collection() {
+        let link = |url: &str| {
+            collection_link(&Request::new(url.parse().unwrap(), Method::Get))
+                .map(|(collection, resources)| (collection, resources.map(str::to_owned)))
+        };
+        assert_eq!(
+            link("https://a.documents.azure.com/dbs/db/colls/coll/docs/id"),
+            Some(("dbs/db/colls/coll".to_owned(), Some("docs".to_owned())))
+        );
+        assert_eq!(
+            link("https://a.documents.azure.com/dbs/db/colls/coll"),
+            Some(("dbs/db/colls/coll".to_owned(), None))
+        );
+        assert_eq!(link("https://a.documents.azure.com/dbs/db/colls"), None);
+    }
+
+    #[test]
+    fn encoded_names_match_their_collection() {
+        let client = CosmosClient::new("a", AuthorizationToken::primary_key("a2V5").unwrap());
+        let collection = client.database_client("my db").collection_client("café");
+        let request = collection.docs_request(Method::Get);
+        let (link, _) = collection_link(&request).unwrap();
+        assert_eq!(link, "dbs/my db/colls/café");
+
+        let container = SessionContainer::default();
+        container.update(&link, "0:-1#12");
+        client.import_session(&container.export());
+        assert_eq!(collection.session_token().as_deref(), Some("0:-1#12"));
+    }
+}
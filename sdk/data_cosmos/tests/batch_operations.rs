@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+mod setup_mock;
+
+use azure_core::StatusCode;
+use azure_data_cosmos::{bulk::BulkOperation, prelude::*};
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct Order {
+    customer: String,
+    id: String,
+    total: u32,
+}
+
+impl Order {
+    fn new(id: &str, customer: &str, total: u32) -> Self {
+        Self {
+            customer: customer.to_owned(),
+            id: id.to_owned(),
+            total,
+        }
+    }
+}
+
+#[tokio::test]
+async fn transactional_batch() {
+    let client = setup_mock::initialize("transactional_batch").unwrap();
+    let orders = client.database_client("shop").collection_client("orders");
+
+    let response = orders
+        .transactional_batch(&"alice")
+        .unwrap()
+        .operation(BatchOperation::create(&Order::new("o1", "alice", 10)).unwrap())
+        .operation(BatchOperation::upsert(&Order::new("o2", "alice", 25)).unwrap())
+        .operation(
+            BatchOperation::patch("o1", vec![Operation::incr("/total", 5).unwrap()])
+                .if_match("\"1\""),
+        )
+        .operation(BatchOperation::read("o2"))
+        .await
+        .unwrap();
+    assert!(response.is_success());
+    assert_eq!(response.results.len(), 4);
+    assert_eq!(response.results[0].status_code, StatusCode::Created);
+    let patched: Order = response.results[2].document().unwrap().unwrap();
+    assert_eq!(patched.total, 15);
+    assert_eq!(response.charge, 6.0);
+
+    // the second operation conflicts, so the first one is not applied
+    let response = orders
+        .transactional_batch(&"alice")
+        .unwrap()
+        .operation(BatchOperation::delete("o2"))
+        .operation(BatchOperation::create(&Order::new("o1", "alice", 10)).unwrap())
+        .await
+        .unwrap();
+    assert!(!response.is_success());
+    assert_eq!(response.status_code, StatusCode::Conflict);
+    assert_eq!(
+        response.results[0].status_code,
+        StatusCode::FailedDependency
+    );
+    assert_eq!(response.results[1].status_code, StatusCode::Conflict);
+
+    let too_many = (0..101).fold(orders.transactional_batch(&"alice").unwrap(), |batch, i| {
+        batch.operation(BatchOperation::read(i.to_string()))
+    });
+    assert!(too_many.await.is_err());
+}
+
+#[tokio::test]
+async fn bulk_operations() {
+    let client = setup_mock::initialize("bulk_operations").unwrap();
+    let orders = client.database_client("shop").collection_client("orders");
+
+    let operations = [
+        Order::new("b1", "bob", 1),
+        Order::new("b2", "alice", 2),
+        Order::new("b3", "carol", 3),
+    ]
+    .iter()
+    .map(|order| BulkOperation::new(&order.customer, BatchOperation::create(order)?))
+    .collect::<azure_core::Result<Vec<_>>>()
+    .unwrap();
+
+    // one request at a time, so that the requests are sent in order
+    let response = orders
+        .bulk()
+        .max_concurrency(1usize)
+        .build()
+        .execute(operations)
+        .await
+        .unwrap();
+    let statuses: Vec<_> = response.results.iter().map(|r| r.status_code).collect();
+    assert_eq!(
+        statuses,
+        [
+            StatusCode::Created,
+            StatusCode::Conflict,
+            StatusCode::Created
+        ]
+    );
+    assert_eq!(response.throttled, 1);
+    assert!(!response.is_success());
+}
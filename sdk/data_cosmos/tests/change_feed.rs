@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+mod setup_mock;
+
+use azure_data_cosmos::change_feed::ChangeFeedPage;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct Event {
+    id: String,
+    kind: String,
+}
+
+#[tokio::test]
+async fn change_feed_iterator_follows_splits() {
+    let client = setup_mock::initialize("change_feed_iterator").unwrap();
+    let collection = client
+        .database_client("events-db")
+        .collection_client("events");
+
+    let mut changes = collection.change_feed().into_iterator();
+
+    let page = changes.next_page::<Event>().await.unwrap();
+    assert!(!page.not_modified);
+    assert_eq!(page.feed_range.max_exclusive, "FF");
+    let ids: Vec<_> = page
+        .documents
+        .iter()
+        .map(|d| d.document.id.as_str())
+        .collect();
+    assert_eq!(ids, ["e1", "e2"]);
+
+    let page = changes.next_page::<Event>().await.unwrap();
+    assert!(page.not_modified);
+    assert!(page.documents.is_empty());
+
+    // the partition key range was split: the left child picks up where its
+    // parent stopped
+    let page = changes.next_page::<Event>().await.unwrap();
+    assert_eq!(page.feed_range.max_exclusive, "7F");
+    assert_eq!(page.documents[0].document.id, "e3");
+
+    // the state resumes with both children
+    let state = changes.state();
+    let mut resumed = collection.change_feed().state(state).into_iterator();
+    let page = resumed.next_page::<Event>().await.unwrap();
+    assert_eq!(page.feed_range.min_inclusive, "7F");
+    assert!(page.not_modified);
+}
+
+#[tokio::test]
+async fn change_feed_processor_acquires_and_checkpoints() {
+    let client = setup_mock::initialize("change_feed_processor").unwrap();
+    let database = client.database_client("events-db");
+    let processor = database
+        .collection_client("events")
+        .change_feed_processor(
+            "projections",
+            database.collection_client("leases"),
+            "host-1",
+        )
+        .build();
+
+    let seen = Mutex::new(Vec::new());
+    let handled = processor
+        .run_once(&|page: ChangeFeedPage<Event>| {
+            let ids = page.documents.iter().map(|d| d.document.id.clone());
+            seen.lock().unwrap().extend(ids);
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+    assert_eq!(handled, 2);
+    assert_eq!(*seen.lock().unwrap(), ["e1", "e2"]);
+
+    let leases = processor.leases().await.unwrap();
+    assert_eq!(leases.len(), 1);
+    assert_eq!(leases[0].owner(), Some("host-1"));
+    assert_eq!(leases[0].continuation(), Some("\"2\""));
+}
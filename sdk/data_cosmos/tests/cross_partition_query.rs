@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+mod setup_mock;
+
+use futures::StreamExt;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct Order {
+    customer: String,
+    id: String,
+    total: u32,
+}
+
+#[tokio::test]
+async fn cross_partition_query_order_by() {
+    let client = setup_mock::initialize("cross_partition_query_order_by").unwrap();
+    let orders = client.database_client("shop").collection_client("orders");
+    let query = "SELECT * FROM c ORDER BY c.total DESC";
+
+    let mut pages = orders
+        .cross_partition_query(query)
+        .max_item_count(2)
+        .into_stream::<Order>();
+    let mut totals = Vec::new();
+    let mut charges = Vec::new();
+    let mut first_continuation = None;
+    while let Some(page) = pages.next().await {
+        let page = page.unwrap();
+        charges.push(page.charge);
+        totals.push(page.results.iter().map(|o| o.total).collect::<Vec<_>>());
+        if first_continuation.is_none() {
+            first_continuation = page.continuation_token;
+        }
+    }
+    assert_eq!(totals, [vec![30, 20], vec![10, 5], vec![1]]);
+    // the pages buffered for the first page are not read again
+    assert_eq!(charges, [5.0, 2.5, 0.0]);
+
+    // the continuation token of a page resumes the query after it
+    let resumed: Vec<_> = orders
+        .cross_partition_query(query)
+        .max_item_count(2)
+        .continuation(first_continuation.unwrap())
+        .into_stream::<Order>()
+        .map(|page| page.unwrap().results)
+        .collect()
+        .await;
+    let totals: Vec<_> = resumed.concat().iter().map(|o| o.total).collect();
+    assert_eq!(totals, [10, 5, 1]);
+}
+
+#[tokio::test]
+async fn cross_partition_query_aggregates() {
+    let client = setup_mock::initialize("cross_partition_query_aggregates").unwrap();
+    let orders = client.database_client("shop").collection_client("orders");
+
+    let page = orders
+        .cross_partition_query("SELECT VALUE COUNT(1) FROM c")
+        .into_stream::<u64>()
+        .next()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(page.results, [7]);
+    assert_eq!(page.charge, 5.0);
+    assert!(page.continuation_token.is_none());
+
+    let page = orders
+        .cross_partition_query("SELECT DISTINCT VALUE c.customer FROM c OFFSET 1 LIMIT 2")
+        .into_stream::<String>()
+        .next()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(page.results, ["bob", "carol"]);
+    assert!(page.continuation_token.is_none());
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use azure_core::{Context, Policy, PolicyResult, Request, TransportOptions};
+use azure_data_cosmos::prelude::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct Order {
+    customer: String,
+    id: String,
+    total: u32,
+}
+
+impl CosmosEntity for Order {
+    type Entity = String;
+
+    fn partition_key(&self) -> Self::Entity {
+        self.customer.clone()
+    }
+}
+
+/// Records the host of every request, before replaying it.
+#[derive(Debug)]
+struct HostRecorder {
+    hosts: Arc<Mutex<Vec<String>>>,
+    transport: Arc<dyn Policy>,
+}
+
+#[async_trait::async_trait]
+impl Policy for HostRecorder {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let host = request.url().host_str().unwrap_or_default().to_owned();
+        self.hosts.lock().unwrap().push(host);
+        self.transport.send(ctx, request, next).await
+    }
+}
+
+#[tokio::test]
+async fn multi_region_failover() {
+    let hosts = Arc::default();
+    let transport = HostRecorder {
+        hosts: Arc::clone(&hosts),
+        transport: mock_transport::new_mock_transport("multi_region_failover".to_owned()),
+    };
+    let client = CosmosClient::builder(
+        "acct",
+        AuthorizationToken::new_resource("MOCK_RESOURCE".to_owned()),
+    )
+    .transport(TransportOptions::new_custom_policy(Arc::new(transport)))
+    .preferred_regions(["East US", "West US"])
+    .build();
+    let orders = client.database_client("shop").collection_client("orders");
+    let order = orders.document_client("o1", &"alice").unwrap();
+
+    // East US is unavailable, so the read is retried in West US
+    let read = order.get_document::<Order>().await.unwrap();
+    assert!(matches!(read, GetDocumentResponse::Found(_)));
+    // and East US is skipped afterwards
+    order.get_document::<Order>().await.unwrap();
+
+    // West US no longer accepts writes: the regions are read again, and the
+    // write is sent to East US
+    orders
+        .create_document(Order {
+            customer: "alice".to_owned(),
+            id: "o1".to_owned(),
+            total: 20,
+        })
+        .is_upsert(true)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *hosts.lock().unwrap(),
+        [
+            "acct.documents.azure.com",
+            "acct-eastus.documents.azure.com",
+            "acct-westus.documents.azure.com",
+            "acct-westus.documents.azure.com",
+            "acct-westus.documents.azure.com",
+            "acct.documents.azure.com",
+            "acct-eastus.documents.azure.com",
+        ]
+    );
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use azure_core::TransportOptions;
+use azure_data_cosmos::prelude::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct Order {
+    customer: String,
+    id: String,
+    total: u32,
+}
+
+impl CosmosEntity for Order {
+    type Entity = String;
+
+    fn partition_key(&self) -> Self::Entity {
+        self.customer.clone()
+    }
+}
+
+#[tokio::test]
+async fn session_tokens() {
+    // the clients replay the same transaction, one request after the other
+    let transport = TransportOptions::new_custom_policy(mock_transport::new_mock_transport(
+        "session_tokens".to_owned(),
+    ));
+    let client = |automatic_session_tokens| {
+        CosmosClient::builder(
+            "acct",
+            AuthorizationToken::new_resource("MOCK_RESOURCE".to_owned()),
+        )
+        .transport(transport.clone())
+        .automatic_session_tokens(automatic_session_tokens)
+        .build()
+    };
+
+    let client_a = client(true);
+    let orders = client_a.database_client("shop").collection_client("orders");
+    assert_eq!(orders.session_token(), None);
+    orders
+        .create_document(Order {
+            customer: "alice".to_owned(),
+            id: "o1".to_owned(),
+            total: 20,
+        })
+        .is_upsert(true)
+        .await
+        .unwrap();
+    assert_eq!(orders.session_token().as_deref(), Some("0:-1#13"));
+
+    // the read carries the token of the write
+    let order = orders.document_client("o1", &"alice").unwrap();
+    order.get_document::<Order>().await.unwrap();
+    // unless it asks for another consistency level
+    order
+        .get_document::<Order>()
+        .consistency_level(ConsistencyLevel::Eventual)
+        .await
+        .unwrap();
+
+    // the session is resumed by another client
+    let state = serde_json::to_string(&client_a.export_session()).unwrap();
+    let client_b = client(true);
+    client_b.import_session(&serde_json::from_str(&state).unwrap());
+    client_b
+        .database_client("shop")
+        .collection_client("orders")
+        .document_client("o1", &"alice")
+        .unwrap()
+        .get_document::<Order>()
+        .await
+        .unwrap();
+
+    // and not kept by a client which opted out
+    let client_c = client(false);
+    client_c.import_session(&serde_json::from_str(&state).unwrap());
+    assert!(client_c.export_session().is_empty());
+    client_c
+        .database_client("shop")
+        .collection_client("orders")
+        .document_client("o1", &"alice")
+        .unwrap()
+        .get_document::<Order>()
+        .await
+        .unwrap();
+}
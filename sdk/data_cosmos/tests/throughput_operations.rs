@@ -0,0 +1,35 @@
+mod setup_mock;
+
+use azure_data_cosmos::prelude::*;
+
+#[tokio::test]
+async fn throughput_operations() {
+    let client = setup_mock::initialize("throughput_operations").unwrap();
+
+    client
+        .create_database("shop")
+        .offer(Offer::Autoscale(4000))
+        .await
+        .unwrap();
+
+    let database = client.database_client("shop");
+    let response = database.read_throughput().await.unwrap();
+    assert_eq!(
+        response.throughput.throughput(),
+        Throughput::Autoscale(4000)
+    );
+    assert_eq!(response.min_throughput, Some(400));
+    assert!(!response.replace_pending);
+
+    // the collection is migrated to autoscale, then scaled
+    let response = database
+        .collection_client("orders")
+        .replace_throughput(Throughput::Autoscale(6000))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.throughput.throughput(),
+        Throughput::Autoscale(6000)
+    );
+    assert!(response.replace_pending);
+}
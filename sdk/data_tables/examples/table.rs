@@ -125,7 +125,7 @@ async fn main() -> azure_core::Result<()> {
 
     let mut stream = table_client
         .query()
-        .filter("name eq 'Carl'")
+        .filter(Property::of::<MyEntity>("name")?.equal("Carl"))
         .top(2)
         .into_stream::<MyEntity>();
 
@@ -0,0 +1,500 @@
+use crate::{
+    operations::{OperationResponse, TransactionBuilder, TransactionResponse},
+    prelude::*,
+    transaction::{encoded_len, TransactionOperations},
+    transaction_operation::TransactionOperation,
+};
+use azure_core::{
+    error::{Error, ErrorKind, ResultExt},
+    Context, Method,
+};
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use serde::Serialize;
+use serde_json::Value as Json;
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    fmt,
+};
+
+/// The most operations the service accepts in a transaction.
+const MAX_OPERATIONS: usize = 100;
+/// The largest transaction the service accepts is 4 MiB, less room for the
+/// boundaries of the batch and the change set.
+const MAX_CHANGESET_SIZE: usize = 4 * 1024 * 1024 - 1024;
+const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+/// Writes any number of entities to a table, in transactions.
+///
+/// Operations are grouped by partition key into change sets the service
+/// accepts: at most 100 operations and 4 MiB, and at most one operation on
+/// each entity. Full change sets are sent as they fill up, up to
+/// `max_concurrency` at the same time, and the transactions being sent make
+/// progress each time an operation is written; `flush` sends the rest and
+/// waits for every transaction. The change sets of a partition are sent one
+/// after the other, so the operations on an entity are applied in the order
+/// they were written.
+///
+/// When an operation fails its transaction, the failure is reported for that
+/// operation only, and the other operations of the transaction are sent again
+/// without it.
+pub struct BulkWriter {
+    client: TableClient,
+    max_concurrency: usize,
+    context: Context,
+    partitions: BTreeMap<String, Partition>,
+    /// The number of operations waiting to be sent.
+    buffered: usize,
+    running: FuturesUnordered<BoxFuture<'static, Completed>>,
+    outcomes: Vec<BulkWriteOutcome>,
+}
+
+impl BulkWriter {
+    pub(crate) fn new(client: TableClient) -> Self {
+        Self {
+            client,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            context: Context::new(),
+            partitions: BTreeMap::new(),
+            buffered: 0,
+            running: FuturesUnordered::new(),
+            outcomes: Vec::new(),
+        }
+    }
+
+    setters! {
+        max_concurrency: usize => max_concurrency.max(1),
+        context: Context => context,
+    }
+
+    /// Insert a new entity.
+    pub async fn insert<E: Serialize>(&mut self, entity: E) -> azure_core::Result<()> {
+        let (partition_key, row_key) = entity_keys(&entity)?;
+        let client = self.client.partition_key_client(&partition_key);
+        let operation = TransactionOperation::insert(&client, entity)?;
+        self.push(partition_key, row_key, operation).await
+    }
+
+    /// Replace an existing entity, if it matches `match_condition`.
+    pub async fn update<E: Serialize>(
+        &mut self,
+        entity: E,
+        match_condition: Option<IfMatchCondition>,
+    ) -> azure_core::Result<()> {
+        self.entity_operation(entity, Method::Put, match_condition)
+            .await
+    }
+
+    /// Merge the properties of an entity into an existing entity, if it
+    /// matches `match_condition`.
+    pub async fn merge<E: Serialize>(
+        &mut self,
+        entity: E,
+        match_condition: Option<IfMatchCondition>,
+    ) -> azure_core::Result<()> {
+        self.entity_operation(entity, Method::Merge, match_condition)
+            .await
+    }
+
+    /// Replace an entity, or insert it if it doesn't exist.
+    pub async fn insert_or_replace<E: Serialize>(&mut self, entity: E) -> azure_core::Result<()> {
+        self.entity_operation(entity, Method::Put, None).await
+    }
+
+    /// Merge the properties of an entity into an existing entity, or insert it
+    /// if it doesn't exist.
+    pub async fn insert_or_merge<E: Serialize>(&mut self, entity: E) -> azure_core::Result<()> {
+        self.entity_operation(entity, Method::Merge, None).await
+    }
+
+    /// Delete an existing entity, if it matches `match_condition`.
+    pub async fn delete<PK: Into<String>, RK: Into<String>>(
+        &mut self,
+        partition_key: PK,
+        row_key: RK,
+        match_condition: Option<IfMatchCondition>,
+    ) -> azure_core::Result<()> {
+        let (partition_key, row_key) = (partition_key.into(), row_key.into());
+        let client = self
+            .client
+            .partition_key_client(&partition_key)
+            .entity_client(&row_key);
+        let operation = TransactionOperation::delete(&client, match_condition)?;
+        self.push(partition_key, row_key, operation).await
+    }
+
+    /// Send every operation written so far and wait for them, returning the
+    /// outcomes not taken yet.
+    pub async fn flush(&mut self) -> Vec<BulkWriteOutcome> {
+        loop {
+            self.start(true);
+            if !self.complete_next().await {
+                break;
+            }
+        }
+        self.take_outcomes()
+    }
+
+    /// The outcomes of the operations completed so far, in the order they
+    /// completed.
+    pub fn take_outcomes(&mut self) -> Vec<BulkWriteOutcome> {
+        std::mem::take(&mut self.outcomes)
+    }
+
+    async fn entity_operation<E: Serialize>(
+        &mut self,
+        entity: E,
+        method: Method,
+        match_condition: Option<IfMatchCondition>,
+    ) -> azure_core::Result<()> {
+        let (partition_key, row_key) = entity_keys(&entity)?;
+        let client = self
+            .client
+            .partition_key_client(&partition_key)
+            .entity_client(&row_key);
+        let operation = TransactionOperation::entity(&client, entity, method, match_condition)?;
+        self.push(partition_key, row_key, operation).await
+    }
+
+    async fn push(
+        &mut self,
+        partition_key: String,
+        row_key: String,
+        operation: TransactionOperation,
+    ) -> azure_core::Result<()> {
+        let operation = BulkOperation {
+            size: encoded_len(&operation)?,
+            row_key,
+            operation,
+        };
+        let partition = self.partitions.entry(partition_key.clone()).or_default();
+        partition.push(operation);
+        self.buffered += 1;
+        if partition.is_ready(false) && self.running.len() < self.max_concurrency {
+            self.start_partition(&partition_key);
+        }
+
+        // drive the running transactions, without waiting for them
+        while let Some(Some(completed)) = self.running.next().now_or_never() {
+            self.complete(completed);
+            self.start(false);
+        }
+
+        // bound the memory used by operations waiting to be sent, sending
+        // change sets that aren't full if need be
+        while self.buffered > self.max_concurrency * MAX_OPERATIONS {
+            self.start(true);
+            if !self.complete_next().await {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Start sending change sets, full ones only unless `all`, up to
+    /// `max_concurrency` transactions at the same time.
+    fn start(&mut self, all: bool) {
+        let ready: Vec<String> = self
+            .partitions
+            .iter()
+            .filter(|(_, partition)| partition.is_ready(all))
+            .map(|(partition_key, _)| partition_key.clone())
+            .take(self.max_concurrency.saturating_sub(self.running.len()))
+            .collect();
+        for partition_key in ready {
+            self.start_partition(&partition_key);
+        }
+    }
+
+    fn start_partition(&mut self, partition_key: &str) {
+        let Some(partition) = self.partitions.get_mut(partition_key) else {
+            return;
+        };
+        let Some(changeset) = partition.changesets.pop_front() else {
+            return;
+        };
+        partition.running = true;
+        self.buffered -= changeset.operations.len();
+
+        let mut operations = TransactionOperations::new();
+        for operation in &changeset.operations {
+            operations.add(operation.operation.clone());
+        }
+        let transaction =
+            TransactionBuilder::new(self.client.partition_key_client(partition_key), operations)
+                .context(self.context.clone())
+                .into_future();
+        let partition_key = partition_key.to_owned();
+        self.running.push(Box::pin(async move {
+            Completed {
+                partition_key,
+                changeset,
+                result: transaction.await,
+            }
+        }));
+    }
+
+    /// Wait for a transaction to complete, if any is running.
+    async fn complete_next(&mut self) -> bool {
+        let Some(completed) = self.running.next().await else {
+            return false;
+        };
+        self.complete(completed);
+        self.start(false);
+        true
+    }
+
+    fn complete(&mut self, completed: Completed) {
+        let Completed {
+            partition_key,
+            mut changeset,
+            result,
+        } = completed;
+        let partition = self.partitions.entry(partition_key.clone()).or_default();
+        partition.running = false;
+
+        let outcome = |operation: BulkOperation, result| BulkWriteOutcome {
+            partition_key: partition_key.clone(),
+            row_key: operation.row_key,
+            result,
+        };
+        match result {
+            Ok(response) => match failed_operation(&response) {
+                None => {
+                    let mut responses = response.operation_responses.into_iter();
+                    for operation in changeset.operations {
+                        let response = responses.next().unwrap_or_default();
+                        self.outcomes.push(outcome(operation, Ok(response)));
+                    }
+                }
+                // only the failed operation is reported, as none of the
+                // others were applied: they are sent again without it
+                Some((index, error)) if index < changeset.operations.len() => {
+                    let failed = changeset.operations.remove(index);
+                    self.outcomes.push(outcome(failed, Err(error)));
+                    if !changeset.operations.is_empty() {
+                        self.buffered += changeset.operations.len();
+                        partition
+                            .changesets
+                            .push_front(Changeset::from(changeset.operations));
+                    }
+                }
+                Some((_, error)) => {
+                    for operation in changeset.operations {
+                        let error = copy_error(&error);
+                        self.outcomes.push(outcome(operation, Err(error)));
+                    }
+                }
+            },
+            Err(error) => {
+                for operation in changeset.operations {
+                    let error = copy_error(&error);
+                    self.outcomes.push(outcome(operation, Err(error)));
+                }
+            }
+        }
+
+        if !partition.running && partition.changesets.is_empty() {
+            self.partitions.remove(&partition_key);
+        }
+    }
+}
+
+impl fmt::Debug for BulkWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkWriter")
+            .field("client", &self.client)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("buffered", &self.buffered)
+            .field("running", &self.running.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The outcome of an operation written by a [`BulkWriter`].
+#[derive(Debug)]
+pub struct BulkWriteOutcome {
+    pub partition_key: String,
+    pub row_key: String,
+    pub result: azure_core::Result<OperationResponse>,
+}
+
+struct BulkOperation {
+    row_key: String,
+    operation: TransactionOperation,
+    /// The size of the operation in the body of a transaction.
+    size: usize,
+}
+
+/// The operations of a partition waiting to be sent.
+#[derive(Default)]
+struct Partition {
+    changesets: VecDeque<Changeset>,
+    /// Whether a transaction of the partition is running.
+    running: bool,
+}
+
+impl Partition {
+    fn push(&mut self, operation: BulkOperation) {
+        match self.changesets.back_mut() {
+            Some(changeset) if changeset.accepts(&operation) => changeset.push(operation),
+            _ => self.changesets.push_back(Changeset::from(vec![operation])),
+        }
+    }
+
+    /// Whether a change set can be sent: a full one, or any if `all`.
+    fn is_ready(&self, all: bool) -> bool {
+        match self.changesets.front() {
+            Some(changeset) if !self.running => {
+                all || self.changesets.len() > 1 || !changeset.has_room()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Changeset {
+    operations: Vec<BulkOperation>,
+    row_keys: HashSet<String>,
+    size: usize,
+}
+
+impl Changeset {
+    fn push(&mut self, operation: BulkOperation) {
+        self.row_keys.insert(operation.row_key.clone());
+        self.size += operation.size;
+        self.operations.push(operation);
+    }
+
+    fn has_room(&self) -> bool {
+        self.operations.len() < MAX_OPERATIONS
+    }
+
+    fn accepts(&self, operation: &BulkOperation) -> bool {
+        self.operations.is_empty()
+            || (self.has_room()
+                && self.size + operation.size <= MAX_CHANGESET_SIZE
+                && !self.row_keys.contains(&operation.row_key))
+    }
+}
+
+impl From<Vec<BulkOperation>> for Changeset {
+    fn from(operations: Vec<BulkOperation>) -> Self {
+        let mut changeset = Self::default();
+        for operation in operations {
+            changeset.push(operation);
+        }
+        changeset
+    }
+}
+
+struct Completed {
+    partition_key: String,
+    changeset: Changeset,
+    result: azure_core::Result<TransactionResponse>,
+}
+
+/// The index of the operation that failed the transaction, or `usize::MAX` if
+/// the service didn't report it, along with its error.
+fn failed_operation(response: &TransactionResponse) -> Option<(usize, Error)> {
+    let failed = response
+        .operation_responses
+        .iter()
+        .find(|response| !response.status_code.is_success())?;
+    let (index, code, message) = match &failed.error {
+        Some(error) => (
+            error.index.unwrap_or(usize::MAX),
+            Some(error.code.clone()),
+            error.message.clone(),
+        ),
+        None => (usize::MAX, None, "the transaction failed".to_owned()),
+    };
+    let error = Error::message(ErrorKind::http_response(failed.status_code, code), message);
+    Some((index, error))
+}
+
+fn copy_error(error: &Error) -> Error {
+    Error::with_message(error.kind().clone(), || error.to_string())
+}
+
+/// The partition and row keys of a serialized entity.
+fn entity_keys<E: Serialize>(entity: &E) -> azure_core::Result<(String, String)> {
+    let entity = serde_json::to_value(entity)
+        .context(ErrorKind::DataConversion, "failed to serialize the entity")?;
+    let key = |name: &str| match entity.get(name) {
+        Some(Json::String(key)) => Ok(key.clone()),
+        _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!("the entity has no {name}")
+        })),
+    };
+    Ok((key("PartitionKey")?, key("RowKey")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::{Request, Url};
+
+    fn operation(row_key: &str, size: usize) -> BulkOperation {
+        let url = Url::parse("https://account.table.core.windows.net/table").unwrap();
+        BulkOperation {
+            row_key: row_key.to_owned(),
+            operation: TransactionOperation::new(Request::new(url, Method::Post)),
+            size,
+        }
+    }
+
+    fn lengths(partition: &Partition) -> Vec<usize> {
+        partition
+            .changesets
+            .iter()
+            .map(|changeset| changeset.operations.len())
+            .collect()
+    }
+
+    #[test]
+    fn changesets_hold_at_most_100_operations() {
+        let mut partition = Partition::default();
+        for row in 0..250 {
+            partition.push(operation(&row.to_string(), 100));
+            assert_eq!(partition.is_ready(false), row >= 99);
+        }
+        assert_eq!(lengths(&partition), [100, 100, 50]);
+        assert!(partition.is_ready(true));
+        partition.running = true;
+        assert!(!partition.is_ready(true));
+    }
+
+    #[test]
+    fn changesets_fit_the_payload_limit() {
+        let mut partition = Partition::default();
+        for row in 0..5 {
+            partition.push(operation(&row.to_string(), 1024 * 1024));
+        }
+        // an operation too large for any change set is sent on its own
+        partition.push(operation("large", MAX_CHANGESET_SIZE + 1));
+        partition.push(operation("small", 1));
+        assert_eq!(lengths(&partition), [3, 2, 1, 1]);
+    }
+
+    #[test]
+    fn changesets_change_an_entity_once() {
+        let mut partition = Partition::default();
+        for row_key in ["a", "b", "a", "c", "b", "b"] {
+            partition.push(operation(row_key, 1));
+        }
+        assert_eq!(lengths(&partition), [2, 3, 1]);
+        assert!(partition.is_ready(false));
+    }
+
+    #[test]
+    fn keys_are_read_from_the_entity() {
+        let entity = serde_json::json!({ "PartitionKey": "pk", "RowKey": "rk", "n": 1 });
+        assert_eq!(
+            entity_keys(&entity).unwrap(),
+            ("pk".to_owned(), "rk".to_owned())
+        );
+        assert!(entity_keys(&serde_json::json!({ "PartitionKey": "pk" })).is_err());
+        assert!(entity_keys(&TableEntity::new("pk", "rk")).is_ok());
+    }
+}
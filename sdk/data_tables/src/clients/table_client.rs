@@ -1,6 +1,19 @@
-use crate::{clients::*, operations::*};
-use azure_core::{headers::Headers, to_json, Body, Context, Method, Request, Response, Url};
+use crate::{clients::*, edm::entity_json, operations::*, BulkWriter};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::Headers,
+    Body, Context, Method, Request, Response, Url,
+};
+use azure_storage::{
+    shared_access_signature::{
+        table_sas::{TableSasPermissions, TableSharedAccessSignature},
+        SasToken,
+    },
+    StorageCredentialsInner, StoredAccessPolicyList,
+};
 use serde::{de::DeserializeOwned, Serialize};
+use std::ops::Deref;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct TableClient {
@@ -36,14 +49,79 @@ impl TableClient {
         &self,
         entity: E,
     ) -> azure_core::Result<InsertEntityBuilder<R>> {
-        let body = to_json(&entity)?.into();
+        let body = entity_json(&entity)?.into();
         Ok(InsertEntityBuilder::new(self.clone(), body))
     }
 
+    /// Get the stored access policies of the table.
+    pub fn get_acl(&self) -> GetTableACLBuilder {
+        GetTableACLBuilder::new(self.clone())
+    }
+
+    /// Replace the stored access policies of the table, which shared access
+    /// signatures refer to by their `identifier`. The service allows up to
+    /// five policies on a table.
+    pub fn set_acl(&self, stored_access_policy_list: StoredAccessPolicyList) -> SetTableACLBuilder {
+        SetTableACLBuilder::new(self.clone(), stored_access_policy_list)
+    }
+
+    /// Write any number of entities in transactions, see [`BulkWriter`].
+    pub fn bulk_writer(&self) -> BulkWriter {
+        BulkWriter::new(self.clone())
+    }
+
+    /// Create a shared access signature scoped to this table.
+    ///
+    /// Use the partition and row key setters of the returned signature to
+    /// restrict it to a range of entities.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: TableSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<TableSharedAccessSignature> {
+        let creds = self.table_service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource =
+            format!("/table/{}/{}", account, self.table_name().to_lowercase());
+        Ok(TableSharedAccessSignature::new(
+            key.clone(),
+            canonicalized_resource,
+            self.table_name().to_owned(),
+            permissions,
+            expiry,
+        ))
+    }
+
+    /// The URL of the table carrying `signature`, for clients without
+    /// credentials of their own.
+    pub fn generate_signed_table_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.table_url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
     pub(crate) fn url(&self) -> azure_core::Result<Url> {
         self.table_service_client.url()
     }
 
+    /// The URL of the table itself, rather than of its entities.
+    pub(crate) fn table_url(&self) -> azure_core::Result<Url> {
+        let mut url = self.table_service_client.service_url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::message(ErrorKind::Other, "invalid table URL"))?
+            .push(self.table_name());
+        Ok(url)
+    }
+
     pub fn partition_key_client<PK: Into<String>>(&self, partition_key: PK) -> PartitionKeyClient {
         PartitionKeyClient::new(self.clone(), partition_key)
     }
@@ -1,8 +1,16 @@
-use crate::operations::ListTablesBuilder;
+use crate::{operations::*, TableServiceProperties};
 use azure_core::{
-    headers::Headers, Body, ClientOptions, Context, Method, Pipeline, Request, Response, Url,
+    error::{Error, ErrorKind},
+    headers::Headers,
+    Body, ClientOptions, Context, Method, Pipeline, Request, Response, Url,
 };
-use azure_storage::{clients::ServiceType, prelude::StorageCredentials, CloudLocation};
+use azure_storage::{
+    clients::ServiceType, prelude::StorageCredentials, CloudLocation, ConnectionString,
+    EndpointProtocol, TokenScope,
+};
+
+/// The scope of the Azure AD tokens accepted by the Cosmos DB Table API.
+const COSMOS_TOKEN_SCOPE: &str = "https://cosmos.azure.com/.default";
 
 use super::TableClient;
 
@@ -43,6 +51,84 @@ impl TableServiceClientBuilder {
         }
     }
 
+    /// Create a new instance of `TableServiceClientBuilder` for an account of
+    /// the Cosmos DB Table API, at `https://{account}.table.cosmos.azure.com`.
+    #[must_use]
+    pub fn cosmos<A, C>(account: A, credentials: C) -> Self
+    where
+        A: Into<String>,
+        C: Into<StorageCredentials>,
+    {
+        let account = account.into();
+        Self::with_location(
+            CloudLocation::Custom {
+                uri: format!("https://{account}.table.cosmos.azure.com"),
+                account,
+            },
+            credentials,
+        )
+    }
+
+    /// Create a new instance of `TableServiceClientBuilder` from a connection
+    /// string of a storage account or of a Cosmos DB Table API account.
+    ///
+    /// The `TableEndpoint` of the connection string takes precedence over the
+    /// endpoint derived from its `AccountName` and `EndpointSuffix`.
+    pub fn from_connection_string(connection_string: &str) -> azure_core::Result<Self> {
+        let connection_string = ConnectionString::new(connection_string)?;
+        if connection_string.use_development_storage == Some(true) {
+            return Ok(Self::emulator());
+        }
+        let credentials = connection_string.storage_credentials()?;
+
+        let cloud_location = match connection_string {
+            ConnectionString {
+                table_endpoint: Some(endpoint),
+                account_name,
+                ..
+            } => {
+                let uri = endpoint.trim_end_matches('/').to_owned();
+                let account = match account_name {
+                    Some(account) => account.to_owned(),
+                    None => Url::parse(&uri)?
+                        .host_str()
+                        .and_then(|host| host.split('.').next())
+                        .unwrap_or_default()
+                        .to_owned(),
+                };
+                CloudLocation::Custom { account, uri }
+            }
+            ConnectionString {
+                account_name: Some(account),
+                endpoint_suffix,
+                default_endpoints_protocol,
+                ..
+            } => {
+                let account = account.to_owned();
+                match endpoint_suffix {
+                    None | Some("core.windows.net") => CloudLocation::Public { account },
+                    Some("core.chinacloudapi.cn") => CloudLocation::China { account },
+                    Some(suffix) => {
+                        let protocol =
+                            default_endpoints_protocol.unwrap_or(EndpointProtocol::Https);
+                        CloudLocation::Custom {
+                            uri: format!("{protocol}://{account}.table.{suffix}"),
+                            account,
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Err(Error::message(
+                    ErrorKind::Other,
+                    "the connection string has neither a TableEndpoint nor an AccountName",
+                ))
+            }
+        };
+
+        Ok(Self::with_location(cloud_location, credentials))
+    }
+
     /// Use the emulator with default settings
     #[must_use]
     pub fn emulator() -> Self {
@@ -63,9 +149,19 @@ impl TableServiceClientBuilder {
             options,
             credentials,
         } = self;
+        let is_cosmos_endpoint = cloud_location.url(ServiceType::Table).map_or(false, |url| {
+            url.host_str().map_or(false, |host| {
+                host.contains(".table.cosmos.") || host.contains(".table.cosmosdb.")
+            })
+        });
         TableServiceClient {
-            pipeline: azure_storage::clients::new_pipeline_from_options(options, credentials),
+            pipeline: azure_storage::clients::new_pipeline_from_options(
+                options,
+                credentials.clone(),
+            ),
             cloud_location,
+            credentials,
+            is_cosmos_endpoint,
         }
     }
 
@@ -104,6 +200,8 @@ impl TableServiceClientBuilder {
 pub struct TableServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    credentials: StorageCredentials,
+    is_cosmos_endpoint: bool,
 }
 
 impl TableServiceClient {
@@ -111,20 +209,75 @@ impl TableServiceClient {
         TableServiceClientBuilder::new(account, credentials).build()
     }
 
+    /// Whether the client talks to the Cosmos DB Table API rather than to
+    /// Azure Table storage.
+    ///
+    /// Cosmos DB accepts the same shared key signatures as storage, but Azure AD
+    /// tokens for its own audience, and it does not replicate to a storage
+    /// secondary location, so it has no service stats.
+    pub fn is_cosmos_endpoint(&self) -> bool {
+        self.is_cosmos_endpoint
+    }
+
     pub fn list(&self) -> ListTablesBuilder {
         ListTablesBuilder::new(self.clone())
     }
 
+    pub fn get_properties(&self) -> GetTableServicePropertiesBuilder {
+        GetTableServicePropertiesBuilder::new(self.clone())
+    }
+
+    /// Set the logging, metrics and CORS properties of the Table service.
+    ///
+    /// The sections of `properties` set to `None` keep their current value.
+    pub fn set_properties(
+        &self,
+        properties: TableServiceProperties,
+    ) -> SetTableServicePropertiesBuilder {
+        SetTableServicePropertiesBuilder::new(self.clone(), properties)
+    }
+
+    /// Get the geo-replication status of the account, which needs read-access
+    /// geo-redundant replication.
+    pub fn get_stats(&self) -> GetTableServiceStatsBuilder {
+        GetTableServiceStatsBuilder::new(self.clone())
+    }
+
     pub(crate) fn url(&self) -> azure_core::Result<Url> {
-        let mut url = self.cloud_location.url(ServiceType::Table)?;
+        let mut url = self.service_url()?;
         url.path_segments_mut().unwrap().push("Tables");
         Ok(url)
     }
 
+    pub(crate) fn service_url(&self) -> azure_core::Result<Url> {
+        self.cloud_location.url(ServiceType::Table)
+    }
+
+    /// The service URL of the secondary location of the account. Only the
+    /// public and China clouds have a well-known secondary location.
+    pub(crate) fn secondary_service_url(&self) -> azure_core::Result<Url> {
+        let mut url = self.service_url()?;
+        if let CloudLocation::Public { account } | CloudLocation::China { account } =
+            &self.cloud_location
+        {
+            let host = url.host_str().unwrap_or_default().replacen(
+                account.as_str(),
+                &format!("{account}-secondary"),
+                1,
+            );
+            url.set_host(Some(&host))?;
+        }
+        Ok(url)
+    }
+
     pub fn table_client<S: Into<String>>(&self, table_name: S) -> TableClient {
         TableClient::new(self.clone(), table_name)
     }
 
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: Method,
@@ -139,9 +292,76 @@ impl TableServiceClient {
         context: &mut Context,
         request: &mut Request,
     ) -> azure_core::Result<Response> {
-        self.pipeline
-            .send(context.insert(ServiceType::Table), request)
-            .await
+        context.insert(ServiceType::Table);
+        if self.is_cosmos_endpoint {
+            context.insert(TokenScope(COSMOS_TOKEN_SCOPE));
+        }
+        self.pipeline.send(context, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_connection_string(connection_string: &str) -> TableServiceClient {
+        TableServiceClientBuilder::from_connection_string(connection_string)
+            .unwrap()
+            .build()
+    }
+
+    #[test]
+    fn connection_string_endpoints() {
+        let client = from_connection_string(
+            "DefaultEndpointsProtocol=https;AccountName=contoso;AccountKey=a2V5;TableEndpoint=https://contoso.table.cosmos.azure.com:443/;",
+        );
+        assert!(client.is_cosmos_endpoint());
+        assert_eq!(
+            client.url().unwrap().as_str(),
+            "https://contoso.table.cosmos.azure.com/Tables"
+        );
+
+        let client = from_connection_string(
+            "DefaultEndpointsProtocol=https;AccountName=contoso;AccountKey=a2V5;EndpointSuffix=core.windows.net",
+        );
+        assert!(!client.is_cosmos_endpoint());
+        assert_eq!(
+            client.url().unwrap().as_str(),
+            "https://contoso.table.core.windows.net/Tables"
+        );
+
+        let client = from_connection_string(
+            "DefaultEndpointsProtocol=http;AccountName=contoso;AccountKey=a2V5;EndpointSuffix=core.usgovcloudapi.net",
+        );
+        assert_eq!(
+            client.url().unwrap().as_str(),
+            "http://contoso.table.core.usgovcloudapi.net/Tables"
+        );
+
+        let client = from_connection_string("UseDevelopmentStorage=true");
+        assert_eq!(
+            client.url().unwrap().as_str(),
+            "http://127.0.0.1:10002/devstoreaccount1/Tables"
+        );
+
+        assert!(
+            TableServiceClientBuilder::from_connection_string("SharedAccessSignature=sig=a")
+                .is_err()
+        );
+        assert!(TableServiceClientBuilder::from_connection_string("AccountName=contoso").is_err());
+    }
+
+    #[tokio::test]
+    async fn cosmos_has_no_service_stats() {
+        let client =
+            TableServiceClientBuilder::cosmos("contoso", StorageCredentials::anonymous()).build();
+        assert!(client.is_cosmos_endpoint());
+        assert_eq!(
+            client.url().unwrap().as_str(),
+            "https://contoso.table.cosmos.azure.com/Tables"
+        );
+        let err = client.get_stats().await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Other);
     }
 }
 
@@ -0,0 +1,486 @@
+//! Typed property values of the Entity Data Model (EDM).
+//!
+//! Table storage infers the type of an untyped JSON property: strings are
+//! `Edm.String`, booleans `Edm.Boolean` and numbers `Edm.Int32` or
+//! `Edm.Double`. Every other type is sent as a string along with a
+//! `<name>@odata.type` annotation. [`EdmValue`] carries the type of a value,
+//! and the modules in here are serde `with` helpers that annotate a field of
+//! an entity struct:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use time::OffsetDateTime;
+//! use uuid::Uuid;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Order {
+//!     #[serde(rename = "PartitionKey")]
+//!     customer: String,
+//!     #[serde(rename = "RowKey")]
+//!     id: String,
+//!     #[serde(with = "azure_data_tables::edm::int64")]
+//!     total: i64,
+//!     #[serde(with = "azure_data_tables::edm::guid")]
+//!     tracking_id: Uuid,
+//!     #[serde(default, with = "azure_data_tables::edm::datetime::option")]
+//!     shipped: Option<OffsetDateTime>,
+//! }
+//! ```
+//!
+//! The annotations are only added to the top level properties of entities
+//! sent by the clients of this crate. Serialized any other way, e.g. with
+//! `serde_json::to_string`, a field is the JSON value sent for it, without
+//! its annotation.
+
+use azure_core::{
+    base64,
+    date::{parse_rfc3339, to_rfc3339},
+    error::{Error, ErrorKind, ResultExt},
+};
+use bytes::Bytes;
+use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Number, Value as Json};
+use std::cell::Cell;
+use time::{OffsetDateTime, UtcOffset};
+use uuid::Uuid;
+
+/// The suffix of the property holding the type of a property.
+pub(crate) const TYPE_ANNOTATION: &str = "@odata.type";
+
+// While `entity_json` serializes an entity, a field using one of the helpers
+// is serialized as an object with these keys, that is replaced by the value
+// and its annotation.
+const MARKER_TYPE: &str = "$edm.type";
+const MARKER_VALUE: &str = "$edm.value";
+
+thread_local! {
+    static MARKING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Has the helpers mark their values until dropped.
+struct MarkingGuard(bool);
+
+impl MarkingGuard {
+    fn new() -> Self {
+        Self(MARKING.with(|marking| marking.replace(true)))
+    }
+}
+
+impl Drop for MarkingGuard {
+    fn drop(&mut self) {
+        MARKING.with(|marking| marking.set(self.0));
+    }
+}
+
+pub const STRING: &str = "Edm.String";
+pub const BOOLEAN: &str = "Edm.Boolean";
+pub const INT32: &str = "Edm.Int32";
+pub const INT64: &str = "Edm.Int64";
+pub const DOUBLE: &str = "Edm.Double";
+pub const DATETIME: &str = "Edm.DateTime";
+pub const GUID: &str = "Edm.Guid";
+pub const BINARY: &str = "Edm.Binary";
+
+/// A property value along with its EDM type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EdmValue {
+    String(String),
+    Boolean(bool),
+    Int32(i32),
+    Int64(i64),
+    Double(f64),
+    DateTime(OffsetDateTime),
+    Guid(Uuid),
+    Binary(Vec<u8>),
+}
+
+impl EdmValue {
+    /// The name of the EDM type, e.g. `Edm.Int64`.
+    pub fn edm_type(&self) -> &'static str {
+        match self {
+            Self::String(_) => STRING,
+            Self::Boolean(_) => BOOLEAN,
+            Self::Int32(_) => INT32,
+            Self::Int64(_) => INT64,
+            Self::Double(_) => DOUBLE,
+            Self::DateTime(_) => DATETIME,
+            Self::Guid(_) => GUID,
+            Self::Binary(_) => BINARY,
+        }
+    }
+
+    /// The annotation sent with the value, if the service can't infer the
+    /// type from the JSON value.
+    pub(crate) fn annotation(&self) -> Option<&'static str> {
+        match self {
+            Self::String(_) | Self::Boolean(_) | Self::Int32(_) => None,
+            Self::Double(value) if value.is_finite() && value.fract() == 0.0 => Some(DOUBLE),
+            Self::Double(value) if value.is_finite() => None,
+            _ => Some(self.edm_type()),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Self::Int32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value of an `Edm.Int64` or `Edm.Int32` property.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int32(value) => Some(i64::from(*value)),
+            Self::Int64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Double(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_datetime(&self) -> Option<OffsetDateTime> {
+        match self {
+            Self::DateTime(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_guid(&self) -> Option<Uuid> {
+        match self {
+            Self::Guid(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary(&self) -> Option<&[u8]> {
+        match self {
+            Self::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The JSON value sent for this value, without its annotation.
+    pub(crate) fn to_json(&self) -> Json {
+        match self {
+            Self::String(value) => Json::String(value.clone()),
+            Self::Boolean(value) => Json::Bool(*value),
+            Self::Int32(value) => Json::from(*value),
+            Self::Int64(value) => Json::String(value.to_string()),
+            Self::Double(value) => match Number::from_f64(*value) {
+                Some(number) => Json::Number(number),
+                None if value.is_nan() => Json::String("NaN".to_owned()),
+                None if *value > 0.0 => Json::String("Infinity".to_owned()),
+                None => Json::String("-Infinity".to_owned()),
+            },
+            Self::DateTime(value) => Json::String(to_rfc3339(&value.to_offset(UtcOffset::UTC))),
+            Self::Guid(value) => Json::String(value.hyphenated().to_string()),
+            Self::Binary(value) => Json::String(base64::encode(value)),
+        }
+    }
+
+    /// Read a JSON value of the type `edm_type`, or of the type inferred from
+    /// the JSON value when there is no annotation.
+    pub(crate) fn from_json(value: Json, edm_type: Option<&str>) -> azure_core::Result<Self> {
+        let invalid = |value: &Json, edm_type: &str| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid {edm_type} value {value}")
+            })
+        };
+        let edm_value = match (edm_type, value) {
+            (None | Some(STRING), Json::String(value)) => Self::String(value),
+            (None | Some(BOOLEAN), Json::Bool(value)) => Self::Boolean(value),
+            (None, Json::Number(number)) => match number.as_i64() {
+                Some(value) => i32::try_from(value)
+                    .map(Self::Int32)
+                    .unwrap_or(Self::Int64(value)),
+                None => Self::Double(
+                    number
+                        .as_f64()
+                        .ok_or_else(|| invalid(&number.into(), DOUBLE))?,
+                ),
+            },
+            (Some(INT32), Json::Number(number)) => number
+                .as_i64()
+                .and_then(|value| i32::try_from(value).ok())
+                .map(Self::Int32)
+                .ok_or_else(|| invalid(&number.into(), INT32))?,
+            (Some(INT64), Json::String(value)) => Self::Int64(
+                value
+                    .parse()
+                    .map_err(|_| invalid(&Json::String(value), INT64))?,
+            ),
+            (Some(INT64), Json::Number(number)) => number
+                .as_i64()
+                .map(Self::Int64)
+                .ok_or_else(|| invalid(&number.into(), INT64))?,
+            (Some(DOUBLE), Json::Number(number)) => Self::Double(
+                number
+                    .as_f64()
+                    .ok_or_else(|| invalid(&number.into(), DOUBLE))?,
+            ),
+            (Some(DOUBLE), Json::String(value)) => Self::Double(match value.as_str() {
+                "NaN" => f64::NAN,
+                "Infinity" => f64::INFINITY,
+                "-Infinity" => f64::NEG_INFINITY,
+                _ => value
+                    .parse()
+                    .map_err(|_| invalid(&Json::String(value), DOUBLE))?,
+            }),
+            (Some(DATETIME), Json::String(value)) => Self::DateTime(parse_rfc3339(&value)?),
+            (Some(GUID), Json::String(value)) => Self::Guid(
+                Uuid::parse_str(&value).map_err(|_| invalid(&Json::String(value), GUID))?,
+            ),
+            (Some(BINARY), Json::String(value)) => Self::Binary(base64::decode(value)?),
+            (Some(edm_type), value) => return Err(invalid(&value, edm_type)),
+            (None, value) => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("unsupported property value {value}")
+                }))
+            }
+        };
+        Ok(edm_value)
+    }
+}
+
+impl From<String> for EdmValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for EdmValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<bool> for EdmValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<i32> for EdmValue {
+    fn from(value: i32) -> Self {
+        Self::Int32(value)
+    }
+}
+
+impl From<i64> for EdmValue {
+    fn from(value: i64) -> Self {
+        Self::Int64(value)
+    }
+}
+
+impl From<f64> for EdmValue {
+    fn from(value: f64) -> Self {
+        Self::Double(value)
+    }
+}
+
+impl From<OffsetDateTime> for EdmValue {
+    fn from(value: OffsetDateTime) -> Self {
+        Self::DateTime(value)
+    }
+}
+
+impl From<Uuid> for EdmValue {
+    fn from(value: Uuid) -> Self {
+        Self::Guid(value)
+    }
+}
+
+impl From<Vec<u8>> for EdmValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Binary(value)
+    }
+}
+
+impl From<&[u8]> for EdmValue {
+    fn from(value: &[u8]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+/// Serializes a value the way the helpers do, as a marker object while
+/// `entity_json` serializes an entity, or else as the JSON value sent for it.
+struct Marked<'a>(&'a EdmValue);
+
+impl Serialize for Marked<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let edm_type = match self.0.annotation() {
+            Some(edm_type) if MARKING.with(Cell::get) => edm_type,
+            _ => return self.0.to_json().serialize(serializer),
+        };
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry(MARKER_TYPE, edm_type)?;
+        map.serialize_entry(MARKER_VALUE, &self.0.to_json())?;
+        map.end()
+    }
+}
+
+fn deserialize_as<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    edm_type: &str,
+) -> Result<EdmValue, D::Error> {
+    let value = Json::deserialize(deserializer)?;
+    EdmValue::from_json(value, Some(edm_type)).map_err(serde::de::Error::custom)
+}
+
+macro_rules! edm_helper {
+    ($(#[$meta:meta])* $module:ident, $variant:ident($ty:ty), $edm_type:expr) => {
+        $(#[$meta])*
+        pub mod $module {
+            use super::*;
+
+            pub fn serialize<S: Serializer>(value: &$ty, serializer: S) -> Result<S::Ok, S::Error> {
+                Marked(&EdmValue::$variant(value.to_owned())).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<$ty, D::Error> {
+                match deserialize_as(deserializer, $edm_type)? {
+                    EdmValue::$variant(value) => Ok(value),
+                    value => Err(serde::de::Error::custom(format!(
+                        "expected an {} value, found an {} value",
+                        $edm_type,
+                        value.edm_type()
+                    ))),
+                }
+            }
+
+            pub mod option {
+                use super::*;
+
+                pub fn serialize<S: Serializer>(
+                    value: &Option<$ty>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    match value {
+                        Some(value) => super::serialize(value, serializer),
+                        None => serializer.serialize_none(),
+                    }
+                }
+
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Option<$ty>, D::Error> {
+                    match Option::<Json>::deserialize(deserializer)? {
+                        Some(value) => super::deserialize(value)
+                            .map(Some)
+                            .map_err(serde::de::Error::custom),
+                        None => Ok(None),
+                    }
+                }
+            }
+        }
+    };
+}
+
+edm_helper!(
+    /// Sends an `i64` as an `Edm.Int64`, rather than a number that is
+    /// stored as an `Edm.Double` or rejected.
+    int64,
+    Int64(i64),
+    INT64
+);
+edm_helper!(
+    /// Sends an `f64` as an `Edm.Double`, including whole numbers and values
+    /// that JSON can't represent.
+    double,
+    Double(f64),
+    DOUBLE
+);
+edm_helper!(
+    /// Sends an `OffsetDateTime` as an `Edm.DateTime`.
+    datetime,
+    DateTime(OffsetDateTime),
+    DATETIME
+);
+edm_helper!(
+    /// Sends a `Uuid` as an `Edm.Guid`.
+    guid,
+    Guid(Uuid),
+    GUID
+);
+edm_helper!(
+    /// Sends bytes as an `Edm.Binary`.
+    binary,
+    Binary(Vec<u8>),
+    BINARY
+);
+
+/// Serialize an entity, replacing the values marked by the helpers with the
+/// value and its type annotation, and leaving out the metadata the service
+/// sets.
+pub(crate) fn entity_json<E: Serialize>(entity: &E) -> azure_core::Result<Bytes> {
+    let value = {
+        let _marking = MarkingGuard::new();
+        serde_json::to_value(entity)
+            .context(ErrorKind::DataConversion, "failed to serialize the entity")?
+    };
+    let value = match value {
+        Json::Object(properties) => {
+            let mut body = Map::with_capacity(properties.len());
+            for (name, value) in properties {
+                if is_metadata(&name) {
+                    continue;
+                }
+                match value {
+                    Json::Object(mut marker) if marker.contains_key(MARKER_TYPE) => {
+                        let edm_type = marker.remove(MARKER_TYPE).unwrap_or(Json::Null);
+                        let value = marker.remove(MARKER_VALUE).unwrap_or(Json::Null);
+                        body.insert(format!("{name}{TYPE_ANNOTATION}"), edm_type);
+                        body.insert(name, value);
+                    }
+                    value => {
+                        body.insert(name, unmark(value));
+                    }
+                }
+            }
+            Json::Object(body)
+        }
+        value => value,
+    };
+    azure_core::to_json(&value)
+}
+
+/// Replace the values marked below the top level, which are not annotated, by
+/// their value.
+fn unmark(value: Json) -> Json {
+    match value {
+        Json::Object(mut marker) if marker.contains_key(MARKER_TYPE) => {
+            marker.remove(MARKER_VALUE).unwrap_or(Json::Null)
+        }
+        Json::Object(object) => Json::Object(
+            object
+                .into_iter()
+                .map(|(name, value)| (name, unmark(value)))
+                .collect(),
+        ),
+        Json::Array(values) => Json::Array(values.into_iter().map(unmark).collect()),
+        value => value,
+    }
+}
+
+fn is_metadata(name: &str) -> bool {
+    name.starts_with("odata.") || name == "Timestamp" || name == "Timestamp@odata.type"
+}
@@ -1,19 +1,133 @@
-use azure_core::{AppendToUrlQuery, Url};
-use std::borrow::Cow;
+//! OData `$filter` expressions for table queries.
+//!
+//! A [`Filter`] can be built from a raw OData string, or from the comparisons
+//! of a [`Property`] with typed values, which are formatted and escaped as
+//! OData literals:
+//!
+//! ```
+//! use azure_data_tables::{Filter, Property};
+//!
+//! let name = "O'Brien";
+//! let filter = Filter::partition_key("customers")
+//!     .and(Property::new("Name").equal(name))
+//!     .and(Property::new("Orders").greater_than(10i64));
+//! assert_eq!(
+//!     filter.as_str(),
+//!     "(PartitionKey eq 'customers') and (Name eq 'O''Brien') and (Orders gt 10L)"
+//! );
+//! ```
+//!
+//! ref: <https://docs.microsoft.com/rest/api/storageservices/querying-tables-and-entities>
 
+use crate::EdmValue;
+use azure_core::{
+    date,
+    error::{Error, ErrorKind},
+    AppendToUrlQuery, Url,
+};
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use std::{borrow::Cow, fmt, ops::Not};
+use time::{OffsetDateTime, UtcOffset};
+use uuid::Uuid;
+
+/// An OData `$filter` expression.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Filter(Cow<'static, str>);
+pub struct Filter {
+    expression: Cow<'static, str>,
+    /// The operator joining the top level operands of the expression, if
+    /// built by `and` or `or`.
+    chain: Option<&'static str>,
+}
 
 impl Filter {
+    /// A filter from a raw OData expression, which is sent as is.
+    ///
+    /// Prefer comparing a [`Property`] with a value to build a filter from
+    /// user input, as the value is escaped.
     pub fn new(s: impl Into<Cow<'static, str>>) -> Self {
-        Self(s.into())
+        Self {
+            expression: s.into(),
+            chain: None,
+        }
+    }
+
+    /// Entities with the given partition key.
+    pub fn partition_key(partition_key: impl Into<String>) -> Self {
+        Property::partition_key().equal(partition_key.into())
+    }
+
+    /// Entities with a partition key from `start`, inclusive, to `end`, exclusive.
+    pub fn partition_key_range(start: impl Into<String>, end: impl Into<String>) -> Self {
+        Property::partition_key().between(start.into(), end.into())
+    }
+
+    /// Entities with a partition key starting with `prefix`.
+    pub fn partition_key_prefix(prefix: impl Into<String>) -> Self {
+        Property::partition_key().starts_with(prefix)
+    }
+
+    /// Entities with a row key from `start`, inclusive, to `end`, exclusive.
+    pub fn row_key_range(start: impl Into<String>, end: impl Into<String>) -> Self {
+        Property::row_key().between(start.into(), end.into())
+    }
+
+    /// Entities with a row key starting with `prefix`.
+    pub fn row_key_prefix(prefix: impl Into<String>) -> Self {
+        Property::row_key().starts_with(prefix)
+    }
+
+    /// Entities matching both this filter and `other`.
+    #[must_use]
+    pub fn and(self, other: impl Into<Filter>) -> Self {
+        self.join("and", other.into())
+    }
+
+    /// Entities matching this filter, `other` or both.
+    #[must_use]
+    pub fn or(self, other: impl Into<Filter>) -> Self {
+        self.join("or", other.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.expression.as_ref()
+    }
+
+    fn join(self, operator: &'static str, other: Filter) -> Self {
+        // operands of a chain of the same operator don't need parentheses
+        let operand = |filter: Filter| {
+            if filter.chain == Some(operator) {
+                filter.expression.into_owned()
+            } else {
+                format!("({})", filter.expression)
+            }
+        };
+        let expression = format!("{} {operator} {}", operand(self), operand(other));
+        Self {
+            expression: expression.into(),
+            chain: Some(operator),
+        }
+    }
+}
+
+impl Not for Filter {
+    type Output = Filter;
+
+    /// Entities not matching this filter.
+    fn not(self) -> Self::Output {
+        Self::new(format!("not ({})", self.expression))
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.expression)
     }
 }
 
 impl AppendToUrlQuery for Filter {
     fn append_to_url_query(&self, url: &mut Url) {
         url.query_pairs_mut()
-            .append_pair("$filter", self.0.as_ref());
+            .append_pair("$filter", self.expression.as_ref());
     }
 }
 
@@ -25,3 +139,408 @@ where
         Self::new(s)
     }
 }
+
+/// A property of the entities, to compare with values in a [`Filter`] or to
+/// [`Select`](crate::Select).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property(Cow<'static, str>);
+
+impl Property {
+    /// The property with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a valid property name. Use [`Property::try_new`]
+    /// for names from user input.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        match Self::try_new(name) {
+            Ok(property) => property,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// The property with the given name, if it is a valid property name: a
+    /// letter or underscore followed by letters, digits and underscores.
+    pub fn try_new(name: impl Into<Cow<'static, str>>) -> azure_core::Result<Self> {
+        let name = name.into();
+        let mut chars = name.chars();
+        let valid = chars
+            .next()
+            .map_or(false, |c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+            && name.len() <= 255;
+        if !valid {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid property name '{name}'")
+            }));
+        }
+        Ok(Self(name))
+    }
+
+    /// The property with the given name, if entities of type `E` have it. The
+    /// fields of `E` are read from its `Deserialize` implementation, as
+    /// derived for a struct, so renamed fields are checked by their property
+    /// name.
+    pub fn of<E>(name: impl Into<Cow<'static, str>>) -> azure_core::Result<Self>
+    where
+        E: DeserializeOwned,
+    {
+        let property = Self::try_new(name)?;
+        let fields = struct_fields::<E>().ok_or_else(|| {
+            Error::message(
+                ErrorKind::DataConversion,
+                "the entity type doesn't deserialize from a struct",
+            )
+        })?;
+        let system_property = matches!(property.name(), "PartitionKey" | "RowKey" | "Timestamp");
+        if !system_property && !fields.contains(&property.name()) {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("the entity type has no property '{}'", property.name())
+            }));
+        }
+        Ok(property)
+    }
+
+    pub fn partition_key() -> Self {
+        Self("PartitionKey".into())
+    }
+
+    pub fn row_key() -> Self {
+        Self("RowKey".into())
+    }
+
+    pub fn timestamp() -> Self {
+        Self("Timestamp".into())
+    }
+
+    pub fn name(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    pub fn equal(&self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("eq", value.into())
+    }
+
+    pub fn not_equal(&self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("ne", value.into())
+    }
+
+    pub fn greater_than(&self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("gt", value.into())
+    }
+
+    pub fn greater_or_equal(&self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("ge", value.into())
+    }
+
+    pub fn less_than(&self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("lt", value.into())
+    }
+
+    pub fn less_or_equal(&self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("le", value.into())
+    }
+
+    /// Values from `start`, inclusive, to `end`, exclusive.
+    pub fn between(&self, start: impl Into<FilterValue>, end: impl Into<FilterValue>) -> Filter {
+        self.greater_or_equal(start).and(self.less_than(end))
+    }
+
+    /// String values starting with `prefix`.
+    pub fn starts_with(&self, prefix: impl Into<String>) -> Filter {
+        let prefix = prefix.into();
+        match prefix_end(&prefix) {
+            Some(end) => self.between(prefix, end),
+            None => self.greater_or_equal(prefix),
+        }
+    }
+
+    fn compare(&self, operator: &str, value: FilterValue) -> Filter {
+        Filter::new(format!("{} {operator} {value}", self.0))
+    }
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The first string after all the strings starting with `prefix`, if any.
+fn prefix_end(prefix: &str) -> Option<String> {
+    let mut chars = prefix.chars().collect::<Vec<_>>();
+    while let Some(last) = chars.pop() {
+        // skip the surrogate range, which `char` can't represent
+        let next = match last as u32 + 1 {
+            0xD800 => Some('\u{E000}'),
+            next => char::from_u32(next),
+        };
+        if let Some(next) = next {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
+/// A typed value, compared with a property as an OData literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    String(String),
+    Bool(bool),
+    Int32(i32),
+    Int64(i64),
+    Double(f64),
+    DateTime(OffsetDateTime),
+    Guid(Uuid),
+    Binary(Vec<u8>),
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::String(value) => write!(f, "'{}'", value.replace('\'', "''")),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Int32(value) => write!(f, "{value}"),
+            Self::Int64(value) => write!(f, "{value}L"),
+            // the debug format always has a decimal point or an exponent
+            Self::Double(value) if value.is_finite() => write!(f, "{value:?}"),
+            Self::Double(value) if value.is_nan() => write!(f, "NaN"),
+            Self::Double(value) if value.is_sign_positive() => write!(f, "INF"),
+            Self::Double(_) => write!(f, "-INF"),
+            Self::DateTime(value) => write!(
+                f,
+                "datetime'{}'",
+                date::to_rfc3339(&value.to_offset(UtcOffset::UTC))
+            ),
+            Self::Guid(value) => write!(f, "guid'{}'", value.hyphenated()),
+            Self::Binary(value) => {
+                write!(f, "X'")?;
+                for byte in value {
+                    write!(f, "{byte:02x}")?;
+                }
+                write!(f, "'")
+            }
+        }
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<bool> for FilterValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for FilterValue {
+    fn from(value: i32) -> Self {
+        Self::Int32(value)
+    }
+}
+
+impl From<i64> for FilterValue {
+    fn from(value: i64) -> Self {
+        Self::Int64(value)
+    }
+}
+
+impl From<f64> for FilterValue {
+    fn from(value: f64) -> Self {
+        Self::Double(value)
+    }
+}
+
+impl From<OffsetDateTime> for FilterValue {
+    fn from(value: OffsetDateTime) -> Self {
+        Self::DateTime(value)
+    }
+}
+
+impl From<Uuid> for FilterValue {
+    fn from(value: Uuid) -> Self {
+        Self::Guid(value)
+    }
+}
+
+impl From<Vec<u8>> for FilterValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Binary(value)
+    }
+}
+
+impl From<&[u8]> for FilterValue {
+    fn from(value: &[u8]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl From<EdmValue> for FilterValue {
+    fn from(value: EdmValue) -> Self {
+        match value {
+            EdmValue::String(value) => Self::String(value),
+            EdmValue::Boolean(value) => Self::Bool(value),
+            EdmValue::Int32(value) => Self::Int32(value),
+            EdmValue::Int64(value) => Self::Int64(value),
+            EdmValue::Double(value) => Self::Double(value),
+            EdmValue::DateTime(value) => Self::DateTime(value),
+            EdmValue::Guid(value) => Self::Guid(value),
+            EdmValue::Binary(value) => Self::Binary(value),
+        }
+    }
+}
+
+/// The fields of `E`, if it deserializes from a struct.
+fn struct_fields<E: DeserializeOwned>() -> Option<&'static [&'static str]> {
+    let mut fields = None;
+    let _ = E::deserialize(StructFields(&mut fields));
+    fields
+}
+
+/// A deserializer recording the fields of the struct it is asked for, and
+/// failing.
+struct StructFields<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de, 'a> Deserializer<'de> for StructFields<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Order {
+        #[serde(rename = "PartitionKey")]
+        customer: String,
+        #[serde(rename = "RowKey")]
+        id: String,
+        #[serde(rename = "Quantity")]
+        quantity: u32,
+    }
+
+    #[test]
+    fn literals_are_typed_and_escaped() {
+        let property = Property::new("Value");
+        let cases: Vec<(FilterValue, &str)> = vec![
+            ("it's".into(), "Value eq 'it''s'"),
+            (
+                "' or 1 eq 1 or ''='".into(),
+                "Value eq ''' or 1 eq 1 or ''''='''",
+            ),
+            (true.into(), "Value eq true"),
+            (42i32.into(), "Value eq 42"),
+            (42i64.into(), "Value eq 42L"),
+            (42f64.into(), "Value eq 42.0"),
+            (0.5f64.into(), "Value eq 0.5"),
+            (
+                time::macros::datetime!(2024-03-01 12:30 +02:00).into(),
+                "Value eq datetime'2024-03-01T10:30:00Z'",
+            ),
+            (
+                Uuid::from_u128(0x1234).into(),
+                "Value eq guid'00000000-0000-0000-0000-000000001234'",
+            ),
+            (vec![0u8, 0xab, 0x10].into(), "Value eq X'00ab10'"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(property.equal(value).as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn operators() {
+        let quantity = Property::new("Quantity");
+        let filter = quantity
+            .greater_than(1)
+            .and(quantity.less_or_equal(10))
+            .and(quantity.not_equal(5))
+            .or(!Filter::partition_key("a").or(Filter::partition_key("b")));
+        assert_eq!(
+            filter.as_str(),
+            "((Quantity gt 1) and (Quantity le 10) and (Quantity ne 5)) or (not ((PartitionKey eq 'a') or (PartitionKey eq 'b')))"
+        );
+        assert_eq!(
+            Filter::new("a eq 1").or("b eq 2").or("c eq 3").as_str(),
+            "(a eq 1) or (b eq 2) or (c eq 3)"
+        );
+        // a raw expression is always parenthesized
+        assert_eq!(
+            Filter::new("a eq 1 or b eq 2").and("c eq 3").as_str(),
+            "(a eq 1 or b eq 2) and (c eq 3)"
+        );
+    }
+
+    #[test]
+    fn key_ranges() {
+        assert_eq!(
+            Filter::partition_key_range("2024-01", "2024-04").as_str(),
+            "(PartitionKey ge '2024-01') and (PartitionKey lt '2024-04')"
+        );
+        assert_eq!(
+            Filter::row_key_prefix("order-").as_str(),
+            "(RowKey ge 'order-') and (RowKey lt 'order.')"
+        );
+        assert_eq!(
+            Filter::partition_key("p")
+                .and(Filter::row_key_prefix("az"))
+                .as_str(),
+            "(PartitionKey eq 'p') and (RowKey ge 'az') and (RowKey lt 'a{')"
+        );
+        assert_eq!(prefix_end("a\u{10FFFF}").as_deref(), Some("b"));
+        assert_eq!(prefix_end("\u{D7FF}").as_deref(), Some("\u{E000}"));
+        assert_eq!(prefix_end(""), None);
+    }
+
+    #[test]
+    fn property_names() {
+        assert!(Property::try_new("Quantity_2").is_ok());
+        assert!(Property::try_new("_private").is_ok());
+        assert!(Property::try_new("").is_err());
+        assert!(Property::try_new("2nd").is_err());
+        assert!(Property::try_new("Name eq 'x' or Name").is_err());
+    }
+
+    #[test]
+    fn properties_of_entity() {
+        assert!(Property::of::<Order>("Quantity").is_ok());
+        assert!(Property::of::<Order>("PartitionKey").is_ok());
+        assert!(Property::of::<Order>("Timestamp").is_ok());
+        // the property is renamed
+        assert!(Property::of::<Order>("quantity").is_err());
+        assert!(Property::of::<Order>("Price").is_err());
+        assert!(Property::of::<String>("Quantity").is_err());
+    }
+}
@@ -66,7 +66,9 @@ async fn main() -> azure_core::Result<()> {
 #[macro_use]
 extern crate azure_core;
 
+mod bulk_writer;
 pub mod clients;
+pub mod edm;
 mod entity_metadata;
 mod entity_with_metadata;
 mod filter;
@@ -76,14 +78,22 @@ pub mod operations;
 pub mod prelude;
 mod return_entity;
 mod select;
+mod table_entity;
+mod table_service_properties;
 mod top;
 mod transaction;
 mod transaction_operation;
+pub use bulk_writer::{BulkWriteOutcome, BulkWriter};
+pub use edm::EdmValue;
 pub use entity_metadata::EntityMetadata;
 pub use entity_with_metadata::EntityWithMetadata;
-pub use filter::Filter;
+pub use filter::{Filter, FilterValue, Property};
 pub use if_match_condition::IfMatchCondition;
 pub use model::Table;
 pub use return_entity::ReturnEntity;
 pub use select::Select;
+pub use table_entity::TableEntity;
+pub use table_service_properties::{
+    Cors, CorsRule, Logging, Metrics, RetentionPolicy, TableServiceProperties,
+};
 pub use top::Top;
@@ -0,0 +1,43 @@
+use crate::prelude::*;
+use azure_core::{headers::Headers, Method, Response as AzureResponse};
+use azure_storage::{headers::CommonStorageResponseHeaders, StoredAccessPolicyList};
+
+operation! {
+    GetTableACL,
+    client: TableClient,
+}
+
+impl GetTableACLBuilder {
+    pub fn into_future(mut self) -> GetTableACL {
+        Box::pin(async move {
+            let mut url = self.client.table_url()?;
+
+            url.query_pairs_mut().append_pair("comp", "acl");
+
+            let mut request =
+                TableClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetTableACLResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetTableACLResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub stored_access_policy_list: StoredAccessPolicyList,
+}
+
+impl GetTableACLResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+
+        Ok(GetTableACLResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            stored_access_policy_list: StoredAccessPolicyList::from_xml(&body)?,
+        })
+    }
+}
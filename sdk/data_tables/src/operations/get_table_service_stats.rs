@@ -0,0 +1,89 @@
+use crate::prelude::*;
+use azure_core::{
+    date,
+    error::{Error, ErrorKind, ResultExt},
+    headers::Headers,
+    Method, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+operation! {
+    GetTableServiceStats,
+    client: TableServiceClient,
+}
+
+impl GetTableServiceStatsBuilder {
+    /// The stats are only served by the secondary endpoint of an account with
+    /// read-access geo-redundant replication, which this request is sent to.
+    pub fn into_future(mut self) -> GetTableServiceStats {
+        Box::pin(async move {
+            if self.client.is_cosmos_endpoint() {
+                return Err(Error::message(
+                    ErrorKind::Other,
+                    "the Cosmos DB Table API has no service stats",
+                ));
+            }
+            let mut url = self.client.secondary_service_url()?;
+
+            url.query_pairs_mut().append_pair("restype", "service");
+            url.query_pairs_mut().append_pair("comp", "stats");
+
+            let mut request =
+                TableServiceClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            GetTableServiceStatsResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+    Live,
+    Bootstrap,
+    Unavailable,
+}
+
+#[derive(Debug, Clone)]
+pub struct GetTableServiceStatsResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub status: Status,
+    pub last_sync_time: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetTableServiceStatsResponseInternal {
+    pub geo_replication: GeoReplication,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GeoReplication {
+    pub status: Status,
+    pub last_sync_time: Option<String>,
+}
+
+impl GetTableServiceStatsResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: GetTableServiceStatsResponseInternal = body.xml().await?;
+
+        Ok(GetTableServiceStatsResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            status: response.geo_replication.status,
+            last_sync_time: response
+                .geo_replication
+                .last_sync_time
+                .map(|t| {
+                    date::parse_rfc1123(&t)
+                        .context(ErrorKind::DataConversion, "failed to parse last sync time")
+                })
+                .transpose()?,
+        })
+    }
+}
@@ -2,28 +2,44 @@ mod create_table;
 mod delete_entity;
 mod delete_table;
 mod get_entity;
+mod get_table_acl;
+mod get_table_service_properties;
+mod get_table_service_stats;
 mod insert_entity;
 pub(crate) mod insert_or_replace_or_merge_entity;
 mod list_tables;
 mod query_entity;
+mod set_table_acl;
+mod set_table_service_properties;
 mod transaction;
 pub(crate) mod update_or_merge_entity;
 pub use create_table::CreateTableBuilder;
 pub use delete_entity::DeleteEntityBuilder;
 pub use delete_table::DeleteTableBuilder;
 pub use get_entity::GetEntityBuilder;
+pub use get_table_acl::{GetTableACLBuilder, GetTableACLResponse};
+pub use get_table_service_properties::{
+    GetTableServicePropertiesBuilder, GetTableServicePropertiesResponse,
+};
+pub use get_table_service_stats::{
+    GetTableServiceStatsBuilder, GetTableServiceStatsResponse, Status,
+};
 pub use insert_entity::InsertEntityBuilder;
 pub use insert_or_replace_or_merge_entity::InsertOrReplaceOrMergeEntityBuilder;
 pub use list_tables::ListTablesBuilder;
 pub use query_entity::{QueryEntityBuilder, QueryEntityResponse};
-pub use transaction::TransactionBuilder;
+pub use set_table_acl::{SetTableACLBuilder, SetTableACLResponse};
+pub use set_table_service_properties::{
+    SetTableServicePropertiesBuilder, SetTableServicePropertiesResponse,
+};
+pub use transaction::{OperationError, OperationResponse, TransactionBuilder, TransactionResponse};
 pub use update_or_merge_entity::UpdateOrMergeEntityBuilder;
 
 use crate::EntityWithMetadata;
 use azure_core::{
     error::{Error, ErrorKind},
     headers::{self, etag_from_headers, HeaderName},
-    CollectedResponse, Etag, Url,
+    CollectedResponse, Etag, StatusCode, Url,
 };
 use azure_storage::headers::CommonStorageResponseHeaders;
 use serde::de::DeserializeOwned;
@@ -76,17 +92,24 @@ where
 
     fn try_from(response: CollectedResponse) -> azure_core::Result<Self> {
         let headers = response.headers();
-        let entity_with_metadata =
-            match headers.get_str(&HeaderName::from_static("preference-applied"))? {
-                "return-no-content" => None,
-                "return-content" => Some(response.clone().try_into()?),
-                _ => {
-                    return Err(Error::message(
-                        ErrorKind::DataConversion,
-                        "Unexpected value for preference-applied header",
-                    ))
-                }
-            };
+        // the Cosmos DB Table API does not say which preference it applied,
+        // but only returns the entity with a `201 Created`
+        let preference_applied = headers
+            .get_optional_str(&HeaderName::from_static("preference-applied"))
+            .unwrap_or(match response.status() {
+                StatusCode::NoContent => "return-no-content",
+                _ => "return-content",
+            });
+        let entity_with_metadata = match preference_applied {
+            "return-no-content" => None,
+            "return-content" => Some(response.clone().try_into()?),
+            _ => {
+                return Err(Error::message(
+                    ErrorKind::DataConversion,
+                    "Unexpected value for preference-applied header",
+                ))
+            }
+        };
 
         Ok(InsertEntityResponse {
             common_storage_response_headers: headers.try_into()?,
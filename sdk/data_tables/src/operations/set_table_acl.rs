@@ -0,0 +1,47 @@
+use crate::prelude::*;
+use azure_core::{error::Error, headers::Headers, Method, Response as AzureResponse};
+use azure_storage::{headers::CommonStorageResponseHeaders, StoredAccessPolicyList};
+
+operation! {
+    SetTableACL,
+    client: TableClient,
+    stored_access_policy_list: StoredAccessPolicyList,
+}
+
+impl SetTableACLBuilder {
+    pub fn into_future(mut self) -> SetTableACL {
+        Box::pin(async move {
+            let mut url = self.client.table_url()?;
+
+            url.query_pairs_mut().append_pair("comp", "acl");
+
+            let xml_body = self.stored_access_policy_list.to_xml();
+
+            let mut request = TableClient::finalize_request(
+                url,
+                Method::Put,
+                Headers::new(),
+                Some(xml_body.into()),
+            )?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetTableACLResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
+
+impl TryFrom<AzureResponse> for SetTableACLResponse {
+    type Error = Error;
+
+    fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        Ok(SetTableACLResponse {
+            common_storage_response_headers: response.headers().try_into()?,
+        })
+    }
+}
@@ -6,10 +6,10 @@ use azure_core::{
     error::{Error, ErrorKind},
     headers::*,
     prelude::*,
-    CollectedResponse, Etag, Method, Request, StatusCode, Url,
+    CollectedResponse, Etag, Method, StatusCode, Url,
 };
 use azure_storage::headers::CommonStorageResponseHeaders;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tracing::trace;
 
 operation! {
@@ -24,19 +24,8 @@ impl TransactionBuilder {
     ///
     /// ref: <https://docs.microsoft.com/en-us/rest/api/storageservices/insert-entity>
     pub fn insert<E: Serialize>(mut self, entity: E) -> azure_core::Result<Self> {
-        let mut url = self.client.table_client().url()?;
-        url.path_segments_mut()
-            .map_err(|()| Error::message(ErrorKind::Other, "invalid table URL"))?
-            .pop()
-            .push(self.client.table_client().table_name());
-
-        let mut request = Request::new(url, Method::Post);
-        request.insert_header(ACCEPT, "application/json;odata=fullmetadata");
-        request.insert_headers(&ContentType::APPLICATION_JSON);
-        request.set_json(&entity)?;
-
-        self.transaction.add(TransactionOperation::new(request));
-
+        let operation = TransactionOperation::insert(&self.client, entity)?;
+        self.transaction.add(operation);
         Ok(self)
     }
 
@@ -102,16 +91,8 @@ impl TransactionBuilder {
         match_condition: Option<IfMatchCondition>,
     ) -> azure_core::Result<Self> {
         let entity_client = self.client.entity_client(row_key);
-        let url = entity_client.url()?;
-
-        let mut request = Request::new(url, Method::Delete);
-        request.insert_header(ACCEPT, "application/json;odata=minimalmetadata");
-
-        let match_condition = match_condition.unwrap_or(IfMatchCondition::Any);
-        request.add_mandatory_header(&match_condition);
-        request.set_body("");
-
-        self.transaction.add(TransactionOperation::new(request));
+        let operation = TransactionOperation::delete(&entity_client, match_condition)?;
+        self.transaction.add(operation);
         Ok(self)
     }
 
@@ -154,15 +135,9 @@ impl TransactionBuilder {
         match_condition: Option<IfMatchCondition>,
     ) -> azure_core::Result<Self> {
         let entity_client = self.client.entity_client(row_key);
-        let url = entity_client.url()?;
-
-        let mut request = Request::new(url, method);
-        request.insert_header(ACCEPT, "application/json;odata=fullmetadata");
-        request.insert_headers(&ContentType::APPLICATION_JSON);
-        request.set_json(&entity)?;
-        request.add_optional_header(&match_condition);
-
-        self.transaction.add(TransactionOperation::new(request));
+        let operation =
+            TransactionOperation::entity(&entity_client, entity, method, match_condition)?;
+        self.transaction.add(operation);
         Ok(self)
     }
 }
@@ -173,6 +148,7 @@ pub struct OperationResponse {
     pub location: Option<Url>,
     pub data_service_id: Option<String>,
     pub etag: Option<Etag>,
+    pub error: Option<OperationError>,
 }
 
 impl Default for OperationResponse {
@@ -182,7 +158,53 @@ impl Default for OperationResponse {
             location: None,
             data_service_id: None,
             etag: None,
+            error: None,
+        }
+    }
+}
+
+/// The error that failed a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationError {
+    /// The index of the failed operation in the transaction, if the service
+    /// reported it.
+    pub index: Option<usize>,
+    pub code: String,
+    pub message: String,
+}
+
+impl OperationError {
+    /// Parse the `odata.error` body of a failed operation. The service
+    /// prefixes the message with the index of the operation, e.g.
+    /// `1:The specified entity already exists.`
+    fn from_body(body: &str) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Body {
+            #[serde(rename = "odata.error")]
+            error: ErrorBody,
         }
+        #[derive(Deserialize)]
+        struct ErrorBody {
+            code: String,
+            message: Message,
+        }
+        #[derive(Deserialize)]
+        struct Message {
+            value: String,
+        }
+
+        let error = serde_json::from_str::<Body>(body).ok()?.error;
+        let (index, message) = match error.message.value.split_once(':') {
+            Some((index, message)) if index.parse::<usize>().is_ok() => {
+                (index.parse().ok(), message.to_owned())
+            }
+            _ => (None, error.message.value),
+        };
+        Some(Self {
+            index,
+            code: error.code,
+            message,
+        })
     }
 }
 
@@ -192,6 +214,16 @@ pub struct TransactionResponse {
     pub operation_responses: Vec<OperationResponse>,
 }
 
+impl TransactionResponse {
+    /// The error that failed the transaction, in which case none of its
+    /// operations were applied.
+    pub fn error(&self) -> Option<&OperationError> {
+        self.operation_responses
+            .iter()
+            .find_map(|response| response.error.as_ref())
+    }
+}
+
 impl TryFrom<CollectedResponse> for TransactionResponse {
     type Error = Error;
 
@@ -256,6 +288,8 @@ impl TryFrom<CollectedResponse> for TransactionResponse {
                             .ok_or_else(|| Error::message(ErrorKind::Other, "invalid ETag header"))?
                             .into(),
                     );
+                } else if line.starts_with('{') && !operation_response.status_code.is_success() {
+                    operation_response.error = OperationError::from_body(line);
                 }
             }
 
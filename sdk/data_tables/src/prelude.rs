@@ -1,4 +1,5 @@
 pub use crate::{
     clients::{EntityClient, PartitionKeyClient, TableClient, TableServiceClient},
-    Filter, IfMatchCondition, ReturnEntity, Select, Table, Top,
+    EdmValue, Filter, FilterValue, IfMatchCondition, Property, ReturnEntity, Select, Table,
+    TableEntity, Top,
 };
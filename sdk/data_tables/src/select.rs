@@ -1,3 +1,4 @@
+use crate::Property;
 use azure_core::{AppendToUrlQuery, Url};
 use std::borrow::Cow;
 
@@ -8,6 +9,18 @@ impl Select {
     pub fn new(s: impl Into<Cow<'static, str>>) -> Self {
         Self(s.into())
     }
+
+    /// Select only the given properties of the entities.
+    pub fn properties<I>(properties: I) -> Self
+    where
+        I: IntoIterator<Item = Property>,
+    {
+        let properties = properties
+            .into_iter()
+            .map(|property| property.name().to_owned())
+            .collect::<Vec<_>>();
+        Self(properties.join(",").into())
+    }
 }
 
 impl AppendToUrlQuery for Select {
@@ -0,0 +1,334 @@
+use crate::edm::{EdmValue, DATETIME, TYPE_ANNOTATION};
+use azure_core::{date::parse_rfc3339, Etag};
+use serde::{
+    de::{self, Deserializer},
+    ser::{SerializeMap, Serializer},
+    Deserialize, Serialize,
+};
+use serde_json::{Map, Value as Json};
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
+
+const PARTITION_KEY: &str = "PartitionKey";
+const ROW_KEY: &str = "RowKey";
+const TIMESTAMP: &str = "Timestamp";
+const ETAG: &str = "odata.etag";
+
+/// An entity whose properties are only known at runtime.
+///
+/// The type of every property is kept, so an entity read from a table and
+/// written back keeps its `Edm.Int64`, `Edm.Guid`, `Edm.DateTime` and
+/// `Edm.Binary` properties. The `odata.etag` and `Timestamp` of an entity read
+/// from a table are kept too, and serialized along with it, but never sent to
+/// the service: use the etag as an `IfMatchCondition` instead.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableEntity {
+    pub partition_key: String,
+    pub row_key: String,
+    pub timestamp: Option<OffsetDateTime>,
+    pub etag: Option<Etag>,
+    pub properties: BTreeMap<String, EdmValue>,
+}
+
+impl TableEntity {
+    pub fn new<PK: Into<String>, RK: Into<String>>(partition_key: PK, row_key: RK) -> Self {
+        Self {
+            partition_key: partition_key.into(),
+            row_key: row_key.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set a property, returning the entity.
+    pub fn with<N: Into<String>, V: Into<EdmValue>>(mut self, name: N, value: V) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Set a property, returning its previous value.
+    pub fn insert<N: Into<String>, V: Into<EdmValue>>(
+        &mut self,
+        name: N,
+        value: V,
+    ) -> Option<EdmValue> {
+        self.properties.insert(name.into(), value.into())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EdmValue> {
+        self.properties.get(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<EdmValue> {
+        self.properties.remove(name)
+    }
+}
+
+impl Serialize for TableEntity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry(PARTITION_KEY, &self.partition_key)?;
+        map.serialize_entry(ROW_KEY, &self.row_key)?;
+        if let Some(etag) = &self.etag {
+            map.serialize_entry(ETAG, etag)?;
+        }
+        if let Some(timestamp) = self.timestamp {
+            let timestamp = EdmValue::DateTime(timestamp);
+            map.serialize_entry(&format!("{TIMESTAMP}{TYPE_ANNOTATION}"), DATETIME)?;
+            map.serialize_entry(TIMESTAMP, &timestamp.to_json())?;
+        }
+        for (name, value) in &self.properties {
+            if let Some(edm_type) = value.annotation() {
+                map.serialize_entry(&format!("{name}{TYPE_ANNOTATION}"), edm_type)?;
+            }
+            map.serialize_entry(name, &value.to_json())?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TableEntity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut properties = Map::<String, Json>::deserialize(deserializer)?;
+        let mut annotations = BTreeMap::new();
+        properties.retain(|name, value| match name.strip_suffix(TYPE_ANNOTATION) {
+            Some(property) => {
+                if let Json::String(edm_type) = value {
+                    annotations.insert(property.to_owned(), std::mem::take(edm_type));
+                }
+                false
+            }
+            None => true,
+        });
+
+        let mut entity = TableEntity::default();
+        for (name, value) in properties {
+            match name.as_str() {
+                PARTITION_KEY | ROW_KEY => {
+                    let Json::String(key) = value else {
+                        return Err(de::Error::custom(format!("{name} must be a string")));
+                    };
+                    if name == PARTITION_KEY {
+                        entity.partition_key = key;
+                    } else {
+                        entity.row_key = key;
+                    }
+                }
+                ETAG => {
+                    if let Json::String(etag) = value {
+                        entity.etag = Some(etag.into());
+                    }
+                }
+                TIMESTAMP => {
+                    if let Json::String(timestamp) = value {
+                        entity.timestamp =
+                            Some(parse_rfc3339(&timestamp).map_err(de::Error::custom)?);
+                    }
+                }
+                // the rest of the metadata is about the table, not the entity
+                _ if name.starts_with("odata.") => {}
+                _ => {
+                    let value =
+                        EdmValue::from_json(value, annotations.get(&name).map(String::as_str))
+                            .map_err(de::Error::custom)?;
+                    entity.properties.insert(name, value);
+                }
+            }
+        }
+        Ok(entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edm;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn serializes_annotations() {
+        let entity = TableEntity::new("pk", "rk")
+            .with("name", "Carl")
+            .with("active", true)
+            .with("age", 42i32)
+            .with("visits", 5_000_000_000i64)
+            .with("score", 1.5)
+            .with("ratio", 2.0)
+            .with("infinite", f64::INFINITY)
+            .with("born", parse_rfc3339("1990-05-01T10:30:00+02:00").unwrap())
+            .with("id", Uuid::nil())
+            .with("avatar", vec![1u8, 2, 3]);
+        assert_eq!(
+            serde_json::to_value(entity).unwrap(),
+            json!({
+                "PartitionKey": "pk",
+                "RowKey": "rk",
+                "name": "Carl",
+                "active": true,
+                "age": 42,
+                "visits@odata.type": "Edm.Int64",
+                "visits": "5000000000",
+                "score": 1.5,
+                "ratio@odata.type": "Edm.Double",
+                "ratio": 2.0,
+                "infinite@odata.type": "Edm.Double",
+                "infinite": "Infinity",
+                "born@odata.type": "Edm.DateTime",
+                "born": "1990-05-01T08:30:00Z",
+                "id@odata.type": "Edm.Guid",
+                "id": "00000000-0000-0000-0000-000000000000",
+                "avatar@odata.type": "Edm.Binary",
+                "avatar": "AQID",
+            })
+        );
+    }
+
+    #[test]
+    fn deserializes_annotations_and_metadata() {
+        let entity: TableEntity = serde_json::from_value(json!({
+            "odata.metadata": "https://account.table.core.windows.net/$metadata#people/@Element",
+            "odata.etag": "W/\"datetime'2024-01-01T00%3A00%3A00Z'\"",
+            "odata.id": "https://account.table.core.windows.net/people(PartitionKey='pk',RowKey='rk')",
+            "PartitionKey": "pk",
+            "RowKey": "rk",
+            "Timestamp@odata.type": "Edm.DateTime",
+            "Timestamp": "2024-01-01T00:00:00.1234567Z",
+            "name": "Carl",
+            "age": 42,
+            "big": 5_000_000_000i64,
+            "score": 1.5,
+            "visits@odata.type": "Edm.Int64",
+            "visits": "7",
+            "missing@odata.type": "Edm.Double",
+            "missing": "NaN",
+            "id@odata.type": "Edm.Guid",
+            "id": "00000000-0000-0000-0000-000000000000",
+        }))
+        .unwrap();
+        assert_eq!(entity.partition_key, "pk");
+        assert_eq!(entity.row_key, "rk");
+        assert_eq!(
+            entity.etag,
+            Some("W/\"datetime'2024-01-01T00%3A00%3A00Z'\"".into())
+        );
+        assert_eq!(
+            entity.timestamp,
+            Some(parse_rfc3339("2024-01-01T00:00:00.1234567Z").unwrap())
+        );
+        assert_eq!(entity.get("name"), Some(&EdmValue::String("Carl".into())));
+        assert_eq!(entity.get("age"), Some(&EdmValue::Int32(42)));
+        assert_eq!(entity.get("big"), Some(&EdmValue::Int64(5_000_000_000)));
+        assert_eq!(entity.get("score"), Some(&EdmValue::Double(1.5)));
+        assert_eq!(entity.get("visits"), Some(&EdmValue::Int64(7)));
+        assert!(entity.get("missing").unwrap().as_f64().unwrap().is_nan());
+        assert_eq!(entity.get("id"), Some(&EdmValue::Guid(Uuid::nil())));
+        assert_eq!(entity.properties.len(), 7);
+
+        let err = serde_json::from_value::<TableEntity>(json!({
+            "visits@odata.type": "Edm.Int64",
+            "visits": "seven",
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("Edm.Int64"));
+    }
+
+    #[test]
+    fn helpers_annotate_struct_fields() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Order {
+            #[serde(rename = "PartitionKey")]
+            customer: String,
+            #[serde(rename = "RowKey")]
+            id: String,
+            #[serde(with = "edm::int64")]
+            total: i64,
+            #[serde(with = "edm::binary")]
+            signature: Vec<u8>,
+            #[serde(default, with = "edm::datetime::option")]
+            shipped: Option<OffsetDateTime>,
+        }
+
+        let order = Order {
+            customer: "contoso".to_owned(),
+            id: "1".to_owned(),
+            total: 12,
+            signature: vec![255],
+            shipped: None,
+        };
+        let body = edm::entity_json(&order).unwrap();
+        let body: Json = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "PartitionKey": "contoso",
+                "RowKey": "1",
+                "total@odata.type": "Edm.Int64",
+                "total": "12",
+                "signature@odata.type": "Edm.Binary",
+                "signature": "/w==",
+                "shipped": null,
+            })
+        );
+
+        // read back from the service, or as a plain JSON number
+        let read: Order = serde_json::from_value(body).unwrap();
+        assert_eq!(read, order);
+
+        // serialized any other way, the fields are plain values
+        let plain = serde_json::to_value(&order).unwrap();
+        assert_eq!(
+            plain,
+            json!({
+                "PartitionKey": "contoso",
+                "RowKey": "1",
+                "total": "12",
+                "signature": "/w==",
+                "shipped": null,
+            })
+        );
+        let read: Order = serde_json::from_value(plain).unwrap();
+        assert_eq!(read, order);
+
+        // and so are the fields of nested structs, which are not annotated
+        #[derive(Serialize)]
+        struct Shipment {
+            #[serde(rename = "PartitionKey")]
+            customer: String,
+            #[serde(rename = "RowKey")]
+            id: String,
+            order: Order,
+        }
+        let shipment = Shipment {
+            customer: "contoso".to_owned(),
+            id: "1".to_owned(),
+            order,
+        };
+        let body: Json = serde_json::from_slice(&edm::entity_json(&shipment).unwrap()).unwrap();
+        assert_eq!(
+            body["order"],
+            serde_json::to_value(&shipment.order).unwrap()
+        );
+        let read: Order = serde_json::from_value(json!({
+            "PartitionKey": "contoso",
+            "RowKey": "1",
+            "total": 12,
+            "signature": "/w==",
+            "shipped": "2024-01-01T00:00:00Z",
+        }))
+        .unwrap();
+        assert_eq!(read.total, 12);
+        assert_eq!(
+            read.shipped,
+            Some(parse_rfc3339("2024-01-01T00:00:00Z").unwrap())
+        );
+
+        // a dynamic entity is sent without the metadata set by the service
+        let mut entity = TableEntity::new("pk", "rk").with("visits", 7i64);
+        let body: Json = serde_json::from_slice(&edm::entity_json(&entity).unwrap()).unwrap();
+        assert_eq!(body, serde_json::to_value(&entity).unwrap());
+        entity.etag = Some("W/\"1\"".into());
+        entity.timestamp = Some(OffsetDateTime::UNIX_EPOCH);
+        let sent: Json = serde_json::from_slice(&edm::entity_json(&entity).unwrap()).unwrap();
+        assert_eq!(sent, body);
+    }
+}
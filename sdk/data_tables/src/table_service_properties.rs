@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// The logging, metrics and CORS properties of the Table service. Sections
+/// set to `None` are left out when setting the properties, and keep their
+/// current value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TableServiceProperties {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Logging>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hour_metrics: Option<Metrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minute_metrics: Option<Metrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cors: Option<Cors>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RetentionPolicy {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Logging {
+    pub version: String,
+    pub delete: bool,
+    pub read: bool,
+    pub write: bool,
+    pub retention_policy: RetentionPolicy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Metrics {
+    pub version: String,
+    pub enabled: bool,
+    #[serde(rename = "IncludeAPIs", skip_serializing_if = "Option::is_none")]
+    pub include_apis: Option<bool>,
+    pub retention_policy: RetentionPolicy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Cors {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cors_rule: Option<Vec<CorsRule>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CorsRule {
+    pub allowed_origins: String,
+    pub allowed_methods: String,
+    pub max_age_in_seconds: u64,
+    pub exposed_headers: String,
+    pub allowed_headers: String,
+}
@@ -1,4 +1,5 @@
 use crate::transaction_operation::TransactionOperation;
+use azure_core::error::{Error, ErrorKind};
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -36,33 +37,7 @@ impl TransactionOperations {
         s.push_str("\n\n");
 
         for transaction_operation in &self.transaction_operations {
-            s.push_str("--changeset_");
-            s.push_str(&self.change_set_uuid.hyphenated().to_string());
-            s.push_str("\nContent-Type: application/http\nContent-Transfer-Encoding: binary\n\n");
-
-            s.push_str(transaction_operation.request.method().as_ref());
-            s.push(' ');
-            s.push_str(transaction_operation.request.url().as_ref());
-            s.push_str(" HTTP/1.1\n");
-
-            for (header_name, header_value) in transaction_operation.request.headers().iter() {
-                s.push_str(header_name.as_str());
-                s.push_str(": ");
-                s.push_str(header_value.as_str());
-                s.push('\n');
-            }
-
-            s.push('\n');
-            match transaction_operation.request.body() {
-                azure_core::Body::Bytes(body) => {
-                    if !body.is_empty() {
-                        s.push_str(std::str::from_utf8(body)?);
-                        s.push('\n');
-                    }
-                }
-                #[cfg(not(target_arch = "wasm32"))]
-                azure_core::Body::SeekableStream(_) => todo!(),
-            }
+            write_operation(&mut s, &self.change_set_uuid, transaction_operation)?;
         }
 
         s.push_str("--changeset_");
@@ -74,3 +49,75 @@ impl TransactionOperations {
         Ok(s)
     }
 }
+
+/// The size of `transaction_operation` in the body of a transaction.
+pub(crate) fn encoded_len(
+    transaction_operation: &TransactionOperation,
+) -> azure_core::Result<usize> {
+    let mut s = String::new();
+    write_operation(&mut s, &Uuid::nil(), transaction_operation)?;
+    Ok(s.len())
+}
+
+fn write_operation(
+    s: &mut String,
+    change_set_uuid: &Uuid,
+    transaction_operation: &TransactionOperation,
+) -> azure_core::Result<()> {
+    s.push_str("--changeset_");
+    s.push_str(&change_set_uuid.hyphenated().to_string());
+    s.push_str("\nContent-Type: application/http\nContent-Transfer-Encoding: binary\n\n");
+
+    s.push_str(transaction_operation.request.method().as_ref());
+    s.push(' ');
+    s.push_str(transaction_operation.request.url().as_ref());
+    s.push_str(" HTTP/1.1\n");
+
+    for (header_name, header_value) in transaction_operation.request.headers().iter() {
+        s.push_str(header_name.as_str());
+        s.push_str(": ");
+        s.push_str(header_value.as_str());
+        s.push('\n');
+    }
+
+    s.push('\n');
+    match transaction_operation.request.body() {
+        azure_core::Body::Bytes(body) => {
+            if !body.is_empty() {
+                s.push_str(std::str::from_utf8(body)?);
+                s.push('\n');
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        azure_core::Body::SeekableStream(_) => {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "streaming bodies are not supported in a table transaction",
+            ))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use azure_core::{Body, BytesStream, Method, Request};
+
+    #[test]
+    fn streaming_bodies_are_rejected() -> azure_core::Result<()> {
+        let mut request = Request::new(
+            "https://account.table.core.windows.net/orders".parse()?,
+            Method::Post,
+        );
+        request.set_body(Body::SeekableStream(Box::new(BytesStream::new("{}"))));
+        let operation = TransactionOperation::new(request);
+
+        let err = encoded_len(&operation).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Other);
+        assert!(err
+            .to_string()
+            .contains("streaming bodies are not supported in a table transaction"));
+        Ok(())
+    }
+}
@@ -1,4 +1,11 @@
-use azure_core::Request;
+use crate::{edm::entity_json, prelude::*, IfMatchCondition};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::*,
+    prelude::ContentType,
+    Method, Request,
+};
+use serde::Serialize;
 
 #[derive(Debug, Clone)]
 pub struct TransactionOperation {
@@ -9,4 +16,51 @@ impl TransactionOperation {
     pub(crate) fn new(request: Request) -> Self {
         Self { request }
     }
+
+    pub(crate) fn insert<E: Serialize>(
+        client: &PartitionKeyClient,
+        entity: E,
+    ) -> azure_core::Result<Self> {
+        let mut url = client.table_client().url()?;
+        url.path_segments_mut()
+            .map_err(|()| Error::message(ErrorKind::Other, "invalid table URL"))?
+            .pop()
+            .push(client.table_client().table_name());
+
+        let mut request = Request::new(url, Method::Post);
+        request.insert_header(ACCEPT, "application/json;odata=fullmetadata");
+        request.insert_headers(&ContentType::APPLICATION_JSON);
+        request.set_body(entity_json(&entity)?);
+
+        Ok(Self::new(request))
+    }
+
+    pub(crate) fn entity<E: Serialize>(
+        client: &EntityClient,
+        entity: E,
+        method: Method,
+        match_condition: Option<IfMatchCondition>,
+    ) -> azure_core::Result<Self> {
+        let mut request = Request::new(client.url()?, method);
+        request.insert_header(ACCEPT, "application/json;odata=fullmetadata");
+        request.insert_headers(&ContentType::APPLICATION_JSON);
+        request.set_body(entity_json(&entity)?);
+        request.add_optional_header(&match_condition);
+
+        Ok(Self::new(request))
+    }
+
+    pub(crate) fn delete(
+        client: &EntityClient,
+        match_condition: Option<IfMatchCondition>,
+    ) -> azure_core::Result<Self> {
+        let mut request = Request::new(client.url()?, Method::Delete);
+        request.insert_header(ACCEPT, "application/json;odata=minimalmetadata");
+
+        let match_condition = match_condition.unwrap_or(IfMatchCondition::Any);
+        request.add_mandatory_header(&match_condition);
+        request.set_body("");
+
+        Ok(Self::new(request))
+    }
 }
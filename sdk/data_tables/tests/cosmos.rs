@@ -0,0 +1,119 @@
+use azure_core::{
+    auth::{AccessToken, TokenCredential},
+    error::ErrorKind,
+    StatusCode,
+};
+use azure_data_tables::{operations::InsertEntityResponse, prelude::*};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use time::OffsetDateTime;
+
+mod setup;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Order {
+    #[serde(rename = "PartitionKey")]
+    customer: String,
+    #[serde(rename = "RowKey")]
+    id: String,
+    quantity: u32,
+}
+
+impl Order {
+    fn new(customer: &str, id: &str, quantity: u32) -> Self {
+        Self {
+            customer: customer.to_owned(),
+            id: id.to_owned(),
+            quantity,
+        }
+    }
+}
+
+#[tokio::test]
+async fn cosmos_entity_lifecycle() -> azure_core::Result<()> {
+    let service = setup::initialize("tables_cosmos_entity_lifecycle")?;
+    assert!(service.is_cosmos_endpoint());
+    let table = service.table_client("orders");
+    table.create().await?;
+
+    // Cosmos DB does not send `Preference-Applied`
+    let inserted: InsertEntityResponse<Order> =
+        table.insert(Order::new("contoso", "1", 3))?.await?;
+    assert!(inserted.entity_with_metadata.is_none());
+    let inserted: InsertEntityResponse<Order> = table
+        .insert(Order::new("contoso", "2", 5))?
+        .return_entity(true)
+        .await?;
+    assert_eq!(
+        inserted.entity_with_metadata.unwrap().entity,
+        Order::new("contoso", "2", 5)
+    );
+
+    let entity = table.partition_key_client("contoso").entity_client("1");
+    let order = entity.get::<Order>().await?.entity;
+    assert_eq!(order, Order::new("contoso", "1", 3));
+
+    let mut pages = table
+        .query()
+        .filter(Property::new("quantity").greater_than(4))
+        .into_stream::<Order>();
+    let page = pages.next().await.expect("no page")?;
+    assert_eq!(page.entities, [Order::new("contoso", "2", 5)]);
+
+    entity.delete().await?;
+    // and only reports error codes in the body
+    let err = entity.get::<Order>().await.unwrap_err();
+    match err.kind() {
+        ErrorKind::HttpResponse { status, error_code } => {
+            assert_eq!(*status, StatusCode::NotFound);
+            assert_eq!(error_code.as_deref(), Some("ResourceNotFound"));
+        }
+        kind => panic!("unexpected error kind {kind:?}"),
+    }
+
+    table.delete().await?;
+    Ok(())
+}
+
+/// Hands out the token of `COSMOS_TABLE_TOKEN` when recording, keeping the
+/// requested scopes.
+#[derive(Debug, Default)]
+struct ScopeRecorder(Mutex<Vec<String>>);
+
+#[async_trait::async_trait]
+impl TokenCredential for ScopeRecorder {
+    async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+        self.0
+            .lock()
+            .unwrap()
+            .extend(scopes.iter().map(|scope| scope.to_string()));
+        let token = std::env::var("COSMOS_TABLE_TOKEN").unwrap_or_default();
+        Ok(AccessToken::new(
+            token,
+            OffsetDateTime::now_utc() + time::Duration::hours(1),
+        ))
+    }
+
+    async fn clear_cache(&self) -> azure_core::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn cosmos_token_scope() -> azure_core::Result<()> {
+    let recorder = Arc::new(ScopeRecorder::default());
+    let service = setup::initialize_with_token("tables_cosmos_token_scope", recorder.clone());
+
+    let mut names = Vec::new();
+    let mut pages = service.list().into_stream();
+    while let Some(page) = pages.next().await {
+        names.extend(page?.tables.into_iter().map(|table| table.name));
+    }
+    assert_eq!(names, ["orders"]);
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        ["https://cosmos.azure.com/.default"]
+    );
+    Ok(())
+}
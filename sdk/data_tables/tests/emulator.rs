@@ -0,0 +1,497 @@
+use azure_core::{error::ErrorKind, StatusCode};
+use azure_data_tables::{
+    clients::TableServiceClientBuilder, operations::Status, prelude::*, RetentionPolicy,
+    TableServiceProperties,
+};
+use azure_storage::{
+    shared_access_signature::table_sas::TableSasPermissions, CloudLocation, StorageCredentials,
+    StoredAccessPolicy, StoredAccessPolicyList,
+};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use storage_emulator::StorageEmulator;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Order {
+    #[serde(rename = "PartitionKey")]
+    customer: String,
+    #[serde(rename = "RowKey")]
+    id: String,
+    quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+impl Order {
+    fn new(customer: &str, id: &str, quantity: u32) -> Self {
+        Self {
+            customer: customer.to_owned(),
+            id: id.to_owned(),
+            quantity,
+            note: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    #[serde(with = "azure_data_tables::edm::int64")]
+    balance: i64,
+    #[serde(with = "azure_data_tables::edm::guid")]
+    id: Uuid,
+    #[serde(default, with = "azure_data_tables::edm::datetime::option")]
+    opened: Option<OffsetDateTime>,
+}
+
+#[derive(Serialize)]
+struct Note {
+    note: &'static str,
+}
+
+fn table_service(emulator: &StorageEmulator) -> TableServiceClient {
+    TableServiceClientBuilder::emulator()
+        .transport(emulator.transport_options())
+        .build()
+}
+
+fn status(error: &azure_core::Error) -> StatusCode {
+    match error.kind() {
+        ErrorKind::HttpResponse { status, .. } => *status,
+        kind => panic!("unexpected error kind {kind:?}"),
+    }
+}
+
+#[tokio::test]
+async fn entity_crud_with_etags() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let table = table_service(&emulator).table_client("orders");
+    table.create().await?;
+
+    let order = Order::new("contoso", "001", 5);
+    let inserted = table
+        .insert::<_, Order>(&order)?
+        .return_entity(true)
+        .await?;
+    assert_eq!(inserted.entity_with_metadata.unwrap().entity, order);
+
+    let error = table.insert::<_, Order>(&order)?.await.unwrap_err();
+    assert_eq!(status(&error), StatusCode::Conflict);
+
+    let entity = table.partition_key_client("contoso").entity_client("001");
+    let response = entity.get::<Order>().await?;
+    assert_eq!(response.entity, order);
+    let etag = response.etag;
+
+    let merged = entity
+        .merge(
+            Note { note: "gift wrap" },
+            IfMatchCondition::Etag(etag.clone()),
+        )?
+        .await?;
+    assert_ne!(merged.etag, etag);
+    let response = entity.get::<Order>().await?;
+    assert_eq!(response.entity.note.as_deref(), Some("gift wrap"));
+    assert_eq!(response.entity.quantity, 5);
+
+    // the entity changed since `etag` was read
+    let error = entity
+        .update(&order, IfMatchCondition::Etag(etag))?
+        .await
+        .unwrap_err();
+    assert_eq!(status(&error), StatusCode::PreconditionFailed);
+
+    entity.update(&order, IfMatchCondition::Any)?.await?;
+    assert_eq!(entity.get::<Order>().await?.entity.note, None);
+
+    entity.delete().await?;
+    let error = entity.get::<Order>().await.unwrap_err();
+    assert_eq!(status(&error), StatusCode::NotFound);
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_follows_continuation() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let table = table_service(&emulator).table_client("orders");
+    table.create().await?;
+    for (customer, id, quantity) in [
+        ("contoso", "001", 1),
+        ("contoso", "002", 20),
+        ("fabrikam", "001", 30),
+        ("fabrikam", "002", 4),
+        ("northwind", "001", 50),
+    ] {
+        table
+            .insert::<_, Order>(Order::new(customer, id, quantity))?
+            .await?;
+    }
+
+    let mut pages = table
+        .query()
+        .filter("quantity ge 10")
+        .top(2)
+        .into_stream::<Order>();
+    let mut page_sizes = Vec::new();
+    let mut orders = Vec::new();
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        page_sizes.push(page.entities.len());
+        orders.extend(page.entities);
+    }
+    assert_eq!(page_sizes, [2, 1]);
+    let keys: Vec<_> = orders
+        .iter()
+        .map(|order| (order.customer.as_str(), order.id.as_str()))
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            ("contoso", "002"),
+            ("fabrikam", "001"),
+            ("northwind", "001")
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_with_typed_filter() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let table = table_service(&emulator).table_client("orders");
+    table.create().await?;
+    for (customer, id, quantity) in [
+        ("o'reilly", "2024-01-001", 5),
+        ("o'reilly", "2024-02-001", 15),
+        ("o'reilly", "2025-01-001", 25),
+        ("contoso", "2024-01-001", 35),
+    ] {
+        table
+            .insert::<_, Order>(Order::new(customer, id, quantity))?
+            .await?;
+    }
+
+    // the customer comes from user input, and is escaped
+    let quantity = Property::of::<Order>("quantity")?;
+    let filter = Filter::partition_key("o'reilly")
+        .and(Filter::row_key_prefix("2024-"))
+        .and(quantity.greater_than(1i32));
+    let select = Select::properties([
+        Property::partition_key(),
+        Property::row_key(),
+        quantity.clone(),
+    ]);
+    let mut pages = table
+        .query()
+        .filter(filter)
+        .select(select)
+        .into_stream::<Order>();
+    let mut orders = Vec::new();
+    while let Some(page) = pages.next().await {
+        orders.extend(page?.entities);
+    }
+    assert_eq!(
+        orders,
+        [
+            Order::new("o'reilly", "2024-01-001", 5),
+            Order::new("o'reilly", "2024-02-001", 15)
+        ]
+    );
+
+    // injected OData is compared as a value
+    let mut pages = table
+        .query()
+        .filter(Filter::partition_key("x' or PartitionKey ne 'x"))
+        .into_stream::<Order>();
+    let page = pages.next().await.unwrap()?;
+    assert!(page.entities.is_empty());
+
+    assert!(Property::of::<Order>("Quantity").is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn dynamic_entity_keeps_types() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let table = table_service(&emulator).table_client("accounts");
+    table.create().await?;
+
+    let opened = OffsetDateTime::parse("2024-03-01T12:00:00Z", &Rfc3339).unwrap();
+    let account = TableEntity::new("contoso", "001")
+        .with("owner", "Carl")
+        .with("balance", 9_007_199_254_740_993i64)
+        .with("rate", 2.0)
+        .with("id", Uuid::nil())
+        .with("key", vec![0u8, 255])
+        .with("opened", opened);
+    table.insert::<_, TableEntity>(&account)?.await?;
+
+    let entity = table.partition_key_client("contoso").entity_client("001");
+    let read = entity.get::<TableEntity>().await?.entity;
+    assert_eq!(read.properties, account.properties);
+    assert!(read.timestamp.is_some());
+    let etag = read.etag.clone().unwrap();
+
+    // the etag read with the entity guards the update
+    let mut update = read.clone();
+    update.insert("balance", 0i64);
+    entity
+        .update(&update, IfMatchCondition::Etag(etag.clone()))?
+        .await?;
+    let error = entity
+        .update(&read, IfMatchCondition::Etag(etag))?
+        .await
+        .unwrap_err();
+    assert_eq!(status(&error), StatusCode::PreconditionFailed);
+
+    // struct fields annotated with the edm helpers read the same properties
+    let account: Account = entity.get().await?.entity;
+    assert_eq!(account.balance, 0);
+    assert_eq!(account.id, Uuid::nil());
+    assert_eq!(account.opened, Some(opened));
+
+    let mut pages = table
+        .query()
+        .filter(Property::new("balance").equal(EdmValue::Int64(0)))
+        .into_stream::<TableEntity>();
+    let page = pages.next().await.unwrap()?;
+    assert_eq!(page.entities.len(), 1);
+    assert_eq!(page.entities[0].get("rate"), Some(&EdmValue::Double(2.0)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_is_atomic() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let table = table_service(&emulator).table_client("orders");
+    table.create().await?;
+    let partition = table.partition_key_client("contoso");
+
+    let response = partition
+        .transaction()
+        .insert(Order::new("contoso", "001", 1))?
+        .insert(Order::new("contoso", "002", 2))?
+        .await?;
+    let statuses: Vec<_> = response
+        .operation_responses
+        .iter()
+        .map(|response| response.status_code)
+        .collect();
+    assert_eq!(statuses, [StatusCode::Created, StatusCode::Created]);
+
+    // the second insert conflicts, so the update is rolled back
+    let response = partition
+        .transaction()
+        .insert_or_replace("001", Order::new("contoso", "001", 100))?
+        .insert(Order::new("contoso", "002", 2))?
+        .await?;
+    assert_eq!(response.operation_responses.len(), 1);
+    assert_eq!(
+        response.operation_responses[0].status_code,
+        StatusCode::Conflict
+    );
+    let error = response.error().unwrap();
+    assert_eq!(error.index, Some(1));
+    assert_eq!(error.code, "EntityAlreadyExists");
+
+    let order = partition.entity_client("001").get::<Order>().await?.entity;
+    assert_eq!(order.quantity, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn bulk_writer_reports_each_operation() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let table = table_service(&emulator).table_client("orders");
+    table.create().await?;
+    table
+        .insert::<_, Order>(Order::new("contoso", "0042", 1))?
+        .await?;
+
+    let mut writer = table.bulk_writer().max_concurrency(4usize);
+    for id in 0..250 {
+        writer
+            .insert(Order::new("contoso", &format!("{id:04}"), id))
+            .await?;
+    }
+    for id in 0..30 {
+        writer
+            .insert_or_replace(Order::new("fabrikam", &format!("{id:04}"), id))
+            .await?;
+    }
+    // a second change to an entity is applied after the first
+    writer
+        .merge(
+            TableEntity::new("fabrikam", "0001").with("note", "rush"),
+            None,
+        )
+        .await?;
+    writer.delete("fabrikam", "missing", None).await?;
+    let outcomes = writer.flush().await;
+
+    assert_eq!(outcomes.len(), 282);
+    let mut failures: Vec<_> = outcomes
+        .iter()
+        .filter_map(|outcome| match &outcome.result {
+            Ok(_) => None,
+            Err(error) => Some((outcome.row_key.as_str(), status(error))),
+        })
+        .collect();
+    failures.sort_by_key(|(row_key, _)| *row_key);
+    assert_eq!(
+        failures,
+        [
+            ("0042", StatusCode::Conflict),
+            ("missing", StatusCode::NotFound)
+        ]
+    );
+
+    // the rest of the transaction of the failed insert was applied
+    let mut count = 0;
+    let mut pages = table
+        .query()
+        .filter(Filter::partition_key("contoso"))
+        .into_stream::<Order>();
+    while let Some(page) = pages.next().await {
+        count += page?.entities.len();
+    }
+    assert_eq!(count, 250);
+    let order = table
+        .partition_key_client("contoso")
+        .entity_client("0042")
+        .get::<Order>()
+        .await?
+        .entity;
+    assert_eq!(order.quantity, 1);
+    let order = table
+        .partition_key_client("fabrikam")
+        .entity_client("0001")
+        .get::<Order>()
+        .await?
+        .entity;
+    assert_eq!(order.note.as_deref(), Some("rush"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_tables() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let service = table_service(&emulator);
+    for name in ["alpha", "beta", "gamma"] {
+        service.table_client(name).create().await?;
+    }
+    service.table_client("beta").delete().await?;
+
+    let mut names = Vec::new();
+    let mut pages = service.list().into_stream();
+    while let Some(page) = pages.next().await {
+        names.extend(page?.tables.into_iter().map(|table| table.name));
+    }
+    assert_eq!(names, ["alpha", "gamma"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn table_acl_and_service_properties() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let service = table_service(&emulator);
+    let table = service.table_client("orders");
+    table.create().await?;
+
+    let start = OffsetDateTime::now_utc().replace_nanosecond(0).unwrap();
+    let expiry = start + time::Duration::hours(1);
+    let policies =
+        StoredAccessPolicyList::new(vec![StoredAccessPolicy::new("readers", start, expiry, "r")]);
+    table.set_acl(policies).await?;
+    let acl = table.get_acl().await?.stored_access_policy_list;
+    assert_eq!(acl.stored_access.len(), 1);
+    assert_eq!(acl.stored_access[0].id, "readers");
+    assert_eq!(acl.stored_access[0].expiry, expiry);
+    assert_eq!(acl.stored_access[0].permission, "r");
+
+    let mut properties = service.get_properties().await?.table_service_properties;
+    let mut logging = properties.logging.take().unwrap();
+    assert!(!logging.write);
+    properties.hour_metrics.as_mut().unwrap().enabled = true;
+    service.set_properties(properties).await?;
+
+    // the sections left out keep their value
+    logging.write = true;
+    logging.retention_policy = RetentionPolicy {
+        enabled: true,
+        days: Some(7),
+    };
+    service
+        .set_properties(TableServiceProperties {
+            logging: Some(logging),
+            ..Default::default()
+        })
+        .await?;
+    let properties = service.get_properties().await?.table_service_properties;
+    let logging = properties.logging.unwrap();
+    assert!(logging.write);
+    assert_eq!(logging.retention_policy.days, Some(7));
+    assert!(properties.hour_metrics.unwrap().enabled);
+    assert!(properties.cors.is_some());
+
+    let stats = service.get_stats().await?;
+    assert_eq!(stats.status, Status::Live);
+    assert!(stats.last_sync_time.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn sas_limits_key_range() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let table = table_service(&emulator).table_client("orders");
+    table.create().await?;
+    for customer in ["adam", "beth", "carl"] {
+        table
+            .insert::<_, Order>(Order::new(customer, "1", 1))?
+            .await?;
+    }
+
+    let sas = table
+        .shared_access_signature(
+            TableSasPermissions {
+                query: true,
+                ..Default::default()
+            },
+            OffsetDateTime::now_utc() + time::Duration::hours(1),
+        )
+        .await?
+        .start_partition_key("beth")
+        .end_partition_key("beth");
+    let url = table.generate_signed_table_url(&sas)?;
+    assert!(url.path().ends_with("/orders"));
+    assert!(url.query().unwrap().contains("spk=beth"));
+
+    let signed = TableServiceClientBuilder::with_location(
+        CloudLocation::Emulator {
+            address: "127.0.0.1".to_owned(),
+            port: 10002,
+        },
+        StorageCredentials::sas_token(url.query().unwrap())?,
+    )
+    .transport(emulator.transport_options())
+    .build()
+    .table_client("orders");
+
+    let order = signed
+        .partition_key_client("beth")
+        .entity_client("1")
+        .get::<Order>()
+        .await?
+        .entity;
+    assert_eq!(order.customer, "beth");
+
+    let err = signed
+        .partition_key_client("carl")
+        .entity_client("1")
+        .get::<Order>()
+        .await
+        .unwrap_err();
+    assert_eq!(status(&err), StatusCode::Forbidden);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use azure_core::{auth::TokenCredential, TransportOptions};
+use azure_data_tables::{clients::TableServiceClientBuilder, prelude::*};
+use azure_storage::StorageCredentials;
+use std::{env::var, sync::Arc};
+
+/// Replayed transactions are signed with this key, which is never checked.
+const REPLAY_CONNECTION_STRING: &str = "DefaultEndpointsProtocol=https;AccountName=contoso;AccountKey=a2V5;TableEndpoint=https://contoso.table.cosmos.azure.com:443/;";
+
+fn is_recording() -> bool {
+    var(mock_transport::TESTING_MODE_KEY).as_deref() == Ok(mock_transport::TESTING_MODE_RECORD)
+}
+
+fn transport(transaction_name: &str) -> TransportOptions {
+    TransportOptions::new_custom_policy(mock_transport::new_mock_transport(transaction_name.into()))
+}
+
+/// A client of a Cosmos DB Table API account, from its connection string.
+pub fn initialize(transaction_name: &str) -> azure_core::Result<TableServiceClient> {
+    let connection_string = if is_recording() {
+        var("COSMOS_TABLE_CONNECTION_STRING")
+            .expect("Set env variable COSMOS_TABLE_CONNECTION_STRING first!")
+    } else {
+        REPLAY_CONNECTION_STRING.to_owned()
+    };
+
+    Ok(
+        TableServiceClientBuilder::from_connection_string(&connection_string)?
+            .transport(transport(transaction_name))
+            .build(),
+    )
+}
+
+/// A client of a Cosmos DB Table API account, authorized with Azure AD tokens.
+pub fn initialize_with_token(
+    transaction_name: &str,
+    credential: Arc<dyn TokenCredential>,
+) -> TableServiceClient {
+    let account = if is_recording() {
+        var("COSMOS_TABLE_ACCOUNT").expect("Set env variable COSMOS_TABLE_ACCOUNT first!")
+    } else {
+        "contoso".to_owned()
+    };
+
+    TableServiceClientBuilder::cosmos(account, StorageCredentials::token_credential(credential))
+        .transport(transport(transaction_name))
+        .build()
+}
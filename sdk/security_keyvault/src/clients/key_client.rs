@@ -101,6 +101,18 @@ impl KeyClient {
         GetRandomBytesBuilder::new(self.clone(), hsm_name.into(), count)
     }
 
+    /// Wrap a symmetric key using a specified key.
+    ///
+    /// The WRAP operation supports encryption of a symmetric key using a key
+    /// encryption key that has previously been stored in an Azure Key Vault.
+    /// This operation requires the keys/wrapKey permission.
+    pub fn wrap_key<N>(&self, name: N, wrap_key_parameters: WrapKeyParameters) -> WrapKeyBuilder
+    where
+        N: Into<String>,
+    {
+        WrapKeyBuilder::new(self.clone(), name.into(), wrap_key_parameters)
+    }
+
     pub fn unwrap_key<N>(
         &self,
         name: N,
@@ -418,6 +418,27 @@ pub struct UnwrapKeyParameters {
     pub ciphertext: Vec<u8>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WrapKeyParameters {
+    pub algorithm: EncryptionAlgorithm,
+    #[serde(serialize_with = "ser_base64", deserialize_with = "deser_base64")]
+    pub key: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WrapKeyResult {
+    #[serde(skip)]
+    pub algorithm: EncryptionAlgorithm,
+    #[serde(rename = "kid")]
+    pub key_id: String,
+    #[serde(
+        rename = "value",
+        serialize_with = "ser_base64",
+        deserialize_with = "deser_base64"
+    )]
+    pub result: Vec<u8>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UnwrapKeyResult {
     #[serde(skip)]
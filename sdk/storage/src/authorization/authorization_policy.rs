@@ -11,6 +11,14 @@ use tracing::trace;
 
 const STORAGE_TOKEN_SCOPE: &str = "https://storage.azure.com/.default";
 
+/// The scope of the tokens requested from a `TokenCredential`.
+///
+/// Services which share the storage clients but not the storage audience, such
+/// as the Cosmos DB Table API, insert their scope in the request `Context`.
+/// Requests without one use the Azure Storage scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenScope(pub &'static str);
+
 #[derive(Debug, Clone)]
 pub struct AuthorizationPolicy {
     credentials: StorageCredentials,
@@ -70,8 +78,11 @@ impl Policy for AuthorizationPolicy {
                     request.insert_header(AUTHORIZATION, format!("Bearer {}", token.secret()));
                 }
                 StorageCredentialsInner::TokenCredential(token_credential) => {
+                    let scope = ctx
+                        .get::<TokenScope>()
+                        .map_or(STORAGE_TOKEN_SCOPE, |scope| scope.0);
                     let bearer_token = token_credential
-                        .get_token(&[STORAGE_TOKEN_SCOPE])
+                        .get_token(&[scope])
                         .await
                         .context(ErrorKind::Credential, "failed to get bearer token")?;
 
@@ -170,7 +181,11 @@ fn canonicalize_header(headers: &Headers) -> String {
 }
 
 fn canonicalized_resource_table(account: &str, u: &Url) -> String {
-    format!("/{}{}", account, u.path())
+    // the only query parameter included is `comp`, as in `?comp=acl`
+    match u.query_pairs().find(|(name, _)| name == "comp") {
+        Some((_, comp)) => format!("/{}{}?comp={}", account, u.path(), comp),
+        None => format!("/{}{}", account, u.path()),
+    }
 }
 
 fn canonicalized_resource(account: &str, uri: &Url) -> String {
@@ -231,7 +246,7 @@ fn lexy_sort<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use azure_core::{BytesStream, Response};
+    use azure_core::{auth::AccessToken, BytesStream, Response};
 
     #[derive(Debug, Clone)]
     struct AssertSigHeaderUniqueMockPolicy;
@@ -300,4 +315,66 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[derive(Debug, Default)]
+    struct ScopeRecorder(std::sync::Mutex<Vec<String>>);
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl azure_core::auth::TokenCredential for ScopeRecorder {
+        async fn get_token(&self, scopes: &[&str]) -> azure_core::Result<AccessToken> {
+            self.0
+                .lock()
+                .unwrap()
+                .extend(scopes.iter().map(|s| s.to_string()));
+            Ok(AccessToken::new("token", time::OffsetDateTime::now_utc()))
+        }
+
+        async fn clear_cache(&self) -> azure_core::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn authorization_policy_uses_token_scope_from_context() {
+        let recorder = Arc::new(ScopeRecorder::default());
+        let auth_policy =
+            AuthorizationPolicy::new(StorageCredentials::token_credential(recorder.clone()));
+        let next: Arc<dyn Policy> = Arc::new(AssertSigHeaderUniqueMockPolicy);
+        let mut request = Request::new(Url::parse("https://example.com").unwrap(), Method::Get);
+        request.url_mut().set_query(Some("sig=abc"));
+
+        auth_policy
+            .send(&Context::new(), &mut request, &[next.clone()])
+            .await
+            .unwrap();
+        auth_policy
+            .send(
+                Context::new().insert(TokenScope("https://cosmos.azure.com/.default")),
+                &mut request,
+                &[next],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [STORAGE_TOKEN_SCOPE, "https://cosmos.azure.com/.default"]
+        );
+    }
+
+    #[test]
+    fn canonicalized_resource_table_includes_comp() {
+        let url = Url::parse("https://account.table.core.windows.net/mytable?comp=acl&timeout=30")
+            .unwrap();
+        assert_eq!(
+            canonicalized_resource_table("account", &url),
+            "/account/mytable?comp=acl"
+        );
+
+        let url = Url::parse("https://account.table.core.windows.net/mytable()?$top=1").unwrap();
+        assert_eq!(
+            canonicalized_resource_table("account", &url),
+            "/account/mytable()"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 mod authorization_policy;
 
 pub(crate) use self::authorization_policy::AuthorizationPolicy;
+pub use self::authorization_policy::TokenScope;
 use crate::clients::{EMULATOR_ACCOUNT, EMULATOR_ACCOUNT_KEY};
 use async_lock::RwLock;
 use azure_core::{
@@ -0,0 +1,136 @@
+use aes_kw::{KekAes128, KekAes192, KekAes256};
+use azure_core::error::{Error, ErrorKind};
+use std::{fmt::Debug, sync::Arc};
+
+/// A key used to wrap and unwrap the content encryption keys of encrypted
+/// data, for example a key stored in Azure Key Vault.
+#[async_trait::async_trait]
+pub trait KeyEncryptionKey: Debug + Send + Sync {
+    /// The ID stored with encrypted data, to find the key to decrypt it with.
+    fn key_id(&self) -> &str;
+
+    /// The algorithm used by `wrap_key`.
+    fn key_wrap_algorithm(&self) -> &str;
+
+    async fn wrap_key(&self, key: &[u8]) -> azure_core::Result<Vec<u8>>;
+
+    async fn unwrap_key(&self, algorithm: &str, wrapped_key: &[u8]) -> azure_core::Result<Vec<u8>>;
+}
+
+/// Finds the key that wrapped the content encryption key of encrypted data.
+#[async_trait::async_trait]
+pub trait KeyEncryptionKeyResolver: Debug + Send + Sync {
+    async fn resolve(&self, key_id: &str) -> azure_core::Result<Arc<dyn KeyEncryptionKey>>;
+}
+
+/// A symmetric key encryption key held in memory, wrapping keys with AES key
+/// wrap (RFC 3394).
+#[derive(Clone)]
+pub struct LocalKeyEncryptionKey {
+    key_id: String,
+    key: Vec<u8>,
+}
+
+impl LocalKeyEncryptionKey {
+    /// A 128, 192 or 256 bit AES key.
+    pub fn new(key_id: impl Into<String>, key: impl Into<Vec<u8>>) -> azure_core::Result<Self> {
+        let key = key.into();
+        if !matches!(key.len(), 16 | 24 | 32) {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "a key encryption key must be 16, 24 or 32 bytes long, not {}",
+                    key.len()
+                )
+            }));
+        }
+        Ok(Self {
+            key_id: key_id.into(),
+            key,
+        })
+    }
+
+    fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, aes_kw::Error> {
+        match self.key.len() {
+            16 => KekAes128::try_from(self.key.as_slice())?.wrap_vec(key),
+            24 => KekAes192::try_from(self.key.as_slice())?.wrap_vec(key),
+            _ => KekAes256::try_from(self.key.as_slice())?.wrap_vec(key),
+        }
+    }
+
+    fn unwrap(&self, wrapped_key: &[u8]) -> Result<Vec<u8>, aes_kw::Error> {
+        match self.key.len() {
+            16 => KekAes128::try_from(self.key.as_slice())?.unwrap_vec(wrapped_key),
+            24 => KekAes192::try_from(self.key.as_slice())?.unwrap_vec(wrapped_key),
+            _ => KekAes256::try_from(self.key.as_slice())?.unwrap_vec(wrapped_key),
+        }
+    }
+}
+
+impl Debug for LocalKeyEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalKeyEncryptionKey")
+            .field("key_id", &self.key_id)
+            .field("key", &"<REDACTED>")
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyEncryptionKey for LocalKeyEncryptionKey {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn key_wrap_algorithm(&self) -> &str {
+        match self.key.len() {
+            16 => "A128KW",
+            24 => "A192KW",
+            _ => "A256KW",
+        }
+    }
+
+    async fn wrap_key(&self, key: &[u8]) -> azure_core::Result<Vec<u8>> {
+        self.wrap(key).map_err(|error| {
+            Error::with_message(ErrorKind::Credential, || {
+                format!("failed to wrap the content encryption key: {error}")
+            })
+        })
+    }
+
+    async fn unwrap_key(&self, algorithm: &str, wrapped_key: &[u8]) -> azure_core::Result<Vec<u8>> {
+        if algorithm != self.key_wrap_algorithm() {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "the content encryption key was wrapped with {algorithm}, not {}",
+                    self.key_wrap_algorithm()
+                )
+            }));
+        }
+        self.unwrap(wrapped_key).map_err(|error| {
+            Error::with_message(ErrorKind::Credential, || {
+                format!("failed to unwrap the content encryption key: {error}")
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wrap_round_trip() -> azure_core::Result<()> {
+        for length in [16, 24, 32] {
+            let kek = LocalKeyEncryptionKey::new("key", vec![1; length])?;
+            let wrapped = kek.wrap_key(&[2; 40]).await?;
+            assert_eq!(wrapped.len(), 48);
+            assert_eq!(
+                kek.unwrap_key(kek.key_wrap_algorithm(), &wrapped).await?,
+                [2; 40]
+            );
+            assert!(kek.unwrap_key("RSA-OAEP", &wrapped).await.is_err());
+        }
+        assert!(LocalKeyEncryptionKey::new("key", vec![1; 20]).is_err());
+        Ok(())
+    }
+}
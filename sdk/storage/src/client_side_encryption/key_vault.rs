@@ -0,0 +1,119 @@
+use super::KeyEncryptionKey;
+use azure_core::{
+    base64,
+    error::{Error, ErrorKind, ResultExt},
+};
+use azure_security_keyvault::prelude::{
+    CryptographParamtersEncryption, EncryptionAlgorithm, KeyClient, RsaEncryptionParameters,
+    UnwrapKeyParameters, WrapKeyParameters,
+};
+use url::Url;
+
+/// A key encryption key stored in Azure Key Vault. Content encryption keys
+/// are wrapped and unwrapped by Key Vault, so the key never leaves the vault.
+#[derive(Debug, Clone)]
+pub struct KeyVaultKeyEncryptionKey {
+    client: KeyClient,
+    key_id: String,
+    name: String,
+    version: String,
+    algorithm: EncryptionAlgorithm,
+}
+
+impl KeyVaultKeyEncryptionKey {
+    /// Wrap keys with the RSA key `key_id`, such as
+    /// `https://myvault.vault.azure.net/keys/mykey/0123456789abcdef`, using
+    /// one of the RSA key wrap algorithms.
+    pub fn new(
+        client: KeyClient,
+        key_id: impl Into<String>,
+        algorithm: EncryptionAlgorithm,
+    ) -> azure_core::Result<Self> {
+        let key_id = key_id.into();
+        let url = Url::parse(&key_id).with_context(ErrorKind::DataConversion, || {
+            format!("invalid key ID {key_id}")
+        })?;
+        let segments: Vec<_> = url.path_segments().into_iter().flatten().collect();
+        let [keys, name, version] = segments.as_slice() else {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!(
+                    "the key ID {key_id} is not of the form https://<vault>/keys/<name>/<version>"
+                )
+            }));
+        };
+        if *keys != "keys" {
+            return Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("the key ID {key_id} is not the ID of a key")
+            }));
+        }
+        // validates that the algorithm is a key wrap algorithm
+        RsaEncryptionParameters::new(algorithm.clone())?;
+
+        Ok(Self {
+            client,
+            name: (*name).to_owned(),
+            version: (*version).to_owned(),
+            key_id,
+            algorithm,
+        })
+    }
+}
+
+fn algorithm_name(algorithm: &EncryptionAlgorithm) -> &'static str {
+    match algorithm {
+        EncryptionAlgorithm::RsaOaep256 => "RSA-OAEP-256",
+        EncryptionAlgorithm::Rsa15 => "RSA1_5",
+        _ => "RSA-OAEP",
+    }
+}
+
+fn parse_algorithm(algorithm: &str) -> azure_core::Result<EncryptionAlgorithm> {
+    match algorithm {
+        "RSA-OAEP" => Ok(EncryptionAlgorithm::RsaOaep),
+        "RSA-OAEP-256" => Ok(EncryptionAlgorithm::RsaOaep256),
+        "RSA1_5" => Ok(EncryptionAlgorithm::Rsa15),
+        _ => Err(Error::with_message(ErrorKind::Credential, || {
+            format!("unsupported Key Vault key wrap algorithm {algorithm}")
+        })),
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyEncryptionKey for KeyVaultKeyEncryptionKey {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn key_wrap_algorithm(&self) -> &str {
+        algorithm_name(&self.algorithm)
+    }
+
+    async fn wrap_key(&self, key: &[u8]) -> azure_core::Result<Vec<u8>> {
+        let parameters = WrapKeyParameters {
+            algorithm: self.algorithm.clone(),
+            key: key.to_vec(),
+        };
+        let result = self
+            .client
+            .wrap_key(&self.name, parameters)
+            .version(self.version.clone())
+            .await?;
+        Ok(result.result)
+    }
+
+    async fn unwrap_key(&self, algorithm: &str, wrapped_key: &[u8]) -> azure_core::Result<Vec<u8>> {
+        let parameters = UnwrapKeyParameters {
+            decrypt_parameters_encryption: CryptographParamtersEncryption::Rsa(
+                RsaEncryptionParameters::new(parse_algorithm(algorithm)?)?,
+            ),
+            // the key client sends the ciphertext as it is, base64url encoded
+            ciphertext: base64::encode_url_safe(wrapped_key).into_bytes(),
+        };
+        let result = self
+            .client
+            .unwrap_key(&self.name, parameters)
+            .version(self.version.clone())
+            .await?;
+        Ok(result.result)
+    }
+}
@@ -0,0 +1,429 @@
+//! Client-side encryption in the v2 format of the other Azure Storage SDKs.
+//!
+//! Data is encrypted with a random AES-256-GCM content encryption key (CEK)
+//! before it leaves the process. Blobs are encrypted in regions of 4 MiB of
+//! plaintext, each stored as its nonce, its ciphertext and its authentication
+//! tag, so a range of a blob can be decrypted without downloading the rest.
+//! The CEK is wrapped with a [`KeyEncryptionKey`] and stored next to the data
+//! in an [`EncryptionData`] document.
+//!
+//! ref: <https://docs.microsoft.com/azure/storage/common/storage-client-side-encryption>
+
+mod key_encryption_key;
+#[cfg(feature = "key_vault")]
+mod key_vault;
+
+pub use key_encryption_key::{KeyEncryptionKey, KeyEncryptionKeyResolver, LocalKeyEncryptionKey};
+#[cfg(feature = "key_vault")]
+pub use key_vault::KeyVaultKeyEncryptionKey;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Nonce,
+};
+use azure_core::{
+    base64,
+    error::{Error, ErrorKind},
+};
+use std::{collections::BTreeMap, ops::Range, sync::Arc};
+
+/// The name of the blob metadata holding the [`EncryptionData`] of a blob.
+pub const ENCRYPTION_DATA_METADATA: &str = "encryptiondata";
+
+/// The version of the encryption protocol.
+pub const PROTOCOL_V2: &str = "2.0";
+
+/// The length of the plaintext of each encrypted region.
+pub const REGION_DATA_LENGTH: u64 = 4 * 1024 * 1024;
+
+/// The length of a full encrypted region: its nonce, ciphertext and tag.
+pub const ENCRYPTED_REGION_LENGTH: u64 = NONCE_LENGTH + REGION_DATA_LENGTH + TAG_LENGTH;
+
+const ENCRYPTION_ALGORITHM: &str = "AES_GCM_256";
+const NONCE_LENGTH: u64 = 12;
+const TAG_LENGTH: u64 = 16;
+const CONTENT_KEY_LENGTH: usize = 32;
+
+/// The protocol version is wrapped together with the CEK, so that the
+/// metadata cannot be altered to downgrade the protocol. It is padded to the
+/// 8 byte blocks of AES key wrap.
+const WRAPPED_PROTOCOL: &[u8; 8] = b"2.0\0\0\0\0\0";
+
+/// The metadata stored next to encrypted data, needed to decrypt it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EncryptionData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_mode: Option<String>,
+    pub wrapped_content_key: WrappedContentKey,
+    pub encryption_agent: EncryptionAgent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_region_info: Option<EncryptedRegionInfo>,
+    #[serde(default)]
+    pub key_wrapping_metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct WrappedContentKey {
+    pub key_id: String,
+    /// The base64 encoded wrapped key.
+    pub encrypted_key: String,
+    pub algorithm: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EncryptionAgent {
+    pub protocol: String,
+    pub encryption_algorithm: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EncryptedRegionInfo {
+    pub data_length: u64,
+    pub nonce_length: u64,
+}
+
+/// The body of an encrypted queue message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EncryptedMessage {
+    encrypted_message_contents: String,
+    encryption_data: EncryptionData,
+}
+
+/// Encrypts data before it is uploaded, and decrypts it after it is
+/// downloaded.
+#[derive(Debug, Clone)]
+pub struct ClientSideEncryption {
+    key_encryption_key: Arc<dyn KeyEncryptionKey>,
+    key_resolver: Option<Arc<dyn KeyEncryptionKeyResolver>>,
+    allow_unencrypted_messages: bool,
+}
+
+impl ClientSideEncryption {
+    /// Encrypt with content keys wrapped by `key_encryption_key`, which is
+    /// also used to unwrap them unless a key resolver is set.
+    pub fn new(key_encryption_key: impl KeyEncryptionKey + 'static) -> Self {
+        Self {
+            key_encryption_key: Arc::new(key_encryption_key),
+            key_resolver: None,
+            allow_unencrypted_messages: false,
+        }
+    }
+
+    /// Find the key to unwrap content keys with by the key ID stored with the
+    /// data, for example to read data written before a key rotation.
+    #[must_use]
+    pub fn key_resolver(mut self, key_resolver: impl KeyEncryptionKeyResolver + 'static) -> Self {
+        self.key_resolver = Some(Arc::new(key_resolver));
+        self
+    }
+
+    /// Read queue messages which were not encrypted as they are, for example
+    /// while the senders of a queue move to client-side encryption.
+    ///
+    /// By default they fail to decrypt, like messages that were tampered with.
+    #[must_use]
+    pub fn allow_unencrypted_messages(mut self, allow_unencrypted_messages: bool) -> Self {
+        self.allow_unencrypted_messages = allow_unencrypted_messages;
+        self
+    }
+
+    /// Encrypt the content of a blob, returning the ciphertext and the
+    /// encryption data to store in the blob's metadata.
+    pub async fn encrypt_blob(&self, data: &[u8]) -> azure_core::Result<(Vec<u8>, EncryptionData)> {
+        let content_key = Aes256Gcm::generate_key(OsRng);
+        let cipher = Aes256Gcm::new(&content_key);
+
+        let regions = (data.len() as u64).div_ceil(REGION_DATA_LENGTH);
+        let mut encrypted =
+            Vec::with_capacity(data.len() + (regions * (NONCE_LENGTH + TAG_LENGTH)) as usize);
+        // the content key is only used for this blob, so a counter is a
+        // unique nonce for each region
+        for (index, region) in data.chunks(REGION_DATA_LENGTH as usize).enumerate() {
+            let mut nonce = [0; NONCE_LENGTH as usize];
+            nonce[4..].copy_from_slice(&(index as u64).to_be_bytes());
+            let nonce = Nonce::from_slice(&nonce);
+            encrypted.extend_from_slice(nonce);
+            encrypted.extend(cipher.encrypt(nonce, region).map_err(encryption_error)?);
+        }
+
+        let mut encryption_data = self.encryption_data(&content_key).await?;
+        encryption_data.encryption_mode = Some("FullBlob".to_owned());
+        Ok((encrypted, encryption_data))
+    }
+
+    /// Decrypt whole encrypted regions of a blob.
+    pub async fn decrypt_blob(
+        &self,
+        encryption_data: &EncryptionData,
+        data: &[u8],
+    ) -> azure_core::Result<Vec<u8>> {
+        validate(encryption_data)?;
+        let cipher = self.content_cipher(encryption_data).await?;
+
+        let mut decrypted = Vec::with_capacity(data.len());
+        for region in data.chunks(ENCRYPTED_REGION_LENGTH as usize) {
+            decrypted.extend(decrypt_region(&cipher, region)?);
+        }
+        Ok(decrypted)
+    }
+
+    /// Encrypt the text of a queue message, returning the message to send.
+    pub async fn encrypt_message(&self, message: &str) -> azure_core::Result<String> {
+        let content_key = Aes256Gcm::generate_key(OsRng);
+        let cipher = Aes256Gcm::new(&content_key);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let mut contents = nonce.to_vec();
+        contents.extend(
+            cipher
+                .encrypt(&nonce, message.as_bytes())
+                .map_err(encryption_error)?,
+        );
+
+        let message = EncryptedMessage {
+            encrypted_message_contents: base64::encode(contents),
+            encryption_data: self.encryption_data(&content_key).await?,
+        };
+        Ok(String::from_utf8(azure_core::to_json(&message)?.to_vec())?)
+    }
+
+    /// Decrypt the text of a queue message. Messages that were not encrypted
+    /// fail, unless [`allow_unencrypted_messages`](Self::allow_unencrypted_messages)
+    /// is set.
+    pub async fn decrypt_message(&self, message: &str) -> azure_core::Result<String> {
+        let message = match azure_core::from_json::<_, EncryptedMessage>(message) {
+            Ok(message) => message,
+            Err(_) if self.allow_unencrypted_messages => return Ok(message.to_owned()),
+            Err(error) => {
+                return Err(Error::full(
+                    ErrorKind::DataConversion,
+                    error,
+                    "the queue message is not encrypted",
+                ))
+            }
+        };
+        validate(&message.encryption_data)?;
+        let cipher = self.content_cipher(&message.encryption_data).await?;
+        let contents = base64::decode(&message.encrypted_message_contents)?;
+        let decrypted = decrypt_region(&cipher, &contents)?;
+        Ok(String::from_utf8(decrypted)?)
+    }
+
+    async fn encryption_data(&self, content_key: &[u8]) -> azure_core::Result<EncryptionData> {
+        let mut key = WRAPPED_PROTOCOL.to_vec();
+        key.extend_from_slice(content_key);
+        let wrapped_key = self.key_encryption_key.wrap_key(&key).await?;
+
+        Ok(EncryptionData {
+            encryption_mode: None,
+            wrapped_content_key: WrappedContentKey {
+                key_id: self.key_encryption_key.key_id().to_owned(),
+                encrypted_key: base64::encode(wrapped_key),
+                algorithm: self.key_encryption_key.key_wrap_algorithm().to_owned(),
+            },
+            encryption_agent: EncryptionAgent {
+                protocol: PROTOCOL_V2.to_owned(),
+                encryption_algorithm: ENCRYPTION_ALGORITHM.to_owned(),
+            },
+            encrypted_region_info: Some(EncryptedRegionInfo {
+                data_length: REGION_DATA_LENGTH,
+                nonce_length: NONCE_LENGTH,
+            }),
+            key_wrapping_metadata: BTreeMap::from([(
+                "EncryptionLibrary".to_owned(),
+                concat!("Rust ", env!("CARGO_PKG_VERSION")).to_owned(),
+            )]),
+        })
+    }
+
+    /// Unwrap the content key of encrypted data.
+    async fn content_cipher(
+        &self,
+        encryption_data: &EncryptionData,
+    ) -> azure_core::Result<Aes256Gcm> {
+        let wrapped_content_key = &encryption_data.wrapped_content_key;
+        let key_encryption_key = match &self.key_resolver {
+            Some(key_resolver) => key_resolver.resolve(&wrapped_content_key.key_id).await?,
+            None if wrapped_content_key.key_id == self.key_encryption_key.key_id() => {
+                self.key_encryption_key.clone()
+            }
+            None => {
+                return Err(Error::with_message(ErrorKind::Credential, || {
+                    format!(
+                        "the data was encrypted with the key {}, not with the key encryption key {}",
+                        wrapped_content_key.key_id,
+                        self.key_encryption_key.key_id()
+                    )
+                }))
+            }
+        };
+
+        let key = key_encryption_key
+            .unwrap_key(
+                &wrapped_content_key.algorithm,
+                &base64::decode(&wrapped_content_key.encrypted_key)?,
+            )
+            .await?;
+        match key.strip_prefix(WRAPPED_PROTOCOL) {
+            Some(content_key) if content_key.len() == CONTENT_KEY_LENGTH => {
+                Aes256Gcm::new_from_slice(content_key).map_err(|_| invalid_content_key())
+            }
+            _ => Err(invalid_content_key()),
+        }
+    }
+}
+
+/// The range of encrypted data holding the plaintext `range` of a blob: the
+/// encrypted regions that the range overlaps.
+pub fn encrypted_range(range: Range<u64>) -> Range<u64> {
+    let first_region = range.start / REGION_DATA_LENGTH;
+    let end_region = range.end.div_ceil(REGION_DATA_LENGTH);
+    first_region * ENCRYPTED_REGION_LENGTH..end_region * ENCRYPTED_REGION_LENGTH
+}
+
+/// The length of the plaintext of an encrypted blob.
+pub fn plaintext_length(encrypted_length: u64) -> u64 {
+    let regions = encrypted_length.div_ceil(ENCRYPTED_REGION_LENGTH);
+    encrypted_length.saturating_sub(regions * (NONCE_LENGTH + TAG_LENGTH))
+}
+
+fn validate(encryption_data: &EncryptionData) -> azure_core::Result<()> {
+    let agent = &encryption_data.encryption_agent;
+    if agent.protocol != PROTOCOL_V2 || agent.encryption_algorithm != ENCRYPTION_ALGORITHM {
+        return Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!(
+                "unsupported client-side encryption protocol {} with algorithm {}",
+                agent.protocol, agent.encryption_algorithm
+            )
+        }));
+    }
+    match encryption_data.encrypted_region_info {
+        Some(EncryptedRegionInfo {
+            data_length: REGION_DATA_LENGTH,
+            nonce_length: NONCE_LENGTH,
+        }) => Ok(()),
+        region_info => Err(Error::with_message(ErrorKind::DataConversion, || {
+            format!("unsupported encrypted region info {region_info:?}")
+        })),
+    }
+}
+
+fn decrypt_region(cipher: &Aes256Gcm, region: &[u8]) -> azure_core::Result<Vec<u8>> {
+    if region.len() < (NONCE_LENGTH + TAG_LENGTH) as usize {
+        return Err(Error::message(
+            ErrorKind::DataConversion,
+            "the encrypted data is truncated",
+        ));
+    }
+    let (nonce, ciphertext) = region.split_at(NONCE_LENGTH as usize);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| {
+            Error::message(
+                ErrorKind::DataConversion,
+                "failed to decrypt: the data was modified or the content key is wrong",
+            )
+        })
+}
+
+fn encryption_error(_: aes_gcm::Error) -> Error {
+    Error::message(ErrorKind::Other, "failed to encrypt the data")
+}
+
+fn invalid_content_key() -> Error {
+    Error::message(
+        ErrorKind::DataConversion,
+        "the unwrapped content key is not a v2 AES-256 key",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encryption() -> ClientSideEncryption {
+        ClientSideEncryption::new(LocalKeyEncryptionKey::new("local:key1", vec![7; 32]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn blob_round_trip() -> azure_core::Result<()> {
+        let encryption = encryption();
+        let data: Vec<u8> = (0..(REGION_DATA_LENGTH + 100))
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let (encrypted, encryption_data) = encryption.encrypt_blob(&data).await?;
+        assert_eq!(encrypted.len() as u64, data.len() as u64 + 2 * 28);
+        assert_eq!(plaintext_length(encrypted.len() as u64), data.len() as u64);
+
+        // the second region can be decrypted on its own
+        let second = &encrypted[ENCRYPTED_REGION_LENGTH as usize..];
+        assert_eq!(
+            encryption.decrypt_blob(&encryption_data, second).await?,
+            &data[REGION_DATA_LENGTH as usize..]
+        );
+        assert_eq!(
+            encryption
+                .decrypt_blob(&encryption_data, &encrypted)
+                .await?,
+            data
+        );
+
+        let mut tampered = encrypted;
+        tampered[20] ^= 1;
+        assert!(encryption
+            .decrypt_blob(&encryption_data, &tampered)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn message_round_trip() -> azure_core::Result<()> {
+        let encryption = encryption();
+        let message = encryption.encrypt_message("hello").await?;
+        assert!(message.contains("\"EncryptedMessageContents\""));
+        assert_eq!(encryption.decrypt_message(&message).await?, "hello");
+        let error = encryption.decrypt_message("plain").await.unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::DataConversion);
+        let mixed = encryption.allow_unencrypted_messages(true);
+        assert_eq!(mixed.decrypt_message("plain").await?, "plain");
+        assert_eq!(mixed.decrypt_message(&message).await?, "hello");
+
+        let other = ClientSideEncryption::new(
+            LocalKeyEncryptionKey::new("local:key2", vec![7; 32]).unwrap(),
+        );
+        assert!(other.decrypt_message(&message).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn encryption_data_format() {
+        // as written by the other SDKs
+        let json = r#"{"WrappedContentKey":{"KeyId":"local:key1","EncryptedKey":"AAAA","Algorithm":"A256KW"},"EncryptionAgent":{"Protocol":"2.0","EncryptionAlgorithm":"AES_GCM_256"},"EncryptedRegionInfo":{"DataLength":4194304,"NonceLength":12},"KeyWrappingMetadata":{"EncryptionLibrary":"Python 12.14.0"}}"#;
+        let encryption_data: EncryptionData = azure_core::from_json(json).unwrap();
+        assert_eq!(encryption_data.wrapped_content_key.algorithm, "A256KW");
+        assert!(validate(&encryption_data).is_ok());
+    }
+
+    #[test]
+    fn region_ranges() {
+        let region = ENCRYPTED_REGION_LENGTH;
+        assert_eq!(encrypted_range(0..1), 0..region);
+        assert_eq!(
+            encrypted_range(REGION_DATA_LENGTH - 1..REGION_DATA_LENGTH + 1),
+            0..2 * region
+        );
+        assert_eq!(
+            encrypted_range(REGION_DATA_LENGTH..2 * REGION_DATA_LENGTH),
+            region..2 * region
+        );
+        assert_eq!(plaintext_length(0), 0);
+        assert_eq!(plaintext_length(region + 29), REGION_DATA_LENGTH + 1);
+    }
+}
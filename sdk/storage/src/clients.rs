@@ -30,7 +30,7 @@ const AZURE_VERSION: HeaderValue = HeaderValue::from_static("2022-11-02");
 pub enum ServiceType {
     Blob,
     Queue,
-    // File,
+    File,
     Table,
     DataLake,
 }
@@ -40,6 +40,7 @@ impl ServiceType {
         match self {
             ServiceType::Blob => "blob",
             ServiceType::Queue => "queue",
+            ServiceType::File => "file",
             ServiceType::Table => "table",
             ServiceType::DataLake => "dfs",
         }
@@ -3,6 +3,7 @@ use azure_core::error::Error;
 use azure_core::headers::{
     client_request_id_from_headers_optional, date_from_headers, request_id_from_headers,
     server_from_headers, version_from_headers, HeaderName, Headers, CONTENT_MD5,
+    ENCRYPTION_KEY_SHA256,
 };
 use azure_core::RequestId;
 use time::OffsetDateTime;
@@ -31,8 +32,60 @@ impl TryFrom<&Headers> for CommonStorageResponseHeaders {
 }
 
 pub const CONTENT_CRC64: HeaderName = HeaderName::from_static("x-ms-content-crc64");
+pub const COPY_ACTION: HeaderName = HeaderName::from_static("x-ms-copy-action");
 pub const COPY_ID: HeaderName = HeaderName::from_static("x-ms-copy-id");
+pub const COPY_SOURCE_AUTHORIZATION: HeaderName =
+    HeaderName::from_static("x-ms-copy-source-authorization");
+pub const DEFAULT_ENCRYPTION_SCOPE: HeaderName =
+    HeaderName::from_static("x-ms-default-encryption-scope");
+pub const DELETED_CONTAINER_NAME: HeaderName =
+    HeaderName::from_static("x-ms-deleted-container-name");
+pub const DELETED_CONTAINER_VERSION: HeaderName =
+    HeaderName::from_static("x-ms-deleted-container-version");
+pub const DELETION_ID: HeaderName = HeaderName::from_static("x-ms-deletion-id");
+pub const DENY_ENCRYPTION_SCOPE_OVERRIDE: HeaderName =
+    HeaderName::from_static("x-ms-deny-encryption-scope-override");
+pub const ENCRYPTION_SCOPE: HeaderName = HeaderName::from_static("x-ms-encryption-scope");
+pub const EXPIRY_OPTION: HeaderName = HeaderName::from_static("x-ms-expiry-option");
+pub const EXPIRY_TIME: HeaderName = HeaderName::from_static("x-ms-expiry-time");
+pub const FILE_ATTRIBUTES: HeaderName = HeaderName::from_static("x-ms-file-attributes");
+pub const FILE_CHANGE_TIME: HeaderName = HeaderName::from_static("x-ms-file-change-time");
+pub const FILE_CONTENT_LENGTH: HeaderName = HeaderName::from_static("x-ms-content-length");
+pub const FILE_CONTENT_MD5: HeaderName = HeaderName::from_static("x-ms-content-md5");
+pub const FILE_CREATION_TIME: HeaderName = HeaderName::from_static("x-ms-file-creation-time");
+pub const FILE_ID: HeaderName = HeaderName::from_static("x-ms-file-id");
+pub const FILE_LAST_WRITE_TIME: HeaderName = HeaderName::from_static("x-ms-file-last-write-time");
+pub const FILE_PARENT_ID: HeaderName = HeaderName::from_static("x-ms-file-parent-id");
+pub const FILE_PERMISSION: HeaderName = HeaderName::from_static("x-ms-file-permission");
+pub const FILE_PERMISSION_KEY: HeaderName = HeaderName::from_static("x-ms-file-permission-key");
+pub const FILE_TYPE: HeaderName = HeaderName::from_static("x-ms-type");
+pub const FILE_WRITE: HeaderName = HeaderName::from_static("x-ms-write");
+pub const GROUP: HeaderName = HeaderName::from_static("x-ms-group");
+pub const HANDLE_ID: HeaderName = HeaderName::from_static("x-ms-handle-id");
+pub const IMMUTABILITY_POLICY_MODE: HeaderName =
+    HeaderName::from_static("x-ms-immutability-policy-mode");
+pub const IMMUTABILITY_POLICY_UNTIL_DATE: HeaderName =
+    HeaderName::from_static("x-ms-immutability-policy-until-date");
+pub const IMMUTABLE_STORAGE_WITH_VERSIONING_ENABLED: HeaderName =
+    HeaderName::from_static("x-ms-immutable-storage-with-versioning-enabled");
+pub const LEGAL_HOLD: HeaderName = HeaderName::from_static("x-ms-legal-hold");
+pub const MARKER: HeaderName = HeaderName::from_static("x-ms-marker");
+pub const NUMBER_OF_HANDLES_CLOSED: HeaderName =
+    HeaderName::from_static("x-ms-number-of-handles-closed");
+pub const NUMBER_OF_HANDLES_FAILED: HeaderName =
+    HeaderName::from_static("x-ms-number-of-handles-failed");
+pub const OWNER: HeaderName = HeaderName::from_static("x-ms-owner");
+pub const PERMISSIONS: HeaderName = HeaderName::from_static("x-ms-permissions");
+pub const PREVIOUS_SNAPSHOT_URL: HeaderName = HeaderName::from_static("x-ms-previous-snapshot-url");
+pub const RECURSIVE: HeaderName = HeaderName::from_static("x-ms-recursive");
 pub const RENAME_SOURCE: HeaderName = HeaderName::from_static("x-ms-rename-source");
+pub const RESOURCE_TYPE: HeaderName = HeaderName::from_static("x-ms-resource-type");
+pub const SEQUENCE_NUMBER_ACTION: HeaderName =
+    HeaderName::from_static("x-ms-sequence-number-action");
+pub const SHARE_QUOTA: HeaderName = HeaderName::from_static("x-ms-share-quota");
+pub const SNAPSHOT: HeaderName = HeaderName::from_static("x-ms-snapshot");
+pub const UMASK: HeaderName = HeaderName::from_static("x-ms-umask");
+pub const UNDELETE_SOURCE: HeaderName = HeaderName::from_static("x-ms-undelete-source");
 
 pub fn content_crc64_from_headers(headers: &Headers) -> azure_core::Result<ConsistencyCRC64> {
     headers.get_as(&CONTENT_CRC64)
@@ -54,6 +107,17 @@ pub fn content_md5_from_headers_optional(
     headers.get_optional_as(&CONTENT_MD5)
 }
 
+/// The SHA-256 hash of the customer-provided key the request was encrypted
+/// with, if any.
+pub fn encryption_key_sha256_from_headers_optional(headers: &Headers) -> Option<String> {
+    headers.get_optional_string(&ENCRYPTION_KEY_SHA256)
+}
+
+/// The encryption scope the request was encrypted with, if any.
+pub fn encryption_scope_from_headers_optional(headers: &Headers) -> Option<String> {
+    headers.get_optional_string(&ENCRYPTION_SCOPE)
+}
+
 pub fn consistency_from_headers(
     headers: &Headers,
 ) -> azure_core::Result<(Option<ConsistencyMD5>, Option<ConsistencyCRC64>)> {
@@ -10,6 +10,7 @@ Please use these crates for additional functionality:
 - [`azure_data_tables`](https://crates.io/crates/azure_data_tables)
 - [`azure_storage_blobs`](https://crates.io/crates/azure_storage_blobs)
 - [`azure_storage_datalake`](https://crates.io/crates/azure_storage_datalake)
+- [`azure_storage_files`](https://crates.io/crates/azure_storage_files)
 - [`azure_storage_queues`](https://crates.io/crates/azure_storage_queues)
 */
 
@@ -24,6 +25,7 @@ extern crate serde_derive;
 extern crate azure_core;
 
 mod authorization;
+pub mod client_side_encryption;
 pub mod clients;
 mod cloud_location;
 mod connection_string;
@@ -36,7 +38,7 @@ pub mod shared_access_signature;
 
 pub use self::connection_string::{ConnectionString, EndpointProtocol};
 pub use self::connection_string_builder::ConnectionStringBuilder;
-pub use authorization::{StorageCredentials, StorageCredentialsInner};
+pub use authorization::{StorageCredentials, StorageCredentialsInner, TokenScope};
 pub use cloud_location::*;
 pub mod headers;
 pub use copy_id::{copy_id_from_headers, CopyId};
@@ -1,6 +1,6 @@
-use crate::shared_access_signature::{format_date, SasProtocol, SasToken};
-use azure_core::{auth::Secret, hmac::hmac_sha256};
-use std::fmt;
+use crate::shared_access_signature::{format_date, invalid_permission, SasProtocol, SasToken};
+use azure_core::{auth::Secret, error::Error, hmac::hmac_sha256};
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 use url::form_urlencoded;
 
@@ -128,6 +128,28 @@ impl fmt::Display for AccountSasPermissions {
     }
 }
 
+impl FromStr for AccountSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.read = true,
+                'a' => permissions.add = true,
+                'c' => permissions.create = true,
+                'w' => permissions.write = true,
+                'd' => permissions.delete = true,
+                'l' => permissions.list = true,
+                'u' => permissions.update = true,
+                'p' => permissions.process = true,
+                c => return Err(invalid_permission(c, s)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct AccountSharedAccessSignature {
     account: String,
@@ -0,0 +1,166 @@
+use crate::shared_access_signature::{
+    format_date, invalid_permission, SasProtocol, SasToken, SERVICE_SAS_VERSION,
+};
+use azure_core::{auth::Secret, error::Error, hmac::hmac_sha256};
+use std::{fmt, str::FromStr};
+use time::OffsetDateTime;
+use url::form_urlencoded;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FileSignedResource {
+    File,  // f
+    Share, // s
+}
+
+impl fmt::Display for FileSignedResource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::File => write!(f, "f"),
+            Self::Share => write!(f, "s"),
+        }
+    }
+}
+
+/// Indicate which operations may be performed on a file or share ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#permissions-for-a-file)).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct FileSasPermissions {
+    pub read: bool,   // r - Share | File
+    pub create: bool, // c - Share | File
+    pub write: bool,  // w - Share | File
+    pub delete: bool, // d - Share | File
+    pub list: bool,   // l - Share
+}
+
+impl fmt::Display for FileSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.read {
+            write!(f, "r")?;
+        }
+        if self.create {
+            write!(f, "c")?;
+        }
+        if self.write {
+            write!(f, "w")?;
+        }
+        if self.delete {
+            write!(f, "d")?;
+        }
+        if self.list {
+            write!(f, "l")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for FileSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.read = true,
+                'c' => permissions.create = true,
+                'w' => permissions.write = true,
+                'd' => permissions.delete = true,
+                'l' => permissions.list = true,
+                c => return Err(invalid_permission(c, s)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
+/// A service SAS scoped to a file share or a single file.
+pub struct FileSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    resource: FileSignedResource,
+    permissions: FileSasPermissions, // sp
+    expiry: OffsetDateTime,          // se
+    start: Option<OffsetDateTime>,   // st
+    identifier: Option<String>,
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+}
+
+impl FileSharedAccessSignature {
+    /// `canonicalized_resource` has the form `/file/{account}/{share}` or
+    /// `/file/{account}/{share}/{path}`.
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+        resource: FileSignedResource,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            resource,
+            permissions,
+            expiry,
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+        }
+    }
+
+    setters! {
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+    }
+
+    // Azure documentation: https://docs.microsoft.com/rest/api/storageservices/create-service-sas#version-2018-11-09-and-later
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.to_string(),
+            self.start.map_or(String::new(), format_date),
+            format_date(self.expiry),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+            String::new(), // SIGNED CACHE CONTROL
+            String::new(), // SIGNED CONTENT DISPOSITION
+            String::new(), // SIGNED CONTENT ENCODING
+            String::new(), // SIGNED CONTENT LANGUAGE
+            String::new(), // SIGNED CONTENT TYPE
+        ];
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for FileSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.extend_pairs(&[
+            ("sv", SERVICE_SAS_VERSION),
+            ("sp", &self.permissions.to_string()),
+            ("sr", &self.resource.to_string()),
+            ("se", &format_date(self.expiry)),
+        ]);
+
+        if let Some(start) = &self.start {
+            form.append_pair("st", &format_date(*start));
+        }
+        if let Some(identifier) = &self.identifier {
+            form.append_pair("si", identifier);
+        }
+        if let Some(ip) = &self.ip {
+            form.append_pair("sip", ip);
+        }
+        if let Some(protocol) = &self.protocol {
+            form.append_pair("spr", &protocol.to_string());
+        }
+
+        let sig = self.sign()?;
+        form.append_pair("sig", &sig);
+        Ok(form.finish())
+    }
+}
@@ -1,8 +1,16 @@
-use std::fmt;
+use azure_core::error::{Error, ErrorKind};
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 
 pub mod account_sas;
+pub mod file_sas;
+pub mod parsed_sas;
+pub mod queue_sas;
 pub mod service_sas;
+pub mod table_sas;
+
+/// The version used to sign service shared access signatures.
+pub(crate) const SERVICE_SAS_VERSION: &str = "2022-11-02";
 
 pub trait SasToken {
     fn token(&self) -> azure_core::Result<String>;
@@ -39,6 +47,26 @@ impl fmt::Display for SasProtocol {
     }
 }
 
+impl FromStr for SasProtocol {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        match s {
+            "https" => Ok(SasProtocol::Https),
+            "http,https" => Ok(SasProtocol::HttpHttps),
+            _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("unknown SAS protocol: {s}")
+            })),
+        }
+    }
+}
+
+pub(crate) fn invalid_permission(permission: char, permissions: &str) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!("unknown permission '{permission}' in SAS permissions '{permissions}'")
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,599 @@
+use crate::CloudLocation;
+use azure_core::{
+    auth::Secret,
+    date,
+    error::{Error, ErrorKind, ResultExt},
+    hmac::hmac_sha256,
+};
+use std::str::FromStr;
+use subtle::ConstantTimeEq;
+use time::OffsetDateTime;
+use url::{form_urlencoded, Url};
+
+/// The kind of a parsed shared access signature.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SasKind {
+    /// Signed with the account key, scoped to one or more services.
+    Account,
+    /// Signed with the account key, scoped to a single resource.
+    Service,
+    /// Signed with a user delegation key obtained with Azure AD credentials.
+    UserDelegation,
+}
+
+/// A shared access signature parsed from a SAS token or a SAS URL.
+///
+/// Parsing does not check the signature. Use [`Self::verify_account`],
+/// [`Self::verify_service`] or [`Self::verify_url`] to validate it against an
+/// account key.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ParsedSharedAccessSignature {
+    pub version: String,                     // sv
+    pub services: Option<String>,            // ss
+    pub resource_types: Option<String>,      // srt
+    pub resource: Option<String>,            // sr
+    pub permissions: Option<String>,         // sp
+    pub start: Option<OffsetDateTime>,       // st
+    pub expiry: Option<OffsetDateTime>,      // se
+    pub identifier: Option<String>,          // si
+    pub ip: Option<String>,                  // sip
+    pub protocol: Option<String>,            // spr
+    pub table_name: Option<String>,          // tn
+    pub start_partition_key: Option<String>, // spk
+    pub start_row_key: Option<String>,       // srk
+    pub end_partition_key: Option<String>,   // epk
+    pub end_row_key: Option<String>,         // erk
+    pub directory_depth: Option<usize>,      // sdd
+    pub encryption_scope: Option<String>,    // ses
+    pub cache_control: Option<String>,       // rscc
+    pub content_disposition: Option<String>, // rscd
+    pub content_encoding: Option<String>,    // rsce
+    pub content_language: Option<String>,    // rscl
+    pub content_type: Option<String>,        // rsct
+    pub signed_object_id: Option<String>,    // skoid
+    pub signature: String,                   // sig
+    // the dates as signed, which may not be in canonical form
+    signed_start: Option<String>,
+    signed_expiry: Option<String>,
+}
+
+impl ParsedSharedAccessSignature {
+    /// Parse a SAS token, with or without the leading `?`, or a URL carrying
+    /// one in its query string.
+    pub fn parse(sas: &str) -> azure_core::Result<Self> {
+        let query = match Url::parse(sas) {
+            Ok(url) if url.has_host() => url.query().unwrap_or_default().to_owned(),
+            _ => sas.trim_start_matches('?').to_owned(),
+        };
+
+        let mut parsed = Self::default();
+        let mut version = None;
+        let mut signature = None;
+        for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+            let value = value.into_owned();
+            match name.as_ref() {
+                "sv" => version = Some(value),
+                "ss" => parsed.services = Some(value),
+                "srt" => parsed.resource_types = Some(value),
+                "sr" => parsed.resource = Some(value),
+                "sp" => parsed.permissions = Some(value),
+                "st" => {
+                    parsed.start = Some(parse_sas_date(&value)?);
+                    parsed.signed_start = Some(value);
+                }
+                "se" => {
+                    parsed.expiry = Some(parse_sas_date(&value)?);
+                    parsed.signed_expiry = Some(value);
+                }
+                "si" => parsed.identifier = Some(value),
+                "sip" => parsed.ip = Some(value),
+                "spr" => parsed.protocol = Some(value),
+                "tn" => parsed.table_name = Some(value),
+                "spk" => parsed.start_partition_key = Some(value),
+                "srk" => parsed.start_row_key = Some(value),
+                "epk" => parsed.end_partition_key = Some(value),
+                "erk" => parsed.end_row_key = Some(value),
+                "sdd" => {
+                    parsed.directory_depth =
+                        Some(value.parse().with_context(ErrorKind::DataConversion, || {
+                            format!("invalid signed directory depth: {value}")
+                        })?);
+                }
+                "ses" => parsed.encryption_scope = Some(value),
+                "rscc" => parsed.cache_control = Some(value),
+                "rscd" => parsed.content_disposition = Some(value),
+                "rsce" => parsed.content_encoding = Some(value),
+                "rscl" => parsed.content_language = Some(value),
+                "rsct" => parsed.content_type = Some(value),
+                "skoid" => parsed.signed_object_id = Some(value),
+                "sig" => signature = Some(value),
+                // other parameters belong to the request, or to the user
+                // delegation key, and are not needed for inspection
+                _ => {}
+            }
+        }
+
+        parsed.version = version.ok_or_else(|| missing_parameter("sv"))?;
+        parsed.signature = signature.ok_or_else(|| missing_parameter("sig"))?;
+        Ok(parsed)
+    }
+
+    pub fn kind(&self) -> SasKind {
+        if self.services.is_some() {
+            SasKind::Account
+        } else if self.signed_object_id.is_some() {
+            SasKind::UserDelegation
+        } else {
+            SasKind::Service
+        }
+    }
+
+    /// The permissions as one of the typed permission sets, such as
+    /// `AccountSasPermissions` or `TableSasPermissions`.
+    ///
+    /// Returns the default (empty) set when the permissions come from a stored
+    /// access policy.
+    pub fn typed_permissions<P>(&self) -> azure_core::Result<P>
+    where
+        P: FromStr<Err = Error> + Default,
+    {
+        self.permissions
+            .as_deref()
+            .map_or_else(|| Ok(P::default()), str::parse)
+    }
+
+    /// Whether the signature is within its validity window at `time`.
+    ///
+    /// A signature whose expiry comes from a stored access policy is
+    /// considered valid, as the policy is only known to the service.
+    pub fn is_valid_at(&self, time: OffsetDateTime) -> bool {
+        self.start.map_or(true, |start| start <= time)
+            && self.expiry.map_or(true, |expiry| time < expiry)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expiry
+            .map_or(false, |expiry| expiry <= OffsetDateTime::now_utc())
+    }
+
+    /// Check the signature of an account SAS against the account key.
+    pub fn verify_account(&self, account: &str, key: &Secret) -> azure_core::Result<()> {
+        if self.kind() != SasKind::Account {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "the shared access signature is not an account SAS",
+            ));
+        }
+
+        let mut content = vec![
+            account.to_owned(),
+            self.permissions.clone().unwrap_or_default(),
+            self.services.clone().unwrap_or_default(),
+            self.resource_types.clone().unwrap_or_default(),
+            self.signed_start.clone().unwrap_or_default(),
+            self.signed_expiry.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.clone().unwrap_or_default(),
+            self.version.clone(),
+        ];
+        // the encryption scope was added to the string to sign in 2020-12-06
+        if self.version.as_str() >= "2020-12-06" {
+            content.push(self.encryption_scope.clone().unwrap_or_default());
+        }
+        content.push(String::new());
+
+        self.check_signature(&content.join("\n"), key)
+    }
+
+    /// Check the signature of a service SAS against the account key.
+    ///
+    /// `canonicalized_resource` is the resource the signature was created for,
+    /// such as `/blob/{account}/{container}/{blob}`, `/queue/{account}/{queue}`,
+    /// `/table/{account}/{table}` or `/file/{account}/{share}/{path}`.
+    pub fn verify_service(
+        &self,
+        canonicalized_resource: &str,
+        key: &Secret,
+    ) -> azure_core::Result<()> {
+        match self.kind() {
+            SasKind::Service => {}
+            SasKind::Account => {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "the shared access signature is not a service SAS",
+                ))
+            }
+            SasKind::UserDelegation => {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "user delegation signatures cannot be verified with an account key",
+                ))
+            }
+        }
+
+        let mut content = vec![
+            self.permissions.clone().unwrap_or_default(),
+            self.signed_start.clone().unwrap_or_default(),
+            self.signed_expiry.clone().unwrap_or_default(),
+            canonicalized_resource.to_owned(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.clone().unwrap_or_default(),
+            self.version.clone(),
+        ];
+
+        let service = canonicalized_resource
+            .trim_start_matches('/')
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        match service {
+            "blob" => {
+                // the signed resource and snapshot time were added to the
+                // string to sign in 2018-11-09, the encryption scope in 2020-12-06
+                if self.version.as_str() >= "2018-11-09" {
+                    content.extend([
+                        self.resource.clone().unwrap_or_default(),
+                        String::new(), // snapshot time
+                    ]);
+                }
+                if self.version.as_str() >= "2020-12-06" {
+                    content.push(self.encryption_scope.clone().unwrap_or_default());
+                }
+                content.extend(self.response_headers());
+            }
+            "file" => content.extend(self.response_headers()),
+            "table" => content.extend([
+                self.start_partition_key.clone().unwrap_or_default(),
+                self.start_row_key.clone().unwrap_or_default(),
+                self.end_partition_key.clone().unwrap_or_default(),
+                self.end_row_key.clone().unwrap_or_default(),
+            ]),
+            "queue" => {}
+            _ => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("unsupported canonicalized resource: {canonicalized_resource}")
+                }))
+            }
+        }
+
+        self.check_signature(&content.join("\n"), key)
+    }
+
+    /// Check the signature carried by a SAS URL against the account key.
+    ///
+    /// The account, service and canonicalized resource are derived from the
+    /// URL, which must point to the public or China cloud.
+    pub fn verify_url(url: &Url, key: &Secret) -> azure_core::Result<Self> {
+        let parsed = Self::parse(url.as_str())?;
+        let cloud_location = CloudLocation::try_from(url)?;
+        let account = cloud_location.account();
+
+        if parsed.kind() == SasKind::Account {
+            parsed.verify_account(account, key)?;
+            return Ok(parsed);
+        }
+
+        let subdomain = url
+            .host_str()
+            .and_then(|host| host.split('.').nth(1))
+            .unwrap_or_default();
+        let segments: Vec<String> = url
+            .path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|segment| !segment.is_empty())
+                    .map(percent_decode)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let canonicalized_resource = match subdomain {
+            "blob" | "dfs" => {
+                let segments = match parsed.resource.as_deref() {
+                    Some("c") => &segments[..segments.len().min(1)],
+                    _ => &segments[..],
+                };
+                format!("/blob/{}/{}", account, segments.join("/"))
+            }
+            "file" => {
+                let segments = match parsed.resource.as_deref() {
+                    Some("s") => &segments[..segments.len().min(1)],
+                    _ => &segments[..],
+                };
+                format!("/file/{}/{}", account, segments.join("/"))
+            }
+            "queue" => format!(
+                "/queue/{}/{}",
+                account,
+                segments.first().map(String::as_str).unwrap_or_default()
+            ),
+            "table" => {
+                let table_name = parsed
+                    .table_name
+                    .as_deref()
+                    .ok_or_else(|| missing_parameter("tn"))?;
+                format!("/table/{}/{}", account, table_name.to_lowercase())
+            }
+            _ => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("unsupported storage service in URL: {url}")
+                }))
+            }
+        };
+
+        parsed.verify_service(&canonicalized_resource, key)?;
+        Ok(parsed)
+    }
+
+    fn response_headers(&self) -> [String; 5] {
+        [
+            self.cache_control.clone().unwrap_or_default(),
+            self.content_disposition.clone().unwrap_or_default(),
+            self.content_encoding.clone().unwrap_or_default(),
+            self.content_language.clone().unwrap_or_default(),
+            self.content_type.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn check_signature(&self, string_to_sign: &str, key: &Secret) -> azure_core::Result<()> {
+        // compare in constant time, not to leak how much of a forged signature matches
+        let signature = hmac_sha256(string_to_sign, key)?;
+        if bool::from(signature.as_bytes().ct_eq(self.signature.as_bytes())) {
+            Ok(())
+        } else {
+            Err(Error::message(
+                ErrorKind::Credential,
+                "the shared access signature does not match the account key",
+            ))
+        }
+    }
+}
+
+impl FromStr for ParsedSharedAccessSignature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        Self::parse(s)
+    }
+}
+
+fn parse_sas_date(s: &str) -> azure_core::Result<OffsetDateTime> {
+    // dates may omit the seconds, or the time altogether
+    let normalized = match s.len() {
+        10 => format!("{s}T00:00:00Z"),
+        17 => format!("{}:00Z", s.trim_end_matches('Z')),
+        _ => s.to_owned(),
+    };
+    date::parse_rfc3339(&normalized)
+}
+
+fn missing_parameter(name: &str) -> Error {
+    Error::with_message(ErrorKind::DataConversion, || {
+        format!("shared access signature is missing the '{name}' parameter")
+    })
+}
+
+fn percent_decode(segment: &str) -> String {
+    percent_encoding::percent_decode_str(segment)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared_access_signature::{
+        account_sas::{
+            AccountSasPermissions, AccountSasResource, AccountSasResourceType,
+            AccountSharedAccessSignature,
+        },
+        file_sas::{FileSasPermissions, FileSharedAccessSignature, FileSignedResource},
+        queue_sas::{QueueSasPermissions, QueueSharedAccessSignature},
+        service_sas::BlobSasPermissions,
+        table_sas::{TableSasPermissions, TableSharedAccessSignature},
+        SasToken,
+    };
+    use time::{macros::datetime, Duration};
+
+    const MOCK_SECRET_KEY: &str = "RZfi3m1W7eyQ5zD4ymSmGANVdJ2SDQmg4sE89SW104s=";
+    const OTHER_SECRET_KEY: &str = "c2VjcmV0c2VjcmV0c2VjcmV0c2VjcmV0c2VjcmV0MTI=";
+
+    fn key() -> Secret {
+        Secret::new(MOCK_SECRET_KEY)
+    }
+
+    #[test]
+    fn parse_blob_token() -> azure_core::Result<()> {
+        // token from the `service_sas` tests
+        let token = "sv=2022-11-02&sp=r&sr=b&se=1970-01-08T00%3A00%3A00Z&sig=VRZjVZ1c%2FLz7IXCp17Sdx9%2BR9JDrnJdzE3NW56DMjNs%3D";
+        let parsed = ParsedSharedAccessSignature::parse(token)?;
+
+        assert_eq!(parsed.kind(), SasKind::Service);
+        assert_eq!(parsed.version, "2022-11-02");
+        assert_eq!(parsed.resource.as_deref(), Some("b"));
+        assert_eq!(parsed.expiry, Some(datetime!(1970-01-08 0:00 UTC)));
+        assert!(parsed.is_expired());
+        assert!(parsed.typed_permissions::<BlobSasPermissions>()?.read);
+
+        parsed.verify_service("/blob/STORAGE_ACCOUNT_NAME/CONTAINER_NAME/", &key())?;
+        assert!(parsed
+            .verify_service("/blob/STORAGE_ACCOUNT_NAME/OTHER/", &key())
+            .is_err());
+        assert!(parsed
+            .verify_service(
+                "/blob/STORAGE_ACCOUNT_NAME/CONTAINER_NAME/",
+                &Secret::new(OTHER_SECRET_KEY)
+            )
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_url_and_validity_window() -> azure_core::Result<()> {
+        let parsed = ParsedSharedAccessSignature::parse(
+            "https://account.queue.core.windows.net/jobs?sv=2022-11-02&st=2023-04-05&se=2023-04-06T12%3A30Z&sp=raup&sig=abc",
+        )?;
+        assert_eq!(parsed.start, Some(datetime!(2023-04-05 0:00 UTC)));
+        assert_eq!(parsed.expiry, Some(datetime!(2023-04-06 12:30 UTC)));
+        assert!(parsed.is_valid_at(datetime!(2023-04-05 8:00 UTC)));
+        assert!(!parsed.is_valid_at(datetime!(2023-04-04 8:00 UTC)));
+        assert!(!parsed.is_valid_at(datetime!(2023-04-06 12:30 UTC)));
+
+        let permissions: QueueSasPermissions = parsed.typed_permissions()?;
+        assert!(permissions.read && permissions.add && permissions.update && permissions.process);
+
+        assert!(ParsedSharedAccessSignature::parse("?sp=r&sig=abc").is_err());
+        assert!(ParsedSharedAccessSignature::parse("sv=2022-11-02&sp=r").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn verify_account_token() -> azure_core::Result<()> {
+        let token = AccountSharedAccessSignature::new(
+            "account".to_owned(),
+            key(),
+            AccountSasResource::Blob,
+            AccountSasResourceType::Container,
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            AccountSasPermissions {
+                read: true,
+                list: true,
+                ..Default::default()
+            },
+        )
+        .token()?;
+
+        let parsed: ParsedSharedAccessSignature = token.parse()?;
+        assert_eq!(parsed.kind(), SasKind::Account);
+        parsed.verify_account("account", &key())?;
+        assert!(parsed.verify_account("other", &key()).is_err());
+        assert!(parsed.verify_service("/blob/account/c", &key()).is_err());
+
+        let url = Url::parse(&format!("https://account.blob.core.windows.net/?{token}"))?;
+        ParsedSharedAccessSignature::verify_url(&url, &key())?;
+        Ok(())
+    }
+
+    #[test]
+    fn verify_service_tokens() -> azure_core::Result<()> {
+        let expiry = datetime!(2030-01-01 0:00 UTC);
+
+        let queue = QueueSharedAccessSignature::new(
+            key(),
+            "/queue/account/jobs".to_owned(),
+            QueueSasPermissions {
+                process: true,
+                ..Default::default()
+            },
+            expiry,
+        )
+        .protocol(crate::shared_access_signature::SasProtocol::Https)
+        .token()?;
+        let url = Url::parse(&format!(
+            "https://account.queue.core.windows.net/jobs/messages?{queue}"
+        ))?;
+        let parsed = ParsedSharedAccessSignature::verify_url(&url, &key())?;
+        assert_eq!(parsed.protocol.as_deref(), Some("https"));
+
+        let table = TableSharedAccessSignature::new(
+            key(),
+            "/table/account/orders".to_owned(),
+            "Orders".to_owned(),
+            TableSasPermissions {
+                query: true,
+                ..Default::default()
+            },
+            expiry,
+        )
+        .start_partition_key("2023")
+        .end_partition_key("2023")
+        .token()?;
+        let url = Url::parse(&format!(
+            "https://account.table.core.windows.net/Orders()?{table}"
+        ))?;
+        let parsed = ParsedSharedAccessSignature::verify_url(&url, &key())?;
+        assert_eq!(parsed.table_name.as_deref(), Some("Orders"));
+        assert_eq!(parsed.start_partition_key.as_deref(), Some("2023"));
+        assert_eq!(parsed.start_row_key, None);
+
+        let file = FileSharedAccessSignature::new(
+            key(),
+            "/file/account/reports/2023/q1 summary.txt".to_owned(),
+            FileSasPermissions {
+                read: true,
+                ..Default::default()
+            },
+            expiry,
+            FileSignedResource::File,
+        )
+        .token()?;
+        let url = Url::parse(&format!(
+            "https://account.file.core.windows.net/reports/2023/q1%20summary.txt?{file}"
+        ))?;
+        ParsedSharedAccessSignature::verify_url(&url, &key())?;
+
+        let share_url = Url::parse(&format!(
+            "https://account.file.core.windows.net/reports/2023/other.txt?{file}"
+        ))?;
+        assert!(ParsedSharedAccessSignature::verify_url(&share_url, &key()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn verify_older_blob_tokens() -> azure_core::Result<()> {
+        // (version, the fields which follow the version in the string to sign)
+        let versions = [
+            ("2017-11-09", "\n\n\n\n\n"),
+            ("2019-12-12", "\nb\n\n\n\n\n\n"),
+        ];
+        for (version, fields) in versions {
+            let string_to_sign = format!(
+                "r\n\n2030-01-01T00:00:00Z\n/blob/account/c/b.txt\n\n\nhttps\n{version}{fields}"
+            );
+            let signature = hmac_sha256(&string_to_sign, &key())?;
+            let token = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs([
+                    ("sv", version),
+                    ("sr", "b"),
+                    ("sp", "r"),
+                    ("se", "2030-01-01T00:00:00Z"),
+                    ("spr", "https"),
+                    ("sig", &signature),
+                ])
+                .finish();
+            let url = Url::parse(&format!(
+                "https://account.blob.core.windows.net/c/b.txt?{token}"
+            ))?;
+            ParsedSharedAccessSignature::verify_url(&url, &key())?;
+            assert!(
+                ParsedSharedAccessSignature::verify_url(&url, &Secret::new(OTHER_SECRET_KEY))
+                    .is_err()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn verify_escaped_paths() -> azure_core::Result<()> {
+        let file = FileSharedAccessSignature::new(
+            key(),
+            "/file/account/reports/growth 100%".to_owned(),
+            FileSasPermissions {
+                read: true,
+                ..Default::default()
+            },
+            datetime!(2030-01-01 0:00 UTC),
+            FileSignedResource::File,
+        )
+        .token()?;
+        let url = Url::parse(&format!(
+            "https://account.file.core.windows.net/reports/growth%20100%25?{file}"
+        ))?;
+        ParsedSharedAccessSignature::verify_url(&url, &key())?;
+
+        let forged = url.as_str().replace("&sig=", "&sig=A");
+        assert!(ParsedSharedAccessSignature::verify_url(&Url::parse(&forged)?, &key()).is_err());
+        Ok(())
+    }
+}
@@ -1,12 +1,19 @@
-use crate::shared_access_signature::{format_date, SasProtocol, SasToken};
-use azure_core::{auth::Secret, date::iso8601, hmac::hmac_sha256};
-use std::fmt;
+use crate::shared_access_signature::{
+    format_date, invalid_permission, SasProtocol, SasToken, SERVICE_SAS_VERSION,
+};
+use azure_core::{
+    auth::Secret,
+    date::iso8601,
+    error::{Error, ErrorKind},
+    hmac::hmac_sha256,
+    xml::to_xml,
+};
+use bytes::{Bytes, BytesMut};
+use std::{fmt, str::FromStr};
 use time::OffsetDateTime;
 use url::form_urlencoded;
 use uuid::Uuid;
 
-const SERVICE_SAS_VERSION: &str = "2022-11-02";
-
 pub enum BlobSignedResource {
     Blob,         // b
     BlobVersion,  // bv
@@ -28,7 +35,7 @@ impl fmt::Display for BlobSignedResource {
 }
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Default)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
 pub struct BlobSasPermissions {
     pub read: bool,             // r - Container | Directory | Blob
     pub add: bool,              // a - Container | Directory | Blob
@@ -91,6 +98,33 @@ impl fmt::Display for BlobSasPermissions {
     }
 }
 
+impl FromStr for BlobSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.read = true,
+                'a' => permissions.add = true,
+                'c' => permissions.create = true,
+                'w' => permissions.write = true,
+                'd' => permissions.delete = true,
+                'x' => permissions.delete_version = true,
+                'y' => permissions.permanent_delete = true,
+                'l' => permissions.list = true,
+                't' => permissions.tags = true,
+                'm' => permissions.move_ = true,
+                'e' => permissions.execute = true,
+                'o' => permissions.ownership = true,
+                'p' => permissions.permissions = true,
+                c => return Err(invalid_permission(c, s)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct UserDeligationKey {
@@ -105,6 +139,25 @@ pub struct UserDeligationKey {
     pub value: Secret,
 }
 
+/// The body of a request for a user delegation key, shared by the services
+/// which issue one.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename = "KeyInfo")]
+pub struct UserDelegationKeyRequest {
+    #[serde(rename = "Start", with = "iso8601")]
+    pub start: OffsetDateTime,
+    #[serde(rename = "Expiry", with = "iso8601")]
+    pub expiry: OffsetDateTime,
+}
+
+impl UserDelegationKeyRequest {
+    pub fn encode(&self) -> azure_core::Result<Bytes> {
+        let mut body = BytesMut::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>");
+        body.extend(to_xml(self)?);
+        Ok(body.freeze())
+    }
+}
+
 pub enum SasKey {
     Key(Secret),
     UserDelegationKey(UserDeligationKey),
@@ -132,7 +185,10 @@ pub struct BlobSharedAccessSignature {
     identifier: Option<String>,
     ip: Option<String>,
     protocol: Option<SasProtocol>,
-    signed_directory_depth: Option<usize>, // sdd
+    signed_directory_depth: Option<usize>,         // sdd
+    signed_authorized_object_id: Option<String>,   // saoid
+    signed_unauthorized_object_id: Option<String>, // suoid
+    signed_correlation_id: Option<String>,         // scid
 }
 
 impl BlobSharedAccessSignature {
@@ -157,6 +213,9 @@ impl BlobSharedAccessSignature {
             ip: None,
             protocol: None,
             signed_directory_depth: None,
+            signed_authorized_object_id: None,
+            signed_unauthorized_object_id: None,
+            signed_correlation_id: None,
         }
     }
 
@@ -166,6 +225,16 @@ impl BlobSharedAccessSignature {
         ip: String => Some(ip),
         protocol: SasProtocol => Some(protocol),
         signed_directory_depth: usize => Some(signed_directory_depth),
+        /// With a user delegation key, the object ID of the user the signature is issued to.
+        /// Data Lake checks its POSIX ACLs for this user. Cannot be combined with
+        /// `signed_unauthorized_object_id`.
+        signed_authorized_object_id: String => Some(signed_authorized_object_id),
+        /// With a user delegation key, the object ID of a user, not authorized by the key
+        /// owner, for whom Data Lake checks its POSIX ACLs. Cannot be combined with
+        /// `signed_authorized_object_id`.
+        signed_unauthorized_object_id: String => Some(signed_unauthorized_object_id),
+        /// With a user delegation key, an ID logged with the requests using the signature.
+        signed_correlation_id: String => Some(signed_correlation_id),
     }
 
     fn sign(&self) -> azure_core::Result<String> {
@@ -193,9 +262,11 @@ impl BlobSharedAccessSignature {
                     format_date(key.signed_expiry),
                     key.signed_service.to_string(),
                     key.signed_version.to_string(),
-                    String::new(), // SIGNED AUTHORIZED_OID
-                    String::new(), // SIGNED UNAUTHORIZED_OID
-                    String::new(), // SIGNED CORRELATION ID
+                    self.signed_authorized_object_id.clone().unwrap_or_default(),
+                    self.signed_unauthorized_object_id
+                        .clone()
+                        .unwrap_or_default(),
+                    self.signed_correlation_id.clone().unwrap_or_default(),
                 ];
 
                 content.extend(user_delegated);
@@ -223,6 +294,15 @@ impl BlobSharedAccessSignature {
 
 impl SasToken for BlobSharedAccessSignature {
     fn token(&self) -> azure_core::Result<String> {
+        if self.signed_authorized_object_id.is_some()
+            && self.signed_unauthorized_object_id.is_some()
+        {
+            return Err(Error::message(
+                ErrorKind::Other,
+                "a signed authorized object id and a signed unauthorized object id cannot both be set",
+            ));
+        }
+
         let mut form = form_urlencoded::Serializer::new(String::new());
 
         if let SasKey::UserDelegationKey(key) = &self.key {
@@ -234,6 +314,16 @@ impl SasToken for BlobSharedAccessSignature {
                 ("sks", &key.signed_service),
                 ("skv", &key.signed_version),
             ]);
+
+            if let Some(object_id) = &self.signed_authorized_object_id {
+                form.append_pair("saoid", object_id);
+            }
+            if let Some(object_id) = &self.signed_unauthorized_object_id {
+                form.append_pair("suoid", object_id);
+            }
+            if let Some(correlation_id) = &self.signed_correlation_id {
+                form.append_pair("scid", correlation_id);
+            }
         }
 
         form.extend_pairs(&[
@@ -247,6 +337,10 @@ impl SasToken for BlobSharedAccessSignature {
             form.append_pair("st", &format_date(*start));
         }
 
+        if let Some(identifier) = &self.identifier {
+            form.append_pair("si", identifier);
+        }
+
         if let Some(ip) = &self.ip {
             form.append_pair("sip", ip);
         }
@@ -272,6 +366,18 @@ mod test {
 
     const MOCK_SECRET_KEY: &str = "RZfi3m1W7eyQ5zD4ymSmGANVdJ2SDQmg4sE89SW104s=";
     const MOCK_CANONICALIZED_RESOURCE: &str = "/blob/STORAGE_ACCOUNT_NAME/CONTAINER_NAME/";
+    const USER_DELEGATION_KEY_REQUEST: &[u8] = b"<?xml version=\"1.0\" encoding=\"utf-8\"?><KeyInfo><Start>1970-01-01T00:00:00Z</Start><Expiry>1970-01-01T00:00:01Z</Expiry></KeyInfo>";
+
+    #[test]
+    fn user_delegation_key_request_xml() -> azure_core::Result<()> {
+        let request = UserDelegationKeyRequest {
+            start: OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            expiry: OffsetDateTime::from_unix_timestamp(1).unwrap(),
+        }
+        .encode()?;
+        assert_eq!(USER_DELEGATION_KEY_REQUEST, request);
+        Ok(())
+    }
 
     #[test]
     fn test_blob_scoped_sas_token() -> azure_core::Result<()> {
@@ -290,13 +396,13 @@ mod test {
 
         assert_eq!(signed_token, "sv=2022-11-02&sp=r&sr=b&se=1970-01-08T00%3A00%3A00Z&sig=VRZjVZ1c%2FLz7IXCp17Sdx9%2BR9JDrnJdzE3NW56DMjNs%3D");
 
-        let mut parsed = url::form_urlencoded::parse(&signed_token.as_bytes());
+        let mut parsed = url::form_urlencoded::parse(signed_token.as_bytes());
 
         // BlobSignedResource::Blob
-        assert!(parsed.find(|(k, v)| k == "sr" && v == "b").is_some());
+        assert!(parsed.any(|(k, v)| k == "sr" && v == "b"));
 
         // signed_directory_depth NOT set
-        assert!(parsed.find(|(k, _)| k == "sdd").is_none());
+        assert!(!parsed.any(|(k, _)| k == "sdd"));
         Ok(())
     }
 
@@ -318,13 +424,77 @@ mod test {
 
         assert_eq!(signed_token, "sv=2022-11-02&sp=r&sr=d&se=1970-01-08T00%3A00%3A00Z&sdd=2&sig=zVN%2FRgDWllHZH6%2FqWt5gFrV89vzp4EU6ULDTdYoHils%3D");
 
-        let mut parsed = url::form_urlencoded::parse(&signed_token.as_bytes());
+        let mut parsed = url::form_urlencoded::parse(signed_token.as_bytes());
 
         // BlobSignedResource::Directory
-        assert!(parsed.find(|(k, v)| k == "sr" && v == "d").is_some());
+        assert!(parsed.any(|(k, v)| k == "sr" && v == "d"));
 
         // signed_directory_depth set
-        assert!(parsed.find(|(k, v)| k == "sdd" && v == "2").is_some());
+        assert!(parsed.any(|(k, v)| k == "sdd" && v == "2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_delegation_directory_sas_token() -> azure_core::Result<()> {
+        let permissions = BlobSasPermissions {
+            read: true,
+            list: true,
+            ..Default::default()
+        };
+        let user_delegation_key = UserDeligationKey {
+            signed_oid: Uuid::from_u128(0),
+            signed_tid: Uuid::from_u128(1),
+            signed_start: OffsetDateTime::UNIX_EPOCH,
+            signed_expiry: OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            signed_service: "b".to_owned(),
+            signed_version: SERVICE_SAS_VERSION.to_owned(),
+            value: Secret::new(MOCK_SECRET_KEY),
+        };
+        let signed_token = BlobSharedAccessSignature::new(
+            user_delegation_key,
+            format!("{MOCK_CANONICALIZED_RESOURCE}dir/sub"),
+            permissions,
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            BlobSignedResource::Directory,
+        )
+        .signed_directory_depth(2_usize)
+        .signed_authorized_object_id(Uuid::from_u128(2).to_string())
+        .signed_correlation_id("corr")
+        .token()?;
+
+        assert_eq!(signed_token, "skoid=00000000-0000-0000-0000-000000000000&sktid=00000000-0000-0000-0000-000000000001&skt=1970-01-01T00%3A00%3A00Z&ske=1970-01-08T00%3A00%3A00Z&sks=b&skv=2022-11-02&saoid=00000000-0000-0000-0000-000000000002&scid=corr&sv=2022-11-02&sp=rl&sr=d&se=1970-01-08T00%3A00%3A00Z&sdd=2&sig=xw%2BrR2%2FHvuEKhCuE2V9SEc%2FxC4HFVjEyVGsVTu6nbsE%3D");
+
+        // signed_unauthorized_object_id NOT set
+        let mut parsed = url::form_urlencoded::parse(signed_token.as_bytes());
+        assert!(!parsed.any(|(k, _)| k == "suoid"));
         Ok(())
     }
+
+    #[test]
+    fn test_blob_user_delegation_sas_rejects_both_object_ids() {
+        let user_delegation_key = UserDeligationKey {
+            signed_oid: Uuid::from_u128(0),
+            signed_tid: Uuid::from_u128(1),
+            signed_start: OffsetDateTime::UNIX_EPOCH,
+            signed_expiry: OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            signed_service: "b".to_owned(),
+            signed_version: SERVICE_SAS_VERSION.to_owned(),
+            value: Secret::new(MOCK_SECRET_KEY),
+        };
+        let result = BlobSharedAccessSignature::new(
+            user_delegation_key,
+            format!("{MOCK_CANONICALIZED_RESOURCE}dir"),
+            BlobSasPermissions {
+                read: true,
+                ..Default::default()
+            },
+            OffsetDateTime::UNIX_EPOCH + Duration::days(7),
+            BlobSignedResource::Directory,
+        )
+        .signed_authorized_object_id(Uuid::from_u128(2).to_string())
+        .signed_unauthorized_object_id(Uuid::from_u128(3).to_string())
+        .token();
+
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,168 @@
+use crate::shared_access_signature::{
+    format_date, invalid_permission, SasProtocol, SasToken, SERVICE_SAS_VERSION,
+};
+use azure_core::{auth::Secret, error::Error, hmac::hmac_sha256};
+use std::{fmt, str::FromStr};
+use time::OffsetDateTime;
+use url::form_urlencoded;
+
+/// Indicate which operations may be performed on a table ([Azure documentation](https://docs.microsoft.com/rest/api/storageservices/create-service-sas#permissions-for-a-table)).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct TableSasPermissions {
+    pub query: bool,  // r - query entities
+    pub add: bool,    // a - insert entities
+    pub update: bool, // u - update and merge entities
+    pub delete: bool, // d - delete entities
+}
+
+impl fmt::Display for TableSasPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.query {
+            write!(f, "r")?;
+        }
+        if self.add {
+            write!(f, "a")?;
+        }
+        if self.update {
+            write!(f, "u")?;
+        }
+        if self.delete {
+            write!(f, "d")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TableSasPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let mut permissions = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => permissions.query = true,
+                'a' => permissions.add = true,
+                'u' => permissions.update = true,
+                'd' => permissions.delete = true,
+                c => return Err(invalid_permission(c, s)),
+            }
+        }
+        Ok(permissions)
+    }
+}
+
+/// A service SAS scoped to a table, optionally restricted to a range of
+/// partition and row keys.
+pub struct TableSharedAccessSignature {
+    key: Secret,
+    canonicalized_resource: String,
+    table_name: String,               // tn
+    permissions: TableSasPermissions, // sp
+    expiry: OffsetDateTime,           // se
+    start: Option<OffsetDateTime>,    // st
+    identifier: Option<String>,
+    ip: Option<String>,
+    protocol: Option<SasProtocol>,
+    start_partition_key: Option<String>, // spk
+    start_row_key: Option<String>,       // srk
+    end_partition_key: Option<String>,   // epk
+    end_row_key: Option<String>,         // erk
+}
+
+impl TableSharedAccessSignature {
+    /// `canonicalized_resource` has the form `/table/{account}/{table}`, with
+    /// the table name in lowercase.
+    pub fn new(
+        key: Secret,
+        canonicalized_resource: String,
+        table_name: String,
+        permissions: TableSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> Self {
+        Self {
+            key,
+            canonicalized_resource,
+            table_name,
+            permissions,
+            expiry,
+            start: None,
+            identifier: None,
+            ip: None,
+            protocol: None,
+            start_partition_key: None,
+            start_row_key: None,
+            end_partition_key: None,
+            end_row_key: None,
+        }
+    }
+
+    setters! {
+        start: OffsetDateTime => Some(start),
+        identifier: String => Some(identifier),
+        ip: String => Some(ip),
+        protocol: SasProtocol => Some(protocol),
+        start_partition_key: String => Some(start_partition_key),
+        start_row_key: String => Some(start_row_key),
+        end_partition_key: String => Some(end_partition_key),
+        end_row_key: String => Some(end_row_key),
+    }
+
+    // Azure documentation: https://docs.microsoft.com/rest/api/storageservices/create-service-sas#version-2018-11-09-and-later
+    fn sign(&self) -> azure_core::Result<String> {
+        let content = [
+            self.permissions.to_string(),
+            self.start.map_or(String::new(), format_date),
+            format_date(self.expiry),
+            self.canonicalized_resource.clone(),
+            self.identifier.clone().unwrap_or_default(),
+            self.ip.clone().unwrap_or_default(),
+            self.protocol.map(|x| x.to_string()).unwrap_or_default(),
+            SERVICE_SAS_VERSION.to_string(),
+            self.start_partition_key.clone().unwrap_or_default(),
+            self.start_row_key.clone().unwrap_or_default(),
+            self.end_partition_key.clone().unwrap_or_default(),
+            self.end_row_key.clone().unwrap_or_default(),
+        ];
+        hmac_sha256(&content.join("\n"), &self.key)
+    }
+}
+
+impl SasToken for TableSharedAccessSignature {
+    fn token(&self) -> azure_core::Result<String> {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.extend_pairs(&[
+            ("sv", SERVICE_SAS_VERSION),
+            ("tn", &self.table_name),
+            ("sp", &self.permissions.to_string()),
+            ("se", &format_date(self.expiry)),
+        ]);
+
+        if let Some(start) = &self.start {
+            form.append_pair("st", &format_date(*start));
+        }
+        if let Some(identifier) = &self.identifier {
+            form.append_pair("si", identifier);
+        }
+        if let Some(ip) = &self.ip {
+            form.append_pair("sip", ip);
+        }
+        if let Some(protocol) = &self.protocol {
+            form.append_pair("spr", &protocol.to_string());
+        }
+        for (name, value) in [
+            ("spk", &self.start_partition_key),
+            ("srk", &self.start_row_key),
+            ("epk", &self.end_partition_key),
+            ("erk", &self.end_row_key),
+        ] {
+            if let Some(value) = value {
+                form.append_pair(name, value);
+            }
+        }
+
+        let sig = self.sign()?;
+        form.append_pair("sig", &sig);
+        Ok(form.finish())
+    }
+}
@@ -17,7 +17,7 @@ pub use block_with_size_list::BlockWithSizeList;
 pub use lease_blob_options::{LeaseBlobOptions, LEASE_BLOB_OPTIONS_DEFAULT};
 pub use page_range_list::PageRangeList;
 
-use crate::options::{AccessTier, Snapshot, Tags, SNAPSHOT};
+use crate::options::{AccessTier, ImmutabilityPolicyMode, Snapshot, Tags, SNAPSHOT};
 use azure_core::{
     content_type, date,
     headers::{self, Headers},
@@ -94,11 +94,22 @@ pub struct Blob {
     pub version_id: Option<String>,
     pub is_current_version: Option<bool>,
     pub deleted: Option<bool>,
+    pub has_versions_only: Option<bool>,
     pub properties: BlobProperties,
     pub metadata: Option<HashMap<String, String>>,
     pub tags: Option<Tags>,
 }
 
+/// The soft delete state of a blob returned when listing with `include_deleted`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobDeletedState {
+    pub deleted_time: Option<OffsetDateTime>,
+    pub remaining_retention_days: Option<u32>,
+    /// The blob itself is deleted, but it still has versions that can be
+    /// promoted to restore it.
+    pub has_versions_only: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct BlobProperties {
@@ -176,11 +187,28 @@ pub struct BlobProperties {
     pub expiry_time: Option<OffsetDateTime>,
     pub blob_committed_block_count: Option<u64>,
     pub resource_type: Option<String>,
+    #[serde(default, with = "azure_core::date::rfc1123::option")]
+    pub immutability_policy_until_date: Option<OffsetDateTime>,
+    pub immutability_policy_mode: Option<ImmutabilityPolicyMode>,
+    pub legal_hold: Option<bool>,
     #[serde(flatten)]
     extra: HashMap<String, Value>, // For debug purposes, should be compiled out in the future
 }
 
 impl Blob {
+    /// Returns the soft delete state of the blob, or `None` if it is not deleted.
+    pub fn deleted_state(&self) -> Option<BlobDeletedState> {
+        let has_versions_only = self.has_versions_only.unwrap_or(false);
+        if !self.deleted.unwrap_or(false) && !has_versions_only {
+            return None;
+        }
+        Some(BlobDeletedState {
+            deleted_time: self.properties.deleted_time,
+            remaining_retention_days: self.properties.remaining_retention_days,
+            has_versions_only,
+        })
+    }
+
     pub(crate) fn from_headers<BN: Into<String>>(
         blob_name: BN,
         h: &Headers,
@@ -223,6 +251,16 @@ impl Blob {
         let copy_status_description = h.get_optional_string(&headers::COPY_STATUS_DESCRIPTION);
         let server_encrypted = h.get_as(&headers::SERVER_ENCRYPTED)?;
         let blob_committed_block_count = h.get_optional_as(&headers::BLOB_COMMITTED_BLOCK_COUNT)?;
+        let immutability_policy_until_date = h
+            .get_optional_str(&azure_storage::headers::IMMUTABILITY_POLICY_UNTIL_DATE)
+            .map(date::parse_rfc1123)
+            .transpose()?;
+        let immutability_policy_mode =
+            h.get_optional_as(&azure_storage::headers::IMMUTABILITY_POLICY_MODE)?;
+        let legal_hold = h.get_optional_as(&azure_storage::headers::LEGAL_HOLD)?;
+        let customer_provided_key_sha256 =
+            azure_storage::headers::encryption_key_sha256_from_headers_optional(h);
+        let encryption_scope = azure_storage::headers::encryption_scope_from_headers_optional(h);
 
         let mut metadata = HashMap::new();
         for (name, value) in h.iter() {
@@ -244,7 +282,8 @@ impl Blob {
         Ok(Blob {
             name: blob_name.into(),
             snapshot,
-            deleted: None,            //TODO
+            deleted: None, //TODO
+            has_versions_only: None,
             is_current_version: None, //TODO
             version_id: None,         //TODO
             properties: BlobProperties {
@@ -274,17 +313,20 @@ impl Blob {
                 copy_status_description,
                 incremental_copy: None, // TODO: Not present or documentation bug?
                 server_encrypted,
-                customer_provided_key_sha256: None, // TODO
-                encryption_scope: None,             // TODO
-                access_tier_inferred: None,         // TODO: Not present
-                access_tier_change_time: None,      // TODO: Not present
-                deleted_time: None,                 // TODO
-                remaining_retention_days: None,     // TODO: Not present or documentation bug?
-                tag_count: None,                    // TODO
-                rehydrate_priority: None,           // TODO
+                customer_provided_key_sha256,
+                encryption_scope,
+                access_tier_inferred: None,     // TODO: Not present
+                access_tier_change_time: None,  // TODO: Not present
+                deleted_time: None,             // TODO
+                remaining_retention_days: None, // TODO: Not present or documentation bug?
+                tag_count: None,                // TODO
+                rehydrate_priority: None,       // TODO
                 expiry_time: None,
                 resource_type: None,
                 blob_committed_block_count,
+                immutability_policy_until_date,
+                immutability_policy_mode,
+                legal_hold,
                 extra: HashMap::new(),
             },
             metadata,
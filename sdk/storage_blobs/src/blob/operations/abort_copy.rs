@@ -0,0 +1,53 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, RequestId};
+use azure_storage::{headers::COPY_ACTION, CopyId};
+use time::OffsetDateTime;
+
+operation! {
+    AbortCopy,
+    client: BlobClient,
+    copy_id: CopyId,
+    ?lease_id: LeaseId
+}
+
+impl AbortCopyBuilder {
+    pub fn into_future(mut self) -> AbortCopy {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("comp", "copy")
+                .append_pair("copyid", &self.copy_id.to_string());
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_ACTION, "abort");
+            headers.add(self.lease_id);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AbortCopyResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub version: String,
+    pub date: OffsetDateTime,
+}
+
+impl TryFrom<&Headers> for AbortCopyResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(AbortCopyResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers),
+            version: version_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
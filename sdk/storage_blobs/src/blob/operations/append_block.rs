@@ -11,12 +11,16 @@ operation! {
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl AppendBlockBuilder {
     pub fn into_future(mut self) -> AppendBlock {
         Box::pin(async move {
+            self.client.check_plaintext_write("append_block")?;
+
             let mut url = self.client.url()?;
 
             url.query_pairs_mut().append_pair("comp", "appendblock");
@@ -29,6 +33,8 @@ impl AppendBlockBuilder {
             headers.add(self.if_match);
             headers.add(self.if_tags);
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
 
             let mut request = BlobClient::finalize_request(
                 url,
@@ -9,7 +9,9 @@ operation! {
     ?if_sequence_number: IfSequenceNumber,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl ClearPageBuilder {
@@ -27,6 +29,8 @@ impl ClearPageBuilder {
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
 
             let mut request =
                 BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
@@ -16,6 +16,8 @@ operation! {
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
     ?access_tier: AccessTier,
+    ?immutability_policy: ImmutabilityPolicy,
+    ?legal_hold: LegalHold,
     ?lease_id: LeaseId,
     ?if_source_since: IfSourceModifiedSinceCondition,
     ?if_source_match: IfSourceMatchCondition,
@@ -26,6 +28,8 @@ operation! {
 impl CopyBlobBuilder {
     pub fn into_future(mut self) -> CopyBlob {
         Box::pin(async move {
+            self.client.check_plaintext_write("copy")?;
+
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
@@ -39,6 +43,8 @@ impl CopyBlobBuilder {
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.access_tier);
+            headers.add(self.immutability_policy);
+            headers.add(self.legal_hold);
             headers.add(self.lease_id);
             headers.add(self.if_source_since);
             headers.add(self.if_source_match);
@@ -4,7 +4,12 @@ use crate::{
 };
 use azure_core::{headers::*, prelude::*, RequestId, Url};
 use azure_storage::{
-    copy_id_from_headers, headers::content_md5_from_headers_optional, ConsistencyMD5, CopyId,
+    copy_id_from_headers,
+    headers::{
+        content_md5_from_headers_optional, encryption_key_sha256_from_headers_optional,
+        encryption_scope_from_headers_optional,
+    },
+    ConsistencyMD5, CopyId,
 };
 use time::OffsetDateTime;
 
@@ -12,6 +17,7 @@ operation! {
     CopyBlobFromUrl,
     client: BlobClient,
     source_url: Url,
+    ?source_authorization: CopySourceAuthorization,
     ?is_synchronous: bool,
     ?metadata: Metadata,
     ?if_modified_since: IfModifiedSinceCondition,
@@ -19,16 +25,23 @@ operation! {
     ?if_source_since: IfSourceModifiedSinceCondition,
     ?if_source_match: IfSourceMatchCondition,
     ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
+    ?immutability_policy: ImmutabilityPolicy,
+    ?legal_hold: LegalHold,
     ?source_content_md5: SourceContentMD5
 }
 
 impl CopyBlobFromUrlBuilder {
     pub fn into_future(mut self) -> CopyBlobFromUrl {
         Box::pin(async move {
+            self.client.check_plaintext_write("copy_blob_from_url")?;
+
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
             headers.insert(COPY_SOURCE, self.source_url.to_string());
+            headers.add(self.source_authorization);
             headers.insert(
                 REQUIRES_SYNC,
                 format!("{}", self.is_synchronous.unwrap_or(false)),
@@ -41,6 +54,10 @@ impl CopyBlobFromUrlBuilder {
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
+            headers.add(self.immutability_policy);
+            headers.add(self.legal_hold);
             headers.add(self.if_source_since);
             headers.add(self.if_source_match);
             headers.add(self.source_content_md5);
@@ -66,6 +83,8 @@ pub struct CopyBlobFromUrlResponse {
     pub copy_id: CopyId,
     pub copy_status: CopyStatus,
     pub date: OffsetDateTime,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl TryFrom<&Headers> for CopyBlobFromUrlResponse {
@@ -81,6 +100,8 @@ impl TryFrom<&Headers> for CopyBlobFromUrlResponse {
             copy_id: copy_id_from_headers(headers)?,
             copy_status: copy_status_from_headers(headers)?,
             date: date_from_headers(headers)?,
+            encryption_key_sha256: encryption_key_sha256_from_headers_optional(headers),
+            encryption_scope: encryption_scope_from_headers_optional(headers),
         })
     }
 }
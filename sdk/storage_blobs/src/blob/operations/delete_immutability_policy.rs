@@ -0,0 +1,51 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    DeleteImmutabilityPolicy,
+    client: BlobClient,
+    ?blob_versioning: BlobVersioning
+}
+
+impl DeleteImmutabilityPolicyBuilder {
+    pub fn into_future(mut self) -> DeleteImmutabilityPolicy {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("comp", "immutabilityPolicies");
+            self.blob_versioning.append_to_url_query(&mut url);
+
+            let mut request = BlobClient::finalize_request(
+                url,
+                azure_core::Method::Delete,
+                Headers::new(),
+                None,
+            )?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteImmutabilityPolicyResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub version: String,
+    pub date: OffsetDateTime,
+}
+
+impl TryFrom<&Headers> for DeleteImmutabilityPolicyResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(DeleteImmutabilityPolicyResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers),
+            version: version_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
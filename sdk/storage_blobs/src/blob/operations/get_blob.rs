@@ -1,7 +1,13 @@
 use crate::prelude::*;
 use azure_core::{
-    error::Error, headers::*, prelude::*, Pageable, RequestId, Response as AzureResponse,
-    ResponseBody,
+    error::{Error, ErrorKind},
+    headers::*,
+    prelude::*,
+    Pageable, RequestId, Response as AzureResponse, ResponseBody,
+};
+use azure_storage::client_side_encryption::{
+    encrypted_range, plaintext_length, ClientSideEncryption, EncryptionData,
+    ENCRYPTED_REGION_LENGTH, ENCRYPTION_DATA_METADATA, REGION_DATA_LENGTH,
 };
 use time::OffsetDateTime;
 
@@ -31,16 +37,21 @@ impl GetBlobBuilder {
 
                 let range = match continuation {
                     Some(range) => range,
-                    None => initial_range(
-                        this.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
-                        this.range.clone(),
-                    ),
+                    None => initial_range(this.effective_chunk_size(), this.range.clone()),
                 };
 
                 this.blob_versioning.append_to_url_query(&mut url);
 
+                // encrypted blobs are downloaded in whole encrypted regions
+                let client_side_encryption =
+                    this.client.container_client().client_side_encryption();
+                let request_range = match (client_side_encryption, &range) {
+                    (Some(_), Range::Range(range)) => encrypted_range(range.clone()).into(),
+                    _ => range.clone(),
+                };
+
                 let mut headers = Headers::new();
-                for (name, value) in range.as_headers() {
+                for (name, value) in request_range.as_headers() {
                     headers.insert(name, value);
                 }
 
@@ -55,11 +66,27 @@ impl GetBlobBuilder {
 
                 let response = this.client.send(&mut ctx, &mut request).await?;
 
-                GetBlobResponse::try_from(this, response)
+                match (client_side_encryption, range) {
+                    (Some(encryption), Range::Range(range)) => {
+                        let encryption = encryption.clone();
+                        GetBlobResponse::try_from_encrypted(this, response, &encryption, range)
+                            .await
+                    }
+                    _ => GetBlobResponse::try_from(this, response),
+                }
             }
         };
         Pageable::new(make_request)
     }
+
+    fn effective_chunk_size(&self) -> u64 {
+        let chunk_size = self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+        // whole regions are downloaded anyway, so don't download any twice
+        match self.client.container_client().client_side_encryption() {
+            Some(_) => chunk_size.div_ceil(REGION_DATA_LENGTH) * REGION_DATA_LENGTH,
+            None => chunk_size,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -81,11 +108,8 @@ impl GetBlobResponse {
 
         let content_range = headers.get_optional_as(&CONTENT_RANGE)?;
 
-        let remaining_range = remaining_range(
-            request.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
-            request.range,
-            content_range,
-        );
+        let remaining_range =
+            remaining_range(request.effective_chunk_size(), request.range, content_range);
         let blob = Blob::from_headers(request.client.blob_name(), headers)?;
         let data = response.into_body();
 
@@ -98,6 +122,77 @@ impl GetBlobResponse {
             remaining_range,
         })
     }
+
+    /// Decrypt the regions of a blob downloaded for the plaintext `range`.
+    /// Blobs that were not encrypted on the client are returned as is.
+    async fn try_from_encrypted(
+        request: GetBlobBuilder,
+        response: AzureResponse,
+        encryption: &ClientSideEncryption,
+        range: std::ops::Range<u64>,
+    ) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+
+        let request_id = request_id_from_headers(&headers)?;
+        let date = date_from_headers(&headers)?;
+        let mut blob = Blob::from_headers(request.client.blob_name(), &headers)?;
+        let data = body.collect().await?;
+
+        let encryption_data = headers.get_optional_str(&HeaderName::from(format!(
+            "{}{ENCRYPTION_DATA_METADATA}",
+            META_PREFIX.as_str()
+        )));
+        let content_range: Option<ContentRange> = headers.get_optional_as(&CONTENT_RANGE)?;
+        let (start, total_length, data) = match (encryption_data, content_range) {
+            (Some(encryption_data), Some(content_range)) => {
+                let encryption_data: EncryptionData = azure_core::from_json(encryption_data)?;
+                let data = encryption.decrypt_blob(&encryption_data, &data).await?;
+                let start = content_range.start() / ENCRYPTED_REGION_LENGTH * REGION_DATA_LENGTH;
+                let total_length = plaintext_length(content_range.total_length());
+                (start, total_length, data.into())
+            }
+            (Some(encryption_data), None) => {
+                let encryption_data: EncryptionData = azure_core::from_json(encryption_data)?;
+                let data = encryption.decrypt_blob(&encryption_data, &data).await?;
+                (0, data.len() as u64, data.into())
+            }
+            (None, Some(content_range)) => {
+                (content_range.start(), content_range.total_length(), data)
+            }
+            (None, None) => (0, data.len() as u64, data),
+        };
+
+        let end = std::cmp::min(range.end, start + data.len() as u64);
+        if range.start >= end {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "the range {range:?} is beyond the end of the blob {}",
+                    request.client.blob_name()
+                )
+            }));
+        }
+        let data = data.slice((range.start - start) as usize..(end - start) as usize);
+        let content_range = ContentRange::new(range.start, end - 1, total_length);
+
+        // describe the decrypted content rather than the stored content
+        blob.properties.content_length = data.len() as u64;
+        blob.properties.content_md5 = None;
+
+        let remaining_range = remaining_range(
+            request.effective_chunk_size(),
+            request.range,
+            Some(content_range),
+        );
+
+        Ok(Self {
+            request_id,
+            blob,
+            data: data.into(),
+            date,
+            content_range: Some(Range::new(content_range.start(), content_range.end())),
+            remaining_range,
+        })
+    }
 }
 
 impl Continuable for GetBlobResponse {
@@ -9,7 +9,8 @@ operation! {
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
     ?blob_versioning: BlobVersioning,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo
 }
 
 impl GetMetadataBuilder {
@@ -22,6 +23,7 @@ impl GetMetadataBuilder {
 
             let mut headers = Headers::new();
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
@@ -46,6 +46,7 @@ pub struct GetPageRangesResponse {
     pub last_modified: OffsetDateTime,
     pub request_id: RequestId,
     pub date: OffsetDateTime,
+    pub blob_content_length: Option<u64>,
     pub page_list: PageRangeList,
 }
 
@@ -58,6 +59,7 @@ impl GetPageRangesResponse {
         let last_modified = last_modified_from_headers(headers)?;
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
+        let blob_content_length = headers.get_optional_as(&BLOB_CONTENT_LENGTH)?;
 
         let body = from_utf8(body)?;
         let page_list = PageRangeList::try_from_xml(body)?;
@@ -67,6 +69,7 @@ impl GetPageRangesResponse {
             last_modified,
             request_id,
             date,
+            blob_content_length,
             page_list,
         })
     }
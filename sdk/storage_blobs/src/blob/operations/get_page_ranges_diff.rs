@@ -0,0 +1,44 @@
+use crate::{blob::operations::GetPageRangesResponse, prelude::*};
+use azure_core::{headers::Headers, prelude::*, AppendToUrlQuery};
+
+operation! {
+    GetPageRangesDiff,
+    client: BlobClient,
+    previous_snapshot: PreviousSnapshot,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags,
+    ?snapshot: Snapshot,
+    ?lease_id: LeaseId
+}
+
+impl GetPageRangesDiffBuilder {
+    pub fn into_future(mut self) -> GetPageRangesDiff {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "pagelist");
+            self.snapshot.append_to_url_query(&mut url);
+            self.previous_snapshot.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            headers.add(self.previous_snapshot.clone());
+            headers.add(self.lease_id);
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match);
+            headers.add(self.if_tags);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            let (_, headers, body) = response.deconstruct();
+            let body = body.collect().await?;
+
+            GetPageRangesResponse::from_response(&headers, &body)
+        })
+    }
+}
+
+type GetPageRangesDiffResponse = GetPageRangesResponse;
@@ -0,0 +1,36 @@
+use crate::{blob::operations::CopyBlobResponse, prelude::*};
+use azure_core::{headers::*, prelude::*, Url};
+
+operation! {
+    IncrementalCopy,
+    client: BlobClient,
+    source_url: Url,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags
+}
+
+impl IncrementalCopyBuilder {
+    pub fn into_future(mut self) -> IncrementalCopy {
+        Box::pin(async move {
+            self.client.check_plaintext_write("incremental_copy")?;
+
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "incrementalcopy");
+
+            let mut headers = Headers::new();
+            headers.insert(COPY_SOURCE, self.source_url.as_str().to_owned());
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match);
+            headers.add(self.if_tags);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+type IncrementalCopyResponse = CopyBlobResponse;
@@ -1,5 +1,8 @@
 use crate::prelude::*;
 use azure_core::{headers::*, prelude::*, RequestId};
+use azure_storage::headers::{
+    encryption_key_sha256_from_headers_optional, encryption_scope_from_headers_optional,
+};
 use time::OffsetDateTime;
 
 operation! {
@@ -13,12 +16,16 @@ operation! {
     ?tags: Tags,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl PutAppendBlobBuilder {
     pub fn into_future(mut self) -> PutAppendBlob {
         Box::pin(async move {
+            self.client.check_plaintext_write("put_append_blob")?;
+
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
@@ -36,6 +43,8 @@ impl PutAppendBlobBuilder {
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
 
             let mut request =
                 BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
@@ -55,6 +64,8 @@ pub struct PutBlobResponse {
     pub request_id: RequestId,
     pub date: OffsetDateTime,
     pub request_server_encrypted: bool,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl PutBlobResponse {
@@ -64,6 +75,8 @@ impl PutBlobResponse {
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let encryption_key_sha256 = encryption_key_sha256_from_headers_optional(headers);
+        let encryption_scope = encryption_scope_from_headers_optional(headers);
 
         Ok(PutBlobResponse {
             etag,
@@ -71,6 +84,8 @@ impl PutBlobResponse {
             request_id,
             date,
             request_server_encrypted,
+            encryption_key_sha256,
+            encryption_scope,
         })
     }
 }
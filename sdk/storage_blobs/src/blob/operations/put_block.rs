@@ -1,6 +1,12 @@
 use crate::prelude::*;
 use azure_core::{headers::*, prelude::*, Body, RequestId};
-use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, ConsistencyMD5};
+use azure_storage::{
+    headers::{
+        consistency_from_headers, encryption_key_sha256_from_headers_optional,
+        encryption_scope_from_headers_optional,
+    },
+    ConsistencyCRC64, ConsistencyMD5,
+};
 use time::OffsetDateTime;
 
 operation! {
@@ -9,12 +15,16 @@ operation! {
     block_id: BlockId,
     body: Body,
     ?hash: Hash,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl PutBlockBuilder {
     pub fn into_future(mut self) -> PutBlock {
         Box::pin(async move {
+            self.client.check_plaintext_write("put_block")?;
+
             let mut url = self.client.url()?;
 
             self.block_id.append_to_url_query(&mut url);
@@ -22,6 +32,8 @@ impl PutBlockBuilder {
 
             let mut headers = Headers::new();
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
 
             let mut request = BlobClient::finalize_request(
                 url,
@@ -43,6 +55,8 @@ pub struct PutBlockResponse {
     pub request_id: RequestId,
     pub date: OffsetDateTime,
     pub request_server_encrypted: bool,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl PutBlockResponse {
@@ -51,6 +65,8 @@ impl PutBlockResponse {
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let encryption_key_sha256 = encryption_key_sha256_from_headers_optional(headers);
+        let encryption_scope = encryption_scope_from_headers_optional(headers);
 
         Ok(PutBlockResponse {
             content_md5,
@@ -58,6 +74,8 @@ impl PutBlockResponse {
             request_id,
             date,
             request_server_encrypted,
+            encryption_key_sha256,
+            encryption_scope,
         })
     }
 }
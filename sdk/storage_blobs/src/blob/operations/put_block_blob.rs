@@ -1,6 +1,14 @@
 use crate::prelude::*;
+use azure_core::error::{Error, ErrorKind};
 use azure_core::{headers::*, prelude::*, Body, RequestId};
-use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, ConsistencyMD5};
+use azure_storage::{
+    client_side_encryption::ENCRYPTION_DATA_METADATA,
+    headers::{
+        consistency_from_headers, encryption_key_sha256_from_headers_optional,
+        encryption_scope_from_headers_optional,
+    },
+    ConsistencyCRC64, ConsistencyMD5,
+};
 use time::OffsetDateTime;
 
 operation! {
@@ -18,6 +26,8 @@ operation! {
     ?lease_id: LeaseId,
     ?encryption_key: CPKInfo,
     ?encryption_scope: EncryptionScope,
+    ?immutability_policy: ImmutabilityPolicy,
+    ?legal_hold: LegalHold,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags
@@ -28,6 +38,23 @@ impl PutBlockBlobBuilder {
         Box::pin(async move {
             let url = self.client.url()?;
 
+            if let Some(encryption) = self.client.container_client().client_side_encryption() {
+                let Body::Bytes(data) = &self.body else {
+                    return Err(Error::message(
+                        ErrorKind::Other,
+                        "client-side encryption requires the blob's content in memory",
+                    ));
+                };
+                let (encrypted, encryption_data) = encryption.encrypt_blob(data).await?;
+                self.body = encrypted.into();
+                // the hash is of the plaintext, not of the uploaded content
+                self.hash = None;
+                self.metadata.get_or_insert_with(Metadata::new).insert(
+                    ENCRYPTION_DATA_METADATA,
+                    azure_core::to_json(&encryption_data)?,
+                );
+            }
+
             let mut headers = Headers::new();
             headers.insert(BLOB_TYPE, "BlockBlob");
             headers.add(self.hash);
@@ -45,6 +72,8 @@ impl PutBlockBlobBuilder {
             headers.add(self.lease_id);
             headers.add(self.encryption_key);
             headers.add(self.encryption_scope);
+            headers.add(self.immutability_policy);
+            headers.add(self.legal_hold);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
@@ -71,6 +100,8 @@ pub struct PutBlockBlobResponse {
     pub request_id: RequestId,
     pub date: OffsetDateTime,
     pub request_server_encrypted: bool,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl PutBlockBlobResponse {
@@ -81,6 +112,8 @@ impl PutBlockBlobResponse {
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let encryption_key_sha256 = encryption_key_sha256_from_headers_optional(headers);
+        let encryption_scope = encryption_scope_from_headers_optional(headers);
 
         Ok(PutBlockBlobResponse {
             etag,
@@ -90,6 +123,8 @@ impl PutBlockBlobResponse {
             request_id,
             date,
             request_server_encrypted,
+            encryption_key_sha256,
+            encryption_scope,
         })
     }
 }
@@ -2,7 +2,13 @@ use crate::prelude::*;
 #[cfg(feature = "md5")]
 use azure_core::base64;
 use azure_core::{headers::*, prelude::*, RequestId};
-use azure_storage::{headers::content_md5_from_headers_optional, ConsistencyMD5};
+use azure_storage::{
+    headers::{
+        content_md5_from_headers_optional, encryption_key_sha256_from_headers_optional,
+        encryption_scope_from_headers_optional,
+    },
+    ConsistencyMD5,
+};
 use bytes::Bytes;
 use time::OffsetDateTime;
 
@@ -19,6 +25,8 @@ operation! {
     ?access_tier: AccessTier,
     ?tags: Tags,
     ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags
@@ -27,6 +35,8 @@ operation! {
 impl PutBlockListBuilder {
     pub fn into_future(mut self) -> PutBlockList {
         Box::pin(async move {
+            self.client.check_plaintext_write("put_block_list")?;
+
             let mut url = self.client.url()?;
 
             url.query_pairs_mut().append_pair("comp", "blocklist");
@@ -58,6 +68,8 @@ impl PutBlockListBuilder {
             }
             headers.add(self.access_tier);
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
@@ -83,6 +95,8 @@ pub struct PutBlockListResponse {
     pub request_id: RequestId,
     pub date: OffsetDateTime,
     pub request_server_encrypted: bool,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl PutBlockListResponse {
@@ -93,6 +107,8 @@ impl PutBlockListResponse {
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let encryption_key_sha256 = encryption_key_sha256_from_headers_optional(headers);
+        let encryption_scope = encryption_scope_from_headers_optional(headers);
 
         Ok(PutBlockListResponse {
             etag,
@@ -101,6 +117,8 @@ impl PutBlockListResponse {
             request_id,
             date,
             request_server_encrypted,
+            encryption_key_sha256,
+            encryption_scope,
         })
     }
 }
@@ -1,6 +1,12 @@
 use crate::prelude::*;
 use azure_core::{headers::*, prelude::*, RequestId, Url};
-use azure_storage::{headers::consistency_from_headers, ConsistencyCRC64, ConsistencyMD5};
+use azure_storage::{
+    headers::{
+        consistency_from_headers, encryption_key_sha256_from_headers_optional,
+        encryption_scope_from_headers_optional,
+    },
+    ConsistencyCRC64, ConsistencyMD5,
+};
 use time::OffsetDateTime;
 
 operation! {
@@ -8,14 +14,19 @@ operation! {
     client: BlobClient,
     block_id: BlockId,
     url: Url,
+    ?source_authorization: CopySourceAuthorization,
     ?hash: Hash,
     ?range: Range,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl PutBlockUrlBuilder {
     pub fn into_future(mut self) -> PutBlockUrl {
         Box::pin(async move {
+            self.client.check_plaintext_write("put_block_url")?;
+
             let mut url = self.client.url()?;
 
             self.block_id.append_to_url_query(&mut url);
@@ -23,7 +34,10 @@ impl PutBlockUrlBuilder {
 
             let mut headers = Headers::new();
             headers.insert(COPY_SOURCE, self.url.to_string());
+            headers.add(self.source_authorization);
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
             if let Some(range) = self.range {
                 headers.insert(SOURCE_RANGE, format!("{range}"));
             }
@@ -44,6 +58,8 @@ pub struct PutBlockUrlResponse {
     pub request_id: RequestId,
     pub date: OffsetDateTime,
     pub request_server_encrypted: bool,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl PutBlockUrlResponse {
@@ -52,6 +68,8 @@ impl PutBlockUrlResponse {
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let encryption_key_sha256 = encryption_key_sha256_from_headers_optional(headers);
+        let encryption_scope = encryption_scope_from_headers_optional(headers);
 
         Ok(PutBlockUrlResponse {
             content_md5,
@@ -59,6 +77,8 @@ impl PutBlockUrlResponse {
             request_id,
             date,
             request_server_encrypted,
+            encryption_key_sha256,
+            encryption_scope,
         })
     }
 }
@@ -1,6 +1,12 @@
 use crate::prelude::*;
 use azure_core::{headers::*, prelude::*, Body, RequestId};
-use azure_storage::{headers::content_md5_from_headers_optional, ConsistencyMD5};
+use azure_storage::{
+    headers::{
+        content_md5_from_headers_optional, encryption_key_sha256_from_headers_optional,
+        encryption_scope_from_headers_optional,
+    },
+    ConsistencyMD5,
+};
 use time::OffsetDateTime;
 
 operation! {
@@ -13,12 +19,16 @@ operation! {
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl PutPageBuilder {
     pub fn into_future(mut self) -> PutPage {
         Box::pin(async move {
+            self.client.check_plaintext_write("put_page")?;
+
             let mut url = self.client.url()?;
 
             url.query_pairs_mut().append_pair("comp", "page");
@@ -33,6 +43,8 @@ impl PutPageBuilder {
             headers.add(self.if_match);
             headers.add(self.if_tags);
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
 
             let mut request = BlobClient::finalize_request(
                 url,
@@ -56,6 +68,8 @@ pub struct PutPageResponse {
     pub request_id: RequestId,
     pub date: OffsetDateTime,
     pub request_server_encrypted: bool,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl PutPageResponse {
@@ -67,6 +81,8 @@ impl PutPageResponse {
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
         let request_server_encrypted = request_server_encrypted_from_headers(headers)?;
+        let encryption_key_sha256 = encryption_key_sha256_from_headers_optional(headers);
+        let encryption_scope = encryption_scope_from_headers_optional(headers);
 
         Ok(Self {
             etag,
@@ -76,6 +92,8 @@ impl PutPageResponse {
             request_id,
             date,
             request_server_encrypted,
+            encryption_key_sha256,
+            encryption_scope,
         })
     }
 }
@@ -15,12 +15,16 @@ operation! {
     ?metadata: Metadata,
     ?tags: Tags,
     ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
     ?sequence_number: SequenceNumber
 }
 
 impl PutPageBlobBuilder {
     pub fn into_future(mut self) -> PutPageBlob {
         Box::pin(async move {
+            self.client.check_plaintext_write("put_page_blob")?;
+
             let url = self.client.url()?;
 
             let mut headers = Headers::new();
@@ -37,6 +41,8 @@ impl PutPageBlobBuilder {
                 }
             }
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
             headers.add(self.sequence_number);
 
             let mut request =
@@ -0,0 +1,58 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    ResizeBlob,
+    client: BlobClient,
+    length: u128,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags,
+    ?lease_id: LeaseId
+}
+
+impl ResizeBlobBuilder {
+    pub fn into_future(mut self) -> ResizeBlob {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let mut headers = Headers::new();
+            headers.insert(BLOB_CONTENT_LENGTH, format!("{}", self.length));
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match);
+            headers.add(self.if_tags);
+            headers.add(self.lease_id);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResizeBlobResponse {
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub sequence_number: u64,
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+}
+
+impl TryFrom<&Headers> for ResizeBlobResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(ResizeBlobResponse {
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            sequence_number: sequence_number_from_headers(headers)?,
+            request_id: request_id_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, RequestId};
+use azure_storage::headers::{IMMUTABILITY_POLICY_MODE, IMMUTABILITY_POLICY_UNTIL_DATE};
+use time::OffsetDateTime;
+
+operation! {
+    SetImmutabilityPolicy,
+    client: BlobClient,
+    immutability_policy: ImmutabilityPolicy,
+    ?blob_versioning: BlobVersioning,
+    ?if_modified_since: IfModifiedSinceCondition
+}
+
+impl SetImmutabilityPolicyBuilder {
+    pub fn into_future(mut self) -> SetImmutabilityPolicy {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut()
+                .append_pair("comp", "immutabilityPolicies");
+            self.blob_versioning.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            headers.add(self.immutability_policy);
+            headers.add(self.if_modified_since);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetImmutabilityPolicyResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub version: String,
+    pub date: OffsetDateTime,
+    pub immutability_policy_until_date: Option<OffsetDateTime>,
+    pub immutability_policy_mode: Option<ImmutabilityPolicyMode>,
+}
+
+impl TryFrom<&Headers> for SetImmutabilityPolicyResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(SetImmutabilityPolicyResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers),
+            version: version_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            immutability_policy_until_date: headers
+                .get_optional_str(&IMMUTABILITY_POLICY_UNTIL_DATE)
+                .map(azure_core::date::parse_rfc1123)
+                .transpose()?,
+            immutability_policy_mode: headers.get_optional_as(&IMMUTABILITY_POLICY_MODE)?,
+        })
+    }
+}
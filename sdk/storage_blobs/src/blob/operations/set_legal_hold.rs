@@ -0,0 +1,53 @@
+use crate::prelude::*;
+use azure_core::{headers::*, prelude::*, RequestId};
+use azure_storage::headers::LEGAL_HOLD;
+use time::OffsetDateTime;
+
+operation! {
+    SetLegalHold,
+    client: BlobClient,
+    legal_hold: LegalHold,
+    ?blob_versioning: BlobVersioning
+}
+
+impl SetLegalHoldBuilder {
+    pub fn into_future(mut self) -> SetLegalHold {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "legalhold");
+            self.blob_versioning.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            headers.add(self.legal_hold);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetLegalHoldResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub version: String,
+    pub date: OffsetDateTime,
+    pub legal_hold: bool,
+}
+
+impl TryFrom<&Headers> for SetLegalHoldResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(SetLegalHoldResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers),
+            version: version_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+            legal_hold: headers.get_as(&LEGAL_HOLD)?,
+        })
+    }
+}
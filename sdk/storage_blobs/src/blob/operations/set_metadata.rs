@@ -1,5 +1,8 @@
 use crate::prelude::*;
 use azure_core::{headers::*, prelude::*, Method, RequestId};
+use azure_storage::headers::{
+    encryption_key_sha256_from_headers_optional, encryption_scope_from_headers_optional,
+};
 use time::OffsetDateTime;
 
 operation! {
@@ -9,6 +12,8 @@ operation! {
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
     ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope,
     ?metadata: Metadata
 }
 
@@ -21,6 +26,8 @@ impl SetMetadataBuilder {
 
             let mut headers = Headers::new();
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
@@ -44,6 +51,8 @@ pub struct SetMetadataResponse {
     pub etag: String,
     pub server: String,
     pub date: OffsetDateTime,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl TryFrom<&Headers> for SetMetadataResponse {
@@ -55,6 +64,8 @@ impl TryFrom<&Headers> for SetMetadataResponse {
             etag: etag_from_headers(headers)?,
             server: server_from_headers(headers)?,
             date: date_from_headers(headers)?,
+            encryption_key_sha256: encryption_key_sha256_from_headers_optional(headers),
+            encryption_scope: encryption_scope_from_headers_optional(headers),
         })
     }
 }
@@ -0,0 +1,36 @@
+use crate::{blob::operations::ResizeBlobResponse, prelude::*};
+use azure_core::{headers::Headers, prelude::*};
+
+operation! {
+    SetSequenceNumber,
+    client: BlobClient,
+    action: SequenceNumberAction,
+    ?if_modified_since: IfModifiedSinceCondition,
+    ?if_match: IfMatchCondition,
+    ?if_tags: IfTags,
+    ?lease_id: LeaseId
+}
+
+impl SetSequenceNumberBuilder {
+    pub fn into_future(mut self) -> SetSequenceNumber {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let mut headers = Headers::new();
+            headers.add(self.action);
+            headers.add(self.if_modified_since);
+            headers.add(self.if_match);
+            headers.add(self.if_tags);
+            headers.add(self.lease_id);
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+type SetSequenceNumberResponse = ResizeBlobResponse;
@@ -6,6 +6,9 @@ use azure_core::{
     Method::Put,
     RequestId,
 };
+use azure_storage::headers::{
+    encryption_key_sha256_from_headers_optional, encryption_scope_from_headers_optional,
+};
 use time::OffsetDateTime;
 
 operation! {
@@ -15,7 +18,9 @@ operation! {
     ?if_modified_since: IfModifiedSinceCondition,
     ?if_match: IfMatchCondition,
     ?if_tags: IfTags,
-    ?lease_id: LeaseId
+    ?lease_id: LeaseId,
+    ?encryption_key: CPKInfo,
+    ?encryption_scope: EncryptionScope
 }
 
 impl SnapshotBlobBuilder {
@@ -27,6 +32,8 @@ impl SnapshotBlobBuilder {
 
             let mut headers = Headers::new();
             headers.add(self.lease_id);
+            headers.add(self.encryption_key);
+            headers.add(self.encryption_scope);
             headers.add(self.if_modified_since);
             headers.add(self.if_match);
             headers.add(self.if_tags);
@@ -51,6 +58,8 @@ pub struct SnapshotBlobResponse {
     pub date: OffsetDateTime,
     pub snapshot: Snapshot,
     pub last_modified: OffsetDateTime,
+    pub encryption_key_sha256: Option<String>,
+    pub encryption_scope: Option<String>,
 }
 
 impl TryFrom<&Headers> for SnapshotBlobResponse {
@@ -63,6 +72,8 @@ impl TryFrom<&Headers> for SnapshotBlobResponse {
             date: date_from_headers(headers)?,
             snapshot: Snapshot::new(headers.get_str(&SNAPSHOT)?.to_string()),
             last_modified: last_modified_from_headers(headers)?,
+            encryption_key_sha256: encryption_key_sha256_from_headers_optional(headers),
+            encryption_scope: encryption_scope_from_headers_optional(headers),
         })
     }
 }
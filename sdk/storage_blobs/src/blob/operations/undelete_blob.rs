@@ -0,0 +1,44 @@
+use crate::prelude::*;
+use azure_core::{headers::*, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    UndeleteBlob,
+    client: BlobClient,
+}
+
+impl UndeleteBlobBuilder {
+    pub fn into_future(mut self) -> UndeleteBlob {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+            url.query_pairs_mut().append_pair("comp", "undelete");
+
+            let mut request =
+                BlobClient::finalize_request(url, azure_core::Method::Put, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UndeleteBlobResponse {
+    pub request_id: RequestId,
+    pub client_request_id: Option<String>,
+    pub version: String,
+    pub date: OffsetDateTime,
+}
+
+impl TryFrom<&Headers> for UndeleteBlobResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> Result<Self, Self::Error> {
+        Ok(UndeleteBlobResponse {
+            request_id: request_id_from_headers(headers)?,
+            client_request_id: client_request_id_from_headers_optional(headers),
+            version: version_from_headers(headers)?,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
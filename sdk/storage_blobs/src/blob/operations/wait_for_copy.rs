@@ -0,0 +1,127 @@
+use crate::{
+    blob::{operations::GetPropertiesResponse, CopyStatus},
+    prelude::*,
+};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    sleep::sleep,
+    Pageable, RequestId,
+};
+use azure_storage::{CopyId, CopyProgress};
+use futures::StreamExt;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+operation! {
+    WaitForCopy,
+    client: BlobClient,
+    copy_id: CopyId,
+    ?poll_interval: Duration,
+    ?max_polls: u32
+}
+
+impl WaitForCopyBuilder {
+    /// Poll the destination blob until the copy is no longer pending,
+    /// yielding the status observed by each poll.
+    ///
+    /// When `max_polls` is set, the stream ends with an error if the copy is
+    /// still pending after that many polls.
+    pub fn into_stream(self) -> Pageable<WaitForCopyResponse, Error> {
+        let make_request = move |continuation: Option<u32>| {
+            let this = self.clone();
+            async move {
+                let polls = continuation.unwrap_or_default();
+                if let Some(max_polls) = this.max_polls {
+                    if polls >= max_polls {
+                        return Err(Error::with_message(ErrorKind::Other, || {
+                            format!(
+                                "copy {} is still pending after {max_polls} polls",
+                                this.copy_id
+                            )
+                        }));
+                    }
+                }
+                if polls > 0 {
+                    sleep(this.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL)).await;
+                }
+
+                let properties = this
+                    .client
+                    .get_properties()
+                    .context(this.context.clone())
+                    .await?;
+
+                let mut response = WaitForCopyResponse::try_from(&this.copy_id, properties)?;
+                response.polls = polls + 1;
+                Ok(response)
+            }
+        };
+        Pageable::new(make_request)
+    }
+
+    /// Poll the destination blob until the copy succeeds, fails or is
+    /// aborted, and return the final status.
+    pub fn into_future(self) -> WaitForCopy {
+        Box::pin(async move {
+            let mut stream = self.into_stream();
+            let mut last = None;
+            while let Some(response) = stream.next().await {
+                last = Some(response?);
+            }
+            last.ok_or_else(|| Error::message(ErrorKind::Other, "copy status was never polled"))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WaitForCopyResponse {
+    pub copy_id: CopyId,
+    pub copy_status: CopyStatus,
+    pub copy_progress: Option<CopyProgress>,
+    pub copy_status_description: Option<String>,
+    pub copy_completion_time: Option<OffsetDateTime>,
+    pub blob: Blob,
+    pub request_id: RequestId,
+    pub date: OffsetDateTime,
+    polls: u32,
+}
+
+impl WaitForCopyResponse {
+    fn try_from(copy_id: &CopyId, properties: GetPropertiesResponse) -> azure_core::Result<Self> {
+        let blob = properties.blob;
+        let observed = blob.properties.copy_id.clone();
+        if observed.as_ref() != Some(copy_id) {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "blob {} is no longer the destination of copy {copy_id}, found {observed:?}",
+                    blob.name
+                )
+            }));
+        }
+        let copy_status = blob.properties.copy_status.ok_or_else(|| {
+            Error::message(ErrorKind::DataConversion, "missing copy status on blob")
+        })?;
+
+        Ok(Self {
+            copy_id: copy_id.clone(),
+            copy_status,
+            copy_progress: blob.properties.copy_progress.clone(),
+            copy_status_description: blob.properties.copy_status_description.clone(),
+            copy_completion_time: blob.properties.copy_completion_time,
+            blob,
+            request_id: properties.request_id,
+            date: properties.date,
+            polls: 0,
+        })
+    }
+}
+
+impl Continuable for WaitForCopyResponse {
+    type Continuation = u32;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        (self.copy_status == CopyStatus::Pending).then_some(self.polls)
+    }
+}
@@ -20,26 +20,43 @@ struct PageRange {
     pub end: End,
 }
 
+// Page ranges and cleared ranges are interleaved in a diff, so they are read
+// as a single sequence of elements.
+#[derive(Debug, Deserialize)]
+enum PageListEntry {
+    PageRange(PageRange),
+    ClearRange(PageRange),
+}
+
 #[derive(Debug, Deserialize)]
 struct PageList {
-    #[serde(rename = "PageRange", default)]
-    pub page_list: Vec<PageRange>,
+    #[serde(rename = "$value", default)]
+    pub page_list: Vec<PageListEntry>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct PageRangeList {
     pub ranges: Vec<Range>,
+    /// Ranges cleared since the previous snapshot. Only returned when
+    /// requesting a diff.
+    pub cleared_ranges: Vec<Range>,
 }
 
 impl PageRangeList {
     pub fn try_from_xml(xml: &str) -> azure_core::Result<Self> {
         let pl: PageList = read_xml_str(xml)?;
 
-        let mut prl = PageRangeList { ranges: Vec::new() };
+        let mut prl = PageRangeList::default();
 
-        for range in pl.page_list {
-            prl.ranges
-                .push(Range::new(range.start.value, range.end.value));
+        for entry in pl.page_list {
+            match entry {
+                PageListEntry::PageRange(range) => prl
+                    .ranges
+                    .push(Range::new(range.start.value, range.end.value)),
+                PageListEntry::ClearRange(range) => prl
+                    .cleared_ranges
+                    .push(Range::new(range.start.value, range.end.value)),
+            }
         }
 
         Ok(prl)
@@ -77,4 +94,30 @@ mod test {
         let prl = PageRangeList::try_from_xml(page_list).unwrap();
         assert!(prl.ranges.is_empty());
     }
+
+    #[test]
+    fn try_parse_diff() {
+        let page_list = "<?xml version=\"1.0\" encoding=\"utf-8\"?>
+            <PageList>
+              <ClearRange>
+                <Start>0</Start>
+                <End>511</End>
+              </ClearRange>
+              <PageRange>
+                <Start>512</Start>
+                <End>1023</End>
+              </PageRange>
+              <ClearRange>
+                <Start>2048</Start>
+                <End>4095</End>
+              </ClearRange>
+            </PageList>";
+
+        let prl = PageRangeList::try_from_xml(page_list).unwrap();
+        assert_eq!(prl.ranges, vec![Range::new(512, 1023)]);
+        assert_eq!(
+            prl.cleared_ranges,
+            vec![Range::new(0, 511), Range::new(2048, 4095)]
+        );
+    }
 }
@@ -106,6 +106,35 @@ impl BlobClient {
         SetBlobExpiryBuilder::new(self.clone(), blob_expiry)
     }
 
+    /// Set a time-based retention policy on the blob.
+    ///
+    /// Requires version-level immutability support to be enabled on the
+    /// container or the storage account.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/set-blob-immutability-policy>
+    pub fn set_immutability_policy(
+        &self,
+        immutability_policy: ImmutabilityPolicy,
+    ) -> SetImmutabilityPolicyBuilder {
+        SetImmutabilityPolicyBuilder::new(self.clone(), immutability_policy)
+    }
+
+    /// Delete the immutability policy of the blob.
+    ///
+    /// Only unlocked policies can be deleted.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/delete-blob-immutability-policy>
+    pub fn delete_immutability_policy(&self) -> DeleteImmutabilityPolicyBuilder {
+        DeleteImmutabilityPolicyBuilder::new(self.clone())
+    }
+
+    /// Place or clear a legal hold on the blob.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/set-blob-legal-hold>
+    pub fn set_legal_hold(&self, legal_hold: impl Into<LegalHold>) -> SetLegalHoldBuilder {
+        SetLegalHoldBuilder::new(self.clone(), legal_hold.into())
+    }
+
     /// Creates a new page blob.
     pub fn put_page_blob(&self, length: u128) -> PutPageBlobBuilder {
         PutPageBlobBuilder::new(self.clone(), length)
@@ -131,6 +160,23 @@ impl BlobClient {
         CopyBlobFromUrlBuilder::new(self.clone(), copy_source)
     }
 
+    /// Abort a pending copy, leaving the destination blob with zero length
+    /// and full metadata.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/abort-copy-blob>
+    pub fn abort_copy(&self, copy_id: CopyId) -> AbortCopyBuilder {
+        AbortCopyBuilder::new(self.clone(), copy_id)
+    }
+
+    /// Wait for a pending copy to this blob to complete.
+    ///
+    /// The blob properties are polled until the copy status is no longer
+    /// `pending`. Use `into_stream` to observe the copy progress along the way,
+    /// and `max_polls` to give up on a copy which stays pending.
+    pub fn wait_for_copy(&self, copy_id: CopyId) -> WaitForCopyBuilder {
+        WaitForCopyBuilder::new(self.clone(), copy_id)
+    }
+
     /// Create a lease on the blob to lock for write and delete operations.
     pub fn acquire_lease<LD: Into<LeaseDuration>>(
         &self,
@@ -159,6 +205,14 @@ impl BlobClient {
         DeleteBlobVersionBuilder::new(self.clone(), version_id)
     }
 
+    /// Restore the contents and metadata of a soft deleted blob and any
+    /// associated soft deleted snapshots.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/undelete-blob>
+    pub fn undelete(&self) -> UndeleteBlobBuilder {
+        UndeleteBlobBuilder::new(self.clone())
+    }
+
     /* Operations specific to certain blob types */
 
     /// Creates a new block to be committed as part of a block blob.
@@ -211,6 +265,37 @@ impl BlobClient {
         GetPageRangesBuilder::new(self.clone())
     }
 
+    /// Return the page ranges that changed between a previous snapshot and
+    /// the page blob, or a snapshot of the page blob.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/get-page-ranges>
+    pub fn get_page_ranges_diff(
+        &self,
+        previous_snapshot: impl Into<PreviousSnapshot>,
+    ) -> GetPageRangesDiffBuilder {
+        GetPageRangesDiffBuilder::new(self.clone(), previous_snapshot.into())
+    }
+
+    /// Resize a page blob.
+    ///
+    /// The length must be aligned to a 512-byte boundary.
+    pub fn resize(&self, length: u128) -> ResizeBlobBuilder {
+        ResizeBlobBuilder::new(self.clone(), length)
+    }
+
+    /// Set the sequence number of a page blob.
+    pub fn set_sequence_number(&self, action: SequenceNumberAction) -> SetSequenceNumberBuilder {
+        SetSequenceNumberBuilder::new(self.clone(), action)
+    }
+
+    /// Copy a snapshot of the source page blob to the destination page blob,
+    /// transferring only the differences since the previous incremental copy.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/incremental-copy-blob>
+    pub fn incremental_copy(&self, copy_source: Url) -> IncrementalCopyBuilder {
+        IncrementalCopyBuilder::new(self.clone(), copy_source)
+    }
+
     /// Commits a new block of data to the end of an existing append blob.
     pub fn append_block(&self, body: impl Into<Body>) -> AppendBlockBuilder {
         AppendBlockBuilder::new(self.clone(), body.into())
@@ -336,6 +421,20 @@ impl BlobClient {
         Ok(url)
     }
 
+    /// Fail operations which would store plaintext, or content encrypted by
+    /// someone else, while client-side encryption is configured: only
+    /// `put_block_blob` encrypts what it uploads.
+    pub(crate) fn check_plaintext_write(&self, operation: &str) -> azure_core::Result<()> {
+        if self.container_client().client_side_encryption().is_some() {
+            return Err(Error::with_message(ErrorKind::Other, || {
+                format!(
+                    "{operation} is not supported with client-side encryption, use put_block_blob"
+                )
+            }));
+        }
+        Ok(())
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: Method,
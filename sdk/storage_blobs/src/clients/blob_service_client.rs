@@ -7,6 +7,7 @@ use azure_core::{
     Request, Response, Url,
 };
 use azure_storage::{
+    client_side_encryption::ClientSideEncryption,
     clients::{new_pipeline_from_options, shared_access_signature, ServiceType},
     prelude::{AccountSasPermissions, AccountSasResource, AccountSasResourceType},
     shared_access_signature::account_sas::AccountSharedAccessSignature,
@@ -20,6 +21,7 @@ pub struct ClientBuilder {
     cloud_location: CloudLocation,
     options: ClientOptions,
     credentials: StorageCredentials,
+    client_side_encryption: Option<ClientSideEncryption>,
 }
 
 impl ClientBuilder {
@@ -48,6 +50,7 @@ impl ClientBuilder {
             options: ClientOptions::default(),
             cloud_location,
             credentials: credentials.into(),
+            client_side_encryption: None,
         }
     }
 
@@ -70,12 +73,14 @@ impl ClientBuilder {
             cloud_location,
             options,
             credentials,
+            client_side_encryption,
         } = self;
 
         BlobServiceClient {
             pipeline: new_pipeline_from_options(options, credentials.clone()),
             cloud_location,
             credentials,
+            client_side_encryption,
         }
     }
 
@@ -144,6 +149,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Encrypt block blobs before they are uploaded, and decrypt blobs
+    /// encrypted on the client when they are downloaded.
+    ///
+    /// Uploads go through `put_block_blob`: the operations which would store
+    /// content the client did not encrypt, like `put_block`, `append_block`,
+    /// `put_page` or copies, fail instead.
+    #[must_use]
+    pub fn client_side_encryption(mut self, client_side_encryption: ClientSideEncryption) -> Self {
+        self.client_side_encryption = Some(client_side_encryption);
+        self
+    }
+
     /// Override all of the client options.
     ///
     /// *Warning!*: This overrides all client options that have been previously set on this builder.
@@ -160,6 +177,7 @@ pub struct BlobServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
     credentials: StorageCredentials,
+    client_side_encryption: Option<ClientSideEncryption>,
 }
 
 impl BlobServiceClient {
@@ -244,6 +262,10 @@ impl BlobServiceClient {
         &self.credentials
     }
 
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.client_side_encryption.as_ref()
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: Method,
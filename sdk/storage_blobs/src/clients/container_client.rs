@@ -6,6 +6,7 @@ use azure_core::{
     Body, Method, Request, Response, StatusCode, Url,
 };
 use azure_storage::{
+    client_side_encryption::ClientSideEncryption,
     prelude::BlobSasPermissions,
     shared_access_signature::{
         service_sas::{BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey},
@@ -56,6 +57,24 @@ impl ContainerClient {
         DeleteBuilder::new(self.clone())
     }
 
+    /// Restore a soft deleted container into this container.
+    ///
+    /// The deleted container name and version are returned when listing
+    /// containers with `include_deleted`.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/restore-container>
+    pub fn restore(
+        &self,
+        deleted_container_name: impl Into<String>,
+        deleted_container_version: impl Into<String>,
+    ) -> RestoreBuilder {
+        RestoreBuilder::new(
+            self.clone(),
+            deleted_container_name.into(),
+            deleted_container_version.into(),
+        )
+    }
+
     /// Get a container acl
     pub fn get_acl(&self) -> GetACLBuilder {
         GetACLBuilder::new(self.clone())
@@ -76,6 +95,12 @@ impl ContainerClient {
         ListBlobsBuilder::new(self.clone())
     }
 
+    /// Recursively list the blobs in a container, following virtual
+    /// directories delimited by `/`.
+    pub fn walk_blobs(&self) -> WalkBlobsBuilder {
+        WalkBlobsBuilder::new(self.clone())
+    }
+
     /// Acquite a lease on a container
     pub fn acquire_lease<LD: Into<LeaseDuration>>(
         &self,
@@ -194,6 +219,10 @@ impl ContainerClient {
         self.service_client.credentials()
     }
 
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.service_client.client_side_encryption()
+    }
+
     pub(crate) async fn send(
         &self,
         context: &mut Context,
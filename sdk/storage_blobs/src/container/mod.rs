@@ -12,6 +12,10 @@ use azure_core::{
     },
     LeaseDuration, LeaseState, LeaseStatus,
 };
+use azure_storage::headers::{
+    DEFAULT_ENCRYPTION_SCOPE, DENY_ENCRYPTION_SCOPE_OVERRIDE,
+    IMMUTABLE_STORAGE_WITH_VERSIONING_ENABLED,
+};
 use azure_storage::parsing_xml::{cast_must, cast_optional, traverse};
 use std::collections::HashMap;
 use time::OffsetDateTime;
@@ -54,7 +58,23 @@ pub struct Container {
     pub public_access: PublicAccess,
     pub has_immutability_policy: bool,
     pub has_legal_hold: bool,
+    /// Whether version-level immutability is enabled, which allows
+    /// immutability policies and legal holds to be set on individual blobs.
+    pub immutable_storage_with_versioning_enabled: bool,
+    /// The encryption scope blobs are encrypted with when a write does not
+    /// specify one.
+    pub default_encryption_scope: Option<String>,
+    /// Whether writes specifying another encryption scope are rejected.
+    pub prevent_encryption_scope_override: bool,
     pub metadata: HashMap<String, String>,
+    /// Whether the container is soft deleted.
+    ///
+    /// Only returned when listing containers with `include_deleted`.
+    pub deleted: bool,
+    /// The version of a soft deleted container, used to restore it.
+    pub version: Option<String>,
+    pub deleted_time: Option<OffsetDateTime>,
+    pub remaining_retention_days: Option<u32>,
 }
 
 impl AsRef<str> for Container {
@@ -75,7 +95,14 @@ impl Container {
             public_access: PublicAccess::None,
             has_immutability_policy: false,
             has_legal_hold: false,
+            immutable_storage_with_versioning_enabled: false,
+            default_encryption_scope: None,
+            prevent_encryption_scope_override: false,
             metadata: HashMap::new(),
+            deleted: false,
+            version: None,
+            deleted_time: None,
+            remaining_retention_days: None,
         }
     }
 
@@ -100,6 +127,13 @@ impl Container {
 
         let has_immutability_policy = headers.get_as(&HAS_IMMUTABILITY_POLICY)?;
         let has_legal_hold = headers.get_as(&HAS_LEGAL_HOLD)?;
+        let immutable_storage_with_versioning_enabled = headers
+            .get_optional_as(&IMMUTABLE_STORAGE_WITH_VERSIONING_ENABLED)?
+            .unwrap_or(false);
+        let default_encryption_scope = headers.get_optional_string(&DEFAULT_ENCRYPTION_SCOPE);
+        let prevent_encryption_scope_override = headers
+            .get_optional_as(&DENY_ENCRYPTION_SCOPE_OVERRIDE)?
+            .unwrap_or(false);
 
         let mut metadata: HashMap<String, String> = HashMap::new();
         for (key, value) in headers.iter() {
@@ -118,7 +152,14 @@ impl Container {
             public_access,
             has_immutability_policy,
             has_legal_hold,
+            immutable_storage_with_versioning_enabled,
+            default_encryption_scope,
+            prevent_encryption_scope_override,
             metadata,
+            deleted: false,
+            version: None,
+            deleted_time: None,
+            remaining_retention_days: None,
         })
     }
 
@@ -133,6 +174,20 @@ impl Container {
             cast_optional(elem, &["Properties", "PublicAccess"])?.unwrap_or(PublicAccess::None);
         let has_immutability_policy = cast_must(elem, &["Properties", "HasImmutabilityPolicy"])?;
         let has_legal_hold = cast_must(elem, &["Properties", "HasLegalHold"])?;
+        let immutable_storage_with_versioning_enabled = cast_optional(
+            elem,
+            &["Properties", "ImmutableStorageWithVersioningEnabled"],
+        )?
+        .unwrap_or(false);
+        let default_encryption_scope =
+            cast_optional(elem, &["Properties", "DefaultEncryptionScope"])?;
+        let prevent_encryption_scope_override =
+            cast_optional(elem, &["Properties", "DenyEncryptionScopeOverride"])?.unwrap_or(false);
+        let deleted = cast_optional(elem, &["Deleted"])?.unwrap_or(false);
+        let version = cast_optional(elem, &["Version"])?;
+        let deleted_time = cast_optional(elem, &["Properties", "DeletedTime"])?;
+        let remaining_retention_days =
+            cast_optional(elem, &["Properties", "RemainingRetentionDays"])?;
         let metadata = {
             let mut hm = HashMap::new();
             let metadata = traverse(elem, &["Metadata"], true)?;
@@ -183,7 +238,14 @@ impl Container {
             public_access,
             has_immutability_policy,
             has_legal_hold,
+            immutable_storage_with_versioning_enabled,
+            default_encryption_scope,
+            prevent_encryption_scope_override,
             metadata,
+            deleted,
+            version,
+            deleted_time,
+            remaining_retention_days,
         })
     }
 }
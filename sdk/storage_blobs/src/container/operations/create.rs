@@ -6,7 +6,8 @@ operation! {
     Create,
     client: ContainerClient,
     ?public_access: PublicAccess,
-    ?metadata: Metadata
+    ?metadata: Metadata,
+    ?encryption_scope: ContainerEncryptionScope
 }
 
 impl CreateBuilder {
@@ -30,6 +31,7 @@ impl CreateBuilder {
             {
                 headers.insert(name, value);
             }
+            headers.add(self.encryption_scope);
 
             let mut request = ContainerClient::finalize_request(url, Method::Put, headers, None)?;
 
@@ -19,8 +19,11 @@ operation! {
     ?include_uncommitted_blobs: bool,
     ?include_copy: bool,
     ?include_deleted: bool,
+    ?include_deleted_with_versions: bool,
     ?include_tags: bool,
     ?include_versions: bool,
+    ?include_immutability_policy: bool,
+    ?include_legal_hold: bool,
     ?marker: NextMarker,
 }
 
@@ -62,12 +65,21 @@ impl ListBlobsBuilder {
                 if this.include_deleted.unwrap_or(false) {
                     optional_includes.push("deleted");
                 }
+                if this.include_deleted_with_versions.unwrap_or(false) {
+                    optional_includes.push("deletedwithversions");
+                }
                 if this.include_tags.unwrap_or(false) {
                     optional_includes.push("tags");
                 }
                 if this.include_versions.unwrap_or(false) {
                     optional_includes.push("versions");
                 }
+                if this.include_immutability_policy.unwrap_or(false) {
+                    optional_includes.push("immutabilitypolicy");
+                }
+                if this.include_legal_hold.unwrap_or(false) {
+                    optional_includes.push("legalhold");
+                }
                 if !optional_includes.is_empty() {
                     url.query_pairs_mut()
                         .append_pair("include", &optional_includes.join(","));
@@ -7,7 +7,9 @@ pub mod get_properties;
 pub mod list_blobs;
 pub mod release_lease;
 pub mod renew_lease;
+pub mod restore;
 pub mod set_acl;
+pub mod walk_blobs;
 pub use self::acquire_lease::*;
 pub use self::break_lease::*;
 pub use self::create::*;
@@ -17,4 +19,6 @@ pub use self::get_properties::*;
 pub use self::list_blobs::*;
 pub use self::release_lease::*;
 pub use self::renew_lease::*;
+pub use self::restore::*;
 pub use self::set_acl::*;
+pub use self::walk_blobs::*;
@@ -0,0 +1,41 @@
+use crate::prelude::*;
+use azure_core::Method;
+use azure_core::{headers::*, RequestId};
+use azure_storage::headers::{DELETED_CONTAINER_NAME, DELETED_CONTAINER_VERSION};
+use time::OffsetDateTime;
+
+operation! {
+    Restore,
+    client: ContainerClient,
+    deleted_container_name: String,
+    deleted_container_version: String,
+}
+
+impl RestoreBuilder {
+    pub fn into_future(mut self) -> Restore {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "container");
+            url.query_pairs_mut().append_pair("comp", "undelete");
+
+            let mut headers = Headers::new();
+            headers.insert(DELETED_CONTAINER_NAME, self.deleted_container_name.clone());
+            headers.insert(
+                DELETED_CONTAINER_VERSION,
+                self.deleted_container_version.clone(),
+            );
+
+            let mut request = ContainerClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            RestoreResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(RestoreResponse ,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
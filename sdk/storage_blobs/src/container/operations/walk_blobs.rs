@@ -0,0 +1,216 @@
+use crate::{container::operations::BlobItem, prelude::*};
+use futures::{stream::SelectAll, Stream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+
+const DELIMITER: &str = "/";
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+operation! {
+    #[stream]
+    WalkBlobs,
+    client: ContainerClient,
+    ?prefix: String,
+    ?include: Vec<BlobPattern>,
+    ?exclude: Vec<BlobPattern>,
+    ?max_depth: usize,
+    ?max_concurrency: usize,
+    ?include_metadata: bool,
+}
+
+/// A blob or virtual directory found while walking a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalkEntry {
+    /// How many virtual directories below the starting prefix the item is.
+    pub depth: usize,
+    pub item: BlobItem,
+}
+
+/// A virtual directory and everything found below it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VirtualDirectory {
+    /// The full prefix of the directory, including the trailing delimiter.
+    /// Empty for the root of the container.
+    pub name: String,
+    pub blobs: Vec<Blob>,
+    pub directories: Vec<VirtualDirectory>,
+}
+
+impl WalkBlobsBuilder {
+    /// Recursively list the blobs below `prefix`, descending into virtual
+    /// directories.
+    ///
+    /// Up to `max_concurrency` directories are listed at the same time, so
+    /// entries of sibling directories can be interleaved. Entries are returned
+    /// as each page of a directory is listed. Directories matching
+    /// an `exclude` pattern are neither returned nor descended into. Blobs are
+    /// returned if they match any `include` pattern (or if there are none),
+    /// and no `exclude` pattern.
+    pub fn into_stream(self) -> impl Stream<Item = azure_core::Result<WalkEntry>> + 'static {
+        let max_concurrency = self
+            .max_concurrency
+            .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+            .max(1);
+        let mut queue = VecDeque::new();
+        queue.push_back((self.prefix.clone().unwrap_or_default(), 0));
+        let state = (
+            self,
+            queue,
+            SelectAll::new(),
+            VecDeque::<azure_core::Result<WalkEntry>>::new(),
+        );
+
+        futures::stream::unfold(
+            state,
+            move |(this, mut queue, mut running, mut ready)| async move {
+                loop {
+                    if let Some(entry) = ready.pop_front() {
+                        return Some((entry, (this, queue, running, ready)));
+                    }
+                    while running.len() < max_concurrency {
+                        let Some((prefix, depth)) = queue.pop_front() else {
+                            break;
+                        };
+                        running.push(list_directory(this.clone(), prefix, depth));
+                    }
+                    // a directory is only dropped once its last page was
+                    // listed, so go on while some are still queued
+                    let Some((depth, items)) = running.next().await else {
+                        if queue.is_empty() {
+                            return None;
+                        }
+                        continue;
+                    };
+                    let items = match items {
+                        Ok(items) => items,
+                        Err(err) => {
+                            ready.push_back(Err(err));
+                            continue;
+                        }
+                    };
+                    for item in items {
+                        match &item {
+                            BlobItem::Blob(blob) if !this.is_included(&blob.name) => continue,
+                            BlobItem::BlobPrefix(prefix) => {
+                                if this.is_excluded(&prefix.name) {
+                                    continue;
+                                }
+                                if this.max_depth.map_or(true, |max| depth < max) {
+                                    queue.push_back((prefix.name.clone(), depth + 1));
+                                }
+                            }
+                            BlobItem::Blob(_) => {}
+                        }
+                        ready.push_back(Ok(WalkEntry { depth, item }));
+                    }
+                }
+            },
+        )
+    }
+
+    /// Walk the container and assemble the results into a tree of virtual
+    /// directories rooted at `prefix`.
+    pub async fn into_tree(self) -> azure_core::Result<VirtualDirectory> {
+        let root = self.prefix.clone().unwrap_or_default();
+        let mut directories: HashMap<String, VirtualDirectory> = HashMap::new();
+        directories.insert(root.clone(), VirtualDirectory::new(root.clone()));
+
+        let mut stream = Box::pin(self.into_stream());
+        while let Some(entry) = stream.next().await {
+            match entry?.item {
+                BlobItem::Blob(blob) => directories
+                    .entry(parent_of(&root, &blob.name))
+                    .or_insert_with_key(|name| VirtualDirectory::new(name.clone()))
+                    .blobs
+                    .push(blob),
+                BlobItem::BlobPrefix(prefix) => {
+                    directories
+                        .entry(prefix.name.clone())
+                        .or_insert_with(|| VirtualDirectory::new(prefix.name));
+                }
+            }
+        }
+
+        // Attach the deepest directories first, so that every directory is
+        // complete before it is moved into its parent.
+        let mut names: Vec<String> = directories.keys().cloned().collect();
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        for name in names {
+            if name == root {
+                continue;
+            }
+            if let Some(directory) = directories.remove(&name) {
+                directories
+                    .entry(parent_of(&root, &name))
+                    .or_insert_with_key(|name| VirtualDirectory::new(name.clone()))
+                    .directories
+                    .push(directory);
+            }
+        }
+
+        let mut tree = directories.remove(&root).unwrap_or_default();
+        tree.sort();
+        Ok(tree)
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.exclude
+            .iter()
+            .flatten()
+            .any(|pattern| pattern.matches(name))
+    }
+
+    fn is_included(&self, name: &str) -> bool {
+        let included = match &self.include {
+            Some(include) if !include.is_empty() => {
+                include.iter().any(|pattern| pattern.matches(name))
+            }
+            _ => true,
+        };
+        included && !self.is_excluded(name)
+    }
+}
+
+impl VirtualDirectory {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    fn sort(&mut self) {
+        self.blobs.sort_by(|a, b| a.name.cmp(&b.name));
+        self.directories.sort_by(|a, b| a.name.cmp(&b.name));
+        self.directories.iter_mut().for_each(Self::sort);
+    }
+}
+
+/// List the pages of a single virtual directory, ending after an error.
+fn list_directory(
+    this: WalkBlobsBuilder,
+    prefix: String,
+    depth: usize,
+) -> impl Stream<Item = (usize, azure_core::Result<Vec<BlobItem>>)> + Unpin {
+    let mut builder = this
+        .client
+        .list_blobs()
+        .delimiter(DELIMITER)
+        .include_metadata(this.include_metadata.unwrap_or(false))
+        .context(this.context.clone());
+    if !prefix.is_empty() {
+        builder = builder.prefix(prefix);
+    }
+
+    builder
+        .into_stream()
+        .map(move |page| (depth, page.map(|page| page.blobs.items)))
+}
+
+/// The virtual directory containing `name`, never above `root`.
+fn parent_of(root: &str, name: &str) -> String {
+    let trimmed = name.strip_suffix(DELIMITER).unwrap_or(name);
+    match trimmed.rfind(DELIMITER) {
+        Some(index) if index + 1 > root.len() => trimmed[..=index].to_owned(),
+        _ => root.to_owned(),
+    }
+}
@@ -0,0 +1,145 @@
+/// A glob pattern matched against full blob names.
+///
+/// `?` matches a single character and `*` any sequence of characters within
+/// a path segment, while `**` also matches across `/`. A `**/` sequence
+/// matches zero or more whole segments, so `logs/**/*.json` matches both
+/// `logs/a.json` and `logs/2023/01/a.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobPattern {
+    pattern: String,
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    Star,
+    DoubleStar,
+    DoubleStarSlash,
+}
+
+impl BlobPattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '?' => Token::AnyChar,
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        Token::DoubleStarSlash
+                    } else {
+                        Token::DoubleStar
+                    }
+                }
+                '*' => Token::Star,
+                c => Token::Literal(c),
+            };
+            tokens.push(token);
+        }
+        Self { pattern, tokens }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the blob (or virtual directory) name matches the pattern.
+    pub fn matches(&self, name: &str) -> bool {
+        let name: Vec<char> = name.chars().collect();
+        matches(&self.tokens, &name)
+    }
+}
+
+impl From<&str> for BlobPattern {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl From<String> for BlobPattern {
+    fn from(pattern: String) -> Self {
+        Self::new(pattern)
+    }
+}
+
+/// Match the tokens against the name one token at a time, from the last one,
+/// keeping for each position of the name whether the tokens matched so far
+/// match the rest of the name from there.
+fn matches(tokens: &[Token], name: &[char]) -> bool {
+    let len = name.len();
+    let mut next = vec![false; len + 1];
+    next[len] = true;
+    for token in tokens.iter().rev() {
+        let mut current = vec![false; len + 1];
+        // whether the rest of the tokens match after a `/` past the position
+        let mut after_slash = false;
+        for i in (0..=len).rev() {
+            let c = name.get(i);
+            current[i] = match token {
+                Token::Literal(literal) => c == Some(literal) && next[i + 1],
+                Token::AnyChar => c.map_or(false, |c| *c != '/') && next[i + 1],
+                Token::Star => next[i] || (c.map_or(false, |c| *c != '/') && current[i + 1]),
+                Token::DoubleStar => next[i] || (c.is_some() && current[i + 1]),
+                Token::DoubleStarSlash => {
+                    after_slash = after_slash || (c == Some(&'/') && next[i + 1]);
+                    next[i] || after_slash
+                }
+            };
+        }
+        next = current;
+    }
+    next[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_segment_wildcards() {
+        let pattern = BlobPattern::new("data/*.csv");
+        assert!(pattern.matches("data/a.csv"));
+        assert!(pattern.matches("data/.csv"));
+        assert!(!pattern.matches("data/2023/a.csv"));
+        assert!(!pattern.matches("data/a.json"));
+
+        let pattern = BlobPattern::new("file-?.txt");
+        assert!(pattern.matches("file-1.txt"));
+        assert!(!pattern.matches("file-10.txt"));
+        assert!(!pattern.matches("file-/.txt"));
+    }
+
+    #[test]
+    fn recursive_wildcards() {
+        let pattern = BlobPattern::new("logs/**/*.json");
+        assert!(pattern.matches("logs/a.json"));
+        assert!(pattern.matches("logs/2023/01/a.json"));
+        assert!(!pattern.matches("other/a.json"));
+
+        let pattern = BlobPattern::new("**/node_modules/**");
+        assert!(pattern.matches("node_modules/"));
+        assert!(pattern.matches("app/node_modules/"));
+        assert!(pattern.matches("app/node_modules/lib/index.js"));
+        assert!(!pattern.matches("app/src/index.js"));
+
+        let pattern = BlobPattern::new("tmp**");
+        assert!(pattern.matches("tmp"));
+        assert!(pattern.matches("tmp/a/b"));
+    }
+
+    #[test]
+    fn many_wildcards_do_not_backtrack() {
+        let pattern = BlobPattern::new("**a**a**a**a**a**a**a**a**a**a**b");
+        let name = "a".repeat(64);
+        assert!(!pattern.matches(&name));
+        assert!(pattern.matches(&format!("{name}b")));
+
+        let pattern = BlobPattern::new("*a*a*a*a*a*a*a*a*a*a*b");
+        assert!(!pattern.matches(&name));
+    }
+}
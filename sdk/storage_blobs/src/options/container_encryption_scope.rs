@@ -0,0 +1,57 @@
+use azure_core::headers::{AsHeaders, HeaderName, HeaderValue};
+use azure_storage::headers::{DEFAULT_ENCRYPTION_SCOPE, DENY_ENCRYPTION_SCOPE_OVERRIDE};
+
+/// The encryption scope blobs in a container are encrypted with by default.
+///
+/// ref: <https://docs.microsoft.com/azure/storage/blobs/encryption-scope-overview>
+#[derive(Debug, Clone)]
+pub struct ContainerEncryptionScope {
+    default_encryption_scope: String,
+    prevent_override: bool,
+}
+
+impl ContainerEncryptionScope {
+    pub fn new(default_encryption_scope: impl Into<String>) -> Self {
+        Self {
+            default_encryption_scope: default_encryption_scope.into(),
+            prevent_override: false,
+        }
+    }
+
+    /// Reject writes to the container that specify another encryption scope.
+    #[must_use]
+    pub fn prevent_override(mut self, prevent_override: bool) -> Self {
+        self.prevent_override = prevent_override;
+        self
+    }
+}
+
+impl From<&str> for ContainerEncryptionScope {
+    fn from(default_encryption_scope: &str) -> Self {
+        Self::new(default_encryption_scope)
+    }
+}
+
+impl From<String> for ContainerEncryptionScope {
+    fn from(default_encryption_scope: String) -> Self {
+        Self::new(default_encryption_scope)
+    }
+}
+
+impl AsHeaders for ContainerEncryptionScope {
+    type Iter = std::vec::IntoIter<(HeaderName, HeaderValue)>;
+
+    fn as_headers(&self) -> Self::Iter {
+        vec![
+            (
+                DEFAULT_ENCRYPTION_SCOPE,
+                self.default_encryption_scope.clone().into(),
+            ),
+            (
+                DENY_ENCRYPTION_SCOPE_OVERRIDE,
+                self.prevent_override.to_string().into(),
+            ),
+        ]
+        .into_iter()
+    }
+}
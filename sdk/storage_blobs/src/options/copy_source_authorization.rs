@@ -0,0 +1,29 @@
+use azure_core::headers::{self, Header};
+use azure_storage::headers::COPY_SOURCE_AUTHORIZATION;
+
+/// Authorization for the source of a copy operation, used to copy from
+/// another storage account with an OAuth token instead of a SAS.
+///
+/// Only the `Bearer` scheme is supported by the service.
+///
+/// See: <https://docs.microsoft.com/rest/api/storageservices/copy-blob-from-url>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopySourceAuthorization(String);
+
+impl CopySourceAuthorization {
+    /// Create the authorization from a bearer token, without the `Bearer `
+    /// prefix.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+}
+
+impl Header for CopySourceAuthorization {
+    fn name(&self) -> headers::HeaderName {
+        COPY_SOURCE_AUTHORIZATION
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        format!("Bearer {}", self.0).into()
+    }
+}
@@ -1,11 +1,20 @@
 use azure_core::headers::{self, Header};
+use azure_storage::headers::ENCRYPTION_SCOPE;
 
+/// The encryption scope to encrypt the data of a request with, instead of
+/// the default encryption of the account or container.
 #[derive(Debug, Clone)]
 pub struct EncryptionScope(String);
 
+impl EncryptionScope {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 impl Header for EncryptionScope {
     fn name(&self) -> headers::HeaderName {
-        "x-ms-encryption-scope".into()
+        ENCRYPTION_SCOPE
     }
 
     fn value(&self) -> headers::HeaderValue {
@@ -18,3 +27,9 @@ impl From<String> for EncryptionScope {
         Self(s)
     }
 }
+
+impl From<&str> for EncryptionScope {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
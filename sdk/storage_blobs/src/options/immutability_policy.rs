@@ -0,0 +1,156 @@
+use azure_core::{
+    date,
+    error::{Error, ErrorKind},
+    headers::{self, AsHeaders, Header, HeaderName, HeaderValue},
+};
+use azure_storage::headers::{
+    IMMUTABILITY_POLICY_MODE, IMMUTABILITY_POLICY_UNTIL_DATE, LEGAL_HOLD,
+};
+use serde::{Deserialize, Deserializer};
+use std::{fmt, str::FromStr};
+use time::OffsetDateTime;
+
+/// The mode of a blob immutability policy.
+///
+/// The service is not consistent about the casing it uses for these values,
+/// so parsing is case insensitive.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ImmutabilityPolicyMode {
+    Mutable,
+    Unlocked,
+    Locked,
+}
+
+impl ImmutabilityPolicyMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImmutabilityPolicyMode::Mutable => "Mutable",
+            ImmutabilityPolicyMode::Unlocked => "Unlocked",
+            ImmutabilityPolicyMode::Locked => "Locked",
+        }
+    }
+}
+
+impl fmt::Display for ImmutabilityPolicyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ImmutabilityPolicyMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        [
+            ImmutabilityPolicyMode::Mutable,
+            ImmutabilityPolicyMode::Unlocked,
+            ImmutabilityPolicyMode::Locked,
+        ]
+        .into_iter()
+        .find(|mode| mode.as_str().eq_ignore_ascii_case(s))
+        .ok_or_else(|| {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("unknown variant of ImmutabilityPolicyMode found: \"{s}\"")
+            })
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ImmutabilityPolicyMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A time-based retention policy that prevents a blob from being modified or
+/// deleted until the given date.
+///
+/// ref: <https://docs.microsoft.com/azure/storage/blobs/immutable-time-based-retention-policy-overview>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImmutabilityPolicy {
+    pub until: OffsetDateTime,
+    pub mode: ImmutabilityPolicyMode,
+}
+
+impl ImmutabilityPolicy {
+    pub fn new(until: OffsetDateTime, mode: ImmutabilityPolicyMode) -> Self {
+        Self { until, mode }
+    }
+
+    /// An unlocked policy, which can still be shortened or removed.
+    pub fn unlocked(until: OffsetDateTime) -> Self {
+        Self::new(until, ImmutabilityPolicyMode::Unlocked)
+    }
+
+    /// A locked policy, which can only be extended.
+    pub fn locked(until: OffsetDateTime) -> Self {
+        Self::new(until, ImmutabilityPolicyMode::Locked)
+    }
+}
+
+impl AsHeaders for ImmutabilityPolicy {
+    type Iter = std::vec::IntoIter<(HeaderName, HeaderValue)>;
+
+    fn as_headers(&self) -> Self::Iter {
+        vec![
+            (
+                IMMUTABILITY_POLICY_UNTIL_DATE,
+                date::to_rfc1123(&self.until).into(),
+            ),
+            (IMMUTABILITY_POLICY_MODE, self.mode.as_str().into()),
+        ]
+        .into_iter()
+    }
+}
+
+/// Whether a legal hold is placed on the blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegalHold(bool);
+
+impl LegalHold {
+    pub fn new(legal_hold: bool) -> Self {
+        Self(legal_hold)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0
+    }
+}
+
+impl From<bool> for LegalHold {
+    fn from(legal_hold: bool) -> Self {
+        Self(legal_hold)
+    }
+}
+
+impl Header for LegalHold {
+    fn name(&self) -> headers::HeaderName {
+        LEGAL_HOLD
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        self.0.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mode_case_insensitive() {
+        assert_eq!(
+            "unlocked".parse::<ImmutabilityPolicyMode>().unwrap(),
+            ImmutabilityPolicyMode::Unlocked
+        );
+        assert_eq!(
+            "Locked".parse::<ImmutabilityPolicyMode>().unwrap(),
+            ImmutabilityPolicyMode::Locked
+        );
+        assert!("frozen".parse::<ImmutabilityPolicyMode>().is_err());
+    }
+}
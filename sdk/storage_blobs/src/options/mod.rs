@@ -9,15 +9,21 @@ mod blob_content_language;
 mod blob_content_md5;
 mod blob_content_type;
 mod blob_expiry;
+mod blob_pattern;
 mod blob_versioning;
 mod block_id;
 mod condition_append_position;
 mod condition_max_size;
+mod container_encryption_scope;
+mod copy_source_authorization;
 mod delete_snapshot_method;
 mod encryption_key;
 mod encryption_scope;
 mod hash;
+mod immutability_policy;
+mod previous_snapshot;
 mod rehydrate_policy;
+mod sequence_number_action;
 mod tags;
 
 pub use access_tier::AccessTier;
@@ -29,15 +35,21 @@ pub use blob_content_language::BlobContentLanguage;
 pub use blob_content_md5::BlobContentMD5;
 pub use blob_content_type::BlobContentType;
 pub use blob_expiry::BlobExpiry;
+pub use blob_pattern::BlobPattern;
 pub use blob_versioning::BlobVersioning;
 pub use block_id::BlockId;
 pub use condition_append_position::ConditionAppendPosition;
 pub use condition_max_size::ConditionMaxSize;
+pub use container_encryption_scope::ContainerEncryptionScope;
+pub use copy_source_authorization::CopySourceAuthorization;
 pub use delete_snapshot_method::DeleteSnapshotsMethod;
 pub use encryption_key::CPKInfo;
 pub use encryption_scope::EncryptionScope;
 pub use hash::Hash;
+pub use immutability_policy::{ImmutabilityPolicy, ImmutabilityPolicyMode, LegalHold};
+pub use previous_snapshot::PreviousSnapshot;
 pub use rehydrate_policy::RehydratePriority;
+pub use sequence_number_action::SequenceNumberAction;
 pub use tags::Tags;
 
 use std::str::FromStr;
@@ -0,0 +1,69 @@
+use crate::options::Snapshot;
+use azure_core::{
+    headers::{AsHeaders, HeaderName, HeaderValue},
+    AppendToUrlQuery, Url,
+};
+use azure_storage::headers::PREVIOUS_SNAPSHOT_URL;
+
+/// The snapshot a page range diff is computed against.
+///
+/// Managed disk snapshots must be referenced by URL rather than by snapshot
+/// timestamp.
+///
+/// See: <https://docs.microsoft.com/rest/api/storageservices/get-page-ranges>
+#[derive(Debug, Clone)]
+pub enum PreviousSnapshot {
+    Snapshot(Snapshot),
+    Url(Url),
+}
+
+impl AsHeaders for PreviousSnapshot {
+    type Iter = std::option::IntoIter<(HeaderName, HeaderValue)>;
+
+    fn as_headers(&self) -> Self::Iter {
+        match self {
+            PreviousSnapshot::Snapshot(_) => None,
+            PreviousSnapshot::Url(url) => Some((PREVIOUS_SNAPSHOT_URL, url.to_string().into())),
+        }
+        .into_iter()
+    }
+}
+
+impl From<Snapshot> for PreviousSnapshot {
+    fn from(snapshot: Snapshot) -> Self {
+        PreviousSnapshot::Snapshot(snapshot)
+    }
+}
+
+impl From<Url> for PreviousSnapshot {
+    fn from(url: Url) -> Self {
+        PreviousSnapshot::Url(url)
+    }
+}
+
+impl AppendToUrlQuery for PreviousSnapshot {
+    fn append_to_url_query(&self, url: &mut Url) {
+        if let PreviousSnapshot::Snapshot(snapshot) = self {
+            url.query_pairs_mut()
+                .append_pair("prevsnapshot", &snapshot.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn managed_disk_snapshots_are_sent_as_a_header() {
+        let url = Url::parse("https://md.blob.core.windows.net/abcd/abcd?snapshot=x").unwrap();
+        let headers: Vec<_> = PreviousSnapshot::from(url.clone()).as_headers().collect();
+        assert_eq!(
+            headers,
+            vec![(PREVIOUS_SNAPSHOT_URL, url.to_string().into())]
+        );
+
+        let previous = PreviousSnapshot::from(Snapshot::new("2026-10-18T02:00:00.0000000Z"));
+        assert_eq!(previous.as_headers().count(), 0);
+    }
+}
@@ -0,0 +1,53 @@
+use azure_core::headers::{AsHeaders, HeaderName, HeaderValue, BLOB_SEQUENCE_NUMBER};
+use azure_storage::headers::SEQUENCE_NUMBER_ACTION;
+
+/// How the sequence number of a page blob should be modified.
+///
+/// See: <https://docs.microsoft.com/rest/api/storageservices/set-blob-properties>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceNumberAction {
+    /// Sets the sequence number to the higher of the current value and the one provided.
+    Max(u64),
+    /// Sets the sequence number to the value provided.
+    Update(u64),
+    /// Increments the sequence number by one.
+    Increment,
+}
+
+impl AsHeaders for SequenceNumberAction {
+    type Iter = std::vec::IntoIter<(HeaderName, HeaderValue)>;
+
+    fn as_headers(&self) -> Self::Iter {
+        let headers = match self {
+            SequenceNumberAction::Max(sequence_number) => vec![
+                (SEQUENCE_NUMBER_ACTION, "max".into()),
+                (BLOB_SEQUENCE_NUMBER, sequence_number.to_string().into()),
+            ],
+            SequenceNumberAction::Update(sequence_number) => vec![
+                (SEQUENCE_NUMBER_ACTION, "update".into()),
+                (BLOB_SEQUENCE_NUMBER, sequence_number.to_string().into()),
+            ],
+            SequenceNumberAction::Increment => vec![(SEQUENCE_NUMBER_ACTION, "increment".into())],
+        };
+        headers.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_carry_their_sequence_number() {
+        let headers: Vec<_> = SequenceNumberAction::Max(7).as_headers().collect();
+        assert_eq!(
+            headers,
+            vec![
+                (SEQUENCE_NUMBER_ACTION, "max".into()),
+                (BLOB_SEQUENCE_NUMBER, "7".into())
+            ]
+        );
+        let headers: Vec<_> = SequenceNumberAction::Increment.as_headers().collect();
+        assert_eq!(headers, vec![(SEQUENCE_NUMBER_ACTION, "increment".into())]);
+    }
+}
@@ -1,14 +1,9 @@
 use crate::prelude::BlobServiceClient;
-use azure_core::{
-    date::iso8601,
-    headers::Headers,
-    xml::{read_xml_str, to_xml},
-    Method,
-};
+use azure_core::{headers::Headers, xml::read_xml_str, Method};
 use azure_storage::{
-    headers::CommonStorageResponseHeaders, shared_access_signature::service_sas::UserDeligationKey,
+    headers::CommonStorageResponseHeaders,
+    shared_access_signature::service_sas::{UserDelegationKeyRequest, UserDeligationKey},
 };
-use bytes::{Bytes, BytesMut};
 use time::OffsetDateTime;
 
 operation! {
@@ -26,7 +21,7 @@ impl GetUserDelegationKeyBuilder {
             url.query_pairs_mut()
                 .extend_pairs([("restype", "service"), ("comp", "userdelegationkey")]);
 
-            let body = GetUserDelegationKeyRequest {
+            let body = UserDelegationKeyRequest {
                 start: self.start_time,
                 expiry: self.expiry_time,
             }
@@ -48,23 +43,6 @@ impl GetUserDelegationKeyBuilder {
     }
 }
 
-#[derive(Serialize)]
-#[serde(rename = "KeyInfo")]
-struct GetUserDelegationKeyRequest {
-    #[serde(rename = "Start", with = "iso8601")]
-    start: OffsetDateTime,
-    #[serde(rename = "Expiry", with = "iso8601")]
-    expiry: OffsetDateTime,
-}
-
-impl GetUserDelegationKeyRequest {
-    pub fn encode(&self) -> azure_core::Result<Bytes> {
-        let mut body = BytesMut::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>");
-        body.extend(to_xml(self)?);
-        Ok(body.freeze())
-    }
-}
-
 #[derive(Debug)]
 pub struct GetUserDelegationKeyResponse {
     pub common: CommonStorageResponseHeaders,
@@ -89,7 +67,6 @@ mod test {
     use azure_core::auth::Secret;
     use uuid::Uuid;
 
-    const BASIC_REQUEST: &[u8] = b"<?xml version=\"1.0\" encoding=\"utf-8\"?><KeyInfo><Start>1970-01-01T00:00:00Z</Start><Expiry>1970-01-01T00:00:01Z</Expiry></KeyInfo>";
     const BASIC_RESPONSE: &str = "
         <UserDeligationKey>
             <SignedOid>00000000-0000-0000-0000-000000000000</SignedOid>
@@ -102,17 +79,6 @@ mod test {
         </UserDeligationKey>
     ";
 
-    #[test]
-    fn request_xml() -> azure_core::Result<()> {
-        let request = GetUserDelegationKeyRequest {
-            start: OffsetDateTime::from_unix_timestamp(0).unwrap(),
-            expiry: OffsetDateTime::from_unix_timestamp(1).unwrap(),
-        }
-        .encode()?;
-        assert_eq!(BASIC_REQUEST, request);
-        Ok(())
-    }
-
     #[test]
     fn parse_response() -> azure_core::Result<()> {
         let expected = UserDeligationKey {
@@ -0,0 +1,95 @@
+use azure_core::Url;
+use azure_storage::CopyProgress;
+use azure_storage_blobs::{blob::CopyStatus, prelude::*};
+use futures::StreamExt;
+use std::time::Duration;
+
+mod setup;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[tokio::test]
+async fn wait_for_copy_and_abort() -> azure_core::Result<()> {
+    let blob_service = setup::initialize("copy_abort_and_wait")?;
+    let container = blob_service.container_client("backups");
+    let archive = container.blob_client("archive.tar");
+    let source = Url::parse("https://source.blob.core.windows.net/data/archive.tar")?;
+
+    let copy_id = archive.copy(source.clone()).await?.copy_id;
+    let statuses: Vec<_> = archive
+        .wait_for_copy(copy_id)
+        .poll_interval(POLL_INTERVAL)
+        .into_stream()
+        .map(|response| response.map(|r| (r.copy_status, r.copy_progress)))
+        .collect()
+        .await;
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(
+        statuses[0].as_ref().unwrap(),
+        &(
+            CopyStatus::Pending,
+            Some(CopyProgress {
+                bytes_copied: 1024,
+                bytes_total: 4096
+            })
+        )
+    );
+    assert_eq!(statuses[1].as_ref().unwrap().0, CopyStatus::Success);
+
+    let copy_id = archive.copy(source).await?.copy_id;
+    archive.abort_copy(copy_id.clone()).await?;
+    let aborted = archive
+        .wait_for_copy(copy_id)
+        .poll_interval(POLL_INTERVAL)
+        .await?;
+    assert_eq!(aborted.copy_status, CopyStatus::Aborted);
+    assert_eq!(
+        aborted.copy_status_description.as_deref(),
+        Some("Aborted by user")
+    );
+
+    let authorization = CopySourceAuthorization::bearer("source-token");
+    let report = container
+        .blob_client("report.csv")
+        .copy_from_url(Url::parse(
+            "https://source.blob.core.windows.net/data/report.csv",
+        )?)
+        .source_authorization(authorization.clone())
+        .is_synchronous(true)
+        .await?;
+    assert_eq!(report.copy_status, CopyStatus::Success);
+
+    container
+        .blob_client("big.bin")
+        .put_block_url(
+            BlockId::new(b"block-0001".to_vec()),
+            Url::parse("https://source.blob.core.windows.net/data/big.bin")?,
+        )
+        .source_authorization(authorization)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn wait_for_copy_gives_up_after_max_polls() -> azure_core::Result<()> {
+    let blob_service = setup::initialize("copy_wait_max_polls")?;
+    let archive = blob_service
+        .container_client("backups")
+        .blob_client("archive.tar");
+    let source = Url::parse("https://source.blob.core.windows.net/data/archive.tar")?;
+
+    let copy_id = archive.copy(source).await?.copy_id;
+    let error = archive
+        .wait_for_copy(copy_id.clone())
+        .poll_interval(POLL_INTERVAL)
+        .max_polls(2u32)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("copy {copy_id} is still pending after 2 polls")
+    );
+
+    Ok(())
+}
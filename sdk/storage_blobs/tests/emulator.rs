@@ -0,0 +1,290 @@
+use azure_core::{error::ErrorKind, prelude::IfMatchCondition, StatusCode};
+use azure_storage_blobs::prelude::*;
+use futures::StreamExt;
+use std::time::Duration;
+use storage_emulator::StorageEmulator;
+
+fn blob_service(emulator: &StorageEmulator) -> BlobServiceClient {
+    ClientBuilder::emulator()
+        .transport(emulator.transport_options())
+        .blob_service_client()
+}
+
+fn status(error: &azure_core::Error) -> StatusCode {
+    match error.kind() {
+        ErrorKind::HttpResponse { status, .. } => *status,
+        kind => panic!("unexpected error kind {kind:?}"),
+    }
+}
+
+#[tokio::test]
+async fn put_and_get_with_conditions() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let container = blob_service(&emulator).container_client("data");
+    container.create().await?;
+    let blob = container.blob_client("hello.txt");
+
+    let first = blob.put_block_blob("hello").await?;
+    assert_eq!(blob.get_content().await?, b"hello");
+
+    let second = blob.put_block_blob("hello, world").await?;
+    assert_ne!(first.etag, second.etag);
+
+    // a write conditioned on the replaced version fails
+    let error = blob
+        .put_block_blob("stale")
+        .if_match(IfMatchCondition::Match(first.etag))
+        .await
+        .unwrap_err();
+    assert_eq!(status(&error), StatusCode::PreconditionFailed);
+
+    blob.put_block_blob("fresh")
+        .if_match(IfMatchCondition::Match(second.etag))
+        .await?;
+    assert_eq!(blob.get_content().await?, b"fresh");
+
+    let properties = blob.get_properties().await?;
+    assert_eq!(properties.blob.properties.content_length, 5);
+
+    blob.delete().await?;
+    assert!(!blob.exists().await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn lease_guards_writes() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let container = blob_service(&emulator).container_client("data");
+    container.create().await?;
+    let blob = container.blob_client("locked.txt");
+    blob.put_block_blob("v1").await?;
+
+    let lease = blob.acquire_lease(Duration::from_secs(15)).await?;
+
+    let error = blob.put_block_blob("v2").await.unwrap_err();
+    assert_eq!(status(&error), StatusCode::PreconditionFailed);
+
+    blob.put_block_blob("v2").lease_id(lease.lease_id).await?;
+    assert_eq!(blob.get_content().await?, b"v2");
+
+    // the lease expires once its duration has passed
+    emulator.advance_time(Duration::from_secs(16));
+    blob.put_block_blob("v3").await?;
+
+    let lease = blob.acquire_lease(Duration::from_secs(60)).await?;
+    blob.blob_lease_client(lease.lease_id).release().await?;
+    blob.delete().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn block_list_commits_staged_blocks() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let container = blob_service(&emulator).container_client("data");
+    container.create().await?;
+    let blob = container.blob_client("blocks.bin");
+
+    blob.put_block(BlockId::new("b1"), "hello, ").await?;
+    blob.put_block(BlockId::new("b2"), "world").await?;
+    // staged blocks are not readable until they are committed
+    assert!(!blob.exists().await?);
+
+    let block_list = BlockList {
+        blocks: vec![
+            BlobBlockType::new_uncommitted(BlockId::new("b1")),
+            BlobBlockType::new_uncommitted(BlockId::new("b2")),
+        ],
+    };
+    blob.put_block_list(block_list).await?;
+    assert_eq!(blob.get_content().await?, b"hello, world");
+
+    let committed = blob
+        .get_block_list()
+        .block_list_type(BlockListType::Committed)
+        .await?;
+    assert_eq!(committed.block_with_size_list.blocks.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_blobs_by_prefix_and_delimiter() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let container = blob_service(&emulator).container_client("data");
+    container.create().await?;
+    for name in ["a.txt", "logs/1.txt", "logs/2.txt", "tmp/x.txt"] {
+        container.blob_client(name).put_block_blob("x").await?;
+    }
+
+    let mut names = Vec::new();
+    let mut prefixes = Vec::new();
+    let mut pages = container
+        .list_blobs()
+        .delimiter("/")
+        .max_results(std::num::NonZeroU32::new(2).unwrap())
+        .into_stream();
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        names.extend(page.blobs.blobs().map(|blob| blob.name.clone()));
+        prefixes.extend(page.blobs.prefixes().map(|prefix| prefix.name.clone()));
+    }
+    assert_eq!(names, ["a.txt"]);
+    assert_eq!(prefixes, ["logs/", "tmp/"]);
+
+    let mut pages = container.list_blobs().prefix("logs/").into_stream();
+    let page = pages.next().await.unwrap()?;
+    let names: Vec<_> = page.blobs.blobs().map(|blob| blob.name.as_str()).collect();
+    assert_eq!(names, ["logs/1.txt", "logs/2.txt"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn client_side_encryption() -> azure_core::Result<()> {
+    use azure_storage::client_side_encryption::{
+        ClientSideEncryption, LocalKeyEncryptionKey, REGION_DATA_LENGTH,
+    };
+
+    let emulator = StorageEmulator::new();
+    let encryption =
+        ClientSideEncryption::new(LocalKeyEncryptionKey::new("local:key1", vec![3; 32])?);
+    let encrypted = ClientBuilder::emulator()
+        .transport(emulator.transport_options())
+        .client_side_encryption(encryption)
+        .container_client("data");
+    let plain = blob_service(&emulator).container_client("data");
+    plain.create().await?;
+
+    let data: Vec<u8> = (0..REGION_DATA_LENGTH + 10)
+        .map(|i| (i % 251) as u8)
+        .collect();
+    let blob = encrypted.blob_client("secret.bin");
+    blob.put_block_blob(data.clone()).await?;
+
+    // the stored content is the encrypted regions
+    let stored = plain.blob_client("secret.bin").get_content().await?;
+    assert_eq!(stored.len(), data.len() + 2 * 28);
+    let properties = plain.blob_client("secret.bin").get_properties().await?;
+    assert!(properties
+        .blob
+        .metadata
+        .unwrap()
+        .contains_key("encryptiondata"));
+
+    assert_eq!(blob.get_content().await?, data);
+
+    // a range across the region boundary
+    let start = REGION_DATA_LENGTH - 5;
+    let mut stream = blob.get().range(start..start + 10).into_stream();
+    let response = stream.next().await.unwrap()?;
+    assert_eq!(response.blob.properties.content_length, 10);
+    assert_eq!(
+        response.data.collect().await?,
+        &data[start as usize..start as usize + 10]
+    );
+    assert!(stream.next().await.is_none());
+
+    // blobs that were not encrypted are read as is
+    plain
+        .blob_client("plain.txt")
+        .put_block_blob("hello")
+        .await?;
+    assert_eq!(
+        encrypted.blob_client("plain.txt").get_content().await?,
+        b"hello"
+    );
+
+    // the other writes would store plaintext, so they fail without a request
+    let staged = encrypted.blob_client("staged.bin");
+    let error = staged
+        .put_block(BlockId::new(b"block-0001".to_vec()), data.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "put_block is not supported with client-side encryption, use put_block_blob"
+    );
+    assert!(staged.append_block(data).await.is_err());
+    assert!(!plain.blob_client("staged.bin").exists().await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn customer_provided_keys_and_encryption_scopes() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let service = blob_service(&emulator);
+    let key = || {
+        CPKInfo::new(
+            azure_core::base64::encode([1; 32]),
+            "tenant-a-key-sha256".to_owned(),
+            None,
+        )
+    };
+
+    let container = service.container_client("tenant-a");
+    container.create().await?;
+    let blob = container.blob_client("data.bin");
+    let response = blob.put_block_blob("secret").encryption_key(key()).await?;
+    assert_eq!(
+        response.encryption_key_sha256.as_deref(),
+        Some("tenant-a-key-sha256")
+    );
+
+    // reads and writes of the blob require its key
+    let error = blob.get_properties().await.unwrap_err();
+    assert_eq!(status(&error), StatusCode::Conflict);
+    let properties = blob.get_properties().encryption_key(key()).await?;
+    assert_eq!(
+        properties
+            .blob
+            .properties
+            .customer_provided_key_sha256
+            .as_deref(),
+        Some("tenant-a-key-sha256")
+    );
+    let error = blob.set_metadata().await.unwrap_err();
+    assert_eq!(status(&error), StatusCode::Conflict);
+    blob.set_metadata().encryption_key(key()).await?;
+    let mut stream = blob.get().encryption_key(key()).into_stream();
+    let data = stream.next().await.unwrap()?.data.collect().await?;
+    assert_eq!(data, "secret");
+
+    let staged = container.blob_client("staged.bin");
+    staged
+        .put_block("b1", "staged")
+        .encryption_key(key())
+        .await?;
+    staged
+        .put_block_list(BlockList {
+            blocks: vec![BlobBlockType::new_uncommitted("b1")],
+        })
+        .encryption_key(key())
+        .await?;
+    let appended = container.blob_client("log.txt");
+    appended.put_append_blob().encryption_key(key()).await?;
+    appended.append_block("line").encryption_key(key()).await?;
+    let error = appended.append_block("line").await.unwrap_err();
+    assert_eq!(status(&error), StatusCode::Conflict);
+
+    // the container's scope is used by default, and can't be overridden
+    let scoped = service.container_client("tenant-b");
+    scoped
+        .create()
+        .encryption_scope(ContainerEncryptionScope::new("tenant-b-scope").prevent_override(true))
+        .await?;
+    let properties = scoped.get_properties().await?;
+    assert_eq!(
+        properties.container.default_encryption_scope.as_deref(),
+        Some("tenant-b-scope")
+    );
+    assert!(properties.container.prevent_encryption_scope_override);
+
+    let blob = scoped.blob_client("data.bin");
+    let response = blob.put_block_blob("scoped").await?;
+    assert_eq!(response.encryption_scope.as_deref(), Some("tenant-b-scope"));
+    let error = blob
+        .put_block_blob("elsewhere")
+        .encryption_scope("other-scope")
+        .await
+        .unwrap_err();
+    assert_eq!(status(&error), StatusCode::Conflict);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use azure_storage_blobs::prelude::*;
+use futures::StreamExt;
+use time::Duration;
+
+mod setup;
+
+#[tokio::test]
+async fn immutability_policy_and_legal_hold() -> azure_core::Result<()> {
+    const CONTAINER_NAME: &str = "test-immutability";
+    const BLOB_NAME: &str = "retained.txt";
+
+    let blob_service = setup::initialize("immutability_policy_and_legal_hold")?;
+    let container = blob_service.container_client(CONTAINER_NAME);
+    let blob = container.blob_client(BLOB_NAME);
+
+    let until = azure_core::date::parse_rfc1123("Fri, 01 Jan 2027 00:00:00 GMT")?;
+
+    blob.put_block_blob("hello world")
+        .immutability_policy(ImmutabilityPolicy::unlocked(until))
+        .legal_hold(true)
+        .await?;
+
+    let properties = blob.get_properties().await?.blob.properties;
+    assert_eq!(properties.immutability_policy_until_date, Some(until));
+    assert_eq!(
+        properties.immutability_policy_mode,
+        Some(ImmutabilityPolicyMode::Unlocked)
+    );
+    assert_eq!(properties.legal_hold, Some(true));
+
+    let response = blob.set_legal_hold(false).await?;
+    assert!(!response.legal_hold);
+
+    let extended = until + Duration::days(30);
+    let response = blob
+        .set_immutability_policy(ImmutabilityPolicy::unlocked(extended))
+        .await?;
+    assert_eq!(response.immutability_policy_until_date, Some(extended));
+    assert_eq!(
+        response.immutability_policy_mode,
+        Some(ImmutabilityPolicyMode::Unlocked)
+    );
+
+    let list = container
+        .list_blobs()
+        .include_immutability_policy(true)
+        .include_legal_hold(true)
+        .into_stream()
+        .next()
+        .await
+        .expect("list blobs should return a page")?;
+    let listed = list
+        .blobs
+        .blobs()
+        .find(|b| b.name == BLOB_NAME)
+        .expect("the blob should be listed");
+    assert_eq!(
+        listed.properties.immutability_policy_until_date,
+        Some(extended)
+    );
+    assert_eq!(
+        listed.properties.immutability_policy_mode,
+        Some(ImmutabilityPolicyMode::Unlocked)
+    );
+    assert_eq!(listed.properties.legal_hold, Some(false));
+
+    blob.delete_immutability_policy().await?;
+
+    Ok(())
+}
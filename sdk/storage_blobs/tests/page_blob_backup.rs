@@ -0,0 +1,43 @@
+use azure_core::{prelude::Range, AppendToUrlQuery, Url};
+use azure_storage_blobs::{blob::CopyStatus, prelude::*};
+
+mod setup;
+
+#[tokio::test]
+async fn differential_backup() -> azure_core::Result<()> {
+    let blob_service = setup::initialize("page_blob_differential_backup")?;
+    let disk = blob_service.container_client("disks").blob_client("os.vhd");
+
+    let resized = disk.resize(16384).await?;
+    assert_eq!(resized.sequence_number, 6);
+
+    let updated = disk
+        .set_sequence_number(SequenceNumberAction::Increment)
+        .await?;
+    assert_eq!(updated.sequence_number, 7);
+
+    let snapshot = disk.snapshot().await?.snapshot;
+    let previous = Snapshot::new("2026-10-18T02:00:00.0000000Z");
+
+    let diff = disk
+        .get_page_ranges_diff(previous)
+        .snapshot(snapshot.clone())
+        .await?;
+    assert_eq!(diff.blob_content_length, Some(16384));
+    assert_eq!(
+        diff.page_list.ranges,
+        vec![Range::new(0, 511), Range::new(4096, 8191)]
+    );
+    assert_eq!(diff.page_list.cleared_ranges, vec![Range::new(1024, 2047)]);
+
+    let mut source = Url::parse("https://rustsdktest.blob.core.windows.net/disks/os.vhd")?;
+    snapshot.append_to_url_query(&mut source);
+    let copy = blob_service
+        .container_client("backup")
+        .blob_client("os.vhd")
+        .incremental_copy(source)
+        .await?;
+    assert_eq!(copy.copy_status, CopyStatus::Pending);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use futures::StreamExt;
+
+mod setup;
+
+#[tokio::test]
+async fn restore_container_and_undelete_blob() -> azure_core::Result<()> {
+    const CONTAINER_NAME: &str = "test-soft-delete-recovery";
+    const BLOB_NAME: &str = "recover-me.txt";
+
+    let blob_service = setup::initialize("soft_delete_recovery")?;
+
+    let deleted = blob_service
+        .list_containers()
+        .include_deleted(true)
+        .into_stream()
+        .next()
+        .await
+        .expect("list containers should return a page")?
+        .containers
+        .into_iter()
+        .find(|c| c.name == CONTAINER_NAME && c.deleted)
+        .expect("the deleted container should be listed");
+    assert_eq!(deleted.remaining_retention_days, Some(6));
+    let version = deleted.version.expect("deleted containers have a version");
+
+    let container = blob_service.container_client(CONTAINER_NAME);
+    container.restore(CONTAINER_NAME, version).await?;
+
+    let list = container
+        .list_blobs()
+        .include_deleted(true)
+        .into_stream()
+        .next()
+        .await
+        .expect("list blobs should return a page")?;
+    let blob = list
+        .blobs
+        .blobs()
+        .find(|b| b.name == BLOB_NAME)
+        .expect("the deleted blob should be listed");
+    let state = blob
+        .deleted_state()
+        .expect("the blob should be soft deleted");
+    assert!(state.deleted_time.is_some());
+    assert_eq!(state.remaining_retention_days, Some(6));
+    assert!(!state.has_versions_only);
+
+    let blob_client = container.blob_client(BLOB_NAME);
+    blob_client.undelete().await?;
+
+    let list = container
+        .list_blobs()
+        .include_deleted(true)
+        .into_stream()
+        .next()
+        .await
+        .expect("list blobs should return a page")?;
+    let blob = list
+        .blobs
+        .blobs()
+        .find(|b| b.name == BLOB_NAME)
+        .expect("the restored blob should be listed");
+    assert_eq!(blob.deleted_state(), None);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use azure_storage_blobs::{container::operations::VirtualDirectory, prelude::*};
+
+mod setup;
+
+fn blob_names(directory: &VirtualDirectory) -> Vec<&str> {
+    directory.blobs.iter().map(|b| b.name.as_str()).collect()
+}
+
+#[tokio::test]
+async fn walk_into_tree() -> azure_core::Result<()> {
+    let blob_service = setup::initialize("walk_blobs")?;
+    let container = blob_service.container_client("sync");
+
+    // A single listing at a time keeps the requests in a stable order.
+    let tree = container
+        .walk_blobs()
+        .include(vec![
+            BlobPattern::new("**/*.json"),
+            BlobPattern::new("readme.md"),
+        ])
+        .exclude(vec![BlobPattern::new("tmp/**")])
+        .max_concurrency(1usize)
+        .into_tree()
+        .await?;
+
+    assert_eq!(tree.name, "");
+    assert_eq!(blob_names(&tree), ["readme.md"]);
+    let names: Vec<_> = tree.directories.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["data/", "logs/"]);
+
+    let data = &tree.directories[0];
+    assert_eq!(blob_names(data), ["data/b.json", "data/c.json"]);
+    assert!(data.directories.is_empty());
+
+    let logs = &tree.directories[1];
+    assert_eq!(blob_names(logs), ["logs/a.json"]);
+    assert_eq!(logs.directories.len(), 1);
+    assert_eq!(logs.directories[0].name, "logs/2023/");
+    assert_eq!(blob_names(&logs.directories[0]), ["logs/2023/x.json"]);
+
+    Ok(())
+}
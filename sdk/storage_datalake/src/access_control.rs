@@ -0,0 +1,597 @@
+//! POSIX access control lists and permissions of paths.
+//!
+//! See [Access control in Azure Data Lake Storage Gen2](https://docs.microsoft.com/azure/storage/blobs/data-lake-storage-access-control).
+
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{self, Header},
+};
+use azure_storage::headers::{GROUP, OWNER, PERMISSIONS, UMASK};
+use std::{fmt, str::FromStr};
+
+/// The read, write and execute permissions of a user or group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RolePermissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl RolePermissions {
+    pub const NONE: Self = Self::new(false, false, false);
+    pub const ALL: Self = Self::new(true, true, true);
+
+    pub const fn new(read: bool, write: bool, execute: bool) -> Self {
+        Self {
+            read,
+            write,
+            execute,
+        }
+    }
+
+    /// The permissions of an octal digit, such as `5` for `r-x`.
+    pub const fn from_octal(digit: u8) -> Self {
+        Self::new(digit & 4 != 0, digit & 2 != 0, digit & 1 != 0)
+    }
+
+    pub const fn octal(&self) -> u8 {
+        (self.read as u8) << 2 | (self.write as u8) << 1 | self.execute as u8
+    }
+}
+
+impl fmt::Display for RolePermissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = |set, c| if set { c } else { '-' };
+        write!(
+            f,
+            "{}{}{}",
+            flag(self.read, 'r'),
+            flag(self.write, 'w'),
+            flag(self.execute, 'x')
+        )
+    }
+}
+
+impl FromStr for RolePermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let flag = |c: u8, set: u8| match c {
+            b'-' => Ok(false),
+            c if c == set => Ok(true),
+            _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid permissions {s}")
+            })),
+        };
+        match s.as_bytes() {
+            [r, w, x] => Ok(Self::new(flag(*r, b'r')?, flag(*w, b'w')?, flag(*x, b'x')?)),
+            _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid permissions {s}")
+            })),
+        }
+    }
+}
+
+/// The permissions of a path for its owner, owning group and everyone else.
+///
+/// Parsed from either the symbolic (`rwxr-x---`) or the octal (`0750`)
+/// notation, and sent in the octal notation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PathPermissions {
+    pub owner: RolePermissions,
+    pub group: RolePermissions,
+    pub other: RolePermissions,
+    pub sticky_bit: bool,
+    /// Whether the path has an ACL with more entries than the permissions.
+    /// Only returned by the service.
+    pub extended_acl: bool,
+}
+
+impl PathPermissions {
+    pub fn new(owner: RolePermissions, group: RolePermissions, other: RolePermissions) -> Self {
+        Self {
+            owner,
+            group,
+            other,
+            ..Default::default()
+        }
+    }
+
+    /// The permissions of an octal mode, such as `0o1750`.
+    pub fn from_octal(mode: u16) -> Self {
+        Self {
+            owner: RolePermissions::from_octal((mode >> 6 & 7) as u8),
+            group: RolePermissions::from_octal((mode >> 3 & 7) as u8),
+            other: RolePermissions::from_octal((mode & 7) as u8),
+            sticky_bit: mode & 0o1000 != 0,
+            extended_acl: false,
+        }
+    }
+
+    pub fn octal(&self) -> u16 {
+        (self.sticky_bit as u16) << 9
+            | (self.owner.octal() as u16) << 6
+            | (self.group.octal() as u16) << 3
+            | self.other.octal() as u16
+    }
+
+    #[must_use]
+    pub fn sticky_bit(self, sticky_bit: bool) -> Self {
+        Self { sticky_bit, ..self }
+    }
+}
+
+impl fmt::Display for PathPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let other = self.other.to_string();
+        let other = match (self.sticky_bit, self.other.execute) {
+            (true, true) => format!("{}t", &other[..2]),
+            (true, false) => format!("{}T", &other[..2]),
+            (false, _) => other,
+        };
+        write!(f, "{}{}{other}", self.owner, self.group)?;
+        if self.extended_acl {
+            write!(f, "+")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PathPermissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let invalid = || {
+            Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid path permissions {s}")
+            })
+        };
+
+        if matches!(s.len(), 3 | 4) && s.bytes().all(|c| (b'0'..=b'7').contains(&c)) {
+            let mode = u16::from_str_radix(s, 8).map_err(|_| invalid())?;
+            if mode > 0o1777 {
+                return Err(invalid());
+            }
+            return Ok(Self::from_octal(mode));
+        }
+
+        let (symbolic, extended_acl) = match s.strip_suffix('+') {
+            Some(symbolic) => (symbolic, true),
+            None => (s, false),
+        };
+        if symbolic.len() != 9 || !symbolic.is_ascii() {
+            return Err(invalid());
+        }
+        let (other, sticky_bit) = match &symbolic[6..] {
+            other if other.ends_with('t') => (format!("{}x", &other[..2]), true),
+            other if other.ends_with('T') => (format!("{}-", &other[..2]), true),
+            other => (other.to_owned(), false),
+        };
+        Ok(Self {
+            owner: symbolic[..3].parse()?,
+            group: symbolic[3..6].parse()?,
+            other: other.parse()?,
+            sticky_bit,
+            extended_acl,
+        })
+    }
+}
+
+impl Header for PathPermissions {
+    fn name(&self) -> headers::HeaderName {
+        PERMISSIONS
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        format!("{:04o}", self.octal()).into()
+    }
+}
+
+/// The permissions removed from the default permissions of a new path, such
+/// as `0o027`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Umask(u16);
+
+impl Umask {
+    pub fn new(umask: u16) -> Self {
+        Self(umask & 0o7777)
+    }
+}
+
+impl From<u16> for Umask {
+    fn from(umask: u16) -> Self {
+        Self::new(umask)
+    }
+}
+
+impl Header for Umask {
+    fn name(&self) -> headers::HeaderName {
+        UMASK
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        format!("{:04o}", self.0).into()
+    }
+}
+
+/// The owning user of a path, as an object ID or, with `upn`, a user
+/// principal name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Owner(String);
+
+impl<S: Into<String>> From<S> for Owner {
+    fn from(owner: S) -> Self {
+        Self(owner.into())
+    }
+}
+
+impl Header for Owner {
+    fn name(&self) -> headers::HeaderName {
+        OWNER
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        self.0.clone().into()
+    }
+}
+
+/// The owning group of a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group(String);
+
+impl<S: Into<String>> From<S> for Group {
+    fn from(group: S) -> Self {
+        Self(group.into())
+    }
+}
+
+impl Header for Group {
+    fn name(&self) -> headers::HeaderName {
+        GROUP
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        self.0.clone().into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessControlType {
+    User,
+    Group,
+    Mask,
+    Other,
+}
+
+impl fmt::Display for AccessControlType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::User => "user",
+            Self::Group => "group",
+            Self::Mask => "mask",
+            Self::Other => "other",
+        })
+    }
+}
+
+impl FromStr for AccessControlType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        match s {
+            "user" => Ok(Self::User),
+            "group" => Ok(Self::Group),
+            "mask" => Ok(Self::Mask),
+            "other" => Ok(Self::Other),
+            _ => Err(Error::with_message(ErrorKind::DataConversion, || {
+                format!("invalid access control type {s}")
+            })),
+        }
+    }
+}
+
+/// An entry of an access control list, formatted as
+/// `[default:]type:[entity id]:permissions`.
+///
+/// Entries without permissions are only used to remove entries with
+/// `remove_access_control_recursive`, and are formatted as
+/// `[default:]type[:entity id]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessControlEntry {
+    /// Whether the entry is part of the default ACL of a directory, which is
+    /// inherited by the paths created in it.
+    pub default_scope: bool,
+    pub access_control_type: AccessControlType,
+    /// The user or group the entry applies to. `None` for the owning user or
+    /// group, the mask and other.
+    pub entity_id: Option<String>,
+    pub permissions: Option<RolePermissions>,
+}
+
+impl AccessControlEntry {
+    pub fn new(
+        access_control_type: AccessControlType,
+        entity_id: Option<String>,
+        permissions: RolePermissions,
+    ) -> Self {
+        Self {
+            default_scope: false,
+            access_control_type,
+            entity_id,
+            permissions: Some(permissions),
+        }
+    }
+
+    pub fn user(entity_id: impl Into<String>, permissions: RolePermissions) -> Self {
+        Self::new(AccessControlType::User, Some(entity_id.into()), permissions)
+    }
+
+    pub fn owning_user(permissions: RolePermissions) -> Self {
+        Self::new(AccessControlType::User, None, permissions)
+    }
+
+    pub fn group(entity_id: impl Into<String>, permissions: RolePermissions) -> Self {
+        Self::new(
+            AccessControlType::Group,
+            Some(entity_id.into()),
+            permissions,
+        )
+    }
+
+    pub fn owning_group(permissions: RolePermissions) -> Self {
+        Self::new(AccessControlType::Group, None, permissions)
+    }
+
+    pub fn mask(permissions: RolePermissions) -> Self {
+        Self::new(AccessControlType::Mask, None, permissions)
+    }
+
+    pub fn other(permissions: RolePermissions) -> Self {
+        Self::new(AccessControlType::Other, None, permissions)
+    }
+
+    /// An entry to remove with `remove_access_control_recursive`.
+    pub fn removal(access_control_type: AccessControlType, entity_id: Option<String>) -> Self {
+        Self {
+            default_scope: false,
+            access_control_type,
+            entity_id,
+            permissions: None,
+        }
+    }
+
+    /// Move the entry to the default ACL.
+    #[must_use]
+    pub fn default_scope(self) -> Self {
+        Self {
+            default_scope: true,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for AccessControlEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.default_scope {
+            f.write_str("default:")?;
+        }
+        write!(f, "{}", self.access_control_type)?;
+        match (&self.entity_id, &self.permissions) {
+            (entity_id, Some(permissions)) => {
+                write!(f, ":{}:{permissions}", entity_id.as_deref().unwrap_or(""))
+            }
+            (Some(entity_id), None) => write!(f, ":{entity_id}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl FromStr for AccessControlEntry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let (default_scope, entry) = match s.strip_prefix("default:") {
+            Some(entry) => (true, entry),
+            None => (false, s),
+        };
+        let parts: Vec<_> = entry.split(':').collect();
+        let (access_control_type, entity_id, permissions) = match parts.as_slice() {
+            [access_control_type] => (access_control_type, "", None),
+            [access_control_type, entity_id] => (access_control_type, *entity_id, None),
+            [access_control_type, entity_id, permissions] => {
+                (access_control_type, *entity_id, Some(permissions.parse()?))
+            }
+            _ => {
+                return Err(Error::with_message(ErrorKind::DataConversion, || {
+                    format!("invalid access control entry {s}")
+                }))
+            }
+        };
+        Ok(Self {
+            default_scope,
+            access_control_type: access_control_type.parse()?,
+            entity_id: (!entity_id.is_empty()).then(|| entity_id.to_owned()),
+            permissions,
+        })
+    }
+}
+
+/// A POSIX access control list, formatted as comma separated entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessControlList(Vec<AccessControlEntry>);
+
+impl AccessControlList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[AccessControlEntry] {
+        &self.0
+    }
+
+    pub fn push(&mut self, entry: AccessControlEntry) {
+        self.0.push(entry);
+    }
+
+    /// The entries of the access ACL, which control access to the path.
+    pub fn access_entries(&self) -> impl Iterator<Item = &AccessControlEntry> {
+        self.0.iter().filter(|entry| !entry.default_scope)
+    }
+
+    /// The entries of the default ACL, which are inherited by new paths.
+    pub fn default_entries(&self) -> impl Iterator<Item = &AccessControlEntry> {
+        self.0.iter().filter(|entry| entry.default_scope)
+    }
+}
+
+impl From<Vec<AccessControlEntry>> for AccessControlList {
+    fn from(entries: Vec<AccessControlEntry>) -> Self {
+        Self(entries)
+    }
+}
+
+impl From<AccessControlEntry> for AccessControlList {
+    fn from(entry: AccessControlEntry) -> Self {
+        Self(vec![entry])
+    }
+}
+
+impl FromIterator<AccessControlEntry> for AccessControlList {
+    fn from_iter<I: IntoIterator<Item = AccessControlEntry>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for AccessControlList {
+    type Item = AccessControlEntry;
+    type IntoIter = std::vec::IntoIter<AccessControlEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl fmt::Display for AccessControlList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AccessControlList {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+impl TryFrom<&str> for AccessControlList {
+    type Error = Error;
+
+    fn try_from(s: &str) -> azure_core::Result<Self> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for AccessControlList {
+    type Error = Error;
+
+    fn try_from(s: String) -> azure_core::Result<Self> {
+        s.parse()
+    }
+}
+
+impl Header for AccessControlList {
+    fn name(&self) -> headers::HeaderName {
+        headers::ACL
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        self.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format_acl() -> azure_core::Result<()> {
+        let text =
+            "user::rwx,user:6d8f4b3a:r-x,group::r--,mask::r-x,other::---,default:user:6d8f4b3a:rw-";
+        let acl: AccessControlList = text.parse()?;
+        assert_eq!(acl.to_string(), text);
+        assert_eq!(
+            acl.entries()[1],
+            AccessControlEntry::user("6d8f4b3a", RolePermissions::new(true, false, true))
+        );
+        assert_eq!(acl.access_entries().count(), 5);
+        assert_eq!(
+            acl.default_entries().collect::<Vec<_>>(),
+            [
+                &AccessControlEntry::user("6d8f4b3a", RolePermissions::new(true, true, false))
+                    .default_scope()
+            ]
+        );
+
+        let removal: AccessControlList = "user:6d8f4b3a,mask,default:group:ops".parse()?;
+        assert_eq!(
+            removal.entries(),
+            [
+                AccessControlEntry::removal(AccessControlType::User, Some("6d8f4b3a".to_owned())),
+                AccessControlEntry::removal(AccessControlType::Mask, None),
+                AccessControlEntry::removal(AccessControlType::Group, Some("ops".to_owned()))
+                    .default_scope(),
+            ]
+        );
+        assert_eq!(removal.to_string(), "user:6d8f4b3a,mask,default:group:ops");
+
+        assert!("".parse::<AccessControlList>()?.entries().is_empty());
+        assert!("user::rwz".parse::<AccessControlList>().is_err());
+        assert!("owner::rwx".parse::<AccessControlList>().is_err());
+        assert!("user:a:b:rwx".parse::<AccessControlList>().is_err());
+
+        // strings convert like they did before the ACL was typed, if valid
+        let converted = AccessControlList::try_from(String::from("user::rwx,other::---"))?;
+        assert_eq!(converted.to_string(), "user::rwx,other::---");
+        assert!(AccessControlList::try_from("user::rwz").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_and_format_permissions() -> azure_core::Result<()> {
+        let permissions: PathPermissions = "rwxr-x---+".parse()?;
+        assert_eq!(permissions.octal(), 0o750);
+        assert!(permissions.extended_acl);
+        assert_eq!(permissions.to_string(), "rwxr-x---+");
+
+        let permissions: PathPermissions = "rwxrwxrwT".parse()?;
+        assert_eq!(permissions.octal(), 0o1776);
+        assert_eq!(permissions.to_string(), "rwxrwxrwT");
+
+        let permissions: PathPermissions = "1777".parse()?;
+        assert_eq!(permissions.to_string(), "rwxrwxrwt");
+        assert_eq!(permissions.value().as_str(), "1777");
+
+        let permissions: PathPermissions = "640".parse()?;
+        assert_eq!(permissions, PathPermissions::from_octal(0o640));
+        assert_eq!(permissions.value().as_str(), "0640");
+
+        assert_eq!(Umask::new(0o27).value().as_str(), "0027");
+
+        assert!("rwxr-x--".parse::<PathPermissions>().is_err());
+        assert!("0800".parse::<PathPermissions>().is_err());
+        assert!("rwxr-xq--".parse::<PathPermissions>().is_err());
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
-use crate::{clients::FileSystemClient, operations::ListFileSystemsBuilder};
+use crate::{
+    clients::FileSystemClient,
+    operations::{GetUserDelegationKeyBuilder, ListFileSystemsBuilder},
+};
 use azure_core::{ClientOptions, Pipeline, Url};
 use azure_storage::{
     clients::{new_pipeline_from_options, ServiceType},
     prelude::StorageCredentials,
     CloudLocation,
 };
+use time::OffsetDateTime;
 
 /// A builder for the blob service client.
 #[derive(Debug, Clone)]
@@ -112,10 +116,29 @@ impl DataLakeClient {
         self.cloud_location.url(ServiceType::DataLake)
     }
 
+    /// The blob endpoint of the account, for the operations only offered there.
+    pub(crate) fn blob_url(&self) -> azure_core::Result<Url> {
+        self.cloud_location.url(ServiceType::Blob)
+    }
+
+    pub(crate) fn account(&self) -> &str {
+        self.cloud_location.account()
+    }
+
     pub fn list_file_systems(&self) -> ListFileSystemsBuilder {
         ListFileSystemsBuilder::new(self.clone())
     }
 
+    /// Get a key to sign user delegation shared access signatures with, valid from `start_time`
+    /// to `expiry_time`. The client must be authenticated with a token credential.
+    pub fn get_user_delegation_key(
+        &self,
+        start_time: OffsetDateTime,
+        expiry_time: OffsetDateTime,
+    ) -> GetUserDelegationKeyBuilder {
+        GetUserDelegationKeyBuilder::new(self.clone(), start_time, expiry_time)
+    }
+
     pub fn file_system_client<FS>(&self, file_system_name: FS) -> FileSystemClient
     where
         FS: Into<String>,
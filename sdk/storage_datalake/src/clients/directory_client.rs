@@ -1,7 +1,15 @@
 use crate::{
-    clients::FileSystemClient, operations::*, prelude::PathClient, request_options::*, Properties,
+    access_control::AccessControlList, clients::FileSystemClient, operations::*,
+    prelude::PathClient, request_options::*, util::check_user_delegation_expiry, Properties,
 };
 use azure_core::{prelude::IfMatchCondition, Response, Url};
+use azure_storage::shared_access_signature::{
+    service_sas::{
+        BlobSasPermissions, BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey,
+    },
+    SasToken,
+};
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct DirectoryClient {
@@ -112,4 +120,83 @@ impl DirectoryClient {
 
         PatchPathBuilder::new(self.clone(), action).acl(acl)
     }
+
+    /// Set the owner, owning group, permissions or ACL of the directory.
+    pub fn set_access_control(&self) -> PatchPathBuilder<Self> {
+        PatchPathBuilder::new(self.clone(), PathUpdateAction::SetAccessControl)
+    }
+
+    /// Replace the ACL of the directory and of every path below it.
+    pub fn set_access_control_recursive(
+        &self,
+        acl: impl Into<AccessControlList>,
+    ) -> SetAccessControlRecursiveBuilder<Self> {
+        SetAccessControlRecursiveBuilder::new(
+            self.clone(),
+            AccessControlChangeMode::Set,
+            acl.into(),
+        )
+    }
+
+    /// Add or update entries of the ACL of the directory and of every path below it.
+    pub fn update_access_control_recursive(
+        &self,
+        acl: impl Into<AccessControlList>,
+    ) -> SetAccessControlRecursiveBuilder<Self> {
+        SetAccessControlRecursiveBuilder::new(
+            self.clone(),
+            AccessControlChangeMode::Modify,
+            acl.into(),
+        )
+    }
+
+    /// Remove entries from the ACL of the directory and of every path below it. The entries don't
+    /// need permissions.
+    pub fn remove_access_control_recursive(
+        &self,
+        acl: impl Into<AccessControlList>,
+    ) -> SetAccessControlRecursiveBuilder<Self> {
+        SetAccessControlRecursiveBuilder::new(
+            self.clone(),
+            AccessControlChangeMode::Remove,
+            acl.into(),
+        )
+    }
+
+    /// Create a user delegation shared access signature for the directory and the paths below
+    /// it, valid until `expiry`, which must not be later than the expiry of the key.
+    ///
+    /// Set `signed_authorized_object_id` on the signature to have the POSIX ACLs checked for the
+    /// user it is issued to.
+    pub fn user_delegation_shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        user_delegation_key: &UserDeligationKey,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        check_user_delegation_expiry(user_delegation_key, expiry)?;
+        let depth = self
+            .dir_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .count();
+        Ok(BlobSharedAccessSignature::new(
+            user_delegation_key.clone(),
+            self.file_system_client
+                .canonicalized_resource(&self.dir_path),
+            permissions,
+            expiry,
+            BlobSignedResource::Directory,
+        )
+        .signed_directory_depth(depth))
+    }
+
+    pub fn generate_signed_directory_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
 }
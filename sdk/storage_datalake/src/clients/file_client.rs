@@ -1,7 +1,17 @@
 use super::{FileSystemClient, PathClient};
-use crate::{operations::*, request_options::*, Properties};
-use azure_core::{prelude::IfMatchCondition, Url};
+use crate::{
+    access_control::AccessControlList, operations::*, request_options::*,
+    util::check_user_delegation_expiry, LogWriter, Properties,
+};
+use azure_core::{prelude::IfMatchCondition, SeekableStream, Url};
+use azure_storage::shared_access_signature::{
+    service_sas::{
+        BlobSasPermissions, BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey,
+    },
+    SasToken,
+};
 use bytes::Bytes;
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct FileClient {
@@ -35,6 +45,10 @@ impl FileClient {
         }
     }
 
+    pub(crate) fn blob_url(&self) -> azure_core::Result<Url> {
+        self.file_system_client.blob_path_url(&self.file_path)
+    }
+
     pub fn create(&self) -> PutPathBuilder<Self> {
         PutPathBuilder::new(self.clone()).resource(ResourceType::File)
     }
@@ -61,6 +75,22 @@ impl FileClient {
         GetFileBuilder::new(self.clone())
     }
 
+    /// Upload `source` to the file, replacing its contents, with appends of
+    /// up to `chunk_size` bytes made in parallel and a final flush.
+    pub fn upload(&self, source: impl SeekableStream + 'static) -> UploadFileBuilder {
+        UploadFileBuilder::new(self.clone(), Box::new(source))
+    }
+
+    /// Download the file with ranged reads made in parallel.
+    pub fn download(&self) -> DownloadFileBuilder {
+        DownloadFileBuilder::new(self.clone())
+    }
+
+    /// A writer appending to the end of the file.
+    pub fn log_writer(&self) -> LogWriter {
+        LogWriter::new(self.clone())
+    }
+
     pub fn rename<P>(&self, destination_path: P) -> RenamePathBuilder<Self>
     where
         P: Into<String>,
@@ -108,4 +138,79 @@ impl FileClient {
     ) -> PatchPathBuilder<Self> {
         PatchPathBuilder::new(self.clone(), PathUpdateAction::SetAccessControl).acl(acl)
     }
+
+    /// Set the owner, owning group, permissions or ACL of the file.
+    pub fn set_access_control(&self) -> PatchPathBuilder<Self> {
+        PatchPathBuilder::new(self.clone(), PathUpdateAction::SetAccessControl)
+    }
+
+    /// Replace the ACL of the file.
+    pub fn set_access_control_recursive(
+        &self,
+        acl: impl Into<AccessControlList>,
+    ) -> SetAccessControlRecursiveBuilder<Self> {
+        SetAccessControlRecursiveBuilder::new(
+            self.clone(),
+            AccessControlChangeMode::Set,
+            acl.into(),
+        )
+    }
+
+    /// Add or update entries of the ACL of the file.
+    pub fn update_access_control_recursive(
+        &self,
+        acl: impl Into<AccessControlList>,
+    ) -> SetAccessControlRecursiveBuilder<Self> {
+        SetAccessControlRecursiveBuilder::new(
+            self.clone(),
+            AccessControlChangeMode::Modify,
+            acl.into(),
+        )
+    }
+
+    /// Remove entries from the ACL of the file. The entries don't
+    /// need permissions.
+    pub fn remove_access_control_recursive(
+        &self,
+        acl: impl Into<AccessControlList>,
+    ) -> SetAccessControlRecursiveBuilder<Self> {
+        SetAccessControlRecursiveBuilder::new(
+            self.clone(),
+            AccessControlChangeMode::Remove,
+            acl.into(),
+        )
+    }
+
+    /// Set when the file expires and is deleted.
+    pub fn set_expiry(&self, expiry: FileExpiry) -> SetFileExpiryBuilder {
+        SetFileExpiryBuilder::new(self.clone(), expiry)
+    }
+
+    /// Create a user delegation shared access signature for the file, valid until `expiry`,
+    /// which must not be later than the expiry of the key.
+    pub fn user_delegation_shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        user_delegation_key: &UserDeligationKey,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        check_user_delegation_expiry(user_delegation_key, expiry)?;
+        Ok(BlobSharedAccessSignature::new(
+            user_delegation_key.clone(),
+            self.file_system_client
+                .canonicalized_resource(&self.file_path),
+            permissions,
+            expiry,
+            BlobSignedResource::Blob,
+        ))
+    }
+
+    pub fn generate_signed_file_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
 }
@@ -1,6 +1,15 @@
 use super::{DataLakeClient, DirectoryClient, FileClient};
-use crate::{operations::*, Properties};
+use crate::{
+    operations::*, request_options::UndeleteSource, util::check_user_delegation_expiry, Properties,
+};
 use azure_core::Url;
+use azure_storage::shared_access_signature::{
+    service_sas::{
+        BlobSasPermissions, BlobSharedAccessSignature, BlobSignedResource, UserDeligationKey,
+    },
+    SasToken,
+};
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct FileSystemClient {
@@ -20,6 +29,26 @@ impl FileSystemClient {
         Ok(self.data_lake_client.url()?.join(&self.name)?)
     }
 
+    pub(crate) fn blob_url(&self) -> azure_core::Result<Url> {
+        Ok(self.data_lake_client.blob_url()?.join(&self.name)?)
+    }
+
+    pub(crate) fn blob_path_url(&self, path: &str) -> azure_core::Result<Url> {
+        let fs_url = self.blob_url()?;
+        let path = [fs_url.path(), path].join("/");
+        Ok(fs_url.join(&path)?)
+    }
+
+    /// The resource signed by shared access signatures for `path` in the file system.
+    pub(crate) fn canonicalized_resource(&self, path: &str) -> String {
+        format!(
+            "/blob/{}/{}/{}",
+            self.data_lake_client.account(),
+            self.name,
+            path.trim_matches('/')
+        )
+    }
+
     pub fn get_directory_client<P>(&self, path: P) -> DirectoryClient
     where
         P: Into<String>,
@@ -68,6 +97,48 @@ impl FileSystemClient {
         SetFileSystemPropertiesBuilder::new(self.clone(), properties)
     }
 
+    /// List the soft deleted paths of the file system, which can be restored with
+    /// `undelete_path` until their retention period ends.
+    pub fn list_deleted_paths(&self) -> ListDeletedPathsBuilder {
+        ListDeletedPathsBuilder::new(self.clone())
+    }
+
+    /// Restore the soft deleted `path` with the deletion ID returned by `list_deleted_paths`.
+    pub fn undelete_path<P, D>(&self, path: P, deletion_id: D) -> UndeletePathBuilder
+    where
+        P: Into<String>,
+        D: Into<String>,
+    {
+        UndeletePathBuilder::new(self.clone(), path.into(), UndeleteSource::new(deletion_id))
+    }
+
+    /// Create a user delegation shared access signature for the file system, valid until
+    /// `expiry`, which must not be later than the expiry of the key.
+    pub fn user_delegation_shared_access_signature(
+        &self,
+        permissions: BlobSasPermissions,
+        user_delegation_key: &UserDeligationKey,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<BlobSharedAccessSignature> {
+        check_user_delegation_expiry(user_delegation_key, expiry)?;
+        Ok(BlobSharedAccessSignature::new(
+            user_delegation_key.clone(),
+            format!("/blob/{}/{}", self.data_lake_client.account(), self.name),
+            permissions,
+            expiry,
+            BlobSignedResource::Container,
+        ))
+    }
+
+    pub fn generate_signed_file_system_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
     pub(crate) async fn send(
         &self,
         ctx: &mut azure_core::Context,
@@ -47,6 +47,17 @@ pub struct PathList {
     pub paths: Vec<Path>,
 }
 
+/// A soft deleted path, which can be restored until its retention period ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedPath {
+    pub name: String,
+    /// Identifies this deletion of the path, as a path can be deleted more than once.
+    pub deletion_id: String,
+    pub creation_time: Option<OffsetDateTime>,
+    pub deleted_time: Option<OffsetDateTime>,
+    pub remaining_retention_days: Option<u32>,
+}
+
 impl TryFrom<Bytes> for PathList {
     type Error = azure_core::error::Error;
 
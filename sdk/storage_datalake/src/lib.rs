@@ -10,8 +10,10 @@ extern crate serde_derive;
 #[macro_use]
 extern crate azure_core;
 
+pub mod access_control;
 pub mod clients;
 pub mod file_system;
+mod log_writer;
 pub mod operations;
 pub mod prelude;
 mod properties;
@@ -19,4 +21,5 @@ pub mod request_options;
 mod util;
 
 pub use file_system::FileSystem;
+pub use log_writer::LogWriter;
 pub use properties::Properties;
@@ -0,0 +1,114 @@
+use crate::clients::FileClient;
+use azure_core::StatusCode;
+use bytes::Bytes;
+
+const DEFAULT_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+/// Appends to the end of a file, batching small writes into larger appends.
+///
+/// Writes are buffered until `buffer_size` bytes are pending, and then
+/// appended to the file. Appended data is only visible to readers once it is
+/// committed by `flush` or `close`. The file is created if it doesn't exist
+/// when the first batch is appended.
+#[derive(Debug)]
+pub struct LogWriter {
+    client: FileClient,
+    buffer_size: usize,
+    buffer: Vec<u8>,
+    /// The end of the appended data, once known.
+    position: Option<u64>,
+}
+
+impl LogWriter {
+    pub fn new(client: FileClient) -> Self {
+        Self {
+            client,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            buffer: Vec::new(),
+            position: None,
+        }
+    }
+
+    setters! {
+        buffer_size: usize => buffer_size.max(1),
+    }
+
+    /// The length of the file including all writes, appended or not.
+    pub fn len(&self) -> Option<u64> {
+        self.position
+            .map(|position| position + self.buffer.len() as u64)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// Buffer `data`, appending the buffered writes once they reach
+    /// `buffer_size` bytes.
+    pub async fn write(&mut self, data: impl AsRef<[u8]>) -> azure_core::Result<()> {
+        self.buffer.extend_from_slice(data.as_ref());
+        if self.buffer.len() >= self.buffer_size {
+            self.append().await?;
+        }
+        Ok(())
+    }
+
+    /// Append the buffered writes and commit everything appended so far.
+    pub async fn flush(&mut self) -> azure_core::Result<()> {
+        self.commit(false).await
+    }
+
+    /// Append the buffered writes, commit everything appended and raise the
+    /// close event of the file.
+    pub async fn close(mut self) -> azure_core::Result<()> {
+        self.commit(true).await
+    }
+
+    async fn commit(&mut self, close: bool) -> azure_core::Result<()> {
+        self.append().await?;
+        let position = self.position().await?;
+        self.client
+            .flush(position as i64)
+            .close(close)
+            .retain_uncommitted_data(false)
+            .await?;
+        Ok(())
+    }
+
+    async fn append(&mut self) -> azure_core::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let position = self.position().await?;
+        let data = Bytes::from(std::mem::take(&mut self.buffer));
+        let length = data.len() as u64;
+        if let Err(err) = self.client.append(position as i64, data.clone()).await {
+            // keep the data for the next attempt
+            self.buffer = [&data[..], &self.buffer[..]].concat();
+            return Err(err);
+        }
+        self.position = Some(position + length);
+        Ok(())
+    }
+
+    async fn position(&mut self) -> azure_core::Result<u64> {
+        if let Some(position) = self.position {
+            return Ok(position);
+        }
+        let position = match self.client.get_properties().await {
+            Ok(properties) => properties.content_length.unwrap_or_default().max(0) as u64,
+            Err(err)
+                if err
+                    .as_http_error()
+                    .map(|e| e.status() == StatusCode::NotFound)
+                    .unwrap_or_default() =>
+            {
+                self.client.create().await?;
+                0
+            }
+            Err(err) => return Err(err),
+        };
+        self.position = Some(position);
+        Ok(position)
+    }
+}
@@ -0,0 +1,134 @@
+use crate::clients::FileClient;
+use azure_core::{
+    error::{ErrorKind, ResultExt},
+    prelude::*,
+};
+use bytes::{Bytes, BytesMut};
+use futures::{AsyncWrite, AsyncWriteExt, Stream, StreamExt};
+use time::OffsetDateTime;
+
+const DEFAULT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+operation! {
+    DownloadFile,
+    client: FileClient,
+    ?range: Range,
+    ?chunk_size: u64,
+    ?max_concurrency: usize,
+    ?lease_id: LeaseId
+}
+
+impl DownloadFileBuilder {
+    /// Download the file, or `range` of it, in ranged reads of `chunk_size`
+    /// bytes, up to `max_concurrency` at the same time.
+    ///
+    /// Every read is conditional on the ETag of the file when the download
+    /// started, so a download fails rather than mixing the contents of a file
+    /// that changes meanwhile.
+    pub fn into_future(self) -> DownloadFile {
+        Box::pin(async move {
+            let (properties, chunks) = self.start().await?;
+            let mut chunks = Box::pin(chunks);
+            let mut data = BytesMut::with_capacity(properties.content_length as usize);
+            while let Some(chunk) = chunks.next().await {
+                data.extend_from_slice(&chunk?);
+            }
+            Ok(DownloadFileResponse {
+                etag: properties.etag,
+                last_modified: properties.last_modified,
+                content_length: properties.content_length,
+                data: data.freeze(),
+            })
+        })
+    }
+
+    /// Download the file, or `range` of it, into `writer`, returning the
+    /// number of bytes written. The chunks are written in order, while the
+    /// following chunks are being read.
+    pub async fn download_to<W>(self, writer: &mut W) -> azure_core::Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (properties, chunks) = self.start().await?;
+        let mut chunks = Box::pin(chunks);
+        while let Some(chunk) = chunks.next().await {
+            writer
+                .write_all(&chunk?)
+                .await
+                .context(ErrorKind::Io, "failed to write the downloaded file")?;
+        }
+        writer
+            .flush()
+            .await
+            .context(ErrorKind::Io, "failed to write the downloaded file")?;
+        Ok(properties.content_length)
+    }
+
+    async fn start(
+        &self,
+    ) -> azure_core::Result<(
+        FileProperties,
+        impl Stream<Item = azure_core::Result<Bytes>> + 'static,
+    )> {
+        let mut head = self.client.get_properties().context(self.context.clone());
+        if let Some(lease_id) = self.lease_id {
+            head = head.lease_id(lease_id);
+        }
+        let head = head.await?;
+        let file_length = head.content_length.unwrap_or_default().max(0) as u64;
+
+        let (start, end) = match &self.range {
+            Some(Range::Range(range)) => (range.start, range.end.min(file_length)),
+            Some(Range::RangeFrom(range)) => (range.start, file_length),
+            None => (0, file_length),
+        };
+        let start = start.min(end);
+        let chunk_size = self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).max(1);
+        let max_concurrency = self
+            .max_concurrency
+            .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+            .max(1);
+
+        let this = self.clone();
+        let etag = head.etag.clone();
+        let ranges = (start..end)
+            .step_by(chunk_size as usize)
+            .map(move |offset| Range::new(offset, (offset + chunk_size).min(end)));
+        let chunks = futures::stream::iter(ranges)
+            .map(move |range| {
+                let mut read = this
+                    .client
+                    .read()
+                    .range(range)
+                    .if_match_condition(IfMatchCondition::Match(etag.clone()))
+                    .context(this.context.clone());
+                if let Some(lease_id) = this.lease_id {
+                    read = read.lease_id(lease_id);
+                }
+                async move { Ok(read.await?.data) }
+            })
+            .buffered(max_concurrency);
+
+        let properties = FileProperties {
+            etag: head.etag,
+            last_modified: head.last_modified,
+            content_length: end - start,
+        };
+        Ok((properties, chunks))
+    }
+}
+
+struct FileProperties {
+    etag: String,
+    last_modified: OffsetDateTime,
+    content_length: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadFileResponse {
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub content_length: u64,
+    pub data: Bytes,
+}
@@ -0,0 +1,127 @@
+use crate::{clients::FileClient, request_options::*, Properties};
+use azure_core::{
+    error::{ErrorKind, ResultExt},
+    prelude::*,
+    SeekableStream,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use bytes::Bytes;
+use futures::{stream::FuturesUnordered, AsyncReadExt, StreamExt};
+use time::OffsetDateTime;
+
+const DEFAULT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+operation! {
+    UploadFile,
+    client: FileClient,
+    source: Box<dyn SeekableStream>,
+    ?chunk_size: u64,
+    ?max_concurrency: usize,
+    ?overwrite: bool,
+    ?close: bool,
+    ?content_md5: FileContentMD5,
+    ?properties: Properties
+}
+
+impl UploadFileBuilder {
+    /// Create the file, append the source in chunks of `chunk_size` bytes and
+    /// flush it.
+    ///
+    /// Up to `max_concurrency` chunks are appended at the same time, each at
+    /// its offset in the file. An existing file is replaced unless
+    /// `overwrite` is false. `content_md5` is set as the hash of the file
+    /// when flushing.
+    pub fn into_future(self) -> UploadFile {
+        Box::pin(async move {
+            let chunk_size = self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).max(1) as usize;
+            let max_concurrency = self
+                .max_concurrency
+                .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+                .max(1);
+
+            let mut create = self.client.create().context(self.context.clone());
+            if !self.overwrite.unwrap_or(true) {
+                create = create.if_match_condition(IfMatchCondition::NotMatch("*".to_owned()));
+            }
+            if let Some(properties) = self.properties.clone() {
+                create = create.properties(properties);
+            }
+            create.await?;
+
+            let mut source = self.source.clone();
+            source.reset().await?;
+            let length = source.len();
+
+            let mut running = FuturesUnordered::new();
+            let mut position = 0;
+            loop {
+                while position < length && running.len() < max_concurrency {
+                    let chunk = read_chunk(&mut source, chunk_size.min(length - position)).await?;
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    let append = self
+                        .client
+                        .append(position as i64, chunk.clone())
+                        .context(self.context.clone())
+                        .into_future();
+                    position += chunk.len();
+                    running.push(append);
+                }
+                match running.next().await {
+                    Some(appended) => {
+                        appended?;
+                    }
+                    None => break,
+                }
+            }
+
+            let mut flush = self
+                .client
+                .flush(position as i64)
+                .close(self.close.unwrap_or_default())
+                .context(self.context.clone());
+            if let Some(content_md5) = self.content_md5 {
+                flush = flush.content_md5(content_md5);
+            }
+            let flushed = flush.await?;
+
+            Ok(UploadFileResponse {
+                common_storage_response_headers: flushed.common_storage_response_headers,
+                etag: flushed.etag,
+                last_modified: flushed.last_modified,
+                content_length: position as u64,
+            })
+        })
+    }
+}
+
+/// Read up to `size` bytes, fewer only at the end of the source.
+async fn read_chunk(
+    source: &mut Box<dyn SeekableStream>,
+    size: usize,
+) -> azure_core::Result<Bytes> {
+    let mut buffer = vec![0; size];
+    let mut filled = 0;
+    while filled < size {
+        let read = source
+            .read(&mut buffer[filled..])
+            .await
+            .context(ErrorKind::Io, "failed to read the upload source")?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    buffer.truncate(filled);
+    Ok(buffer.into())
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: Option<String>,
+    pub last_modified: Option<OffsetDateTime>,
+    pub content_length: u64,
+}
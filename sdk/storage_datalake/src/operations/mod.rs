@@ -2,28 +2,42 @@
 
 #![allow(missing_docs)]
 
+mod file_download;
 mod file_system_create;
 mod file_system_delete;
 mod file_system_get_properties;
 mod file_system_set_properties;
 mod file_systems_list;
+mod file_upload;
 mod path_delete;
 mod path_get;
 mod path_head;
 mod path_list;
+mod path_list_deleted;
 mod path_patch;
 mod path_put;
 mod path_rename;
+mod path_set_access_control_recursive;
+mod path_set_expiry;
+mod path_undelete;
+mod user_delegation_key_get;
 
+pub use file_download::*;
 pub use file_system_create::*;
 pub use file_system_delete::*;
 pub use file_system_get_properties::*;
 pub use file_system_set_properties::*;
 pub use file_systems_list::*;
+pub use file_upload::*;
 pub use path_delete::*;
 pub use path_get::*;
 pub use path_head::*;
 pub use path_list::*;
+pub use path_list_deleted::*;
 pub use path_patch::*;
 pub use path_put::*;
 pub use path_rename::*;
+pub use path_set_access_control_recursive::*;
+pub use path_set_expiry::*;
+pub use path_undelete::*;
+pub use user_delegation_key_get::*;
@@ -2,7 +2,7 @@ use crate::clients::PathClient;
 use crate::request_options::*;
 use azure_core::Response as HttpResponse;
 use azure_core::{prelude::*, Pageable, Request};
-use azure_storage::headers::CommonStorageResponseHeaders;
+use azure_storage::headers::{CommonStorageResponseHeaders, DELETION_ID};
 
 operation! {
     DeletePath<C: PathClient + 'static>,
@@ -44,6 +44,8 @@ impl<C: PathClient + 'static> DeletePathBuilder<C> {
 pub struct DeletePathResponse {
     pub common_storage_response_headers: CommonStorageResponseHeaders,
     pub continuation: Option<NextMarker>,
+    /// With soft delete enabled, identifies the deletion to `undelete_path`.
+    pub deletion_id: Option<String>,
 }
 
 impl DeletePathResponse {
@@ -53,6 +55,7 @@ impl DeletePathResponse {
         Ok(Self {
             common_storage_response_headers: (&headers).try_into()?,
             continuation: NextMarker::from_header_optional(&headers)?,
+            deletion_id: headers.get_optional_string(&DELETION_ID),
         })
     }
 }
@@ -1,8 +1,13 @@
-use crate::{clients::PathClient, request_options::*, Properties};
+use crate::{
+    access_control::{AccessControlList, PathPermissions},
+    clients::PathClient,
+    request_options::*,
+    Properties,
+};
 use azure_core::headers::{self, etag_from_headers, last_modified_from_headers};
 use azure_core::Response as HttpResponse;
 use azure_core::{prelude::*, Request};
-use azure_storage::headers::CommonStorageResponseHeaders;
+use azure_storage::headers::{CommonStorageResponseHeaders, GROUP, OWNER, PERMISSIONS};
 use time::OffsetDateTime;
 
 operation! {
@@ -47,7 +52,10 @@ pub struct HeadPathResponse {
     pub content_type: Option<String>,
     pub last_modified: OffsetDateTime,
     pub properties: Option<Properties>,
-    pub acl: Option<String>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub permissions: Option<PathPermissions>,
+    pub acl: Option<AccessControlList>,
 }
 
 impl HeadPathResponse {
@@ -61,7 +69,10 @@ impl HeadPathResponse {
             content_length: headers.get_optional_as(&headers::CONTENT_LENGTH)?,
             content_type: headers.get_optional_as(&headers::CONTENT_TYPE)?,
             properties: headers.get_optional_as(&headers::PROPERTIES)?,
-            acl: headers.get_optional_string(&headers::ACL),
+            owner: headers.get_optional_string(&OWNER),
+            group: headers.get_optional_string(&GROUP),
+            permissions: headers.get_optional_as(&PERMISSIONS)?,
+            acl: headers.get_optional_as(&headers::ACL)?,
         })
     }
 }
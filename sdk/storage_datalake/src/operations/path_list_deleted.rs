@@ -0,0 +1,134 @@
+use crate::{clients::FileSystemClient, file_system::DeletedPath};
+use azure_core::{error::Error, prelude::*, xml::read_xml, Pageable, Request, Response};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    #[stream]
+    ListDeletedPaths,
+    client: FileSystemClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+    ?continuation: NextMarker
+}
+
+impl ListDeletedPathsBuilder {
+    pub fn into_stream(self) -> Pageable<ListDeletedPathsResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let this = self.clone();
+            let mut ctx = self.context.clone();
+
+            async move {
+                // deleted paths are only listed by the blob endpoint
+                let mut url = this.client.blob_url()?;
+                url.query_pairs_mut().extend_pairs([
+                    ("restype", "container"),
+                    ("comp", "list"),
+                    ("showonly", "deleted"),
+                ]);
+                this.prefix.append_to_url_query(&mut url);
+                if let Some(continuation) = continuation.or(this.continuation) {
+                    continuation.append_to_url_query(&mut url);
+                }
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut request = Request::new(url, azure_core::Method::Get);
+
+                let response = this.client.send(&mut ctx, &mut request).await?;
+
+                ListDeletedPathsResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeletedPathList {
+    next_marker: Option<String>,
+    #[serde(default)]
+    blobs: DeletedPathItems,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DeletedPathItems {
+    #[serde(rename = "Blob", default)]
+    items: Vec<DeletedPathItem>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeletedPathItem {
+    name: String,
+    deletion_id: String,
+    properties: DeletedPathProperties,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeletedPathProperties {
+    #[serde(
+        rename = "Creation-Time",
+        default,
+        with = "azure_core::date::rfc1123::option"
+    )]
+    creation_time: Option<OffsetDateTime>,
+    #[serde(default, with = "azure_core::date::rfc1123::option")]
+    deleted_time: Option<OffsetDateTime>,
+    remaining_retention_days: Option<u32>,
+}
+
+impl From<DeletedPathItem> for DeletedPath {
+    fn from(item: DeletedPathItem) -> Self {
+        Self {
+            name: item.name,
+            deletion_id: item.deletion_id,
+            creation_time: item.properties.creation_time,
+            deleted_time: item.properties.deleted_time,
+            remaining_retention_days: item.properties.remaining_retention_days,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListDeletedPathsResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub paths: Vec<DeletedPath>,
+    pub continuation: Option<NextMarker>,
+}
+
+impl ListDeletedPathsResponse {
+    pub(crate) async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+        let list: DeletedPathList = read_xml(&body)?;
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            paths: list.blobs.items.into_iter().map(Into::into).collect(),
+            continuation: list
+                .next_marker
+                .filter(|marker| !marker.is_empty())
+                .map(NextMarker::new),
+        })
+    }
+}
+
+impl Continuable for ListDeletedPathsResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.continuation.clone()
+    }
+}
+
+impl IntoIterator for ListDeletedPathsResponse {
+    type Item = DeletedPath;
+
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.paths.into_iter()
+    }
+}
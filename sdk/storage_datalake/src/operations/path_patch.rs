@@ -1,3 +1,4 @@
+use crate::access_control::{AccessControlList, Group, Owner, PathPermissions};
 use crate::clients::PathClient;
 use crate::request_options::*;
 use crate::Properties;
@@ -14,6 +15,9 @@ operation! {
     client: C,
     action: PathUpdateAction,
     ?acl: AccessControlList,
+    ?owner: Owner,
+    ?group: Group,
+    ?permissions: PathPermissions,
     ?close: Close,
     ?continuation: NextMarker,
     ?position: Position,
@@ -21,6 +25,7 @@ operation! {
     ?if_match_condition: IfMatchCondition,
     ?if_modified_since: IfModifiedSince,
     ?properties: Properties,
+    ?content_md5: FileContentMD5,
     ?bytes: Bytes,
 }
 
@@ -40,7 +45,11 @@ impl<C: PathClient + 'static> PatchPathBuilder<C> {
             let mut request = Request::new(url, azure_core::Method::Patch);
 
             request.insert_headers(&self.acl);
+            request.insert_headers(&self.owner);
+            request.insert_headers(&self.group);
+            request.insert_headers(&self.permissions);
             request.insert_headers(&self.properties);
+            request.insert_headers(&self.content_md5);
             request.insert_headers(&self.if_match_condition);
             request.insert_headers(&self.if_modified_since);
 
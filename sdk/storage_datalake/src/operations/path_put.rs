@@ -1,4 +1,9 @@
-use crate::{clients::PathClient, request_options::*, Properties};
+use crate::{
+    access_control::{PathPermissions, Umask},
+    clients::PathClient,
+    request_options::*,
+    Properties,
+};
 use azure_core::{
     headers::{etag_from_headers, last_modified_from_headers},
     prelude::*,
@@ -16,6 +21,8 @@ operation! {
     ?if_match_condition: IfMatchCondition,
     ?if_modified_since: IfModifiedSince,
     ?properties: Properties,
+    ?permissions: PathPermissions,
+    ?umask: Umask,
 }
 
 impl<C: PathClient + 'static> PutPathBuilder<C> {
@@ -32,6 +39,8 @@ impl<C: PathClient + 'static> PutPathBuilder<C> {
             let mut request = Request::new(url, azure_core::Method::Put);
 
             request.insert_headers(&self.properties);
+            request.insert_headers(&self.permissions);
+            request.insert_headers(&self.umask);
             request.insert_headers(&self.if_match_condition);
             request.insert_headers(&self.if_modified_since);
             request.insert_headers(&ContentLength::new(0));
@@ -0,0 +1,167 @@
+use crate::{access_control::AccessControlList, clients::PathClient, request_options::*};
+use azure_core::{
+    error::{Error, ErrorKind},
+    from_json,
+    prelude::*,
+    Pageable, Request, Response,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use futures::StreamExt;
+
+operation! {
+    SetAccessControlRecursive<C: PathClient + 'static>,
+    client: C,
+    mode: AccessControlChangeMode,
+    acl: AccessControlList,
+    ?batch_size: u32,
+    ?max_batches: u32,
+    ?continue_on_failure: bool,
+    ?continuation: NextMarker,
+}
+
+impl<C: PathClient + 'static> SetAccessControlRecursiveBuilder<C> {
+    /// Change the ACLs one batch of paths at a time, yielding the result of
+    /// each batch as progress.
+    ///
+    /// The continuation of every batch can be passed to `continuation` to
+    /// resume the change from there later.
+    pub fn into_stream(self) -> Pageable<SetAccessControlRecursiveResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let this = self.clone();
+            let mut ctx = self.context.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                PathUpdateAction::SetAccessControlRecursive.append_to_url_query(&mut url);
+                this.mode.append_to_url_query(&mut url);
+                if let Some(batch_size) = this.batch_size {
+                    url.query_pairs_mut()
+                        .append_pair("maxRecords", &batch_size.to_string());
+                }
+                if let Some(continue_on_failure) = this.continue_on_failure {
+                    url.query_pairs_mut()
+                        .append_pair("forceFlag", &continue_on_failure.to_string());
+                }
+                if let Some(continuation) = continuation.or(this.continuation) {
+                    continuation.append_to_url_query_as_continuation(&mut url);
+                }
+
+                let mut request = Request::new(url, azure_core::Method::Patch);
+                request.insert_headers(&this.acl);
+                request.insert_headers(&ContentLength::new(0));
+
+                let response = this.client.send(&mut ctx, &mut request).await?;
+
+                SetAccessControlRecursiveResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+
+    /// Change the ACLs of all paths, or of up to `max_batches` batches.
+    ///
+    /// Unless `continue_on_failure` is set, the change stops after the first
+    /// batch with failures. The counters and failures of all batches are
+    /// summed up, and the continuation is set if the change stopped before
+    /// all paths were changed.
+    pub fn into_future(self) -> SetAccessControlRecursive {
+        Box::pin(async move {
+            let max_batches = self.max_batches;
+            let continue_on_failure = self.continue_on_failure.unwrap_or_default();
+            let mut stream = self.into_stream();
+
+            let mut result: Option<SetAccessControlRecursiveResponse> = None;
+            let mut batches = 0;
+            while let Some(batch) = stream.next().await {
+                let batch = batch?;
+                batches += 1;
+                let failed = batch.counters.failure_count > 0;
+                match &mut result {
+                    Some(result) => result.add(batch),
+                    None => result = Some(batch),
+                }
+                if (failed && !continue_on_failure) || max_batches == Some(batches) {
+                    break;
+                }
+            }
+            result.ok_or_else(|| {
+                Error::message(
+                    ErrorKind::Other,
+                    "the access control change returned no batch",
+                )
+            })
+        })
+    }
+}
+
+/// The number of paths changed, or failing to change, by a recursive access
+/// control change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessControlChangeCounters {
+    #[serde(default)]
+    pub directories_successful: u64,
+    #[serde(default)]
+    pub files_successful: u64,
+    #[serde(default)]
+    pub failure_count: u64,
+}
+
+/// A path whose ACL could not be changed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessControlChangeFailure {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub path_type: String,
+    pub error_message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetAccessControlRecursiveBody {
+    #[serde(flatten)]
+    counters: AccessControlChangeCounters,
+    #[serde(default)]
+    failed_entries: Vec<AccessControlChangeFailure>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetAccessControlRecursiveResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub counters: AccessControlChangeCounters,
+    pub failed_entries: Vec<AccessControlChangeFailure>,
+    /// Where to resume the change, if there are paths left to change.
+    pub continuation: Option<NextMarker>,
+}
+
+impl SetAccessControlRecursiveResponse {
+    async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        let body: SetAccessControlRecursiveBody = from_json(body.collect().await?)?;
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            counters: body.counters,
+            failed_entries: body.failed_entries,
+            continuation: NextMarker::from_header_optional(&headers)?,
+        })
+    }
+
+    fn add(&mut self, batch: Self) {
+        self.common_storage_response_headers = batch.common_storage_response_headers;
+        self.counters.directories_successful += batch.counters.directories_successful;
+        self.counters.files_successful += batch.counters.files_successful;
+        self.counters.failure_count += batch.counters.failure_count;
+        self.failed_entries.extend(batch.failed_entries);
+        self.continuation = batch.continuation;
+    }
+}
+
+impl Continuable for SetAccessControlRecursiveResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.continuation.clone()
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{
+    clients::{FileClient, PathClient},
+    request_options::*,
+};
+use azure_core::{
+    headers::{etag_from_headers, last_modified_from_headers},
+    prelude::*,
+    Request, Response,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    SetFileExpiry,
+    client: FileClient,
+    expiry: FileExpiry,
+}
+
+impl SetFileExpiryBuilder {
+    pub fn into_future(self) -> SetFileExpiry {
+        Box::pin(async move {
+            // the expiry is only set by the blob endpoint
+            let mut url = self.client.blob_url()?;
+            url.query_pairs_mut().append_pair("comp", "expiry");
+
+            let mut request = Request::new(url, azure_core::Method::Put);
+
+            request.insert_headers(&self.expiry);
+            request.insert_headers(&ContentLength::new(0));
+
+            let response = self
+                .client
+                .send(&mut self.context.clone(), &mut request)
+                .await?;
+
+            SetFileExpiryResponse::try_from(response)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetFileExpiryResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
+
+impl SetFileExpiryResponse {
+    pub fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_status_code, headers, _pinned_stream) = response.deconstruct();
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            etag: etag_from_headers(&headers)?,
+            last_modified: last_modified_from_headers(&headers)?,
+        })
+    }
+}
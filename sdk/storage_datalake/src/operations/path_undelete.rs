@@ -0,0 +1,52 @@
+use crate::{clients::FileSystemClient, request_options::*};
+use azure_core::{prelude::*, Request, Response};
+use azure_storage::headers::{CommonStorageResponseHeaders, RESOURCE_TYPE};
+
+operation! {
+    UndeletePath,
+    client: FileSystemClient,
+    path: String,
+    undelete_source: UndeleteSource,
+}
+
+impl UndeletePathBuilder {
+    pub fn into_future(self) -> UndeletePath {
+        Box::pin(async move {
+            // paths are only restored by the blob endpoint
+            let mut url = self.client.blob_path_url(&self.path)?;
+            url.query_pairs_mut().append_pair("comp", "undelete");
+
+            let mut request = Request::new(url, azure_core::Method::Put);
+
+            request.insert_headers(&self.undelete_source);
+            request.insert_headers(&ContentLength::new(0));
+
+            let response = self
+                .client
+                .send(&mut self.context.clone(), &mut request)
+                .await?;
+
+            UndeletePathResponse::try_from(response)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UndeletePathResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// Whether the restored path is a directory.
+    pub is_directory: bool,
+}
+
+impl UndeletePathResponse {
+    pub fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_status_code, headers, _pinned_stream) = response.deconstruct();
+
+        let resource_type = headers.get_optional_str(&RESOURCE_TYPE);
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            is_directory: resource_type == Some("directory"),
+        })
+    }
+}
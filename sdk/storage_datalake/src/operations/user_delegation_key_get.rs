@@ -0,0 +1,59 @@
+use crate::clients::DataLakeClient;
+use azure_core::{xml::read_xml, Request, Response};
+use azure_storage::{
+    headers::CommonStorageResponseHeaders,
+    shared_access_signature::service_sas::{UserDelegationKeyRequest, UserDeligationKey},
+};
+use time::OffsetDateTime;
+
+operation! {
+    GetUserDelegationKey,
+    client: DataLakeClient,
+    start_time: OffsetDateTime,
+    expiry_time: OffsetDateTime,
+}
+
+impl GetUserDelegationKeyBuilder {
+    pub fn into_future(self) -> GetUserDelegationKey {
+        Box::pin(async move {
+            // user delegation keys are only issued by the blob endpoint
+            let mut url = self.client.blob_url()?;
+            url.query_pairs_mut()
+                .extend_pairs([("restype", "service"), ("comp", "userdelegationkey")]);
+
+            let body = UserDelegationKeyRequest {
+                start: self.start_time,
+                expiry: self.expiry_time,
+            }
+            .encode()?;
+
+            let mut request = Request::new(url, azure_core::Method::Post);
+            request.set_body(body);
+
+            let response = self
+                .client
+                .send(&mut self.context.clone(), &mut request)
+                .await?;
+
+            GetUserDelegationKeyResponse::try_from(response).await
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetUserDelegationKeyResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub user_delegation_key: UserDeligationKey,
+}
+
+impl GetUserDelegationKeyResponse {
+    pub(crate) async fn try_from(response: Response) -> azure_core::Result<Self> {
+        let (_status_code, headers, body) = response.deconstruct();
+        let body = body.collect().await?;
+
+        Ok(Self {
+            common_storage_response_headers: (&headers).try_into()?,
+            user_delegation_key: read_xml(&body)?,
+        })
+    }
+}
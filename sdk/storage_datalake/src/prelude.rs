@@ -1,4 +1,5 @@
+pub use crate::access_control::*;
 pub use crate::clients::*;
 pub use crate::file_system::*;
 pub use crate::operations::*;
-pub use crate::Properties;
+pub use crate::{LogWriter, Properties};
@@ -1,7 +1,12 @@
 //! Request properties used in datalake rest api operations
 
-use azure_core::{AppendToUrlQuery, Header, Url};
+use azure_core::{
+    date,
+    headers::{AsHeaders, HeaderName, HeaderValue},
+    AppendToUrlQuery, Header, Url,
+};
 use azure_storage::headers;
+use time::OffsetDateTime;
 use url::form_urlencoded;
 
 #[derive(Debug, Clone)]
@@ -201,5 +206,106 @@ impl Header for RenameSource {
     }
 }
 
+/// How a recursive access control change applies the given ACL entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessControlChangeMode {
+    /// Replace the ACL.
+    Set,
+    /// Add or update the given entries, keeping the others.
+    Modify,
+    /// Remove the given entries.
+    Remove,
+}
+
+impl AppendToUrlQuery for AccessControlChangeMode {
+    fn append_to_url_query(&self, url: &mut Url) {
+        let mode = match self {
+            Self::Set => "set",
+            Self::Modify => "modify",
+            Self::Remove => "remove",
+        };
+        url.query_pairs_mut().append_pair("mode", mode);
+    }
+}
+
+/// The MD5 hash of the whole file, set when flushing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileContentMD5([u8; 16]);
+
+impl From<[u8; 16]> for FileContentMD5 {
+    fn from(md5: [u8; 16]) -> Self {
+        Self(md5)
+    }
+}
+
+#[cfg(feature = "md5")]
+impl From<md5::Digest> for FileContentMD5 {
+    fn from(md5: md5::Digest) -> Self {
+        Self(md5.0)
+    }
+}
+
+impl Header for FileContentMD5 {
+    fn name(&self) -> azure_core::headers::HeaderName {
+        headers::FILE_CONTENT_MD5
+    }
+
+    fn value(&self) -> azure_core::headers::HeaderValue {
+        azure_core::base64::encode(self.0).into()
+    }
+}
+
+/// When a file expires and is deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileExpiry {
+    /// Milliseconds after the file was created.
+    RelativeToCreation(u64),
+    /// Milliseconds from now.
+    RelativeToNow(u64),
+    Absolute(OffsetDateTime),
+    /// Remove the expiry of the file.
+    NeverExpire,
+}
+
+impl AsHeaders for FileExpiry {
+    type Iter = std::vec::IntoIter<(HeaderName, HeaderValue)>;
+
+    fn as_headers(&self) -> Self::Iter {
+        let (option, time) = match self {
+            Self::RelativeToCreation(duration) => {
+                ("RelativeToCreation", Some(duration.to_string()))
+            }
+            Self::RelativeToNow(duration) => ("RelativeToNow", Some(duration.to_string())),
+            Self::Absolute(time) => ("Absolute", Some(date::to_rfc1123(time))),
+            Self::NeverExpire => ("NeverExpire", None),
+        };
+        let mut headers = vec![(headers::EXPIRY_OPTION, HeaderValue::from_static(option))];
+        if let Some(time) = time {
+            headers.push((headers::EXPIRY_TIME, time.into()));
+        }
+        headers.into_iter()
+    }
+}
+
+/// The soft deleted path to restore, identified by its deletion ID.
+#[derive(Debug, Clone)]
+pub struct UndeleteSource(String);
+
+impl UndeleteSource {
+    pub fn new(deletion_id: impl Into<String>) -> Self {
+        Self(deletion_id.into())
+    }
+}
+
+impl Header for UndeleteSource {
+    fn name(&self) -> azure_core::headers::HeaderName {
+        headers::UNDELETE_SOURCE
+    }
+
+    fn value(&self) -> azure_core::headers::HeaderValue {
+        let deletion_id = form_urlencoded::byte_serialize(self.0.as_bytes()).collect::<String>();
+        format!("?deletionid={deletion_id}").into()
+    }
+}
+
 request_query!(Directory, "directory");
-request_header!(AccessControlList, ACL);
@@ -1,5 +1,26 @@
-use azure_core::headers::{HeaderName, Headers};
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::{HeaderName, Headers},
+};
+use azure_storage::shared_access_signature::service_sas::UserDeligationKey;
+use time::OffsetDateTime;
 
 pub(crate) fn namespace_enabled_from_headers(headers: &Headers) -> azure_core::Result<bool> {
     headers.get_as(&HeaderName::from_static("x-ms-namespace-enabled"))
 }
+
+/// A user delegation shared access signature cannot outlive the key it is signed with.
+pub(crate) fn check_user_delegation_expiry(
+    user_delegation_key: &UserDeligationKey,
+    expiry: OffsetDateTime,
+) -> azure_core::Result<()> {
+    if expiry > user_delegation_key.signed_expiry {
+        return Err(Error::with_message(ErrorKind::Other, || {
+            format!(
+                "the signature expiry {expiry} is later than the expiry of the user delegation key {}",
+                user_delegation_key.signed_expiry
+            )
+        }));
+    }
+    Ok(())
+}
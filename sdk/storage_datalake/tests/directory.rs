@@ -0,0 +1,112 @@
+use azure_core::auth::Secret;
+use azure_storage::{
+    prelude::{BlobSasPermissions, StorageCredentials},
+    shared_access_signature::service_sas::UserDeligationKey,
+};
+use azure_storage_datalake::prelude::*;
+use futures::StreamExt;
+use std::collections::HashMap;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+mod setup;
+
+#[tokio::test]
+async fn directory_acl_recursive() -> azure_core::Result<()> {
+    let data_lake_client = setup::create_data_lake_client("datalake_directory_acl_recursive")
+        .await
+        .unwrap();
+
+    let directory_client = data_lake_client
+        .file_system_client("azurerustsdk-datalake-acl-recursive")
+        .get_directory_client("data");
+    let acl = AccessControlList::from(AccessControlEntry::user(
+        "6d8f4b3a",
+        RolePermissions::new(true, false, true),
+    ));
+
+    // change the first batch only
+    let first = directory_client
+        .update_access_control_recursive(acl.clone())
+        .batch_size(2u32)
+        .max_batches(1u32)
+        .await?;
+    assert_eq!(first.counters.directories_successful, 1);
+    assert_eq!(first.counters.files_successful, 1);
+    let continuation = first.continuation.expect("more paths to change");
+
+    // resume, reporting the progress of every batch
+    let mut batches = directory_client
+        .update_access_control_recursive(acl)
+        .batch_size(2u32)
+        .continue_on_failure(true)
+        .continuation(continuation)
+        .into_stream();
+    let mut progress = Vec::new();
+    let mut failed_entries = Vec::new();
+    while let Some(batch) = batches.next().await {
+        let batch = batch?;
+        progress.push(batch.counters);
+        failed_entries.extend(batch.failed_entries);
+    }
+    assert_eq!(progress.len(), 2);
+    assert_eq!(progress[0].failure_count, 1);
+    assert_eq!(progress[1].directories_successful, 1);
+    assert_eq!(failed_entries.len(), 1);
+    assert_eq!(failed_entries[0].name, "data/locked.csv");
+    assert_eq!(failed_entries[0].path_type, "FILE");
+
+    Ok(())
+}
+
+#[test]
+fn directory_user_delegation_sas() -> azure_core::Result<()> {
+    let data_lake_client = DataLakeClient::new("account", StorageCredentials::anonymous());
+    let directory_client = data_lake_client
+        .file_system_client("notebooks")
+        .get_directory_client("users/alice");
+
+    let user_delegation_key = UserDeligationKey {
+        signed_oid: Uuid::from_u128(0),
+        signed_tid: Uuid::from_u128(1),
+        signed_start: OffsetDateTime::UNIX_EPOCH,
+        signed_expiry: OffsetDateTime::UNIX_EPOCH + Duration::days(1),
+        signed_service: "b".to_owned(),
+        signed_version: "2022-11-02".to_owned(),
+        value: Secret::new("RZfi3m1W7eyQ5zD4ymSmGANVdJ2SDQmg4sE89SW104s="),
+    };
+    let permissions = BlobSasPermissions {
+        read: true,
+        list: true,
+        ..Default::default()
+    };
+    let signature = directory_client
+        .user_delegation_shared_access_signature(
+            permissions,
+            &user_delegation_key,
+            OffsetDateTime::UNIX_EPOCH + Duration::hours(1),
+        )?
+        .signed_authorized_object_id(Uuid::from_u128(2).to_string());
+    let url = directory_client.generate_signed_directory_url(&signature)?;
+
+    assert_eq!(url.host_str(), Some("account.dfs.core.windows.net"));
+    assert_eq!(url.path(), "/notebooks/users/alice");
+    let query: HashMap<_, _> = url.query_pairs().collect();
+    assert_eq!(query["sr"], "d");
+    assert_eq!(query["sdd"], "2");
+    assert_eq!(query["sp"], "rl");
+    assert_eq!(query["se"], "1970-01-01T01:00:00Z");
+    assert_eq!(query["saoid"], Uuid::from_u128(2).to_string());
+    assert!(!query.contains_key("suoid"));
+
+    // the signature cannot outlive the key
+    assert!(directory_client
+        .user_delegation_shared_access_signature(
+            permissions,
+            &user_delegation_key,
+            user_delegation_key.signed_expiry + Duration::seconds(1),
+        )
+        .is_err());
+
+    Ok(())
+}
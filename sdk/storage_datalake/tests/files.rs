@@ -1,4 +1,6 @@
-use azure_storage_datalake::Properties;
+use azure_storage_datalake::{
+    access_control::PathPermissions, request_options::FileExpiry, Properties,
+};
 use futures::StreamExt;
 use std::{assert_eq, assert_ne};
 
@@ -211,7 +213,12 @@ async fn file_get_properties() -> azure_core::Result<()> {
     let file_acl = file_client.get_access_control_list().await?;
     assert_eq!(
         file_acl.acl,
-        Some("user::rw-,group::r--,other::---".to_string())
+        Some("user::rw-,group::r--,other::---".parse()?)
+    );
+    assert_eq!(file_acl.owner.as_deref(), Some("$superuser"));
+    assert_eq!(
+        file_acl.permissions,
+        Some(PathPermissions::from_octal(0o640))
     );
 
     // Cleanup
@@ -219,3 +226,138 @@ async fn file_get_properties() -> azure_core::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn file_upload_download() -> azure_core::Result<()> {
+    let data_lake_client = setup::create_data_lake_client("datalake_file_upload_download")
+        .await
+        .unwrap();
+
+    let file_system_name = "azurerustsdk-datalake-file-upload-download";
+    let file_system_client = data_lake_client
+        .clone()
+        .file_system_client(file_system_name.to_string());
+
+    file_system_client.create().await?;
+
+    let file_path = "some/path/e2etest-file.txt";
+    let file_client = file_system_client.get_file_client(file_path);
+
+    let data = bytes::Bytes::from("some data to upload");
+    let content_md5 = [
+        37, 3, 27, 143, 100, 229, 10, 224, 227, 146, 75, 252, 15, 250, 93, 30,
+    ];
+    let upload_response = file_client
+        .upload(azure_core::BytesStream::new(data.clone()))
+        .chunk_size(5u64)
+        .max_concurrency(1usize)
+        .close(true)
+        .content_md5(content_md5)
+        .await?;
+    assert_eq!(upload_response.content_length, data.len() as u64);
+
+    // Download all of the file in chunks
+    let download_response = file_client
+        .download()
+        .chunk_size(8u64)
+        .max_concurrency(1usize)
+        .await?;
+    assert_eq!(download_response.data, data);
+    assert_eq!(download_response.content_length, data.len() as u64);
+
+    // Download a range of the file into a writer
+    let mut downloaded = Vec::new();
+    let written = file_client
+        .download()
+        .range(5u64..14)
+        .download_to(&mut downloaded)
+        .await?;
+    assert_eq!(written, 9);
+    assert_eq!(downloaded, b"data to u");
+
+    file_system_client.delete().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn file_log_writer() -> azure_core::Result<()> {
+    let data_lake_client = setup::create_data_lake_client("datalake_file_log_writer")
+        .await
+        .unwrap();
+
+    let file_system_name = "azurerustsdk-datalake-file-log-writer";
+    let file_system_client = data_lake_client
+        .clone()
+        .file_system_client(file_system_name.to_string());
+
+    file_system_client.create().await?;
+
+    let file_client = file_system_client.get_file_client("logs/e2etest-log.txt");
+
+    // The file is created by the first append, as it doesn't exist
+    let mut log_writer = file_client.log_writer().buffer_size(8usize);
+    log_writer.write("line 1\n").await?;
+    assert_eq!(log_writer.len(), None);
+    log_writer.write("line 2\n").await?;
+    assert_eq!(log_writer.len(), Some(14));
+
+    log_writer.write("line 3\n").await?;
+    log_writer.flush().await?;
+    assert_eq!(log_writer.len(), Some(21));
+    log_writer.close().await?;
+
+    file_system_client.delete().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn file_expiry_undelete() -> azure_core::Result<()> {
+    let data_lake_client = setup::create_data_lake_client("datalake_file_expiry_undelete")
+        .await
+        .unwrap();
+
+    let file_system_name = "azurerustsdk-datalake-file-expiry-undelete";
+    let file_system_client = data_lake_client
+        .clone()
+        .file_system_client(file_system_name.to_string());
+
+    file_system_client.create().await?;
+
+    let file_path = "scratch/e2etest-file.txt";
+    let file_client = file_system_client.get_file_client(file_path);
+
+    file_client.create().await?;
+    file_client
+        .set_expiry(FileExpiry::RelativeToNow(24 * 60 * 60 * 1000))
+        .await?;
+
+    let delete_response = file_client.delete().into_stream().next().await.unwrap()?;
+    assert_eq!(
+        delete_response.deletion_id.as_deref(),
+        Some("133999123270000000")
+    );
+
+    let deleted_paths = file_system_client
+        .list_deleted_paths()
+        .prefix("scratch/")
+        .into_stream()
+        .next()
+        .await
+        .unwrap()?;
+    assert_eq!(deleted_paths.continuation, None);
+    let deleted_paths = deleted_paths.paths;
+    assert_eq!(deleted_paths.len(), 1);
+    assert_eq!(deleted_paths[0].name, file_path);
+    assert_eq!(deleted_paths[0].remaining_retention_days, Some(7));
+
+    let undelete_response = file_system_client
+        .undelete_path(file_path, deleted_paths[0].deletion_id.clone())
+        .await?;
+    assert!(!undelete_response.is_directory);
+
+    file_system_client.delete().await?;
+
+    Ok(())
+}
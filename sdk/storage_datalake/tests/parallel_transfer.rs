@@ -0,0 +1,173 @@
+use azure_core::{
+    headers::Headers, Body, Context, Method, Policy, PolicyResult, Request, Response, StatusCode,
+    TransportOptions,
+};
+use azure_storage::prelude::StorageCredentials;
+use azure_storage_datalake::prelude::*;
+use bytes::Bytes;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+const CHUNK_SIZE: usize = 4;
+const CHUNKS: usize = 6;
+
+/// An in-memory file which answers appends and ranged reads, delaying the
+/// requests for the start of the file the most, so that concurrent requests
+/// complete in reverse order.
+#[derive(Debug, Default)]
+struct InMemoryFile {
+    state: Mutex<FileState>,
+}
+
+#[derive(Debug, Default)]
+struct FileState {
+    data: Vec<u8>,
+    in_flight: usize,
+    max_in_flight: usize,
+}
+
+impl InMemoryFile {
+    fn max_in_flight(&self) -> usize {
+        self.state.lock().unwrap().max_in_flight
+    }
+
+    /// Wait longer the closer `offset` is to the start of the file.
+    async fn wait(&self, offset: usize) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.in_flight += 1;
+            state.max_in_flight = state.max_in_flight.max(state.in_flight);
+        }
+        tokio::time::sleep(Duration::from_millis(
+            10 * (CHUNKS - offset / CHUNK_SIZE) as u64,
+        ))
+        .await;
+        self.state.lock().unwrap().in_flight -= 1;
+    }
+
+    fn response(status: StatusCode, content_length: usize, body: Bytes) -> Response {
+        let mut headers = Headers::new();
+        headers.insert("content-length", content_length.to_string());
+        headers.insert("date", "Mon, 19 Oct 2026 10:12:07 GMT");
+        headers.insert("etag", "\"0x8DCF01A2B3C4D5E\"");
+        headers.insert("last-modified", "Mon, 19 Oct 2026 10:12:07 GMT");
+        headers.insert("server", "Windows-Azure-HDFS/1.0 Microsoft-HTTPAPI/2.0");
+        headers.insert("x-ms-request-id", "5f3c0006-901f-0012-6a1e-2b7c54000000");
+        headers.insert("x-ms-version", "2022-11-02");
+        Response::new(
+            status,
+            headers,
+            Box::pin(futures::stream::once(async move { Ok(body) })),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl Policy for InMemoryFile {
+    async fn send(
+        &self,
+        _ctx: &Context,
+        request: &mut Request,
+        _next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let query = |name: &str| {
+            request
+                .url()
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        let response = match (request.method(), query("action").as_deref()) {
+            (Method::Patch, Some("append")) => {
+                let position: usize = query("position").unwrap().parse().unwrap();
+                let Body::Bytes(chunk) = request.body() else {
+                    panic!("the appended chunk is not in memory");
+                };
+                let chunk = chunk.clone();
+
+                self.wait(position).await;
+
+                let mut state = self.state.lock().unwrap();
+                if state.data.len() < position + chunk.len() {
+                    state.data.resize(position + chunk.len(), 0);
+                }
+                state.data[position..position + chunk.len()].copy_from_slice(&chunk);
+                Self::response(StatusCode::Accepted, 0, Bytes::new())
+            }
+            (Method::Get, _) => {
+                let range = request.headers().get_str(&"x-ms-range".into())?;
+                let (start, end) = range.trim_start_matches("bytes=").split_once('-').unwrap();
+                let (start, end): (usize, usize) = (start.parse().unwrap(), end.parse().unwrap());
+
+                self.wait(start).await;
+
+                let state = self.state.lock().unwrap();
+                let end = (end + 1).min(state.data.len());
+                let chunk = Bytes::copy_from_slice(&state.data[start..end]);
+                Self::response(StatusCode::PartialContent, chunk.len(), chunk)
+            }
+            (Method::Head, _) => {
+                let length = self.state.lock().unwrap().data.len();
+                Self::response(StatusCode::Ok, length, Bytes::new())
+            }
+            (Method::Put, _) => Self::response(StatusCode::Created, 0, Bytes::new()),
+            _ => Self::response(StatusCode::Ok, 0, Bytes::new()),
+        };
+        Ok(response)
+    }
+}
+
+fn file_client(file: &Arc<InMemoryFile>) -> FileClient {
+    let transport = TransportOptions::new_custom_policy(file.clone());
+    DataLakeClient::builder("account", StorageCredentials::anonymous())
+        .transport(transport)
+        .build()
+        .file_system_client("file-system")
+        .get_file_client("some/path/file.txt")
+}
+
+#[tokio::test]
+async fn parallel_upload_and_download_keep_chunk_order() -> azure_core::Result<()> {
+    let upload_file = Arc::new(InMemoryFile::default());
+    let data = Bytes::from(
+        (0..CHUNK_SIZE * CHUNKS - 1)
+            .map(|i| i as u8)
+            .collect::<Vec<_>>(),
+    );
+
+    let uploaded = file_client(&upload_file)
+        .upload(azure_core::BytesStream::new(data.clone()))
+        .chunk_size(CHUNK_SIZE as u64)
+        .max_concurrency(3usize)
+        .await?;
+    assert_eq!(uploaded.content_length, data.len() as u64);
+    assert_eq!(upload_file.max_in_flight(), 3);
+    assert_eq!(upload_file.state.lock().unwrap().data, data);
+
+    let download_file = Arc::new(InMemoryFile::default());
+    download_file.state.lock().unwrap().data = data.to_vec();
+    let client = file_client(&download_file);
+
+    let downloaded = client
+        .download()
+        .chunk_size(CHUNK_SIZE as u64)
+        .max_concurrency(3usize)
+        .await?;
+    assert_eq!(downloaded.data, data);
+    assert_eq!(download_file.max_in_flight(), 3);
+
+    let mut written = Vec::new();
+    client
+        .download()
+        .range(2u64..17)
+        .chunk_size(CHUNK_SIZE as u64)
+        .max_concurrency(CHUNKS)
+        .download_to(&mut written)
+        .await?;
+    assert_eq!(written, data[2..17]);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use azure_storage::prelude::*;
+use azure_storage_files::{operations::DirectoryEntry, prelude::*};
+use futures::StreamExt;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    // First we retrieve the account name and access key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let access_key =
+        std::env::var("STORAGE_ACCESS_KEY").expect("Set env variable STORAGE_ACCESS_KEY first!");
+
+    let share_name = std::env::args()
+        .nth(1)
+        .expect("Please pass the share name as first parameter");
+    let directory_path = std::env::args().nth(2).unwrap_or_default();
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let file_service = FileServiceClient::new(account, storage_credentials);
+    let directory = file_service
+        .share_client(share_name)
+        .directory_client(directory_path);
+
+    let mut stream = directory.list().include_extended_info(true).into_stream();
+    while let Some(page) = stream.next().await {
+        for entry in page?.entries {
+            match entry {
+                DirectoryEntry::File(file) => println!(
+                    "{}\t{} bytes",
+                    file.name,
+                    file.properties.content_length.unwrap_or_default()
+                ),
+                DirectoryEntry::Directory(directory) => println!("{}/", directory.name),
+            }
+        }
+    }
+
+    Ok(())
+}
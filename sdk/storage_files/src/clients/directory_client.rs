@@ -0,0 +1,102 @@
+use crate::{operations::*, FileClient, HandleId, ShareClient, SmbProperties};
+use azure_core::{prelude::*, Request, Response, Url};
+
+/// A client for a directory in a file share.
+#[derive(Debug, Clone)]
+pub struct DirectoryClient {
+    share_client: ShareClient,
+    path: String,
+}
+
+impl DirectoryClient {
+    pub(crate) fn new(share_client: ShareClient, path: String) -> Self {
+        let path = path.trim_matches('/').to_owned();
+        Self { share_client, path }
+    }
+
+    /// Create the directory. The parent directory must already exist.
+    pub fn create(&self) -> CreateDirectoryBuilder {
+        CreateDirectoryBuilder::new(self.clone())
+    }
+
+    /// Delete the directory. The directory must be empty.
+    pub fn delete(&self) -> DeleteDirectoryBuilder {
+        DeleteDirectoryBuilder::new(self.clone())
+    }
+
+    /// Get the properties, SMB properties and metadata of the directory.
+    pub fn get_properties(&self) -> GetDirectoryPropertiesBuilder {
+        GetDirectoryPropertiesBuilder::new(self.clone())
+    }
+
+    /// Set the SMB properties of the directory.
+    pub fn set_properties(&self, smb_properties: SmbProperties) -> SetDirectoryPropertiesBuilder {
+        SetDirectoryPropertiesBuilder::new(self.clone(), smb_properties)
+    }
+
+    /// List the files and directories directly under this directory.
+    pub fn list(&self) -> ListDirectoryBuilder {
+        ListDirectoryBuilder::new(self.clone())
+    }
+
+    /// List the handles open on the directory, or on anything below it when
+    /// `recursive` is set.
+    pub fn list_handles(&self) -> ListHandlesBuilder {
+        ListHandlesBuilder::new(self.share_client.clone(), self.path.clone())
+    }
+
+    /// Close handles open on the directory, or on anything below it when
+    /// `recursive` is set.
+    pub fn force_close_handles(&self, handle_id: HandleId) -> ForceCloseHandlesBuilder {
+        ForceCloseHandlesBuilder::new(self.share_client.clone(), self.path.clone(), handle_id)
+    }
+
+    /// A directory below this directory.
+    pub fn subdirectory_client<S: AsRef<str>>(&self, name: S) -> DirectoryClient {
+        DirectoryClient::new(self.share_client.clone(), self.child_path(name.as_ref()))
+    }
+
+    /// A file in this directory.
+    pub fn file_client<S: AsRef<str>>(&self, name: S) -> FileClient {
+        FileClient::new(self.share_client.clone(), self.child_path(name.as_ref()))
+    }
+
+    pub fn share_client(&self) -> &ShareClient {
+        &self.share_client
+    }
+
+    /// The path of the directory from the root of the share. Empty for the
+    /// root directory.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        self.share_client.path_url(&self.path)
+    }
+
+    fn child_path(&self, name: &str) -> String {
+        if self.path.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{}/{}", self.path, name)
+        }
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        ShareClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.share_client.send(context, request).await
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{operations::*, FileLeaseClient, HandleId, ShareClient, SmbProperties};
+use azure_core::{prelude::*, Body, Request, Response, Url};
+use azure_storage::shared_access_signature::{
+    file_sas::{FileSasPermissions, FileSharedAccessSignature, FileSignedResource},
+    SasToken,
+};
+use time::OffsetDateTime;
+
+/// A client for a file in a file share.
+///
+/// For a full list of operations available on files, check out [the Azure documentation](https://docs.microsoft.com/rest/api/storageservices/operations-on-files).
+#[derive(Debug, Clone)]
+pub struct FileClient {
+    share_client: ShareClient,
+    path: String,
+}
+
+impl FileClient {
+    pub(crate) fn new(share_client: ShareClient, path: String) -> Self {
+        let path = path.trim_matches('/').to_owned();
+        Self { share_client, path }
+    }
+
+    /// Create the file, or replace an existing one, with the given size.
+    ///
+    /// The file is zero-filled; write its content with `put_range`.
+    pub fn create(&self, length: u64) -> CreateFileBuilder {
+        CreateFileBuilder::new(self.clone(), length)
+    }
+
+    /// Delete the file.
+    pub fn delete(&self) -> DeleteFileBuilder {
+        DeleteFileBuilder::new(self.clone())
+    }
+
+    /// Stream the file in chunks.
+    ///
+    /// By default, files are downloaded in 1MB chunks.
+    pub fn get(&self) -> GetFileBuilder {
+        GetFileBuilder::new(self.clone())
+    }
+
+    /// Return the entire file.
+    pub async fn get_content(&self) -> azure_core::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        let mut stream = self.get().into_stream();
+        while let Some(chunk) = futures::StreamExt::next(&mut stream).await {
+            content.extend(chunk?.data.collect().await?);
+        }
+        Ok(content)
+    }
+
+    /// Get the properties, SMB properties and metadata of the file.
+    pub fn get_properties(&self) -> GetFilePropertiesBuilder {
+        GetFilePropertiesBuilder::new(self.clone())
+    }
+
+    /// Set the SMB properties of the file.
+    pub fn set_properties(&self, smb_properties: SmbProperties) -> SetFilePropertiesBuilder {
+        SetFilePropertiesBuilder::new(self.clone(), smb_properties)
+    }
+
+    /// Write a range of bytes to the file. Ranges can be up to 4MiB.
+    pub fn put_range(&self, start: u64, content: impl Into<Body>) -> PutRangeBuilder {
+        PutRangeBuilder::new(self.clone(), start, content.into())
+    }
+
+    /// Clear a range of bytes in the file, releasing the storage it uses.
+    pub fn clear_range(&self, range: impl Into<Range>) -> ClearRangeBuilder {
+        ClearRangeBuilder::new(self.clone(), range.into())
+    }
+
+    /// List the ranges of the file that contain data.
+    pub fn list_ranges(&self) -> ListRangesBuilder {
+        ListRangesBuilder::new(self.clone())
+    }
+
+    /// Acquire an infinite lease on the file, preventing writes and deletes
+    /// without the lease id.
+    pub fn acquire_lease(&self) -> AcquireFileLeaseBuilder {
+        AcquireFileLeaseBuilder::new(self.clone())
+    }
+
+    /// Break the current lease on the file.
+    pub fn break_lease(&self) -> BreakFileLeaseBuilder {
+        BreakFileLeaseBuilder::new(self.clone())
+    }
+
+    /// Turn into a `FileLeaseClient`.
+    pub fn file_lease_client(&self, lease_id: LeaseId) -> FileLeaseClient {
+        FileLeaseClient::new(self.clone(), lease_id)
+    }
+
+    /// List the handles open on the file.
+    pub fn list_handles(&self) -> ListHandlesBuilder {
+        ListHandlesBuilder::new(self.share_client.clone(), self.path.clone())
+    }
+
+    /// Close handles open on the file.
+    pub fn force_close_handles(&self, handle_id: HandleId) -> ForceCloseHandlesBuilder {
+        ForceCloseHandlesBuilder::new(self.share_client.clone(), self.path.clone(), handle_id)
+    }
+
+    /// Create a shared access signature scoped to this file.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        self.share_client
+            .path_shared_access_signature(&self.path, FileSignedResource::File, permissions, expiry)
+            .await
+    }
+
+    /// Create a signed file url
+    pub fn generate_signed_file_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
+    pub fn share_client(&self) -> &ShareClient {
+        &self.share_client
+    }
+
+    /// The path of the file from the root of the share.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        self.share_client.path_url(&self.path)
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        ShareClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.share_client.send(context, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileServiceClient;
+    use azure_core::auth::Secret;
+    use azure_storage::{
+        shared_access_signature::parsed_sas::ParsedSharedAccessSignature, StorageCredentials,
+    };
+    use time::macros::datetime;
+
+    const KEY: &str = "RZfi3m1W7eyQ5zD4ymSmGANVdJ2SDQmg4sE89SW104s=";
+
+    #[tokio::test]
+    async fn signed_file_url() -> azure_core::Result<()> {
+        let credentials = StorageCredentials::access_key("account", KEY);
+        let file = FileServiceClient::new("account", credentials)
+            .share_client("reports")
+            .file_client("2023/q1 summary.txt");
+
+        let sas = file
+            .shared_access_signature(
+                FileSasPermissions {
+                    read: true,
+                    ..Default::default()
+                },
+                datetime!(2030-01-01 0:00 UTC),
+            )
+            .await?;
+        let url = file.generate_signed_file_url(&sas)?;
+        assert!(url
+            .as_str()
+            .starts_with("https://account.file.core.windows.net/reports/2023/q1%20summary.txt?"));
+
+        let parsed = ParsedSharedAccessSignature::verify_url(&url, &Secret::new(KEY))?;
+        assert_eq!(parsed.resource.as_deref(), Some("f"));
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{operations::*, FileClient};
+use azure_core::{headers::Headers, prelude::*, Body, Method, Request, Response, Url};
+
+#[derive(Debug, Clone)]
+pub struct FileLeaseClient {
+    file_client: FileClient,
+    lease_id: LeaseId,
+}
+
+impl FileLeaseClient {
+    pub(crate) fn new(file_client: FileClient, lease_id: LeaseId) -> Self {
+        Self {
+            file_client,
+            lease_id,
+        }
+    }
+
+    pub fn change(&self, proposed_lease_id: ProposedLeaseId) -> ChangeFileLeaseBuilder {
+        ChangeFileLeaseBuilder::new(self.clone(), proposed_lease_id)
+    }
+
+    pub fn release(&self) -> ReleaseFileLeaseBuilder {
+        ReleaseFileLeaseBuilder::new(self.clone())
+    }
+
+    pub fn lease_id(&self) -> LeaseId {
+        self.lease_id
+    }
+
+    pub fn file_client(&self) -> &FileClient {
+        &self.file_client
+    }
+
+    pub(crate) fn url(&self) -> azure_core::Result<Url> {
+        self.file_client.url()
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: Method,
+        headers: Headers,
+        request_body: Option<Body>,
+    ) -> azure_core::Result<Request> {
+        FileClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.file_client.send(context, request).await
+    }
+}
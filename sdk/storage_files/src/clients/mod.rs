@@ -0,0 +1,13 @@
+mod directory_client;
+mod file_client;
+mod file_lease_client;
+mod file_service_client;
+mod share_client;
+mod share_lease_client;
+
+pub use directory_client::DirectoryClient;
+pub use file_client::FileClient;
+pub use file_lease_client::FileLeaseClient;
+pub use file_service_client::{FileServiceClient, FileServiceClientBuilder};
+pub use share_client::ShareClient;
+pub use share_lease_client::ShareLeaseClient;
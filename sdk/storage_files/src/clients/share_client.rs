@@ -0,0 +1,187 @@
+use crate::{operations::*, DirectoryClient, FileClient, FileServiceClient, ShareLeaseClient};
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    Request, Response, Url,
+};
+use azure_storage::{
+    shared_access_signature::{
+        file_sas::{FileSasPermissions, FileSharedAccessSignature, FileSignedResource},
+        SasToken,
+    },
+    StorageCredentialsInner,
+};
+use std::ops::Deref;
+use time::OffsetDateTime;
+
+/// A client for a file share.
+///
+/// For a full list of operations available on shares, check out [the Azure documentation](https://docs.microsoft.com/rest/api/storageservices/operations-on-shares--file-service-).
+#[derive(Debug, Clone)]
+pub struct ShareClient {
+    service_client: FileServiceClient,
+    share_name: String,
+}
+
+impl ShareClient {
+    pub(crate) fn new(service_client: FileServiceClient, share_name: String) -> Self {
+        Self {
+            service_client,
+            share_name,
+        }
+    }
+
+    /// Create the share.
+    pub fn create(&self) -> CreateShareBuilder {
+        CreateShareBuilder::new(self.clone())
+    }
+
+    /// Delete the share, or one of its snapshots.
+    pub fn delete(&self) -> DeleteShareBuilder {
+        DeleteShareBuilder::new(self.clone())
+    }
+
+    /// Get the properties and metadata of the share.
+    pub fn get_properties(&self) -> GetSharePropertiesBuilder {
+        GetSharePropertiesBuilder::new(self.clone())
+    }
+
+    /// Create a read-only snapshot of the share.
+    pub fn snapshot(&self) -> CreateShareSnapshotBuilder {
+        CreateShareSnapshotBuilder::new(self.clone())
+    }
+
+    /// Store a security descriptor at the share level, so that files and
+    /// directories can reference it by key.
+    ///
+    /// ref: <https://docs.microsoft.com/rest/api/storageservices/create-permission>
+    pub fn create_permission(&self, sddl: impl Into<String>) -> CreatePermissionBuilder {
+        CreatePermissionBuilder::new(self.clone(), sddl.into())
+    }
+
+    /// Get the security descriptor stored under a permission key.
+    pub fn get_permission(&self, permission_key: impl Into<String>) -> GetPermissionBuilder {
+        GetPermissionBuilder::new(self.clone(), permission_key.into())
+    }
+
+    /// Acquire a lease on the share, preventing it from being deleted.
+    pub fn acquire_lease<LD: Into<LeaseDuration>>(
+        &self,
+        lease_duration: LD,
+    ) -> AcquireShareLeaseBuilder {
+        AcquireShareLeaseBuilder::new(self.clone(), lease_duration.into())
+    }
+
+    /// Break the current lease on the share.
+    pub fn break_lease(&self) -> BreakShareLeaseBuilder {
+        BreakShareLeaseBuilder::new(self.clone())
+    }
+
+    /// Turn into a `ShareLeaseClient`.
+    pub fn share_lease_client(&self, lease_id: LeaseId) -> ShareLeaseClient {
+        ShareLeaseClient::new(self.clone(), lease_id)
+    }
+
+    /// The root directory of the share.
+    pub fn root_directory_client(&self) -> DirectoryClient {
+        DirectoryClient::new(self.clone(), String::new())
+    }
+
+    /// A directory in the share, given its path from the root.
+    pub fn directory_client<S: Into<String>>(&self, directory_path: S) -> DirectoryClient {
+        DirectoryClient::new(self.clone(), directory_path.into())
+    }
+
+    /// A file in the share, given its path from the root.
+    pub fn file_client<S: Into<String>>(&self, file_path: S) -> FileClient {
+        FileClient::new(self.clone(), file_path.into())
+    }
+
+    /// Create a shared access signature scoped to this share.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        self.path_shared_access_signature("", FileSignedResource::Share, permissions, expiry)
+            .await
+    }
+
+    /// Create a signed share url
+    pub fn generate_signed_share_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
+    pub(crate) async fn path_shared_access_signature(
+        &self,
+        path: &str,
+        resource: FileSignedResource,
+        permissions: FileSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<FileSharedAccessSignature> {
+        let creds = self.service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let mut canonicalized_resource = format!("/file/{}/{}", account, self.share_name());
+        if !path.is_empty() {
+            canonicalized_resource.push('/');
+            canonicalized_resource.push_str(path);
+        }
+        Ok(FileSharedAccessSignature::new(
+            key.clone(),
+            canonicalized_resource,
+            permissions,
+            expiry,
+            resource,
+        ))
+    }
+
+    pub fn share_name(&self) -> &str {
+        &self.share_name
+    }
+
+    pub fn url(&self) -> azure_core::Result<Url> {
+        let mut url = self.service_client.url()?;
+        url.path_segments_mut()
+            .expect("invalid base url")
+            .pop_if_empty()
+            .push(self.share_name());
+        Ok(url)
+    }
+
+    /// The URL of a file or directory in the share.
+    pub(crate) fn path_url(&self, path: &str) -> azure_core::Result<Url> {
+        let mut url = self.url()?;
+        url.path_segments_mut()
+            .expect("invalid base url")
+            .extend(path.split('/').filter(|segment| !segment.is_empty()));
+        Ok(url)
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: azure_core::Method,
+        headers: azure_core::headers::Headers,
+        request_body: Option<azure_core::Body>,
+    ) -> azure_core::Result<Request> {
+        FileServiceClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.service_client.send(context, request).await
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{operations::*, ShareClient};
+use azure_core::{headers::Headers, prelude::*, Body, Method, Request, Response, Url};
+
+#[derive(Debug, Clone)]
+pub struct ShareLeaseClient {
+    share_client: ShareClient,
+    lease_id: LeaseId,
+}
+
+impl ShareLeaseClient {
+    pub(crate) fn new(share_client: ShareClient, lease_id: LeaseId) -> Self {
+        Self {
+            share_client,
+            lease_id,
+        }
+    }
+
+    pub fn change(&self, proposed_lease_id: ProposedLeaseId) -> ChangeShareLeaseBuilder {
+        ChangeShareLeaseBuilder::new(self.clone(), proposed_lease_id)
+    }
+
+    pub fn release(&self) -> ReleaseShareLeaseBuilder {
+        ReleaseShareLeaseBuilder::new(self.clone())
+    }
+
+    pub fn renew(&self) -> RenewShareLeaseBuilder {
+        RenewShareLeaseBuilder::new(self.clone())
+    }
+
+    pub fn lease_id(&self) -> LeaseId {
+        self.lease_id
+    }
+
+    pub fn share_client(&self) -> &ShareClient {
+        &self.share_client
+    }
+
+    pub(crate) fn url(&self) -> azure_core::Result<Url> {
+        self.share_client.url()
+    }
+
+    pub(crate) fn finalize_request(
+        url: Url,
+        method: Method,
+        headers: Headers,
+        request_body: Option<Body>,
+    ) -> azure_core::Result<Request> {
+        ShareClient::finalize_request(url, method, headers, request_body)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        context: &mut Context,
+        request: &mut Request,
+    ) -> azure_core::Result<Response> {
+        self.share_client.send(context, request).await
+    }
+}
@@ -0,0 +1,20 @@
+use azure_core::date;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+/// An open SMB handle on a file or directory.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Handle {
+    pub handle_id: String,
+    pub path: String,
+    pub file_id: String,
+    pub parent_id: Option<String>,
+    pub session_id: String,
+    #[serde(rename = "ClientIp")]
+    pub client_ip: String,
+    #[serde(with = "date::rfc1123")]
+    pub open_time: OffsetDateTime,
+    #[serde(default, with = "date::rfc1123::option")]
+    pub last_reconnect_time: Option<OffsetDateTime>,
+}
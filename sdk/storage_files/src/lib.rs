@@ -0,0 +1,54 @@
+/*!
+# The Azure Storage Files crate
+
+This crate is from the [Azure SDK for Rust](https://github.com/azure/azure-sdk-for-rust).
+It supports [Azure Files](https://docs.microsoft.com/azure/storage/files/storage-files-introduction).
+
+# Example
+```no_run
+use azure_storage::prelude::*;
+use azure_storage_files::prelude::*;
+
+#[tokio::main]
+async fn main() -> azure_core::Result<()> {
+    let account = std::env::var("STORAGE_ACCOUNT").expect("missing STORAGE_ACCOUNT");
+    let access_key = std::env::var("STORAGE_ACCESS_KEY").expect("missing STORAGE_ACCESS_KEY");
+    let share_name = std::env::var("STORAGE_SHARE_NAME").expect("missing STORAGE_SHARE_NAME");
+
+    let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
+    let share = FileServiceClient::new(account, storage_credentials).share_client(share_name);
+
+    share.directory_client("reports").create().await?;
+
+    let content = b"hello world".to_vec();
+    let file = share.file_client("reports/hello.txt");
+    file.create(content.len() as u64).await?;
+    file.put_range(0, content).await?;
+
+    println!("{:?}", file.get_content().await?);
+
+    Ok(())
+}
+
+```
+
+*/
+
+#[macro_use]
+extern crate azure_core;
+
+mod clients;
+mod handle;
+pub mod operations;
+mod options;
+pub mod prelude;
+mod share;
+mod smb_properties;
+
+pub use clients::*;
+pub use handle::Handle;
+pub use options::{DeleteSnapshotsMethod, HandleId, ShareAccessTier, ShareQuota, ShareSnapshot};
+pub use share::{Share, ShareProperties};
+pub use smb_properties::{
+    FileAttribute, FileAttributes, FilePermission, FileSmbProperties, FileTime, SmbProperties,
+};
@@ -0,0 +1,39 @@
+use crate::FileClient;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    AcquireFileLease,
+    client: FileClient,
+    ?proposed_lease_id: ProposedLeaseId
+}
+
+impl AcquireFileLeaseBuilder {
+    pub fn into_future(mut self) -> AcquireFileLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "acquire");
+            // file leases are always infinite
+            headers.insert(LEASE_DURATION, "-1");
+            headers.add(self.proposed_lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            AcquireFileLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(AcquireFileLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_id_from_headers => lease_id: LeaseId,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
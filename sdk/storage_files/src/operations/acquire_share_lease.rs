@@ -0,0 +1,40 @@
+use crate::ShareClient;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    AcquireShareLease,
+    client: ShareClient,
+    lease_duration: LeaseDuration,
+    ?proposed_lease_id: ProposedLeaseId
+}
+
+impl AcquireShareLeaseBuilder {
+    pub fn into_future(mut self) -> AcquireShareLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "acquire");
+            headers.add(self.lease_duration);
+            headers.add(self.proposed_lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            AcquireShareLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(AcquireShareLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_id_from_headers => lease_id: LeaseId,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
@@ -0,0 +1,36 @@
+use crate::FileClient;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    BreakFileLease,
+    client: FileClient,
+    ?lease_id: LeaseId
+}
+
+impl BreakFileLeaseBuilder {
+    pub fn into_future(mut self) -> BreakFileLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "break");
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            BreakFileLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(BreakFileLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
@@ -0,0 +1,40 @@
+use crate::ShareClient;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    BreakShareLease,
+    client: ShareClient,
+    ?lease_break_period: LeaseBreakPeriod,
+    ?lease_id: LeaseId
+}
+
+impl BreakShareLeaseBuilder {
+    pub fn into_future(mut self) -> BreakShareLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "break");
+            headers.add(self.lease_break_period);
+            headers.add(self.lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            BreakShareLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(BreakShareLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_time_from_headers => lease_time: u8,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
@@ -0,0 +1,38 @@
+use crate::FileLeaseClient;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    ChangeFileLease,
+    client: FileLeaseClient,
+    proposed_lease_id: ProposedLeaseId,
+}
+
+impl ChangeFileLeaseBuilder {
+    pub fn into_future(mut self) -> ChangeFileLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "change");
+            headers.add(self.client.lease_id());
+            headers.add(self.proposed_lease_id);
+
+            let mut request = FileLeaseClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            ChangeFileLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(ChangeFileLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_id_from_headers => lease_id: LeaseId,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
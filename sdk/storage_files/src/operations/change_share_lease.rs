@@ -0,0 +1,39 @@
+use crate::ShareLeaseClient;
+use azure_core::{headers::*, prelude::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    ChangeShareLease,
+    client: ShareLeaseClient,
+    proposed_lease_id: ProposedLeaseId,
+}
+
+impl ChangeShareLeaseBuilder {
+    pub fn into_future(mut self) -> ChangeShareLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "change");
+            headers.add(self.client.lease_id());
+            headers.add(self.proposed_lease_id);
+
+            let mut request = ShareLeaseClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            ChangeShareLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(ChangeShareLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    lease_id_from_headers => lease_id: LeaseId,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
@@ -0,0 +1,35 @@
+use super::PutRangeResponse;
+use crate::FileClient;
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::FILE_WRITE;
+
+operation! {
+    ClearRange,
+    client: FileClient,
+    range: Range,
+    ?lease_id: LeaseId
+}
+
+impl ClearRangeBuilder {
+    pub fn into_future(mut self) -> ClearRange {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "range");
+
+            let mut headers = Headers::new();
+            headers.insert(FILE_WRITE, "clear");
+            headers.insert(MS_RANGE, self.range.to_string());
+            headers.insert(CONTENT_LENGTH, "0");
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+pub type ClearRangeResponse = PutRangeResponse;
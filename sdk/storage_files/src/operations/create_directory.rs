@@ -0,0 +1,56 @@
+use crate::{DirectoryClient, FileSmbProperties, SmbProperties};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    CreateDirectory,
+    client: DirectoryClient,
+    ?smb_properties: SmbProperties,
+    ?metadata: Metadata
+}
+
+impl CreateDirectoryBuilder {
+    pub fn into_future(mut self) -> CreateDirectory {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "directory");
+
+            let mut headers = Headers::new();
+            headers.add(self.smb_properties.clone());
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = DirectoryClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateDirectoryResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_properties: FileSmbProperties,
+}
+
+impl TryFrom<&Headers> for CreateDirectoryResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_properties: headers.try_into()?,
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{FileClient, FileSmbProperties, SmbProperties};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::{CommonStorageResponseHeaders, FILE_CONTENT_LENGTH, FILE_TYPE};
+use time::OffsetDateTime;
+
+const FILE_CONTENT_TYPE: HeaderName = HeaderName::from_static("x-ms-content-type");
+
+operation! {
+    CreateFile,
+    client: FileClient,
+    length: u64,
+    ?content_type: String,
+    ?smb_properties: SmbProperties,
+    ?lease_id: LeaseId,
+    ?metadata: Metadata
+}
+
+impl CreateFileBuilder {
+    pub fn into_future(mut self) -> CreateFile {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.insert(FILE_TYPE, "file");
+            headers.insert(FILE_CONTENT_LENGTH, self.length.to_string());
+            if let Some(content_type) = &self.content_type {
+                headers.insert(FILE_CONTENT_TYPE, content_type.clone());
+            }
+            headers.add(self.smb_properties.clone());
+            headers.add(self.lease_id);
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_properties: FileSmbProperties,
+}
+
+impl TryFrom<&Headers> for CreateFileResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            smb_properties: headers.try_into()?,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::ShareClient;
+use azure_core::{headers::*, Method};
+use azure_storage::headers::{CommonStorageResponseHeaders, FILE_PERMISSION_KEY};
+use serde::{Deserialize, Serialize};
+
+operation! {
+    CreatePermission,
+    client: ShareClient,
+    sddl: String,
+}
+
+impl CreatePermissionBuilder {
+    pub fn into_future(mut self) -> CreatePermission {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            url.query_pairs_mut().append_pair("comp", "filepermission");
+
+            let body = serde_json::to_vec(&SharePermission {
+                permission: self.sddl.clone(),
+            })?;
+
+            let mut headers = Headers::new();
+            headers.insert(CONTENT_TYPE, "application/json");
+
+            let mut request =
+                ShareClient::finalize_request(url, Method::Put, headers, Some(body.into()))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let headers = response.headers();
+
+            Ok(CreatePermissionResponse {
+                common_storage_response_headers: headers.try_into()?,
+                permission_key: headers.get_as(&FILE_PERMISSION_KEY)?,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePermissionResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// Use with `FilePermission::Key` to apply the permission to files and
+    /// directories.
+    pub permission_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SharePermission {
+    pub permission: String,
+}
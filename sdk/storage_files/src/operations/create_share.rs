@@ -0,0 +1,56 @@
+use crate::{ShareAccessTier, ShareClient, ShareQuota};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    CreateShare,
+    client: ShareClient,
+    ?quota: ShareQuota,
+    ?access_tier: ShareAccessTier,
+    ?metadata: Metadata
+}
+
+impl CreateShareBuilder {
+    pub fn into_future(mut self) -> CreateShare {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+
+            let mut headers = Headers::new();
+            headers.add(self.quota);
+            headers.add(self.access_tier);
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateShareResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
+
+impl TryFrom<&Headers> for CreateShareResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+        })
+    }
+}
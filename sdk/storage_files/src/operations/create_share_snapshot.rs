@@ -0,0 +1,50 @@
+use crate::{ShareClient, ShareSnapshot};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::{CommonStorageResponseHeaders, SNAPSHOT};
+use time::OffsetDateTime;
+
+operation! {
+    CreateShareSnapshot,
+    client: ShareClient,
+    ?metadata: Metadata
+}
+
+impl CreateShareSnapshotBuilder {
+    pub fn into_future(mut self) -> CreateShareSnapshot {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            url.query_pairs_mut().append_pair("comp", "snapshot");
+
+            let mut headers = Headers::new();
+            if let Some(metadata) = &self.metadata {
+                for m in metadata.iter() {
+                    headers.add(m);
+                }
+            }
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let headers = response.headers();
+
+            Ok(CreateShareSnapshotResponse {
+                common_storage_response_headers: headers.try_into()?,
+                snapshot: ShareSnapshot::new(headers.get_as::<String, _>(&SNAPSHOT)?),
+                etag: etag_from_headers(headers)?,
+                last_modified: last_modified_from_headers(headers)?,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateShareSnapshotResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// Pass this to the `share_snapshot` option of other operations to
+    /// address the snapshot.
+    pub snapshot: ShareSnapshot,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
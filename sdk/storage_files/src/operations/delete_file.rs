@@ -0,0 +1,33 @@
+use crate::FileClient;
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+operation! {
+    DeleteFile,
+    client: FileClient,
+    ?lease_id: LeaseId
+}
+
+impl DeleteFileBuilder {
+    pub fn into_future(mut self) -> DeleteFile {
+        Box::pin(async move {
+            let url = self.client.url()?;
+
+            let mut headers = Headers::new();
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Delete, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            Ok(DeleteFileResponse {
+                common_storage_response_headers: response.headers().try_into()?,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
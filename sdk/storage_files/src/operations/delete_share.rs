@@ -0,0 +1,39 @@
+use crate::{DeleteSnapshotsMethod, ShareClient, ShareSnapshot};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+operation! {
+    DeleteShare,
+    client: ShareClient,
+    ?share_snapshot: ShareSnapshot,
+    ?delete_snapshots: DeleteSnapshotsMethod,
+    ?lease_id: LeaseId
+}
+
+impl DeleteShareBuilder {
+    pub fn into_future(mut self) -> DeleteShare {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            self.share_snapshot.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            headers.add(self.delete_snapshots);
+            headers.add(self.lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Delete, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            Ok(DeleteShareResponse {
+                common_storage_response_headers: response.headers().try_into()?,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteShareResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+}
@@ -0,0 +1,61 @@
+use crate::{HandleId, ShareClient, ShareSnapshot};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::{
+    CommonStorageResponseHeaders, MARKER, NUMBER_OF_HANDLES_CLOSED, NUMBER_OF_HANDLES_FAILED,
+    RECURSIVE,
+};
+
+operation! {
+    ForceCloseHandles,
+    client: ShareClient,
+    path: String,
+    handle_id: HandleId,
+    ?marker: NextMarker,
+    ?share_snapshot: ShareSnapshot,
+    ?recursive: bool
+}
+
+impl ForceCloseHandlesBuilder {
+    pub fn into_future(mut self) -> ForceCloseHandles {
+        Box::pin(async move {
+            let mut url = self.client.path_url(&self.path)?;
+
+            url.query_pairs_mut()
+                .append_pair("comp", "forceclosehandles");
+            self.share_snapshot.append_to_url_query(&mut url);
+            self.marker.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            headers.add(self.handle_id.clone());
+            if let Some(recursive) = self.recursive {
+                headers.insert(RECURSIVE, recursive.to_string());
+            }
+
+            let mut request = ShareClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let headers = response.headers();
+
+            Ok(ForceCloseHandlesResponse {
+                common_storage_response_headers: headers.try_into()?,
+                marker: NextMarker::from_possibly_empty_string(
+                    headers.get_optional_string(&MARKER),
+                ),
+                closed: headers.get_as(&NUMBER_OF_HANDLES_CLOSED)?,
+                failed: headers
+                    .get_optional_as(&NUMBER_OF_HANDLES_FAILED)?
+                    .unwrap_or_default(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ForceCloseHandlesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    /// Set when the service stopped before closing all the matching handles.
+    /// Pass it back as `marker` to continue.
+    pub marker: Option<NextMarker>,
+    pub closed: u32,
+    pub failed: u32,
+}
@@ -0,0 +1,44 @@
+use crate::{DirectoryClient, FileSmbProperties, ShareSnapshot};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    GetDirectoryProperties,
+    client: DirectoryClient,
+    ?share_snapshot: ShareSnapshot
+}
+
+impl GetDirectoryPropertiesBuilder {
+    pub fn into_future(mut self) -> GetDirectoryProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "directory");
+            self.share_snapshot.append_to_url_query(&mut url);
+
+            let mut request =
+                DirectoryClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let headers = response.headers();
+
+            Ok(GetDirectoryPropertiesResponse {
+                common_storage_response_headers: headers.try_into()?,
+                etag: etag_from_headers(headers)?,
+                last_modified: last_modified_from_headers(headers)?,
+                smb_properties: headers.try_into()?,
+                metadata: headers.into(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetDirectoryPropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub smb_properties: FileSmbProperties,
+    pub metadata: Metadata,
+}
@@ -0,0 +1,164 @@
+use crate::{FileClient, ShareSnapshot};
+use azure_core::{
+    error::Error, headers::*, prelude::*, Method, Pageable, Response as AzureResponse, ResponseBody,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+const DEFAULT_CHUNK_SIZE: u64 = 0x1000 * 0x1000;
+
+operation! {
+    #[stream]
+    GetFile,
+    client: FileClient,
+    ?range: Range,
+    ?share_snapshot: ShareSnapshot,
+    ?lease_id: LeaseId,
+    ?chunk_size: u64,
+}
+
+impl GetFileBuilder {
+    pub fn into_stream(self) -> Pageable<GetFileResponse, Error> {
+        let make_request = move |continuation: Option<Range>| {
+            let this = self.clone();
+            let mut ctx = self.context.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                let range = match continuation {
+                    Some(range) => range,
+                    None => initial_range(
+                        this.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+                        this.range.clone(),
+                    ),
+                };
+
+                this.share_snapshot.append_to_url_query(&mut url);
+
+                let mut headers = Headers::new();
+                headers.insert(MS_RANGE, range.to_string());
+                headers.add(this.lease_id);
+
+                let mut request = FileClient::finalize_request(url, Method::Get, headers, None)?;
+
+                let response = this.client.send(&mut ctx, &mut request).await?;
+
+                GetFileResponse::try_from(this, response)
+            }
+        };
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug)]
+pub struct GetFileResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub data: ResponseBody,
+    pub content_range: Option<Range>,
+    pub remaining_range: Option<Range>,
+}
+
+impl GetFileResponse {
+    fn try_from(request: GetFileBuilder, response: AzureResponse) -> azure_core::Result<Self> {
+        let headers = response.headers();
+
+        let common_storage_response_headers = headers.try_into()?;
+        let etag = etag_from_headers(headers)?;
+        let last_modified = last_modified_from_headers(headers)?;
+        let content_range = headers.get_optional_as(&CONTENT_RANGE)?;
+
+        let remaining_range = remaining_range(
+            request.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+            request.range,
+            content_range,
+        );
+        let data = response.into_body();
+
+        Ok(Self {
+            common_storage_response_headers,
+            etag,
+            last_modified,
+            data,
+            content_range: content_range.map(|cr| Range::new(cr.start(), cr.end())),
+            remaining_range,
+        })
+    }
+}
+
+impl Continuable for GetFileResponse {
+    type Continuation = Range;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.remaining_range.clone()
+    }
+}
+
+// calculate the first Range for use at the beginning of the Pageable.
+fn initial_range(chunk_size: u64, request_range: Option<Range>) -> Range {
+    match request_range {
+        Some(Range::Range(x)) => {
+            let len = std::cmp::min(x.end - x.start, chunk_size);
+            (x.start..x.start + len).into()
+        }
+        Some(Range::RangeFrom(x)) => (x.start..x.start + chunk_size).into(),
+        None => Range::new(0, chunk_size),
+    }
+}
+
+// After each request, calculate how much data is left to be read based on the
+// requested chunk size, requested range, and Content-Range header from the
+// response. This mirrors the chunking of blob downloads.
+fn remaining_range(
+    chunk_size: u64,
+    base_range: Option<Range>,
+    content_range: Option<ContentRange>,
+) -> Option<Range> {
+    // if there was no content range in the response, assume the entire file
+    // was returned.
+    let content_range = content_range?;
+
+    if content_range.end() + 1 >= content_range.total_length() {
+        return None;
+    }
+
+    let requested_range = base_range.unwrap_or_else(|| Range::new(0, content_range.total_length()));
+
+    let after = content_range.end() + 1;
+
+    let remaining_size = match requested_range {
+        Range::Range(x) => {
+            if after >= x.end {
+                return None;
+            }
+            x.end - after
+        }
+        Range::RangeFrom(_) => after,
+    };
+
+    let size = std::cmp::min(remaining_size, chunk_size);
+
+    Some(Range::new(after, after + size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_range() {
+        assert_eq!(remaining_range(3, None, None), None);
+        assert_eq!(
+            remaining_range(3, None, Some(ContentRange::new(0, 2, 10))),
+            Some(Range::new(3, 6))
+        );
+        assert_eq!(
+            remaining_range(3, None, Some(ContentRange::new(9, 9, 10))),
+            None
+        );
+        assert_eq!(
+            remaining_range(4, Some(Range::new(2, 8)), Some(ContentRange::new(2, 5, 10))),
+            Some(Range::new(6, 8))
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{FileClient, FileSmbProperties, ShareSnapshot};
+use azure_core::{headers::*, prelude::*, LeaseDuration, LeaseState, LeaseStatus, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use time::OffsetDateTime;
+
+operation! {
+    GetFileProperties,
+    client: FileClient,
+    ?share_snapshot: ShareSnapshot,
+    ?lease_id: LeaseId
+}
+
+impl GetFilePropertiesBuilder {
+    pub fn into_future(mut self) -> GetFileProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            self.share_snapshot.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Head, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let headers = response.headers();
+
+            Ok(GetFilePropertiesResponse {
+                common_storage_response_headers: headers.try_into()?,
+                properties: FileProperties::from_headers(headers)?,
+                smb_properties: headers.try_into()?,
+                metadata: headers.into(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetFilePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub properties: FileProperties,
+    pub smb_properties: FileSmbProperties,
+    pub metadata: Metadata,
+}
+
+/// The HTTP properties of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProperties {
+    pub content_length: u64,
+    pub content_type: Option<String>,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub lease_status: Option<LeaseStatus>,
+    pub lease_state: Option<LeaseState>,
+    pub lease_duration: Option<LeaseDuration>,
+}
+
+impl FileProperties {
+    pub(crate) fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            content_length: headers.get_as(&CONTENT_LENGTH)?,
+            content_type: headers.get_optional_string(&CONTENT_TYPE),
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+            lease_status: headers.get_optional_as(&LEASE_STATUS)?,
+            lease_state: headers.get_optional_as(&LEASE_STATE)?,
+            lease_duration: headers.get_optional_as(&LEASE_DURATION)?,
+        })
+    }
+}
@@ -0,0 +1,41 @@
+use super::create_permission::SharePermission;
+use crate::ShareClient;
+use azure_core::{headers::*, Method};
+use azure_storage::headers::{CommonStorageResponseHeaders, FILE_PERMISSION_KEY};
+
+operation! {
+    GetPermission,
+    client: ShareClient,
+    permission_key: String,
+}
+
+impl GetPermissionBuilder {
+    pub fn into_future(mut self) -> GetPermission {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            url.query_pairs_mut().append_pair("comp", "filepermission");
+
+            let mut headers = Headers::new();
+            headers.insert(FILE_PERMISSION_KEY, self.permission_key.clone());
+
+            let mut request = ShareClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let (_, headers, body) = response.deconstruct();
+            let permission: SharePermission = body.json().await?;
+
+            Ok(GetPermissionResponse {
+                common_storage_response_headers: (&headers).try_into()?,
+                sddl: permission.permission,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetPermissionResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub sddl: String,
+}
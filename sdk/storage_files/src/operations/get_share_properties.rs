@@ -0,0 +1,42 @@
+use crate::{ShareClient, ShareProperties, ShareSnapshot};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::CommonStorageResponseHeaders;
+
+operation! {
+    GetShareProperties,
+    client: ShareClient,
+    ?share_snapshot: ShareSnapshot,
+    ?lease_id: LeaseId
+}
+
+impl GetSharePropertiesBuilder {
+    pub fn into_future(mut self) -> GetShareProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            self.share_snapshot.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            headers.add(self.lease_id);
+
+            let mut request = ShareClient::finalize_request(url, Method::Head, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let headers = response.headers();
+
+            Ok(GetSharePropertiesResponse {
+                common_storage_response_headers: headers.try_into()?,
+                properties: ShareProperties::from_headers(headers)?,
+                metadata: headers.into(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetSharePropertiesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub properties: ShareProperties,
+    pub metadata: Metadata,
+}
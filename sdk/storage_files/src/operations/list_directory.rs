@@ -0,0 +1,245 @@
+use crate::{smb_properties::file_time_option, DirectoryClient, FileAttributes, ShareSnapshot};
+use azure_core::{
+    date, error::Error, headers::*, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+const FILE_EXTENDED_INFO: HeaderName = HeaderName::from_static("x-ms-file-extended-info");
+
+operation! {
+    #[stream]
+    ListDirectory,
+    client: DirectoryClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+    ?share_snapshot: ShareSnapshot,
+    ?include_extended_info: bool,
+}
+
+impl ListDirectoryBuilder {
+    pub fn into_stream(self) -> Pageable<ListDirectoryResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut().append_pair("restype", "directory");
+                url.query_pairs_mut().append_pair("comp", "list");
+
+                this.prefix.append_to_url_query(&mut url);
+                this.share_snapshot.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut headers = Headers::new();
+                if this.include_extended_info.unwrap_or(false) {
+                    url.query_pairs_mut()
+                        .append_pair("include", "Timestamps,ETag,Attributes,PermissionKey");
+                    headers.insert(FILE_EXTENDED_INFO, "true");
+                }
+
+                let mut request =
+                    DirectoryClient::finalize_request(url, Method::Get, headers, None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListDirectoryResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListDirectoryResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub prefix: Option<String>,
+    pub max_results: Option<u32>,
+    /// Only returned when extended info is requested.
+    pub directory_id: Option<String>,
+    pub entries: Vec<DirectoryEntry>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl ListDirectoryResponse {
+    /// The files in this page of results.
+    pub fn files(&self) -> impl Iterator<Item = &FileItem> {
+        self.entries.iter().filter_map(|entry| match entry {
+            DirectoryEntry::File(file) => Some(file),
+            DirectoryEntry::Directory(_) => None,
+        })
+    }
+
+    /// The directories in this page of results.
+    pub fn directories(&self) -> impl Iterator<Item = &DirectoryItem> {
+        self.entries.iter().filter_map(|entry| match entry {
+            DirectoryEntry::Directory(directory) => Some(directory),
+            DirectoryEntry::File(_) => None,
+        })
+    }
+}
+
+impl Continuable for ListDirectoryResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+impl ListDirectoryResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: ListDirectoryResponseInternal = body.xml().await?;
+
+        Ok(ListDirectoryResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            prefix: response.prefix,
+            max_results: response.max_results,
+            directory_id: response.directory_id,
+            entries: response.entries.entries,
+            next_marker: NextMarker::from_possibly_empty_string(response.next_marker),
+        })
+    }
+}
+
+/// A file or directory, in the order returned by the service.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum DirectoryEntry {
+    File(FileItem),
+    Directory(DirectoryItem),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FileItem {
+    pub name: String,
+    pub file_id: Option<String>,
+    pub properties: EntryProperties,
+    pub attributes: Option<FileAttributes>,
+    pub permission_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DirectoryItem {
+    pub name: String,
+    pub file_id: Option<String>,
+    #[serde(default)]
+    pub properties: EntryProperties,
+    pub attributes: Option<FileAttributes>,
+    pub permission_key: Option<String>,
+}
+
+/// The properties returned for each entry. Everything but the content length
+/// of files requires extended info.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EntryProperties {
+    #[serde(rename = "Content-Length")]
+    pub content_length: Option<u64>,
+    #[serde(default, deserialize_with = "file_time_option::deserialize")]
+    pub creation_time: Option<OffsetDateTime>,
+    #[serde(default, deserialize_with = "file_time_option::deserialize")]
+    pub last_access_time: Option<OffsetDateTime>,
+    #[serde(default, deserialize_with = "file_time_option::deserialize")]
+    pub last_write_time: Option<OffsetDateTime>,
+    #[serde(default, deserialize_with = "file_time_option::deserialize")]
+    pub change_time: Option<OffsetDateTime>,
+    #[serde(rename = "Last-Modified", default, with = "date::rfc1123::option")]
+    pub last_modified: Option<OffsetDateTime>,
+    pub etag: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListDirectoryResponseInternal {
+    prefix: Option<String>,
+    max_results: Option<u32>,
+    directory_id: Option<String>,
+    #[serde(default)]
+    entries: Entries,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Entries {
+    #[serde(rename = "$value", default)]
+    entries: Vec<DirectoryEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileAttribute;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn try_parse() -> azure_core::Result<()> {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://myaccount.file.core.windows.net/" ShareName="reports" DirectoryPath="2023">
+  <DirectoryId>13835128424026341376</DirectoryId>
+  <Entries>
+    <File>
+      <Name>a.txt</Name>
+      <FileId>13835093239654252544</FileId>
+      <Properties>
+        <Content-Length>11</Content-Length>
+        <CreationTime>2023-04-05T06:07:08.1234567Z</CreationTime>
+        <LastWriteTime>2023-04-05T06:07:08.1234567Z</LastWriteTime>
+        <Last-Modified>Wed, 05 Apr 2023 06:07:08 GMT</Last-Modified>
+        <Etag>"0x8DB35A1D4C7E2F1"</Etag>
+      </Properties>
+      <Attributes>Archive|ReadOnly</Attributes>
+      <PermissionKey>4066528134148476695*1</PermissionKey>
+    </File>
+    <Directory>
+      <Name>q1</Name>
+      <FileId>13835163608398430208</FileId>
+      <Properties>
+        <CreationTime>2023-04-05T06:07:08.1234567Z</CreationTime>
+      </Properties>
+      <Attributes>Directory</Attributes>
+    </Directory>
+    <File>
+      <Name>b.txt</Name>
+      <Properties>
+        <Content-Length>0</Content-Length>
+      </Properties>
+    </File>
+  </Entries>
+  <NextMarker />
+</EnumerationResults>"#;
+
+        let response: ListDirectoryResponseInternal = read_xml(body.as_bytes())?;
+        assert_eq!(
+            response.directory_id.as_deref(),
+            Some("13835128424026341376")
+        );
+        let entries = response.entries.entries;
+        assert_eq!(entries.len(), 3);
+        match &entries[0] {
+            DirectoryEntry::File(file) => {
+                assert_eq!(file.name, "a.txt");
+                assert_eq!(file.properties.content_length, Some(11));
+                assert!(file
+                    .attributes
+                    .as_ref()
+                    .unwrap()
+                    .contains(FileAttribute::ReadOnly));
+            }
+            other => panic!("expected a file, got {other:?}"),
+        }
+        assert!(
+            matches!(&entries[1], DirectoryEntry::Directory(directory) if directory.name == "q1")
+        );
+        assert!(matches!(&entries[2], DirectoryEntry::File(file) if file.name == "b.txt"));
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{Handle, ShareClient, ShareSnapshot};
+use azure_core::{
+    error::Error, headers::*, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::{CommonStorageResponseHeaders, RECURSIVE};
+use serde::Deserialize;
+
+operation! {
+    #[stream]
+    ListHandles,
+    client: ShareClient,
+    path: String,
+    ?max_results: MaxResults,
+    ?share_snapshot: ShareSnapshot,
+    ?recursive: bool,
+}
+
+impl ListHandlesBuilder {
+    pub fn into_stream(self) -> Pageable<ListHandlesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.path_url(&this.path)?;
+
+                url.query_pairs_mut().append_pair("comp", "listhandles");
+
+                this.share_snapshot.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut headers = Headers::new();
+                if let Some(recursive) = this.recursive {
+                    headers.insert(RECURSIVE, recursive.to_string());
+                }
+
+                let mut request = ShareClient::finalize_request(url, Method::Get, headers, None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListHandlesResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListHandlesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub handles: Vec<Handle>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl Continuable for ListHandlesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+impl ListHandlesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: ListHandlesResponseInternal = body.xml().await?;
+
+        Ok(ListHandlesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            handles: response.entries.handles,
+            next_marker: NextMarker::from_possibly_empty_string(response.next_marker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListHandlesResponseInternal {
+    #[serde(default)]
+    entries: Handles,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Handles {
+    #[serde(rename = "Handle", default)]
+    handles: Vec<Handle>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn try_parse() -> azure_core::Result<()> {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults>
+  <Entries>
+    <Handle>
+      <HandleId>83886080</HandleId>
+      <Path>2023/a.txt</Path>
+      <FileId>13835093239654252544</FileId>
+      <ParentId>13835128424026341376</ParentId>
+      <SessionId>9385737614310506553</SessionId>
+      <ClientIp>10.0.0.4:49721</ClientIp>
+      <OpenTime>Wed, 05 Apr 2023 06:07:08 GMT</OpenTime>
+    </Handle>
+  </Entries>
+  <NextMarker>1!8!ODM4ODYwODA-</NextMarker>
+</EnumerationResults>"#;
+
+        let response: ListHandlesResponseInternal = read_xml(body.as_bytes())?;
+        assert_eq!(response.entries.handles.len(), 1);
+        let handle = &response.entries.handles[0];
+        assert_eq!(handle.handle_id, "83886080");
+        assert_eq!(handle.client_ip, "10.0.0.4:49721");
+        assert_eq!(handle.last_reconnect_time, None);
+        assert_eq!(response.next_marker.as_deref(), Some("1!8!ODM4ODYwODA-"));
+        Ok(())
+    }
+}
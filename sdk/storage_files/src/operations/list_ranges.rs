@@ -0,0 +1,97 @@
+use crate::{FileClient, ShareSnapshot};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::{CommonStorageResponseHeaders, FILE_CONTENT_LENGTH};
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+operation! {
+    ListRanges,
+    client: FileClient,
+    ?range: Range,
+    ?share_snapshot: ShareSnapshot,
+    ?lease_id: LeaseId
+}
+
+impl ListRangesBuilder {
+    pub fn into_future(mut self) -> ListRanges {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "rangelist");
+            self.share_snapshot.append_to_url_query(&mut url);
+
+            let mut headers = Headers::new();
+            if let Some(range) = &self.range {
+                headers.insert(MS_RANGE, range.to_string());
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Get, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+            let (_, headers, body) = response.deconstruct();
+            let ranges: FileRanges = body.xml().await?;
+
+            Ok(ListRangesResponse {
+                common_storage_response_headers: (&headers).try_into()?,
+                etag: etag_from_headers(&headers)?,
+                last_modified: last_modified_from_headers(&headers)?,
+                file_content_length: headers.get_as(&FILE_CONTENT_LENGTH)?,
+                ranges: ranges.into_ranges(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListRangesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+    pub file_content_length: u64,
+    /// The ranges containing data, in ascending order.
+    pub ranges: Vec<Range>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FileRanges {
+    #[serde(rename = "Range", default)]
+    ranges: Vec<FileRange>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FileRange {
+    start: u64,
+    end: u64,
+}
+
+impl FileRanges {
+    fn into_ranges(self) -> Vec<Range> {
+        // the service returns inclusive ends
+        self.ranges
+            .into_iter()
+            .map(|range| Range::new(range.start, range.end + 1))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn try_parse() -> azure_core::Result<()> {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?><Ranges><Range><Start>0</Start><End>511</End></Range><Range><Start>1024</Start><End>1535</End></Range></Ranges>"#;
+        let ranges: FileRanges = read_xml(body.as_bytes())?;
+        assert_eq!(
+            ranges.into_ranges(),
+            vec![Range::new(0, 512), Range::new(1024, 1536)]
+        );
+
+        let ranges: FileRanges = read_xml(b"<?xml version=\"1.0\" encoding=\"utf-8\"?><Ranges />")?;
+        assert!(ranges.into_ranges().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{FileServiceClient, Share};
+use azure_core::{
+    error::Error, headers::Headers, prelude::*, Method, Pageable, Response as AzureResponse,
+};
+use azure_storage::headers::CommonStorageResponseHeaders;
+use serde::Deserialize;
+
+operation! {
+    #[stream]
+    ListShares,
+    client: FileServiceClient,
+    ?prefix: Prefix,
+    ?max_results: MaxResults,
+    ?include_snapshots: bool,
+    ?include_metadata: bool,
+    ?include_deleted: bool,
+}
+
+impl ListSharesBuilder {
+    pub fn into_stream(self) -> Pageable<ListSharesResponse, Error> {
+        let make_request = move |continuation: Option<NextMarker>| {
+            let mut this = self.clone();
+            async move {
+                let mut url = this.client.url()?;
+
+                url.query_pairs_mut().append_pair("comp", "list");
+
+                this.prefix.append_to_url_query(&mut url);
+
+                if let Some(next_marker) = continuation {
+                    next_marker.append_to_url_query(&mut url);
+                }
+
+                this.max_results.append_to_url_query(&mut url);
+
+                let mut include = Vec::new();
+                if this.include_snapshots.unwrap_or(false) {
+                    include.push("snapshots");
+                }
+                if this.include_metadata.unwrap_or(false) {
+                    include.push("metadata");
+                }
+                if this.include_deleted.unwrap_or(false) {
+                    include.push("deleted");
+                }
+                if !include.is_empty() {
+                    url.query_pairs_mut()
+                        .append_pair("include", &include.join(","));
+                }
+
+                let mut request =
+                    FileServiceClient::finalize_request(url, Method::Get, Headers::new(), None)?;
+
+                let response = this.client.send(&mut this.context, &mut request).await?;
+
+                ListSharesResponse::try_from(response).await
+            }
+        };
+
+        Pageable::new(make_request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListSharesResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub prefix: Option<String>,
+    pub max_results: Option<u32>,
+    pub shares: Vec<Share>,
+    pub next_marker: Option<NextMarker>,
+}
+
+impl Continuable for ListSharesResponse {
+    type Continuation = NextMarker;
+    fn continuation(&self) -> Option<Self::Continuation> {
+        self.next_marker.clone()
+    }
+}
+
+impl ListSharesResponse {
+    async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
+        let (_, headers, body) = response.deconstruct();
+        let response: ListSharesResponseInternal = body.xml().await?;
+
+        Ok(ListSharesResponse {
+            common_storage_response_headers: (&headers).try_into()?,
+            prefix: response.prefix,
+            max_results: response.max_results,
+            shares: response.shares.shares,
+            next_marker: NextMarker::from_possibly_empty_string(response.next_marker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListSharesResponseInternal {
+    prefix: Option<String>,
+    max_results: Option<u32>,
+    #[serde(default)]
+    shares: Shares,
+    next_marker: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Shares {
+    #[serde(rename = "Share", default)]
+    shares: Vec<Share>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::xml::read_xml;
+
+    #[test]
+    fn try_parse() -> azure_core::Result<()> {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://myaccount.file.core.windows.net/">
+  <MaxResults>2</MaxResults>
+  <Shares>
+    <Share>
+      <Name>reports</Name>
+      <Snapshot>2023-04-05T06:07:08.0000000Z</Snapshot>
+      <Properties>
+        <Last-Modified>Wed, 05 Apr 2023 06:07:08 GMT</Last-Modified>
+        <Etag>"0x8DB35A1D4C7E2F1"</Etag>
+        <Quota>5120</Quota>
+        <AccessTier>TransactionOptimized</AccessTier>
+      </Properties>
+    </Share>
+    <Share>
+      <Name>archive</Name>
+      <Deleted>true</Deleted>
+      <Version>01D9678C2E4D1A50</Version>
+      <Properties>
+        <Last-Modified>Wed, 05 Apr 2023 06:07:08 GMT</Last-Modified>
+        <Etag>"0x8DB35A1D4C7E2F2"</Etag>
+        <Quota>100</Quota>
+        <DeletedTime>Thu, 06 Apr 2023 06:07:08 GMT</DeletedTime>
+        <RemainingRetentionDays>6</RemainingRetentionDays>
+      </Properties>
+      <Metadata>
+        <owner>finance</owner>
+      </Metadata>
+    </Share>
+  </Shares>
+  <NextMarker />
+</EnumerationResults>"#;
+
+        let response: ListSharesResponseInternal = read_xml(body.as_bytes())?;
+        let shares = response.shares.shares;
+        assert_eq!(shares.len(), 2);
+        assert_eq!(
+            shares[0].snapshot.as_deref(),
+            Some("2023-04-05T06:07:08.0000000Z")
+        );
+        assert_eq!(shares[0].properties.quota, 5120);
+        assert!(shares[1].deleted);
+        assert_eq!(shares[1].properties.remaining_retention_days, Some(6));
+        assert_eq!(
+            shares[1].metadata.as_ref().unwrap().get("owner").unwrap(),
+            "finance"
+        );
+        Ok(())
+    }
+}
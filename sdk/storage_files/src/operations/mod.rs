@@ -0,0 +1,63 @@
+mod acquire_file_lease;
+mod acquire_share_lease;
+mod break_file_lease;
+mod break_share_lease;
+mod change_file_lease;
+mod change_share_lease;
+mod clear_range;
+mod create_directory;
+mod create_file;
+mod create_permission;
+mod create_share;
+mod create_share_snapshot;
+mod delete_directory;
+mod delete_file;
+mod delete_share;
+mod force_close_handles;
+mod get_directory_properties;
+mod get_file;
+mod get_file_properties;
+mod get_permission;
+mod get_share_properties;
+mod list_directory;
+mod list_handles;
+mod list_ranges;
+mod list_shares;
+mod put_range;
+mod release_file_lease;
+mod release_share_lease;
+mod renew_share_lease;
+mod set_directory_properties;
+mod set_file_properties;
+
+pub use acquire_file_lease::*;
+pub use acquire_share_lease::*;
+pub use break_file_lease::*;
+pub use break_share_lease::*;
+pub use change_file_lease::*;
+pub use change_share_lease::*;
+pub use clear_range::*;
+pub use create_directory::*;
+pub use create_file::*;
+pub use create_permission::*;
+pub use create_share::*;
+pub use create_share_snapshot::*;
+pub use delete_directory::*;
+pub use delete_file::*;
+pub use delete_share::*;
+pub use force_close_handles::*;
+pub use get_directory_properties::*;
+pub use get_file::*;
+pub use get_file_properties::*;
+pub use get_permission::*;
+pub use get_share_properties::*;
+pub use list_directory::*;
+pub use list_handles::*;
+pub use list_ranges::*;
+pub use list_shares::*;
+pub use put_range::*;
+pub use release_file_lease::*;
+pub use release_share_lease::*;
+pub use renew_share_lease::*;
+pub use set_directory_properties::*;
+pub use set_file_properties::*;
@@ -0,0 +1,88 @@
+use crate::FileClient;
+use azure_core::{
+    error::{Error, ErrorKind},
+    headers::*,
+    prelude::*,
+    Body, Method,
+};
+use azure_storage::headers::{CommonStorageResponseHeaders, FILE_WRITE};
+use time::OffsetDateTime;
+
+operation! {
+    PutRange,
+    client: FileClient,
+    start: u64,
+    content: Body,
+    ?lease_id: LeaseId
+}
+
+impl PutRangeBuilder {
+    pub fn into_future(mut self) -> PutRange {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "range");
+
+            let range = content_range(self.start, &self.content)?;
+
+            let mut headers = Headers::new();
+            headers.insert(FILE_WRITE, "update");
+            headers.insert(MS_RANGE, range.to_string());
+            headers.add(self.lease_id);
+
+            let mut request =
+                FileClient::finalize_request(url, Method::Put, headers, Some(self.content))?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+/// The range written by `content`, which the service requires to be non-empty.
+fn content_range(start: u64, content: &Body) -> azure_core::Result<Range> {
+    if content.is_empty() {
+        return Err(Error::message(
+            ErrorKind::Other,
+            "the content of a range must not be empty",
+        ));
+    }
+    Ok(Range::new(start, start + content.len() as u64))
+}
+
+#[derive(Debug, Clone)]
+pub struct PutRangeResponse {
+    pub common_storage_response_headers: CommonStorageResponseHeaders,
+    pub etag: String,
+    pub last_modified: OffsetDateTime,
+}
+
+impl TryFrom<&Headers> for PutRangeResponse {
+    type Error = azure_core::Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            common_storage_response_headers: headers.try_into()?,
+            etag: etag_from_headers(headers)?,
+            last_modified: last_modified_from_headers(headers)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_cover_the_content() {
+        let range = content_range(512, &Body::from(vec![0; 11])).unwrap();
+        assert_eq!(range.to_string(), "bytes=512-522");
+
+        let error = content_range(512, &Body::from(Vec::new())).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the content of a range must not be empty"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::FileLeaseClient;
+use azure_core::{headers::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    ReleaseFileLease,
+    client: FileLeaseClient,
+}
+
+impl ReleaseFileLeaseBuilder {
+    pub fn into_future(mut self) -> ReleaseFileLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "release");
+            headers.add(self.client.lease_id());
+
+            let mut request = FileLeaseClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            ReleaseFileLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(ReleaseFileLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
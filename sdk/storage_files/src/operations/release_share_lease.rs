@@ -0,0 +1,36 @@
+use crate::ShareLeaseClient;
+use azure_core::{headers::*, Method, RequestId};
+use time::OffsetDateTime;
+
+operation! {
+    ReleaseShareLease,
+    client: ShareLeaseClient,
+}
+
+impl ReleaseShareLeaseBuilder {
+    pub fn into_future(mut self) -> ReleaseShareLease {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "share");
+            url.query_pairs_mut().append_pair("comp", "lease");
+
+            let mut headers = Headers::new();
+            headers.insert(LEASE_ACTION, "release");
+            headers.add(self.client.lease_id());
+
+            let mut request = ShareLeaseClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            ReleaseShareLeaseResponse::from_headers(response.headers())
+        })
+    }
+}
+
+azure_storage::response_from_headers!(ReleaseShareLeaseResponse,
+    etag_from_headers => etag: String,
+    last_modified_from_headers => last_modified: OffsetDateTime,
+    request_id_from_headers => request_id: RequestId,
+    date_from_headers => date: OffsetDateTime
+);
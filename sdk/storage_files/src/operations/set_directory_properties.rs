@@ -0,0 +1,31 @@
+use super::CreateDirectoryResponse;
+use crate::{DirectoryClient, SmbProperties};
+use azure_core::{headers::*, Method};
+
+operation! {
+    SetDirectoryProperties,
+    client: DirectoryClient,
+    smb_properties: SmbProperties,
+}
+
+impl SetDirectoryPropertiesBuilder {
+    pub fn into_future(mut self) -> SetDirectoryProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("restype", "directory");
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let mut headers = Headers::new();
+            headers.add(self.smb_properties.clone());
+
+            let mut request = DirectoryClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+pub type SetDirectoryPropertiesResponse = CreateDirectoryResponse;
@@ -0,0 +1,38 @@
+use super::CreateFileResponse;
+use crate::{FileClient, SmbProperties};
+use azure_core::{headers::*, prelude::*, Method};
+use azure_storage::headers::FILE_CONTENT_LENGTH;
+
+operation! {
+    SetFileProperties,
+    client: FileClient,
+    smb_properties: SmbProperties,
+    ?length: u64,
+    ?lease_id: LeaseId
+}
+
+impl SetFilePropertiesBuilder {
+    pub fn into_future(mut self) -> SetFileProperties {
+        Box::pin(async move {
+            let mut url = self.client.url()?;
+
+            url.query_pairs_mut().append_pair("comp", "properties");
+
+            let mut headers = Headers::new();
+            headers.add(self.smb_properties.clone());
+            // resizes the file, truncating or zero-extending it
+            if let Some(length) = self.length {
+                headers.insert(FILE_CONTENT_LENGTH, length.to_string());
+            }
+            headers.add(self.lease_id);
+
+            let mut request = FileClient::finalize_request(url, Method::Put, headers, None)?;
+
+            let response = self.client.send(&mut self.context, &mut request).await?;
+
+            response.headers().try_into()
+        })
+    }
+}
+
+pub type SetFilePropertiesResponse = CreateFileResponse;
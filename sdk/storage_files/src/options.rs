@@ -0,0 +1,100 @@
+use azure_core::headers::{
+    self, Header, HeaderName, HeaderValue, BLOB_ACCESS_TIER, DELETE_SNAPSHOTS,
+};
+use azure_storage::headers::{HANDLE_ID, SHARE_QUOTA};
+
+request_query!(
+    /// A share snapshot, as returned by `ShareClient::snapshot`.
+    ///
+    /// The value is opaque and passed back to the service as is.
+    #[derive(PartialEq, Eq)]
+    ShareSnapshot,
+    "sharesnapshot"
+);
+
+impl ShareSnapshot {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The maximum size of a share, in GiB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareQuota(pub u32);
+
+impl From<u32> for ShareQuota {
+    fn from(quota: u32) -> Self {
+        Self(quota)
+    }
+}
+
+impl Header for ShareQuota {
+    fn name(&self) -> HeaderName {
+        SHARE_QUOTA
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.0.to_string().into()
+    }
+}
+
+create_enum!(
+    ShareAccessTier,
+    (TransactionOptimized, "TransactionOptimized"),
+    (Hot, "Hot"),
+    (Cool, "Cool"),
+    (Premium, "Premium")
+);
+
+impl Header for ShareAccessTier {
+    fn name(&self) -> HeaderName {
+        BLOB_ACCESS_TIER
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.to_string().into()
+    }
+}
+
+create_enum!(
+    DeleteSnapshotsMethod,
+    (Include, "include"),
+    (IncludeLeased, "include-leased")
+);
+
+impl Header for DeleteSnapshotsMethod {
+    fn name(&self) -> HeaderName {
+        DELETE_SNAPSHOTS
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.to_string().into()
+    }
+}
+
+/// The open handles to close.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandleId {
+    All,
+    Id(String),
+}
+
+impl From<String> for HandleId {
+    fn from(id: String) -> Self {
+        HandleId::Id(id)
+    }
+}
+
+impl Header for HandleId {
+    fn name(&self) -> headers::HeaderName {
+        HANDLE_ID
+    }
+
+    fn value(&self) -> headers::HeaderValue {
+        match self {
+            HandleId::All => "*".to_owned(),
+            HandleId::Id(id) => id.clone(),
+        }
+        .into()
+    }
+}
@@ -0,0 +1,6 @@
+pub use crate::{
+    options::{DeleteSnapshotsMethod, HandleId, ShareAccessTier, ShareQuota, ShareSnapshot},
+    smb_properties::{FileAttribute, FileAttributes, FilePermission, FileTime, SmbProperties},
+    DirectoryClient, FileClient, FileLeaseClient, FileServiceClient, FileServiceClientBuilder,
+    ShareClient, ShareLeaseClient,
+};
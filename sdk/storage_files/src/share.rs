@@ -0,0 +1,65 @@
+use crate::options::ShareAccessTier;
+use azure_core::{
+    date,
+    headers::{self, Headers},
+    LeaseDuration, LeaseState, LeaseStatus,
+};
+use azure_storage::headers::SHARE_QUOTA;
+use serde::Deserialize;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+
+/// A file share, as returned by `list_shares` or `get_properties`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Share {
+    pub name: String,
+    pub snapshot: Option<String>,
+    #[serde(default)]
+    pub deleted: bool,
+    pub version: Option<String>,
+    pub properties: ShareProperties,
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShareProperties {
+    #[serde(rename = "Last-Modified", with = "date::rfc1123")]
+    pub last_modified: OffsetDateTime,
+    #[serde(rename = "Etag")]
+    pub etag: String,
+    pub quota: u32,
+    pub access_tier: Option<ShareAccessTier>,
+    #[serde(default, with = "date::rfc1123::option")]
+    pub access_tier_change_time: Option<OffsetDateTime>,
+    pub lease_status: Option<LeaseStatus>,
+    pub lease_state: Option<LeaseState>,
+    pub lease_duration: Option<LeaseDuration>,
+    #[serde(default, with = "date::rfc1123::option")]
+    pub deleted_time: Option<OffsetDateTime>,
+    pub remaining_retention_days: Option<u32>,
+}
+
+impl ShareProperties {
+    pub(crate) fn from_headers(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            last_modified: headers::last_modified_from_headers(headers)?,
+            etag: headers::etag_from_headers(headers)?,
+            quota: headers.get_as(&SHARE_QUOTA)?,
+            access_tier: headers.get_optional_as(&headers::BLOB_ACCESS_TIER)?,
+            access_tier_change_time: headers
+                .get_optional_str(&ACCESS_TIER_CHANGE_TIME)
+                .map(date::parse_rfc1123)
+                .transpose()?,
+            lease_status: headers.get_optional_as(&headers::LEASE_STATUS)?,
+            lease_state: headers.get_optional_as(&headers::LEASE_STATE)?,
+            lease_duration: headers.get_optional_as(&headers::LEASE_DURATION)?,
+            deleted_time: None,
+            remaining_retention_days: None,
+        })
+    }
+}
+
+const ACCESS_TIER_CHANGE_TIME: headers::HeaderName =
+    headers::HeaderName::from_static("x-ms-access-tier-change-time");
@@ -0,0 +1,331 @@
+use azure_core::{
+    date,
+    error::Error,
+    headers::{AsHeaders, Header, HeaderName, HeaderValue, Headers},
+};
+use azure_storage::headers::{
+    FILE_ATTRIBUTES, FILE_CHANGE_TIME, FILE_CREATION_TIME, FILE_ID, FILE_LAST_WRITE_TIME,
+    FILE_PARENT_ID, FILE_PERMISSION, FILE_PERMISSION_KEY,
+};
+use serde::{Deserialize, Deserializer};
+use std::{fmt, str::FromStr};
+use time::{OffsetDateTime, UtcOffset};
+
+create_enum!(
+    FileAttribute,
+    (ReadOnly, "ReadOnly"),
+    (Hidden, "Hidden"),
+    (System, "System"),
+    (None, "None"),
+    (Directory, "Directory"),
+    (Archive, "Archive"),
+    (Temporary, "Temporary"),
+    (Offline, "Offline"),
+    (NotContentIndexed, "NotContentIndexed"),
+    (NoScrubData, "NoScrubData")
+);
+
+/// The SMB attributes of a file or directory.
+///
+/// An empty set is sent as `None`, which the service requires to clear all
+/// attributes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileAttributes(Vec<FileAttribute>);
+
+impl FileAttributes {
+    pub fn new(attributes: impl IntoIterator<Item = FileAttribute>) -> Self {
+        let mut set = Vec::new();
+        for attribute in attributes {
+            if attribute != FileAttribute::None && !set.contains(&attribute) {
+                set.push(attribute);
+            }
+        }
+        Self(set)
+    }
+
+    pub fn contains(&self, attribute: FileAttribute) -> bool {
+        self.0.contains(&attribute)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &FileAttribute> {
+        self.0.iter()
+    }
+}
+
+impl From<FileAttribute> for FileAttributes {
+    fn from(attribute: FileAttribute) -> Self {
+        Self::new([attribute])
+    }
+}
+
+impl fmt::Display for FileAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("None");
+        }
+        let attributes: Vec<&str> = self.0.iter().map(AsRef::as_ref).collect();
+        f.write_str(&attributes.join(" | "))
+    }
+}
+
+impl FromStr for FileAttributes {
+    type Err = Error;
+
+    fn from_str(s: &str) -> azure_core::Result<Self> {
+        let attributes = s
+            .split('|')
+            .map(str::trim)
+            .filter(|attribute| !attribute.is_empty())
+            .map(FileAttribute::from_str)
+            .collect::<azure_core::Result<Vec<_>>>()?;
+        Ok(Self::new(attributes))
+    }
+}
+
+impl<'de> Deserialize<'de> for FileAttributes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Header for FileAttributes {
+    fn name(&self) -> HeaderName {
+        FILE_ATTRIBUTES
+    }
+
+    fn value(&self) -> HeaderValue {
+        self.to_string().into()
+    }
+}
+
+/// A timestamp to set on a file or directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileTime {
+    /// Use the time of the request.
+    Now,
+    /// Keep the existing value.
+    Preserve,
+    At(OffsetDateTime),
+}
+
+impl FileTime {
+    fn to_header_value(self) -> String {
+        match self {
+            FileTime::Now => "now".to_owned(),
+            FileTime::Preserve => "preserve".to_owned(),
+            FileTime::At(time) => format_file_time(time),
+        }
+    }
+}
+
+impl From<OffsetDateTime> for FileTime {
+    fn from(time: OffsetDateTime) -> Self {
+        FileTime::At(time)
+    }
+}
+
+/// The security descriptor to set on a file or directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilePermission {
+    /// Inherit the permission of the parent directory.
+    Inherit,
+    /// Keep the existing permission.
+    Preserve,
+    /// A permission in the Security Descriptor Definition Language (SDDL).
+    ///
+    /// Permissions larger than 8KiB must be created at the share level with
+    /// `ShareClient::create_permission` and referenced by key instead.
+    Sddl(String),
+    /// The key of a permission created at the share level.
+    Key(String),
+}
+
+impl Header for FilePermission {
+    fn name(&self) -> HeaderName {
+        match self {
+            FilePermission::Key(_) => FILE_PERMISSION_KEY,
+            _ => FILE_PERMISSION,
+        }
+    }
+
+    fn value(&self) -> HeaderValue {
+        match self {
+            FilePermission::Inherit => "inherit".to_owned(),
+            FilePermission::Preserve => "preserve".to_owned(),
+            FilePermission::Sddl(sddl) => sddl.clone(),
+            FilePermission::Key(key) => key.clone(),
+        }
+        .into()
+    }
+}
+
+/// SMB properties to set when creating or updating a file or directory.
+///
+/// Properties that are not specified are left to the service defaults on
+/// creation, and preserved on update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmbProperties {
+    pub attributes: Option<FileAttributes>,
+    pub creation_time: Option<FileTime>,
+    pub last_write_time: Option<FileTime>,
+    pub change_time: Option<FileTime>,
+    pub permission: Option<FilePermission>,
+}
+
+impl SmbProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setters! {
+        attributes: FileAttributes => Some(attributes),
+        creation_time: FileTime => Some(creation_time),
+        last_write_time: FileTime => Some(last_write_time),
+        change_time: FileTime => Some(change_time),
+        permission: FilePermission => Some(permission),
+    }
+}
+
+impl AsHeaders for SmbProperties {
+    type Iter = std::vec::IntoIter<(HeaderName, HeaderValue)>;
+
+    fn as_headers(&self) -> Self::Iter {
+        let mut headers = Vec::new();
+        if let Some(attributes) = &self.attributes {
+            headers.push((attributes.name(), attributes.value()));
+        }
+        for (name, time) in [
+            (FILE_CREATION_TIME, self.creation_time),
+            (FILE_LAST_WRITE_TIME, self.last_write_time),
+            (FILE_CHANGE_TIME, self.change_time),
+        ] {
+            if let Some(time) = time {
+                headers.push((name, time.to_header_value().into()));
+            }
+        }
+        if let Some(permission) = &self.permission {
+            headers.push((permission.name(), permission.value()));
+        }
+        headers.into_iter()
+    }
+}
+
+/// The SMB properties of a file or directory, as returned by the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSmbProperties {
+    pub attributes: FileAttributes,
+    pub creation_time: OffsetDateTime,
+    pub last_write_time: OffsetDateTime,
+    pub change_time: Option<OffsetDateTime>,
+    pub permission_key: String,
+    pub file_id: String,
+    pub parent_id: String,
+}
+
+impl TryFrom<&Headers> for FileSmbProperties {
+    type Error = Error;
+
+    fn try_from(headers: &Headers) -> azure_core::Result<Self> {
+        Ok(Self {
+            attributes: headers.get_as(&FILE_ATTRIBUTES)?,
+            creation_time: parse_file_time(headers.get_str(&FILE_CREATION_TIME)?)?,
+            last_write_time: parse_file_time(headers.get_str(&FILE_LAST_WRITE_TIME)?)?,
+            change_time: headers
+                .get_optional_str(&FILE_CHANGE_TIME)
+                .map(parse_file_time)
+                .transpose()?,
+            permission_key: headers.get_as(&FILE_PERMISSION_KEY)?,
+            file_id: headers.get_as(&FILE_ID)?,
+            parent_id: headers.get_as(&FILE_PARENT_ID)?,
+        })
+    }
+}
+
+/// Format a timestamp the way the Files service expects it, in UTC with
+/// seven fractional digits.
+pub(crate) fn format_file_time(time: OffsetDateTime) -> String {
+    let time = time.to_offset(UtcOffset::UTC);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:07}Z",
+        time.year(),
+        u8::from(time.month()),
+        time.day(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+        time.nanosecond() / 100
+    )
+}
+
+pub(crate) fn parse_file_time(s: &str) -> azure_core::Result<OffsetDateTime> {
+    date::parse_rfc3339(s)
+}
+
+/// Deserialize an optional timestamp in the format used by the Files service
+/// in listings.
+pub(crate) mod file_time_option {
+    use serde::{Deserialize, Deserializer};
+    use time::OffsetDateTime;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<OffsetDateTime>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| super::parse_file_time(&s))
+            .transpose()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attributes_round_trip() -> azure_core::Result<()> {
+        let attributes: FileAttributes = "ReadOnly | Hidden | Archive".parse()?;
+        assert!(attributes.contains(FileAttribute::Hidden));
+        assert!(!attributes.contains(FileAttribute::System));
+        assert_eq!(attributes.to_string(), "ReadOnly | Hidden | Archive");
+
+        let none: FileAttributes = "None".parse()?;
+        assert_eq!(none, FileAttributes::default());
+        assert_eq!(none.to_string(), "None");
+        Ok(())
+    }
+
+    #[test]
+    fn file_time_format() -> azure_core::Result<()> {
+        let time = date::parse_rfc3339("2023-04-05T08:07:08.123456789+02:00")?;
+        assert_eq!(format_file_time(time), "2023-04-05T06:07:08.1234567Z");
+        let parsed = parse_file_time("2023-04-05T06:07:08.1234567Z")?;
+        assert_eq!(parsed.nanosecond(), 123_456_700);
+        assert_eq!(format_file_time(parsed), "2023-04-05T06:07:08.1234567Z");
+        Ok(())
+    }
+
+    #[test]
+    fn smb_properties_headers() {
+        let properties = SmbProperties::new()
+            .attributes(FileAttributes::default())
+            .creation_time(FileTime::Preserve)
+            .permission(FilePermission::Key("1234".to_owned()));
+        let headers: Vec<_> = properties
+            .as_headers()
+            .map(|(name, value)| (name.as_str().to_owned(), value.as_str().to_owned()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("x-ms-file-attributes".to_owned(), "None".to_owned()),
+                ("x-ms-file-creation-time".to_owned(), "preserve".to_owned()),
+                ("x-ms-file-permission-key".to_owned(), "1234".to_owned()),
+            ]
+        );
+    }
+}
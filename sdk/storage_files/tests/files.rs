@@ -0,0 +1,91 @@
+use azure_core::prelude::Range;
+use azure_storage_files::{operations::DirectoryEntry, prelude::*};
+use futures::StreamExt;
+
+mod setup;
+
+#[tokio::test]
+async fn share_directory_and_file_lifecycle() -> azure_core::Result<()> {
+    let service = setup::initialize("files_share_directory_file")?;
+    let share = service.share_client("reports");
+
+    share.create().quota(ShareQuota(10)).await?;
+
+    let directory = share.directory_client("2023");
+    let created = directory
+        .create()
+        .smb_properties(SmbProperties::new().attributes(FileAttribute::Archive))
+        .await?;
+    assert!(created
+        .smb_properties
+        .attributes
+        .contains(FileAttribute::Archive));
+
+    let content = b"hello world".to_vec();
+    let file = directory.file_client("hello.txt");
+    assert_eq!(file.path(), "2023/hello.txt");
+    file.create(content.len() as u64).await?;
+    file.put_range(0, content.clone()).await?;
+
+    let page = directory
+        .list()
+        .into_stream()
+        .next()
+        .await
+        .expect("stream failed")?;
+    assert_eq!(page.entries.len(), 2);
+    let names: Vec<_> = page.files().map(|file| file.name.as_str()).collect();
+    assert_eq!(names, ["hello.txt"]);
+    assert!(matches!(&page.entries[1], DirectoryEntry::Directory(d) if d.name == "archive"));
+
+    let ranges = file.list_ranges().await?;
+    assert_eq!(ranges.file_content_length, 11);
+    assert_eq!(ranges.ranges.len(), 1);
+
+    let downloaded = file.get_content().await?;
+    assert_eq!(downloaded, content);
+
+    let handles = directory
+        .list_handles()
+        .recursive(true)
+        .into_stream()
+        .next()
+        .await
+        .expect("stream failed")?;
+    assert_eq!(handles.handles.len(), 1);
+    assert_eq!(handles.handles[0].path, "2023/hello.txt");
+
+    let closed = directory
+        .force_close_handles(HandleId::All)
+        .recursive(true)
+        .await?;
+    assert_eq!(closed.closed, 1);
+    assert_eq!(closed.marker, None);
+
+    file.delete().await?;
+    share.delete().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn leased_file_and_share_snapshot() -> azure_core::Result<()> {
+    let service = setup::initialize("files_snapshot_and_lease")?;
+    let share = service.share_client("reports");
+    let file = share.file_client("notes.txt");
+
+    let lease_id = file.acquire_lease().await?.lease_id;
+    file.put_range(0, b"notes".to_vec())
+        .lease_id(lease_id)
+        .await?;
+
+    let snapshot = share.snapshot().await?.snapshot;
+    assert_eq!(snapshot.as_str(), "2026-10-19T10:00:00.0000000Z");
+    let ranges = file.list_ranges().share_snapshot(snapshot).await?;
+    assert_eq!(ranges.file_content_length, 5);
+    assert_eq!(ranges.ranges, vec![Range::new(0, 5)]);
+
+    file.file_lease_client(lease_id).release().await?;
+
+    Ok(())
+}
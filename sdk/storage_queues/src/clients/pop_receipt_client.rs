@@ -1,5 +1,6 @@
 use crate::{operations::*, prelude::*};
 use azure_core::{Context, Request, Response, Url};
+use azure_storage::client_side_encryption::ClientSideEncryption;
 
 #[derive(Debug, Clone)]
 pub struct PopReceiptClient {
@@ -35,6 +36,10 @@ impl PopReceiptClient {
         DeleteMessageBuilder::new(self.clone())
     }
 
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.client.client_side_encryption()
+    }
+
     pub(crate) async fn send(
         &self,
         context: &mut Context,
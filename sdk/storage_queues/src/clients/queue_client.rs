@@ -1,8 +1,22 @@
 use crate::{
-    operations::*, PopReceipt, PopReceiptClient, QueueServiceClient, QueueStoredAccessPolicy,
+    operations::*, MessageCodec, PopReceipt, PopReceiptClient, QueueProcessor, QueueServiceClient,
+    QueueStoredAccessPolicy,
 };
-use azure_core::{prelude::*, Request, Response, Url};
-use std::fmt::Debug;
+use azure_core::{
+    error::{Error, ErrorKind},
+    prelude::*,
+    Request, Response, Url,
+};
+use azure_storage::{
+    client_side_encryption::ClientSideEncryption,
+    shared_access_signature::{
+        queue_sas::{QueueSasPermissions, QueueSharedAccessSignature},
+        SasToken,
+    },
+    StorageCredentialsInner,
+};
+use std::{fmt::Debug, ops::Deref};
+use time::OffsetDateTime;
 
 #[derive(Debug, Clone)]
 pub struct QueueClient {
@@ -70,6 +84,15 @@ impl QueueClient {
         PutMessageBuilder::new(self.clone(), message.into())
     }
 
+    /// Puts a message in the queue, encoded with `codec`.
+    pub fn put_encoded_message<C: MessageCodec>(
+        &self,
+        codec: &C,
+        message: &C::Message,
+    ) -> azure_core::Result<PutMessageBuilder> {
+        Ok(self.put_message(codec.encode(message)?))
+    }
+
     /// Peeks, without removing, one or more messages.
     pub fn peek_messages(&self) -> PeekMessagesBuilder {
         PeekMessagesBuilder::new(self.clone())
@@ -90,6 +113,49 @@ impl QueueClient {
         PopReceiptClient::new(self.clone(), pop_receipt.into())
     }
 
+    /// Create a `QueueProcessor` handling the messages of this queue, decoded
+    /// with `codec`.
+    pub fn processor<C: MessageCodec>(&self, codec: C) -> QueueProcessor<C> {
+        QueueProcessor::new(self.clone(), codec)
+    }
+
+    /// Create a shared access signature scoped to this queue.
+    pub async fn shared_access_signature(
+        &self,
+        permissions: QueueSasPermissions,
+        expiry: OffsetDateTime,
+    ) -> azure_core::Result<QueueSharedAccessSignature> {
+        let creds = self.service_client.credentials().0.read().await;
+        let StorageCredentialsInner::Key(account, key) = creds.deref() else {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "Shared access signature generation - SAS can be generated with access_key clients",
+            ));
+        };
+
+        let canonicalized_resource = format!("/queue/{}/{}", account, self.queue_name());
+        Ok(QueueSharedAccessSignature::new(
+            key.clone(),
+            canonicalized_resource,
+            permissions,
+            expiry,
+        ))
+    }
+
+    /// Create a signed queue url
+    pub fn generate_signed_queue_url<T>(&self, signature: &T) -> azure_core::Result<Url>
+    where
+        T: SasToken,
+    {
+        let mut url = self.url()?;
+        url.set_query(Some(&signature.token()?));
+        Ok(url)
+    }
+
+    pub fn service_client(&self) -> QueueServiceClient {
+        self.service_client.clone()
+    }
+
     pub fn queue_name(&self) -> &str {
         &self.queue_name
     }
@@ -110,6 +176,10 @@ impl QueueClient {
         Ok(url)
     }
 
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.service_client.client_side_encryption()
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: azure_core::Method,
@@ -1,6 +1,7 @@
 use crate::{operations::*, QueueClient, QueueServiceProperties};
 use azure_core::{ClientOptions, Context, Pipeline, Request, Response, Url};
 use azure_storage::{
+    client_side_encryption::ClientSideEncryption,
     clients::{new_pipeline_from_options, ServiceType},
     prelude::StorageCredentials,
     CloudLocation,
@@ -13,6 +14,7 @@ pub struct QueueServiceClientBuilder {
     cloud_location: CloudLocation,
     options: ClientOptions,
     credentials: StorageCredentials,
+    client_side_encryption: Option<ClientSideEncryption>,
 }
 
 impl QueueServiceClientBuilder {
@@ -41,6 +43,7 @@ impl QueueServiceClientBuilder {
             options: ClientOptions::default(),
             cloud_location,
             credentials: credentials.into(),
+            client_side_encryption: None,
         }
     }
 
@@ -63,11 +66,14 @@ impl QueueServiceClientBuilder {
             cloud_location,
             options,
             credentials,
+            client_side_encryption,
         } = self;
 
         QueueServiceClient {
-            pipeline: new_pipeline_from_options(options, credentials),
+            pipeline: new_pipeline_from_options(options, credentials.clone()),
             cloud_location,
+            credentials,
+            client_side_encryption,
         }
     }
 
@@ -92,6 +98,17 @@ impl QueueServiceClientBuilder {
         self
     }
 
+    /// Encrypt the text of messages before they are sent, and decrypt the
+    /// text of messages encrypted on the client when they are received.
+    ///
+    /// Receiving a message which was not encrypted fails, unless
+    /// `ClientSideEncryption::allow_unencrypted_messages` is set.
+    #[must_use]
+    pub fn client_side_encryption(mut self, client_side_encryption: ClientSideEncryption) -> Self {
+        self.client_side_encryption = Some(client_side_encryption);
+        self
+    }
+
     /// Override all of the client options.
     ///
     /// *Warning!*: This overrides all client options that have been previously set on this builder.
@@ -106,6 +123,8 @@ impl QueueServiceClientBuilder {
 pub struct QueueServiceClient {
     pipeline: Pipeline,
     cloud_location: CloudLocation,
+    credentials: StorageCredentials,
+    client_side_encryption: Option<ClientSideEncryption>,
 }
 
 impl QueueServiceClient {
@@ -146,6 +165,14 @@ impl QueueServiceClient {
         self.cloud_location.url(ServiceType::Queue)
     }
 
+    pub(crate) fn credentials(&self) -> &StorageCredentials {
+        &self.credentials
+    }
+
+    pub(crate) fn client_side_encryption(&self) -> Option<&ClientSideEncryption> {
+        self.client_side_encryption.as_ref()
+    }
+
     pub(crate) fn finalize_request(
         url: Url,
         method: azure_core::Method,
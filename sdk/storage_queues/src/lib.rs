@@ -42,20 +42,24 @@ async fn main() -> azure_core::Result<()> {
 extern crate azure_core;
 
 mod clients;
+mod message_codec;
 mod message_ttl;
 mod number_of_messages;
 pub mod operations;
 mod pop_receipt;
 pub mod prelude;
 mod queue_message;
+mod queue_processor;
 mod queue_service_properties;
 mod queue_stored_access_policy;
 mod visibility_timeout;
 
 pub use clients::*;
+pub use message_codec::{Base64Codec, JsonCodec, MessageCodec, TextCodec};
 pub use message_ttl::MessageTTL;
 pub use number_of_messages::NumberOfMessages;
 pub use pop_receipt::PopReceipt;
+pub use queue_processor::{QueueProcessor, ReceivedMessage};
 pub use queue_service_properties::{CorsRule, QueueServiceProperties};
 pub use queue_stored_access_policy::QueueStoredAccessPolicy;
 pub use visibility_timeout::VisibilityTimeout;
@@ -0,0 +1,158 @@
+use azure_core::error::{ErrorKind, ResultExt};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// Converts between typed messages and the text stored in a queue.
+pub trait MessageCodec: Debug + Send + Sync {
+    type Message;
+
+    fn encode(&self, message: &Self::Message) -> azure_core::Result<String>;
+
+    fn decode(&self, message_text: &str) -> azure_core::Result<Self::Message>;
+}
+
+/// Stores messages as they are.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextCodec;
+
+impl MessageCodec for TextCodec {
+    type Message = String;
+
+    fn encode(&self, message: &String) -> azure_core::Result<String> {
+        Ok(message.clone())
+    }
+
+    fn decode(&self, message_text: &str) -> azure_core::Result<String> {
+        Ok(message_text.to_owned())
+    }
+}
+
+/// Stores binary messages base64 encoded, the encoding the other Azure
+/// Storage SDKs use by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base64Codec;
+
+impl MessageCodec for Base64Codec {
+    type Message = Vec<u8>;
+
+    fn encode(&self, message: &Vec<u8>) -> azure_core::Result<String> {
+        Ok(azure_core::base64::encode(message))
+    }
+
+    fn decode(&self, message_text: &str) -> azure_core::Result<Vec<u8>> {
+        azure_core::base64::decode(message_text)
+    }
+}
+
+/// Stores messages as JSON, optionally base64 encoded as Azure Functions
+/// queue triggers expect.
+pub struct JsonCodec<T> {
+    base64: bool,
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonCodec<T> {
+    pub fn new() -> Self {
+        Self {
+            base64: false,
+            _message: PhantomData,
+        }
+    }
+
+    /// Base64 encode the JSON text.
+    pub fn base64() -> Self {
+        Self {
+            base64: true,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for JsonCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for JsonCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            base64: self.base64,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for JsonCodec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonCodec")
+            .field("base64", &self.base64)
+            .finish()
+    }
+}
+
+impl<T> MessageCodec for JsonCodec<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    type Message = T;
+
+    fn encode(&self, message: &T) -> azure_core::Result<String> {
+        let json = azure_core::to_json(message)?;
+        if self.base64 {
+            return Ok(azure_core::base64::encode(json));
+        }
+        String::from_utf8(json.to_vec()).context(ErrorKind::DataConversion, "invalid JSON text")
+    }
+
+    fn decode(&self, message_text: &str) -> azure_core::Result<T> {
+        if self.base64 {
+            let json = azure_core::base64::decode(message_text)?;
+            return azure_core::from_json(json);
+        }
+        azure_core::from_json(message_text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        id: u32,
+        item: String,
+    }
+
+    #[test]
+    fn round_trip() -> azure_core::Result<()> {
+        let text = TextCodec.encode(&"hello".to_owned())?;
+        assert_eq!(text, "hello");
+        assert_eq!(TextCodec.decode(&text)?, "hello");
+
+        let text = Base64Codec.encode(&vec![0, 1, 255])?;
+        assert_eq!(text, "AAH/");
+        assert_eq!(Base64Codec.decode(&text)?, [0, 1, 255]);
+
+        let order = Order {
+            id: 7,
+            item: "apple".to_owned(),
+        };
+        let text = JsonCodec::new().encode(&order)?;
+        assert_eq!(text, r#"{"id":7,"item":"apple"}"#);
+        assert_eq!(JsonCodec::<Order>::new().decode(&text)?, order);
+
+        let text = JsonCodec::base64().encode(&order)?;
+        assert_eq!(text, "eyJpZCI6NywiaXRlbSI6ImFwcGxlIn0=");
+        assert_eq!(JsonCodec::<Order>::base64().decode(&text)?, order);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_invalid_messages() {
+        assert!(Base64Codec.decode("not base64!").is_err());
+        assert!(JsonCodec::<Order>::new().decode("{").is_err());
+        assert!(JsonCodec::<Order>::base64().decode(r#"{"id":7}"#).is_err());
+    }
+}
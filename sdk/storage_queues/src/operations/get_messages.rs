@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{prelude::*, MessageCodec};
 use azure_core::{headers::Headers, prelude::*, xml::read_xml, Method, Response as AzureResponse};
 use azure_storage::headers::CommonStorageResponseHeaders;
 use serde::Deserialize;
@@ -24,7 +24,14 @@ impl GetMessagesBuilder {
 
             let response = self.client.send(&mut self.context, &mut request).await?;
 
-            GetMessagesResponse::try_from(response).await
+            let mut response = GetMessagesResponse::try_from(response).await?;
+            if let Some(encryption) = self.client.client_side_encryption() {
+                for message in &mut response.messages {
+                    message.message_text =
+                        encryption.decrypt_message(&message.message_text).await?;
+                }
+            }
+            Ok(response)
         })
     }
 }
@@ -54,6 +61,11 @@ impl Message {
     pub fn pop_receipt(&self) -> PopReceipt {
         PopReceipt::new(self.message_id.clone(), self.pop_receipt.clone())
     }
+
+    /// Decode the message text with `codec`.
+    pub fn decode<C: MessageCodec>(&self, codec: &C) -> azure_core::Result<C::Message> {
+        codec.decode(&self.message_text)
+    }
 }
 
 impl From<Message> for PopReceipt {
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{prelude::*, MessageCodec};
 use azure_core::{headers::Headers, prelude::*, Method, Response as AzureResponse};
 use azure_storage::headers::CommonStorageResponseHeaders;
 use serde::{Deserialize, Serialize};
@@ -23,7 +23,14 @@ impl PeekMessagesBuilder {
 
             let response = self.client.send(&mut self.context, &mut request).await?;
 
-            PeekMessagesResponse::try_from(response).await
+            let mut response = PeekMessagesResponse::try_from(response).await?;
+            if let Some(encryption) = self.client.client_side_encryption() {
+                for message in &mut response.messages {
+                    message.message_text =
+                        encryption.decrypt_message(&message.message_text).await?;
+                }
+            }
+            Ok(response)
         })
     }
 }
@@ -52,6 +59,13 @@ pub struct PeekMessage {
     pub message_text: String,
 }
 
+impl PeekMessage {
+    /// Decode the message text with `codec`.
+    pub fn decode<C: MessageCodec>(&self, codec: &C) -> azure_core::Result<C::Message> {
+        codec.decode(&self.message_text)
+    }
+}
+
 impl PeekMessagesResponse {
     async fn try_from(response: AzureResponse) -> azure_core::Result<Self> {
         let (_, headers, body) = response.deconstruct();
@@ -24,9 +24,11 @@ impl UpdateMessageBuilder {
 
             self.visibility_timeout.append_to_url_query(&mut url);
 
-            let message = to_xml(&QueueMessageSubmit {
-                message_text: self.body,
-            })?;
+            let message_text = match self.client.client_side_encryption() {
+                Some(encryption) => encryption.encrypt_message(&self.body).await?,
+                None => self.body,
+            };
+            let message = to_xml(&QueueMessageSubmit { message_text })?;
 
             let mut request = PopReceiptClient::finalize_request(
                 url,
@@ -1,4 +1,5 @@
 pub use crate::{
-    MessageTTL, NumberOfMessages, PopReceipt, QueueClient, QueueServiceClient,
-    QueueStoredAccessPolicy, VisibilityTimeout,
+    Base64Codec, JsonCodec, MessageCodec, MessageTTL, NumberOfMessages, PopReceipt, QueueClient,
+    QueueProcessor, QueueServiceClient, QueueStoredAccessPolicy, ReceivedMessage, TextCodec,
+    VisibilityTimeout,
 };
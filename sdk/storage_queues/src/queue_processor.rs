@@ -0,0 +1,293 @@
+use crate::{operations::Message, prelude::*, MessageCodec};
+use azure_core::{sleep::sleep, StatusCode};
+use futures::{
+    future::{select, Either, Fuse, FusedFuture},
+    pin_mut,
+    stream::FuturesUnordered,
+    Future, FutureExt, StreamExt,
+};
+use std::time::Duration;
+use time::OffsetDateTime;
+
+const DEFAULT_MAX_DEQUEUE_COUNT: u64 = 5;
+const DEFAULT_MAX_CONCURRENCY: usize = 16;
+const DEFAULT_VISIBILITY_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_BATCH_SIZE: usize = 32;
+const POISON_QUEUE_SUFFIX: &str = "-poison";
+
+/// Receives messages from a queue and passes them, decoded, to a handler.
+///
+/// A message is deleted once the handler succeeds. When the handler fails the
+/// message becomes visible again after the visibility timeout and is retried,
+/// until it has been dequeued more than `max_dequeue_count` times. Such
+/// messages, and messages that cannot be decoded, are moved to the poison
+/// queue, `<queue name>-poison` unless set otherwise.
+#[derive(Debug, Clone)]
+pub struct QueueProcessor<C> {
+    client: QueueClient,
+    codec: C,
+    poison_queue: QueueClient,
+    max_dequeue_count: u64,
+    max_concurrency: usize,
+    visibility_timeout: Duration,
+    min_poll_interval: Duration,
+    max_poll_interval: Duration,
+}
+
+/// A decoded message passed to a `QueueProcessor` handler.
+#[derive(Debug, Clone)]
+pub struct ReceivedMessage<T> {
+    pub message_id: String,
+    pub insertion_time: OffsetDateTime,
+    pub dequeue_count: u64,
+    pub body: T,
+}
+
+impl<C: MessageCodec> QueueProcessor<C> {
+    pub fn new(client: QueueClient, codec: C) -> Self {
+        let poison_queue = client
+            .service_client()
+            .queue_client(format!("{}{POISON_QUEUE_SUFFIX}", client.queue_name()));
+        Self {
+            client,
+            codec,
+            poison_queue,
+            max_dequeue_count: DEFAULT_MAX_DEQUEUE_COUNT,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            visibility_timeout: DEFAULT_VISIBILITY_TIMEOUT,
+            min_poll_interval: DEFAULT_MIN_POLL_INTERVAL,
+            max_poll_interval: DEFAULT_MAX_POLL_INTERVAL,
+        }
+    }
+
+    setters! {
+        /// The queue that messages failing too often are moved to. It is
+        /// created when the first message is moved.
+        poison_queue: QueueClient => poison_queue,
+        max_dequeue_count: u64 => max_dequeue_count,
+        /// How many messages are handled at the same time.
+        max_concurrency: usize => max_concurrency.max(1),
+        /// How long received messages are hidden from other receivers. The
+        /// timeout is extended while the handler runs.
+        visibility_timeout: Duration => visibility_timeout,
+        /// The delay before polling again after receiving no messages, which
+        /// doubles on every empty poll up to `max_poll_interval`.
+        min_poll_interval: Duration => min_poll_interval,
+        max_poll_interval: Duration => max_poll_interval,
+    }
+
+    /// Handle messages until `shutdown` completes. No messages are received
+    /// after that, but the messages already received are handled before
+    /// returning.
+    pub async fn run_until<F, Fut>(&self, handler: F, shutdown: impl Future<Output = ()>)
+    where
+        F: Fn(ReceivedMessage<C::Message>) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+    {
+        let shutdown = shutdown.fuse();
+        let receive = Fuse::terminated();
+        let mut running = FuturesUnordered::new();
+        let mut poll_interval = Duration::ZERO;
+        pin_mut!(shutdown, receive);
+
+        loop {
+            if receive.is_terminated() && running.len() < self.max_concurrency {
+                let count = self.max_concurrency - running.len();
+                receive.set(self.receive(poll_interval, count).fuse());
+            }
+            futures::select! {
+                () = shutdown => break,
+                () = running.select_next_some() => {}
+                messages = receive => match messages {
+                    Ok(messages) if !messages.is_empty() => {
+                        poll_interval = Duration::ZERO;
+                        for message in messages {
+                            running.push(self.process(message, &handler));
+                        }
+                    }
+                    Ok(_) => poll_interval = self.next_poll_interval(poll_interval),
+                    Err(err) => {
+                        tracing::warn!(
+                            "failed to receive messages from queue {}: {err}",
+                            self.client.queue_name()
+                        );
+                        poll_interval = self.next_poll_interval(poll_interval);
+                    }
+                }
+            }
+        }
+
+        while running.next().await.is_some() {}
+    }
+
+    /// Handle messages until the queue is empty.
+    pub async fn run_until_empty<F, Fut>(&self, handler: F) -> azure_core::Result<()>
+    where
+        F: Fn(ReceivedMessage<C::Message>) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+    {
+        loop {
+            let messages = self.receive(Duration::ZERO, self.max_concurrency).await?;
+            if messages.is_empty() {
+                return Ok(());
+            }
+            messages
+                .into_iter()
+                .map(|message| self.process(message, &handler))
+                .collect::<FuturesUnordered<_>>()
+                .collect::<()>()
+                .await;
+        }
+    }
+
+    fn next_poll_interval(&self, poll_interval: Duration) -> Duration {
+        (poll_interval * 2)
+            .max(self.min_poll_interval)
+            .min(self.max_poll_interval)
+    }
+
+    async fn receive(&self, delay: Duration, count: usize) -> azure_core::Result<Vec<Message>> {
+        if !delay.is_zero() {
+            sleep(delay).await;
+        }
+        let response = self
+            .client
+            .get_messages()
+            .number_of_messages(count.min(MAX_BATCH_SIZE) as u8)
+            .visibility_timeout(self.visibility_timeout)
+            .await?;
+        Ok(response.messages)
+    }
+
+    async fn process<F, Fut>(&self, message: Message, handler: &F)
+    where
+        F: Fn(ReceivedMessage<C::Message>) -> Fut,
+        Fut: Future<Output = azure_core::Result<()>>,
+    {
+        if message.dequeue_count > self.max_dequeue_count {
+            tracing::warn!(
+                "message {} was dequeued {} times, moving it to the poison queue",
+                message.message_id,
+                message.dequeue_count
+            );
+            return self.move_to_poison_queue(message).await;
+        }
+        let body = match self.codec.decode(&message.message_text) {
+            Ok(body) => body,
+            Err(err) => {
+                tracing::warn!(
+                    "failed to decode message {}, moving it to the poison queue: {err}",
+                    message.message_id
+                );
+                return self.move_to_poison_queue(message).await;
+            }
+        };
+
+        let handled = handler(ReceivedMessage {
+            message_id: message.message_id.clone(),
+            insertion_time: message.insertion_time,
+            dequeue_count: message.dequeue_count,
+            body,
+        });
+        pin_mut!(handled);
+
+        // keep the message hidden while the handler runs, tracking the pop
+        // receipt returned by every update
+        let mut pop_receipt = message.pop_receipt();
+        let result = loop {
+            let extend = self.extend_visibility(pop_receipt.clone(), &message.message_text);
+            pin_mut!(extend);
+            match select(handled.as_mut(), extend).await {
+                Either::Left((result, _)) => break result,
+                Either::Right((Ok(receipt), _)) => pop_receipt = receipt,
+                Either::Right((Err(err), _)) => {
+                    tracing::warn!(
+                        "failed to extend the visibility timeout of message {}: {err}",
+                        message.message_id
+                    );
+                    break handled.await;
+                }
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                if let Err(err) = self.client.pop_receipt_client(pop_receipt).delete().await {
+                    tracing::warn!("failed to delete message {}: {err}", message.message_id);
+                }
+            }
+            Err(err) => tracing::warn!("failed to handle message {}: {err}", message.message_id),
+        }
+    }
+
+    async fn extend_visibility(
+        &self,
+        pop_receipt: PopReceipt,
+        message_text: &str,
+    ) -> azure_core::Result<PopReceipt> {
+        let interval = self.visibility_timeout / 2;
+        if interval.is_zero() {
+            return futures::future::pending().await;
+        }
+        sleep(interval).await;
+        let response = self
+            .client
+            .pop_receipt_client(pop_receipt.clone())
+            .update(message_text, self.visibility_timeout)
+            .await?;
+        Ok(PopReceipt::new(
+            pop_receipt.message_id(),
+            response.pop_receipt,
+        ))
+    }
+
+    async fn move_to_poison_queue(&self, message: Message) {
+        let moved = match self.poison_queue.put_message(&message.message_text).await {
+            Err(err) if is_not_found(&err) => {
+                match self.poison_queue.create().await {
+                    Ok(_) => {}
+                    Err(err) if is_conflict(&err) => {}
+                    Err(err) => {
+                        tracing::warn!(
+                            "failed to create poison queue {}: {err}",
+                            self.poison_queue.queue_name()
+                        );
+                        return;
+                    }
+                }
+                self.poison_queue.put_message(&message.message_text).await
+            }
+            moved => moved,
+        };
+        if let Err(err) = moved {
+            tracing::warn!(
+                "failed to move message {} to poison queue {}: {err}",
+                message.message_id,
+                self.poison_queue.queue_name()
+            );
+            return;
+        }
+        if let Err(err) = self
+            .client
+            .pop_receipt_client(message.pop_receipt())
+            .delete()
+            .await
+        {
+            tracing::warn!("failed to delete message {}: {err}", message.message_id);
+        }
+    }
+}
+
+fn is_not_found(err: &azure_core::Error) -> bool {
+    err.as_http_error()
+        .map(|e| e.status() == StatusCode::NotFound)
+        .unwrap_or_default()
+}
+
+fn is_conflict(err: &azure_core::Error) -> bool {
+    err.as_http_error()
+        .map(|e| e.status() == StatusCode::Conflict)
+        .unwrap_or_default()
+}
@@ -0,0 +1,249 @@
+use azure_storage_queues::{prelude::*, QueueServiceClientBuilder};
+use futures::StreamExt;
+use std::{num::NonZeroU32, time::Duration};
+use storage_emulator::StorageEmulator;
+
+fn queue_service(emulator: &StorageEmulator) -> QueueServiceClient {
+    QueueServiceClientBuilder::emulator()
+        .transport(emulator.transport_options())
+        .build()
+}
+
+#[tokio::test]
+async fn messages_become_visible_after_timeout() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let queue = queue_service(&emulator).queue_client("work");
+    queue.create().await?;
+
+    queue.put_message("first").await?;
+    queue.put_message("second").await?;
+
+    let response = queue
+        .get_messages()
+        .visibility_timeout(Duration::from_secs(60))
+        .await?;
+    assert_eq!(response.messages.len(), 1);
+    let message = &response.messages[0];
+    assert_eq!(message.message_text, "first");
+    assert_eq!(message.dequeue_count, 1);
+
+    let peeked = queue
+        .peek_messages()
+        .number_of_messages(32u8)
+        .await?
+        .messages;
+    let texts: Vec<_> = peeked.iter().map(|m| m.message_text.as_str()).collect();
+    assert_eq!(texts, ["second"]);
+
+    emulator.advance_time(Duration::from_secs(61));
+    let response = queue.get_messages().number_of_messages(32u8).await?;
+    let texts: Vec<_> = response
+        .messages
+        .iter()
+        .map(|m| m.message_text.as_str())
+        .collect();
+    assert_eq!(texts, ["first", "second"]);
+    assert_eq!(response.messages[0].dequeue_count, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_and_delete_with_pop_receipt() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let queue = queue_service(&emulator).queue_client("work");
+    queue.create().await?;
+    queue.put_message("draft").await?;
+
+    let message = queue.get_messages().await?.messages.remove(0);
+    let updated = queue
+        .pop_receipt_client(message.clone())
+        .update("final", Duration::from_secs(0))
+        .await?;
+
+    // the old pop receipt is no longer valid
+    let error = queue
+        .pop_receipt_client(message)
+        .delete()
+        .await
+        .unwrap_err();
+    assert_eq!(error.as_http_error().unwrap().status(), 400);
+
+    let peeked = queue.peek_messages().await?.messages;
+    assert_eq!(peeked[0].message_text, "final");
+
+    let receipt = PopReceipt::new(peeked[0].message_id.clone(), updated.pop_receipt);
+    queue.pop_receipt_client(receipt).delete().await?;
+    assert!(queue.peek_messages().await?.messages.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_queues_pages_with_marker() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let service = queue_service(&emulator);
+    for name in ["alpha", "beta", "gamma"] {
+        service.queue_client(name).create().await?;
+    }
+
+    let mut pages = service
+        .list_queues()
+        .max_results(NonZeroU32::new(2).unwrap())
+        .into_stream();
+    let mut names = Vec::new();
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        assert!(page.queues.len() <= 2);
+        names.extend(page.queues.into_iter().map(|queue| queue.name));
+    }
+    assert_eq!(names, ["alpha", "beta", "gamma"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn client_side_encryption() -> azure_core::Result<()> {
+    use azure_storage::client_side_encryption::{ClientSideEncryption, LocalKeyEncryptionKey};
+
+    let emulator = StorageEmulator::new();
+    let encryption =
+        ClientSideEncryption::new(LocalKeyEncryptionKey::new("local:key1", vec![5; 16])?);
+    let encrypted = QueueServiceClientBuilder::emulator()
+        .transport(emulator.transport_options())
+        .client_side_encryption(encryption)
+        .build()
+        .queue_client("secrets");
+    let plain = queue_service(&emulator).queue_client("secrets");
+    plain.create().await?;
+
+    encrypted.put_message("attack at dawn").await?;
+
+    // the queue only holds the encrypted message
+    let peeked = plain.peek_messages().await?;
+    assert!(peeked.messages[0]
+        .message_text
+        .contains("EncryptedMessageContents"));
+    assert!(!peeked.messages[0].message_text.contains("dawn"));
+
+    let peeked = encrypted.peek_messages().await?;
+    assert_eq!(peeked.messages[0].message_text, "attack at dawn");
+
+    let received = encrypted.get_messages().await?;
+    let message = &received.messages[0];
+    assert_eq!(message.message_text, "attack at dawn");
+    encrypted
+        .pop_receipt_client(message.pop_receipt())
+        .update("attack at dusk", Duration::ZERO)
+        .await?;
+
+    let received = encrypted.get_messages().await?;
+    assert_eq!(received.messages[0].message_text, "attack at dusk");
+    Ok(())
+}
+
+#[tokio::test]
+async fn processor_moves_poison_messages() -> azure_core::Result<()> {
+    use futures::channel::oneshot;
+    use serde::{Deserialize, Serialize};
+    use std::sync::Mutex;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Job {
+        id: u32,
+    }
+
+    let emulator = StorageEmulator::new();
+    let service = queue_service(&emulator);
+    let queue = service.queue_client("jobs");
+    queue.create().await?;
+
+    let codec = JsonCodec::<Job>::new();
+    queue.put_encoded_message(&codec, &Job { id: 1 })?.await?;
+    queue.put_encoded_message(&codec, &Job { id: 2 })?.await?;
+    queue.put_message("not json").await?;
+
+    let handled = Mutex::new(Vec::new());
+    let processor = queue
+        .processor(codec)
+        .max_dequeue_count(2u64)
+        .max_concurrency(1usize)
+        .min_poll_interval(Duration::from_millis(5))
+        .max_poll_interval(Duration::from_millis(20));
+    let handler = |message: ReceivedMessage<Job>| {
+        handled
+            .lock()
+            .unwrap()
+            .push((message.body.id, message.dequeue_count));
+        async move {
+            match message.body.id {
+                2 => Err(azure_core::Error::message(
+                    azure_core::error::ErrorKind::Other,
+                    "job 2 always fails",
+                )),
+                _ => Ok(()),
+            }
+        }
+    };
+
+    let (stop, stopped) = oneshot::channel();
+    let poison_queue = service.queue_client("jobs-poison");
+    let driver = async {
+        // job 2 becomes visible again, and is retried, every time the clock
+        // moves past the visibility timeout
+        for _ in 0..100 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let poisoned = poison_queue.peek_messages().number_of_messages(32u8).await;
+            if poisoned.map_or(false, |poisoned| poisoned.messages.len() == 2) {
+                break;
+            }
+            emulator.advance_time(Duration::from_secs(31));
+        }
+        stop.send(()).unwrap();
+    };
+    futures::join!(
+        processor.run_until(handler, async {
+            stopped.await.unwrap();
+        }),
+        driver
+    );
+
+    let mut handled = handled.into_inner().unwrap();
+    handled.sort();
+    assert_eq!(handled, [(1, 1), (2, 1), (2, 2)]);
+    assert!(queue.peek_messages().await?.messages.is_empty());
+
+    let poisoned = poison_queue
+        .peek_messages()
+        .number_of_messages(32u8)
+        .await?
+        .messages;
+    let mut texts: Vec<_> = poisoned.iter().map(|m| m.message_text.as_str()).collect();
+    texts.sort();
+    assert_eq!(texts, ["not json", r#"{"id":2}"#]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn processor_extends_visibility_of_slow_messages() -> azure_core::Result<()> {
+    let emulator = StorageEmulator::new();
+    let queue = queue_service(&emulator).queue_client("slow");
+    queue.create().await?;
+    queue.put_message("slow job").await?;
+
+    // the message stays hidden past its visibility timeout while the
+    // handler runs, and is deleted with the pop receipt of the last update
+    queue
+        .processor(TextCodec)
+        .visibility_timeout(Duration::from_secs(1))
+        .run_until_empty(|message| {
+            let queue = queue.clone();
+            async move {
+                assert_eq!(message.body, "slow job");
+                tokio::time::sleep(Duration::from_millis(1300)).await;
+                assert!(queue.peek_messages().await?.messages.is_empty());
+                Ok(())
+            }
+        })
+        .await?;
+
+    assert!(queue.peek_messages().await?.messages.is_empty());
+    Ok(())
+}